@@ -6,6 +6,7 @@ pub mod iter;
 mod macros;
 pub mod packed_option;
 pub mod pretty;
+pub mod timing;
 pub mod vec;
 pub use crate::ieee64::Ieee64;
 
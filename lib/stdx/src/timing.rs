@@ -0,0 +1,24 @@
+use std::fmt::Display;
+use std::time::Instant;
+
+/// Measures the wall-clock time of consecutive compilation phases (`--print-timing`).
+/// A disabled timer never reads the clock and prints nothing.
+pub struct PhaseTimer {
+    last: Option<Instant>,
+}
+
+impl PhaseTimer {
+    pub fn new(enabled: bool) -> PhaseTimer {
+        PhaseTimer { last: enabled.then(Instant::now) }
+    }
+
+    /// Prints the time that passed since the previous phase finished (or since the timer
+    /// was created) and starts the next phase.
+    pub fn finish(&mut self, phase: impl Display) {
+        if let Some(last) = &mut self.last {
+            let now = Instant::now();
+            println!("time: {:>10.3}ms {phase}", (now - *last).as_secs_f64() * 1000.0);
+            *last = now;
+        }
+    }
+}
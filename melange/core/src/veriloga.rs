@@ -9,17 +9,18 @@ use libc::c_void;
 use libloading::Library;
 use log::{debug, error, info, warn};
 use openvaf::{
-    AbsPathBuf, CompilationDestination, CompilationTermination, LintLevel, OptLevel, Target,
+    AbsPathBuf, CompilationDestination, CompilationLimits, CompilationTermination, FastMathOpts,
+    LintLevel, MinTypMax, OptLevel, Target,
 };
 
 use crate::devices::DeviceImpl;
-use crate::veriloga::osdi_0_4::{
+use crate::veriloga::osdi_0_5::{
     OsdiDescriptor, LOG_FMT_ERR, LOG_LVL_DEBUG, LOG_LVL_DISPLAY, LOG_LVL_ERR, LOG_LVL_FATAL,
     LOG_LVL_INFO, LOG_LVL_MASK, LOG_LVL_WARN,
 };
 use crate::veriloga::osdi_device::OsdiDevice;
 
-pub(crate) use osdi_0_4::{
+pub(crate) use osdi_0_5::{
     ANALYSIS_AC, ANALYSIS_DC, ANALYSIS_IC, ANALYSIS_NOISE, ANALYSIS_STATIC, ANALYSIS_TRAN,
     CALC_NOISE, CALC_REACT_JACOBIAN, CALC_REACT_RESIDUAL, CALC_RESIST_JACOBIAN,
     CALC_RESIST_RESIDUAL,
@@ -27,7 +28,7 @@ pub(crate) use osdi_0_4::{
 
 // autogenerated
 #[allow(warnings)]
-mod osdi_0_4;
+mod osdi_0_5;
 mod osdi_device;
 
 #[derive(Default)]
@@ -70,13 +71,23 @@ pub fn compile_va(path: &Utf8Path, opts: &Opts) -> Result<Vec<Box<dyn DeviceImpl
         output: CompilationDestination::Cache { cache_dir },
         include: opts.include.clone(),
         opt_lvl: opts.opt_lvl.unwrap_or(OptLevel::Aggressive),
+        fast_math: FastMathOpts::default(),
         target: Target::host_target()
             .context("openvaf does currently not support this hardware/os")?,
         target_cpu: "native".to_owned(),
         dry_run: false,
+        dump_hir: false,
         dump_mir: false, 
         dump_unopt_mir: false, 
         dump_ir: false, 
+        emit_llvm_ir: false,
+        print_stats: false,
+        print_timing: false,
+        gmin: false,
+        min_typ_max: MinTypMax::Typ,
+        clamp_sqrt: false,
+        limits: CompilationLimits::default(),
+        host_functions: Vec::new(),
     };
 
     let res = openvaf::compile(&openvaf_opts);
@@ -54,6 +54,18 @@ pub const HINT_UNSUPPORTED: &str = "this is allowed by VerilogAMS language spec
 
 // TODO support expansion backtrace
 
+/// Suggests including the standard header that declares `name` (if there is one).
+/// `display_name` is how `name` is displayed to the user (for example with a leading backtick
+/// for macros).
+pub fn missing_std_include_notes(name: &str, display_name: &str) -> Vec<String> {
+    match vfs::va_std::std_header_declaring(name) {
+        Some(header) => vec![format!(
+            "help: '{display_name}' is declared in the standard header \"{header}\"\nadd `include \"{header}\" at the start of the file"
+        )],
+        None => Vec::new(),
+    }
+}
+
 pub fn to_unified_spans<const N: usize>(
     sm: &SourceMap,
     mut spans: [CtxSpan; N],
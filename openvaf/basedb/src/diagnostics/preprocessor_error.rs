@@ -5,7 +5,9 @@ use stdx::iter::zip;
 use syntax::PreprocessorDiagnostic;
 use vfs::FileId;
 
-use crate::diagnostics::{to_unified_spans, Diagnostic, Label, LabelStyle, Report};
+use crate::diagnostics::{
+    missing_std_include_notes, to_unified_spans, Diagnostic, Label, LabelStyle, Report,
+};
 use crate::lints::{self, Lint, LintSrc};
 use crate::BaseDB;
 
@@ -31,27 +33,52 @@ impl Diagnostic for PreprocessorDiagnostic {
                     message: format!("expected {} arguments", expected),
                 }])
             }
-            PreprocessorDiagnostic::MacroNotFound { span, .. } => {
+            PreprocessorDiagnostic::MacroNotFound { ref name, span } => {
                 let span = span.to_file_span(&sm);
 
-                Report::error().with_labels(vec![Label {
+                Report::error()
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: span.file,
+                        range: span.range.into(),
+                        message: "macro not found here".to_owned(),
+                    }])
+                    .with_notes(missing_std_include_notes(name, &format!("`{name}")))
+            }
+            PreprocessorDiagnostic::MacroNotDefined { span, .. } => {
+                let span = span.to_file_span(&sm);
+
+                Report::warning().with_labels(vec![Label {
                     style: LabelStyle::Primary,
                     file_id: span.file,
                     range: span.range.into(),
-                    message: "macro not found here".to_owned(),
+                    message: "macro not defined here".to_owned(),
                 }])
             }
-            PreprocessorDiagnostic::MacroNotDefined { span, .. } => {
+            PreprocessorDiagnostic::MacroRecursion { span, .. } => {
                 let span = span.to_file_span(&sm);
 
-                Report::warning().with_labels(vec![Label {
+                Report::error().with_labels(vec![Label {
                     style: LabelStyle::Primary,
                     file_id: span.file,
                     range: span.range.into(),
-                    message: "macro not defined here".to_owned(),
+                    message: "recursive macro call".to_owned(),
                 }])
             }
-            PreprocessorDiagnostic::MacroRecursion { .. } => todo!(),
+            PreprocessorDiagnostic::MacroDepthLimit { span, .. } => {
+                let span = span.to_file_span(&sm);
+
+                Report::error()
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: span.file,
+                        range: span.range.into(),
+                        message: "macro not expanded".to_owned(),
+                    }])
+                    .with_notes(vec![
+                        "help: the limit can be increased with --macro-depth-limit".to_owned()
+                    ])
+            }
             PreprocessorDiagnostic::UnsupportedCompDir { span, .. } => {
                 let span = span.to_file_span(&sm);
 
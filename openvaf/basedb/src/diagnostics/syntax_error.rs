@@ -567,6 +567,71 @@ impl Diagnostic for SyntaxError {
 
                 Report::error().with_labels(labels)
             }
+            SyntaxError::IntLiteralTooLarge { range } => {
+                let FileSpan { range, file: file_id } = parse.to_file_span(range, &sm);
+                Report::error()
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id,
+                        range: range.into(),
+                        message: "does not fit into a 32 bit integer".to_owned(),
+                    }])
+                    .with_notes(vec![
+                        "help: use a real literal (for example 1e20) for larger numbers".to_owned(),
+                    ])
+            }
+            SyntaxError::MalformedRealLiteral { range } => {
+                let FileSpan { range, file: file_id } = parse.to_file_span(range, &sm);
+                Report::error()
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id,
+                        range: range.into(),
+                        message: "expected digits after the exponent".to_owned(),
+                    }])
+                    .with_notes(
+                        vec!["help: real literals look like 1.5, 1.5e-3 or 1.5m".to_owned()],
+                    )
+            }
+            SyntaxError::IllegalArrayBounds { range } => {
+                let FileSpan { range, file: file_id } = parse.to_file_span(range, &sm);
+                Report::error()
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id,
+                        range: range.into(),
+                        message: "invalid array bounds".to_owned(),
+                    }])
+                    .with_notes(vec!["help: arrays are declared like real x[0:2];".to_owned()])
+            }
+            SyntaxError::ArrayVarDefault { default } => {
+                let FileSpan { range, file: file_id } = parse.to_file_span(default, &sm);
+                Report::error()
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id,
+                        range: range.into(),
+                        message: "not allowed for arrays".to_owned(),
+                    }])
+                    .with_notes(vec![
+                        "help: all elements of an array are initialized to zero".to_owned()
+                    ])
+            }
+            SyntaxError::IllegalArrayParam { param } => {
+                let FileSpan { range, file: file_id } = parse.to_file_span(param, &sm);
+                Report::error()
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id,
+                        range: range.into(),
+                        message: "invalid array parameter".to_owned(),
+                    }])
+                    .with_notes(vec![
+                        "help: array parameters can not be set by the simulator\n\
+                         declare them like localparam real x[0:1] = '{1.0, 2.0};"
+                            .to_owned(),
+                    ])
+            }
         };
 
         report.with_message(self.to_string())
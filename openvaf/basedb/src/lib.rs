@@ -23,6 +23,37 @@ pub trait VfsStorage {
     fn vfs(&self) -> &RwLock<Vfs>;
 }
 
+/// The value that is selected from a `(min:typ:max)` expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MinTypMax {
+    Min,
+    #[default]
+    Typ,
+    Max,
+}
+
+/// Limits that prevent pathological models from exhausting memory (or hanging) during
+/// compilation. Exceeding a limit produces an error instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CompilationLimits {
+    /// Maximum nesting depth of macro expansions.
+    pub macro_depth: u32,
+    /// Maximum number of genvar loop iterations (which are unrolled during compilation) of a
+    /// single analog block. The iterations of all loops count towards this limit, so for
+    /// nested loops every iteration of the inner loop is counted.
+    pub unroll: u32,
+    /// Maximum number of expressions of a single body once all calls of analog functions
+    /// (which are inlined during compilation) are expanded. Non-recursive functions can still
+    /// grow exponentially when inlined (if every function calls the previous one twice).
+    pub expressions: u32,
+}
+
+impl Default for CompilationLimits {
+    fn default() -> Self {
+        CompilationLimits { macro_depth: 256, unroll: 1 << 16, expressions: 1 << 22 }
+    }
+}
+
 #[salsa::query_group(BaseDatabase)]
 pub trait BaseDB: VfsStorage + salsa::Database {
     #[salsa::input]
@@ -34,6 +65,16 @@ pub trait BaseDB: VfsStorage + salsa::Database {
     fn include_dirs(&self, root_file: FileId) -> Arc<[VfsPath]>;
     #[salsa::input]
     fn macro_flags(&self, file_root: FileId) -> Arc<[Arc<str>]>;
+    #[salsa::input]
+    fn min_typ_max(&self, root_file: FileId) -> MinTypMax;
+    /// Lower `sqrt(x)` as `sqrt(max(x, 0))` so that its value and derivative are zero for `x <= 0`.
+    #[salsa::input]
+    fn clamp_sqrt(&self, root_file: FileId) -> bool;
+    #[salsa::input]
+    fn limits(&self, root_file: FileId) -> CompilationLimits;
+    /// See [`SourceProvider::implicit_std_includes`].
+    #[salsa::input]
+    fn implicit_std_includes(&self, root_file: FileId) -> bool;
 
     fn parse(&self, root_file: FileId) -> Parse<SourceFile>;
     fn preprocess(&self, root_file: FileId) -> Preprocess;
@@ -207,6 +248,16 @@ impl SourceProvider for SourceProviderDelegate<'_> {
         self.0.macro_flags(root_file)
     }
 
+    #[inline(always)]
+    fn macro_depth_limit(&self, root_file: FileId) -> u32 {
+        self.0.limits(root_file).macro_depth
+    }
+
+    #[inline(always)]
+    fn implicit_std_includes(&self, root_file: FileId) -> bool {
+        self.0.implicit_std_includes(root_file)
+    }
+
     #[inline(always)]
     fn file_text(&self, file: FileId) -> Result<Arc<str>, FileReadError> {
         self.0.file_text(file)
@@ -253,6 +304,10 @@ impl dyn BaseDB {
 
         let macro_flags: Vec<_> = STANDARD_FLAGS.iter().map(|x| Arc::from(*x)).collect();
         self.set_macro_flags(root_file, Arc::from(macro_flags));
+        self.set_min_typ_max(root_file, MinTypMax::default());
+        self.set_clamp_sqrt(root_file, false);
+        self.set_limits(root_file, CompilationLimits::default());
+        self.set_implicit_std_includes(root_file, false);
 
         self.set_plugin_lints(&[]);
         let overwrites: Arc<[_]> = Arc::from(self.empty_global_lint_overwrites().as_ref());
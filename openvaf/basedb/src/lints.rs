@@ -24,9 +24,9 @@ pub enum LintLevel {
 impl LintLevel {
     pub fn attr(self) -> &'static str {
         match self {
-            LintLevel::Deny => "openvaf_allow",
+            LintLevel::Deny => "openvaf_deny",
             LintLevel::Warn => "openvaf_warn",
-            LintLevel::Allow => "openvaf_deny",
+            LintLevel::Allow => "openvaf_allow",
         }
     }
 }
@@ -163,11 +163,11 @@ pub mod builtin {
     declare_lints! {
         @OFFSET 0;
         // pub const standard_nature_constants = LintData{default_lvl: Warn, documentation_id: 1};
-        // pub const constant_overflow = LintData{default_lvl: Deny, documentation_id: 2};
+        pub const constant_overflow = LintData{default_lvl: Deny, documentation_id: 2};
         // pub const infinite_loop = LintData{default_lvl: Deny, documentation_id: 3};
         pub const macro_overwritten = LintData{default_lvl: Warn, documentation_id: 4};
         // pub const attribute_overwritten = LintData{default_lvl: Warn, documentation_id:5};
-        // pub const rounding_derivative = LintData{default_lvl: Warn, documentation_id: 6};
+        pub const rounding_derivative = LintData{default_lvl: Warn, documentation_id: 6};
         // pub const noise_derivative = LintData{default_lvl: Warn, documentation_id: 7};
         pub const lint_not_found = LintData{default_lvl: Deny, documentation_id: 8};
         pub const lint_level_overwrite = LintData{default_lvl: Warn, documentation_id: 9};
@@ -179,5 +179,15 @@ pub mod builtin {
         pub const variant_const_simparam = LintData{default_lvl: Warn, documentation_id: 15};
         pub const port_without_direction = LintData{default_lvl: Deny, documentation_id: 16};
         pub const trivial_probe = LintData{default_lvl: Warn, documentation_id: 17};
+        pub const nature_mismatch = LintData{default_lvl: Warn, documentation_id: 18};
+        pub const last_crossing_derivative = LintData{default_lvl: Warn, documentation_id: 19};
+        pub const sign_derivative = LintData{default_lvl: Warn, documentation_id: 20};
+        pub const file_io_in_analog_block = LintData{default_lvl: Warn, documentation_id: 21};
+        pub const celsius_temperature = LintData{default_lvl: Allow, documentation_id: 22};
+        pub const conflicting_contribution = LintData{default_lvl: Warn, documentation_id: 23};
+        pub const time_dependent_contribution = LintData{default_lvl: Allow, documentation_id: 24};
+        pub const charge_conservation = LintData{default_lvl: Allow, documentation_id: 25};
+        pub const sqrt_domain = LintData{default_lvl: Allow, documentation_id: 26};
+        pub const no_contributions = LintData{default_lvl: Warn, documentation_id: 27};
     }
 }
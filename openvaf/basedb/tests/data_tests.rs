@@ -89,6 +89,35 @@ fn ui_test(file: &Path) -> Result {
     Ok(())
 }
 
+/// Feeds random sequences of (valid and invalid) tokens to the parser to ensure that
+/// malformed input is always reported as an error instead of crashing the compiler.
+fn garbage_tokens() -> Result {
+    const TOKENS: &str = "module endmodule analog begin end parameter real ( ) ; <+ V I 1e 1.5e+ \
+        1_000k 99999999999999999999 2. $root . inf [ : \" discipline enddiscipline aliasparam \
+        = < >";
+    let tokens: Vec<_> = TOKENS.split_whitespace().collect();
+
+    // simple LCG to keep the test reproducible without additional dependencies
+    let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = |max: usize| {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (seed >> 33) as usize % max
+    };
+
+    for _ in 0..200 {
+        let len = next(64);
+        let src: Vec<_> = (0..len).map(|_| tokens[next(tokens.len())]).collect();
+        let src = src.join(" ");
+        let db = TestDataBase::new(
+            VfsPath::new_virtual_path("/garbage.va".to_owned()),
+            src.into_bytes().into(),
+        );
+        db.parse_and_check();
+    }
+
+    Ok(())
+}
+
 fn ast_test(file: &Path) -> Result {
     let db = TestDataBase::new_from_fs(file);
     let (_, actual) = db.parse_and_check();
@@ -101,5 +130,6 @@ fn ast_test(file: &Path) -> Result {
 harness! {
     Test::from_dir_filtered("integration", &integration_test, &Path::is_dir, &ignore_dev_tests, &project_root().join("integration_tests")),
     Test::from_dir_filtered("ui", &ui_test, &is_va_file, &ignore_never, &openvaf_test_data("syn_ui")),
-    Test::from_dir_filtered("ast", &ast_test, &is_va_file, &ignore_never, &openvaf_test_data("ast"))
+    Test::from_dir_filtered("ast", &ast_test, &is_va_file, &ignore_never, &openvaf_test_data("ast")),
+    Test::new("garbage_tokens", &garbage_tokens)
 }
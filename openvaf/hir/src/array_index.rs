@@ -0,0 +1,329 @@
+//! Detects array accesses with a constant index that is out of bounds
+//! (or that can not be evaluated, for example because it divides by zero).
+//!
+//! Array indices must be known at compile time (this is checked during validation).
+//! Indices usually depend on genvars, therefore genvar loops are unrolled here
+//! (just like during lowering) and the index is checked for every iteration.
+//! Genvar loops whose header can not be evaluated (for example because they exceed
+//! the unroll limit or divide by zero) are reported here as well.
+//!
+//! Analog functions are inlined once for every iteration of the genvar loops that
+//! contain the call, therefore the size of the inlined analog block of a module
+//! is checked against the expression limit here too.
+
+use std::collections::HashSet;
+
+use ahash::HashMap;
+use basedb::diagnostics::{Diagnostic, Label, LabelStyle, Report};
+use basedb::{BaseDB, FileId};
+use hir_def::body::BodySourceMap;
+use hir_def::db::HirDefDB;
+use hir_def::{DefWithBodyId, ExprId, FunctionId, StmtId, Type};
+use hir_ty::db::HirTyDB;
+use hir_ty::inference::{InferenceResult, ResolvedFun};
+use hir_ty::types::Ty;
+use hir_ty::validation::{inlined_size, BodyValidationDiagnostic};
+use syntax::sourcemap::{FileSpan, SourceMap};
+use syntax::{Parse, SourceFile};
+
+use crate::const_eval::{ConstEvalError, ConstEvaluator, ConstValue};
+use crate::{Body, BodyRef, CompilationDB, Parameter, Variable};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ArrayIndexOutOfBounds {
+    expr: ExprId,
+    /// The name of the array variable or parameter.
+    array: String,
+    start: i32,
+    len: u32,
+    index: i32,
+}
+
+/// An array index that can not be evaluated at compile time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct InvalidArrayIndex {
+    expr: ExprId,
+    err: ConstEvalError,
+}
+
+/// A genvar loop that can not be unrolled because evaluating its header failed
+/// (or because it has more iterations than allowed by the unroll limit).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GenvarLoopError {
+    /// The condition of the loop.
+    cond: ExprId,
+    err: ConstEvalError,
+}
+
+impl ArrayIndexOutOfBounds {
+    /// Must only be called for bodies that compiled without errors.
+    /// The last element is an `InlineLimit` diagnostic if the analog block of a module
+    /// exceeds the expression limit once all analog functions are inlined.
+    pub(crate) fn collect(
+        db: &CompilationDB,
+        def: DefWithBodyId,
+    ) -> (
+        Vec<ArrayIndexOutOfBounds>,
+        Vec<InvalidArrayIndex>,
+        Vec<GenvarLoopError>,
+        Option<BodyValidationDiagnostic>,
+    ) {
+        let body = Body::new(def, db);
+        let body_ref = body.borrow();
+        let infer = db.inference_result(def);
+
+        // the size of functions is checked during validation, functions that exceed
+        // the limit by themselves are only reported within that function
+        let mut inline_sizes = HashMap::default();
+        let inline_limit = match def {
+            DefWithBodyId::ModuleId { .. } => {
+                let limit = db.limits(def.file(db)).expressions;
+                let exceeds_limit = infer.resolved_calls.values().any(|call| match *call {
+                    ResolvedFun::User { func, .. } => {
+                        inlined_size(db, func, &mut inline_sizes) > limit as u64
+                    }
+                    _ => false,
+                });
+                (!exceeds_limit).then_some(limit)
+            }
+            _ => None,
+        };
+        let mut checker = IndexChecker {
+            db,
+            body: &db.body(def),
+            body_ref,
+            infer: &infer,
+            eval: ConstEvaluator::new(db),
+            reported: HashSet::new(),
+            res: Vec::new(),
+            invalid_indices: Vec::new(),
+            loop_errors: Vec::new(),
+            inline_limit,
+            inline_sizes,
+            inlined: db.body(def).exprs.len() as u64,
+            inline_error: None,
+        };
+        for &stmt in body_ref.entry() {
+            checker.check_stmt(stmt);
+        }
+        (checker.res, checker.invalid_indices, checker.loop_errors, checker.inline_error)
+    }
+}
+
+struct IndexChecker<'a> {
+    db: &'a CompilationDB,
+    body: &'a hir_def::body::Body,
+    body_ref: BodyRef<'a>,
+    infer: &'a InferenceResult,
+    eval: ConstEvaluator<'a>,
+    /// Every array access is only reported once (and not for every iteration of a genvar loop).
+    reported: HashSet<ExprId>,
+    res: Vec<ArrayIndexOutOfBounds>,
+    invalid_indices: Vec<InvalidArrayIndex>,
+    loop_errors: Vec<GenvarLoopError>,
+    /// The expression limit, `None` if the inlined size is not checked (or already exceeded).
+    inline_limit: Option<u32>,
+    inline_sizes: HashMap<FunctionId, u64>,
+    /// The number of expressions of the body once the functions called so far are inlined.
+    inlined: u64,
+    inline_error: Option<BodyValidationDiagnostic>,
+}
+
+impl IndexChecker<'_> {
+    fn check_stmt(&mut self, stmt: StmtId) {
+        if let hir_def::Stmt::ForLoop { init, cond, incr, body } = self.body.stmts[stmt] {
+            match self.eval.genvar_loop(self.body_ref, init, cond, incr) {
+                Some(Ok((var, vals))) => {
+                    for val in vals {
+                        self.eval.genvars.insert(var, val);
+                        self.check_stmt(body);
+                    }
+                    self.eval.genvars.remove(&var);
+                    return;
+                }
+                Some(Err(err)) => {
+                    if self.reported.insert(cond) {
+                        self.loop_errors.push(GenvarLoopError { cond, err })
+                    }
+                    return;
+                }
+                None => (),
+            }
+        }
+
+        let body = self.body;
+        body.stmts[stmt].walk_child_exprs(|expr| self.check_expr(expr));
+        body.stmts[stmt].walk_child_stmts(|stmt| self.check_stmt(stmt));
+    }
+
+    fn check_expr(&mut self, expr: ExprId) {
+        let body = self.body;
+        if let hir_def::Expr::Index { base, index } = body.exprs[expr] {
+            self.check_index(base, index);
+        }
+        if let Some(&ResolvedFun::User { func, .. }) = self.infer.resolved_calls.get(&expr) {
+            self.check_inline_size(expr, func);
+        }
+        body.exprs[expr].walk_child_exprs(|expr| self.check_expr(expr));
+    }
+
+    fn check_inline_size(&mut self, expr: ExprId, fun: FunctionId) {
+        let limit = match self.inline_limit {
+            Some(limit) => limit,
+            None => return,
+        };
+        self.inlined =
+            self.inlined.saturating_add(inlined_size(self.db, fun, &mut self.inline_sizes));
+        if self.inlined > limit as u64 {
+            self.inline_limit = None;
+            self.inline_error = Some(BodyValidationDiagnostic::InlineLimit { expr, limit });
+        }
+    }
+
+    fn check_index(&mut self, base: ExprId, index: ExprId) {
+        let (len, array, start) = match self.infer.expr_types[base] {
+            Ty::Var(Type::Array { len, .. }, id) => {
+                let var = Variable { id };
+                (len, var.name(self.db), var.array_start(self.db))
+            }
+            Ty::Param(Type::Array { len, .. }, id) => {
+                let param = Parameter { id };
+                (len, param.name(self.db), param.array_start(self.db))
+            }
+            _ => return,
+        };
+        let index_val = match self.eval.eval_expr(self.body_ref, index) {
+            Ok(ConstValue::Int(val)) => val,
+            res => {
+                if self.reported.insert(index) {
+                    let err = res.err().unwrap_or(ConstEvalError::InvalidOperation);
+                    self.invalid_indices.push(InvalidArrayIndex { expr: index, err })
+                }
+                return;
+            }
+        };
+        let in_bounds = index_val
+            .checked_sub(start)
+            .and_then(|idx| u32::try_from(idx).ok())
+            .map_or(false, |idx| idx < len);
+        if !in_bounds && self.reported.insert(index) {
+            self.res.push(ArrayIndexOutOfBounds {
+                expr: index,
+                array,
+                start,
+                len,
+                index: index_val,
+            })
+        }
+    }
+}
+
+pub(crate) struct ArrayIndexOutOfBoundsWrapped<'a> {
+    pub diag: &'a ArrayIndexOutOfBounds,
+    pub body_sm: &'a BodySourceMap,
+    pub parse: &'a Parse<SourceFile>,
+    pub sm: &'a SourceMap,
+}
+
+impl Diagnostic for ArrayIndexOutOfBoundsWrapped<'_> {
+    fn build_report(&self, _root_file: FileId, _db: &dyn BaseDB) -> Report {
+        let ArrayIndexOutOfBounds { expr, ref array, start, len, index } = *self.diag;
+        let FileSpan { range, file } = self
+            .parse
+            .to_file_span(self.body_sm.expr_map_back[expr].as_ref().unwrap().range(), self.sm);
+        let end = start + len as i32 - 1;
+
+        Report::error()
+            .with_message(format!("index {index} is out of bounds for array '{array}'"))
+            .with_labels(vec![Label {
+                style: LabelStyle::Primary,
+                file_id: file,
+                range: range.into(),
+                message: format!("expected an index between {start} and {end}"),
+            }])
+    }
+}
+
+pub(crate) struct InvalidArrayIndexWrapped<'a> {
+    pub diag: &'a InvalidArrayIndex,
+    pub body_sm: &'a BodySourceMap,
+    pub parse: &'a Parse<SourceFile>,
+    pub sm: &'a SourceMap,
+}
+
+impl Diagnostic for InvalidArrayIndexWrapped<'_> {
+    fn build_report(&self, _root_file: FileId, _db: &dyn BaseDB) -> Report {
+        let InvalidArrayIndex { expr, err } = *self.diag;
+        let FileSpan { range, file } = self
+            .parse
+            .to_file_span(self.body_sm.expr_map_back[expr].as_ref().unwrap().range(), self.sm);
+
+        let message = match err {
+            ConstEvalError::InvalidArgument => {
+                "array index calls a function with an argument outside of its domain"
+            }
+            _ => "array index performs an invalid operation",
+        };
+
+        Report::error()
+            .with_message(message.to_owned())
+            .with_labels(vec![Label {
+                style: LabelStyle::Primary,
+                file_id: file,
+                range: range.into(),
+                message: "evaluated at compile time".to_owned(),
+            }])
+            .with_notes(vec![
+                "help: this is usually caused by an integer division by zero".to_owned()
+            ])
+    }
+}
+
+pub(crate) struct GenvarLoopErrorWrapped<'a> {
+    pub diag: &'a GenvarLoopError,
+    pub body_sm: &'a BodySourceMap,
+    pub parse: &'a Parse<SourceFile>,
+    pub sm: &'a SourceMap,
+}
+
+impl Diagnostic for GenvarLoopErrorWrapped<'_> {
+    fn build_report(&self, _root_file: FileId, _db: &dyn BaseDB) -> Report {
+        let GenvarLoopError { cond, err } = *self.diag;
+        let FileSpan { range, file } = self
+            .parse
+            .to_file_span(self.body_sm.expr_map_back[cond].as_ref().unwrap().range(), self.sm);
+
+        let (message, label, note) = match err {
+            ConstEvalError::IterationLimit(limit) => (
+                format!("genvar loops exceed the maximum of {limit} unrolled iterations"),
+                "unrolling this loop exceeds the limit".to_owned(),
+                "help: genvar loops are unrolled during compilation\n\
+                 the iterations of all (nested) loops of a block count towards the limit\n\
+                 the limit can be increased with --unroll-limit",
+            ),
+            ConstEvalError::InvalidArgument => (
+                "genvar loop calls a function with an argument outside of its domain".to_owned(),
+                "while unrolling this loop".to_owned(),
+                "help: genvar loops are unrolled during compilation\n\
+                 the initializer, condition and increment are evaluated at compile time",
+            ),
+            _ => (
+                "genvar loop performs an invalid operation".to_owned(),
+                "while unrolling this loop".to_owned(),
+                "help: genvar loops are unrolled during compilation\n\
+                 the initializer, condition and increment are evaluated at compile time\n\
+                 this is usually caused by an integer division by zero",
+            ),
+        };
+
+        Report::error()
+            .with_message(message)
+            .with_labels(vec![Label {
+                style: LabelStyle::Primary,
+                file_id: file,
+                range: range.into(),
+                message: label,
+            }])
+            .with_notes(vec![note.to_owned()])
+    }
+}
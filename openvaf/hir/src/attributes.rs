@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use basedb::{AstIdMap, BaseDB, ErasedAstId, FileId};
 use syntax::ast::{self};
-use syntax::AstNode;
+use syntax::{AstNode, AstPtr};
 
 use crate::CompilationDB;
 
@@ -32,4 +32,9 @@ impl AstCache {
         };
         attrs.nth(idx)
     }
+
+    /// Returns all attributes that are attached to a statement.
+    pub(crate) fn stmt_attrs(&self, stmt: &AstPtr<ast::Stmt>) -> Vec<ast::Attr> {
+        ast::attrs(stmt.to_node(self.ast.syntax()).syntax()).collect()
+    }
 }
@@ -1,12 +1,13 @@
 use std::sync::Arc;
 
+use basedb::FileId;
 use hir_def::db::HirDefDB;
 use hir_def::DefWithBodyId;
 use hir_ty::db::HirTyDB;
 use hir_ty::inference;
 use hir_ty::types::{Signature, Ty};
 
-pub use hir_def::expr::Event;
+pub use hir_def::expr::{Event, GlobalEvent};
 pub use hir_def::{/*expr::CaseCond,*/ BuiltIn, Case, ExprId, Literal, ParamSysFun, StmtId, Type};
 pub use syntax::ast::{BinaryOp, UnaryOp};
 
@@ -15,26 +16,33 @@ use crate::{BranchWrite, Function, FunctionArg, NatureAttribute, Parameter, Vari
 
 #[derive(Debug, Clone)]
 pub struct Body {
+    id: DefWithBodyId,
     body: Arc<hir_def::body::Body>,
     infere: Arc<inference::InferenceResult>,
 }
 impl Body {
     pub(crate) fn new(id: DefWithBodyId, db: &CompilationDB) -> Body {
-        Body { body: db.body(id), infere: db.inference_result(id) }
+        Body { id, body: db.body(id), infere: db.inference_result(id) }
     }
 
     pub fn borrow(&self) -> BodyRef<'_> {
-        BodyRef { body: &self.body, infere: &self.infere }
+        BodyRef { id: self.id, body: &self.body, infere: &self.infere }
     }
 }
 
 #[derive(Debug, Clone, Copy)]
 pub struct BodyRef<'a> {
+    id: DefWithBodyId,
     body: &'a hir_def::body::Body,
     infere: &'a inference::InferenceResult,
 }
 
 impl<'a> BodyRef<'a> {
+    /// The root file of the item this body belongs to.
+    pub fn file(&self, db: &CompilationDB) -> FileId {
+        self.id.file(db)
+    }
+
     pub fn entry(&self) -> &'a [StmtId] {
         &self.body.entry_stmts
     }
@@ -74,6 +82,15 @@ impl<'a> BodyRef<'a> {
         }
     }
 
+    /// Array elements are only resolved during lowering (the index is usually a genvar),
+    /// here only the array variable is resolved.
+    fn array_var(&self, base: ExprId) -> Variable {
+        match self.infere.expr_types[base] {
+            Ty::Var(_, id) => Variable { id },
+            ref it => panic!("invalid HIR: {:?} is not an array variable", it),
+        }
+    }
+
     pub fn get_call_signature(&self, expr: ExprId) -> Signature {
         self.infere.resolved_signatures.get(&expr).copied().unwrap_or(Signature(u32::MAX))
     }
@@ -85,6 +102,29 @@ impl<'a> BodyRef<'a> {
         }
     }
 
+    /// Whether `expr` is the literal `inf` (or `-inf` if `negative` is set).
+    pub fn is_infinity(&self, expr: ExprId, negative: bool) -> bool {
+        match self.body.exprs[expr] {
+            hir_def::Expr::Literal(Literal::Inf) => !negative,
+            hir_def::Expr::UnaryOp { expr, op: UnaryOp::Neg } => {
+                negative && self.as_literal(expr) == Some(&Literal::Inf)
+            }
+            _ => false,
+        }
+    }
+
+    /// The value of a (possibly negated) integer or real literal.
+    pub fn as_real_literal(&self, expr: ExprId) -> Option<f64> {
+        match self.body.exprs[expr] {
+            hir_def::Expr::Literal(Literal::Float(val)) => Some(val.into()),
+            hir_def::Expr::Literal(Literal::Int(val)) => Some(val as f64),
+            hir_def::Expr::UnaryOp { expr, op: UnaryOp::Neg } => {
+                self.as_real_literal(expr).map(|val| -val)
+            }
+            _ => None,
+        }
+    }
+
     // AB: get integer literal
     pub fn as_literalint(&self, &expr1: &ExprId) -> Option<i32> {
         match &self.body.exprs[expr1] {
@@ -150,6 +190,7 @@ impl<'a> BodyRef<'a> {
                         ResolvedFun::User { func: Function { id: func }, limit }
                     }
                     inference::ResolvedFun::BuiltIn(builtin) => ResolvedFun::BuiltIn(builtin),
+                    inference::ResolvedFun::HostFunction(id) => ResolvedFun::HostFunction(id),
                     // this is a special case, the VAMS standard allows these parameters
                     // to be called like functions (but its the same as direct access)
                     // we hide that detail from downstream users here
@@ -162,6 +203,10 @@ impl<'a> BodyRef<'a> {
                 };
                 Expr::Call { fun, args }
             }
+            hir_def::Expr::Index { base, index } => match self.infere.expr_types[base] {
+                Ty::Param(_, id) => Expr::ParamArrayElement { param: Parameter { id }, index },
+                _ => Expr::ArrayElement { var: self.array_var(base), index },
+            },
             hir_def::Expr::Array(ref args) => Expr::Array(args),
             hir_def::Expr::Literal(ref literal) => Expr::Literal(literal),
             _ => panic!("invalid HIR: {:?}", self.body.exprs[expr]),
@@ -176,16 +221,25 @@ impl<'a> BodyRef<'a> {
         self.get_stmt(self.entry()[i]).unwrap().unwrap_expr()
     }
 
+    /// Returns all (non-empty) statements of this body including nested statements.
+    pub fn stmts(self) -> impl Iterator<Item = (StmtId, Stmt<'a>)> + 'a {
+        self.body.stmts.keys().filter_map(move |stmt| Some((stmt, self.get_stmt(stmt)?)))
+    }
+
     pub fn get_stmt(&self, stmnt: StmtId) -> Option<Stmt<'a>> {
         match self.body.stmts[stmnt] {
             hir_def::Stmt::Empty | hir_def::Stmt::Missing => None,
             hir_def::Stmt::Expr(e) => Some(Stmt::Expr(e)),
-            hir_def::Stmt::EventControl { ref event, body } => {
-                Some(Stmt::EventControl { event, body })
+            hir_def::Stmt::EventControl { ref events, body } => {
+                Some(Stmt::EventControl { events, body })
             }
-            hir_def::Stmt::Assignment { val, .. } => {
+            hir_def::Stmt::Assignment { dst, val, .. } => {
                 let stmt = match self.infere.assignment_destination[&stmnt] {
                     inference::AssignDst::Var(id) => {
+                        if let hir_def::Expr::Index { index, .. } = self.body.exprs[dst] {
+                            let lhs = AssignmentLhs::ArrayElement { var: Variable { id }, index };
+                            return Some(Stmt::Assignment { lhs, rhs: val });
+                        }
                         Stmt::Assignment { lhs: AssignmentLhs::Variable(Variable { id }), rhs: val }
                     }
                     inference::AssignDst::FunVar { fun, arg: None } => Stmt::Assignment {
@@ -227,6 +281,7 @@ pub enum AssignmentLhs {
     Variable(Variable),
     FunctionReturn(Function),
     FunctionArg(FunctionArg),
+    ArrayElement { var: Variable, index: ExprId },
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -238,7 +293,7 @@ pub enum ContributeKind {
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Stmt<'a> {
     Expr(ExprId),
-    EventControl { event: &'a Event, body: StmtId },
+    EventControl { events: &'a [Event], body: StmtId },
     Contribute { kind: ContributeKind, branch: BranchWrite, rhs: ExprId },
     Assignment { lhs: AssignmentLhs, rhs: ExprId },
     Block { body: &'a [StmtId] },
@@ -265,6 +320,9 @@ pub enum Expr<'a> {
     UnaryOp { expr: ExprId, op: UnaryOp },
     Select { cond: ExprId, then_val: ExprId, else_val: ExprId },
     Call { fun: ResolvedFun, args: &'a [ExprId] },
+    ArrayElement { var: Variable, index: ExprId },
+    /// An element of a (constant) array parameter.
+    ParamArrayElement { param: Parameter, index: ExprId },
     Array(&'a [ExprId]),
     Literal(&'a Literal),
 }
@@ -282,6 +340,7 @@ impl Expr<'_> {
             Expr::Read(Ref::Variable(var)) => AssignmentLhs::Variable(var),
             Expr::Read(Ref::FunctionArg(arg)) => AssignmentLhs::FunctionArg(arg),
             Expr::Read(Ref::FunctionReturn(fun)) => AssignmentLhs::FunctionReturn(fun),
+            Expr::ArrayElement { var, index } => AssignmentLhs::ArrayElement { var, index },
             _ => panic!("{self:?} is not a lhs reference"),
         }
     }
@@ -301,4 +360,6 @@ pub enum Ref {
 pub enum ResolvedFun {
     User { func: Function, limit: bool },
     BuiltIn(BuiltIn),
+    /// See [`CompilationDB::host_function`](crate::CompilationDB::host_function).
+    HostFunction(u32),
 }
@@ -0,0 +1,397 @@
+//! Compile time evaluation of parameter defaults and bounds.
+//!
+//! Most parameter defaults are simple constant expressions (possibly referring to other
+//! parameters). Evaluating these at compile time allows reporting concrete default values
+//! in model metadata without generating and running any code.
+//!
+//! The same machinery is used to compute the iterations of genvar loops which are unrolled
+//! during lowering.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use basedb::BaseDB;
+use hir_def::{BuiltIn, ExprId, Literal, StmtId, Type};
+use syntax::ast::{BinaryOp, UnaryOp};
+
+use crate::{
+    AssignmentLhs, BodyRef, CompilationDB, Expr, Parameter, Ref, ResolvedFun, Stmt, Variable,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstValue {
+    Real(f64),
+    Int(i32),
+    Bool(bool),
+    Str(Box<str>),
+}
+
+impl ConstValue {
+    pub(crate) fn cast(self, dst: &Type) -> ConstValue {
+        match (self, dst) {
+            (ConstValue::Int(val), Type::Real) => ConstValue::Real(val as f64),
+            (ConstValue::Real(val), Type::Integer) => ConstValue::Int(val.round() as i32),
+            (ConstValue::Bool(val), Type::Real) => ConstValue::Real(val as i32 as f64),
+            (ConstValue::Bool(val), Type::Integer) => ConstValue::Int(val as i32),
+            (ConstValue::Real(val), Type::Bool) => ConstValue::Bool(val != 0.0),
+            (ConstValue::Int(val), Type::Bool) => ConstValue::Bool(val != 0),
+            (val, _) => val,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstEvalError {
+    /// The expression depends on a value that is only known at runtime
+    /// (for example `$mfactor` or an operating point dependent function).
+    NotConstant,
+    /// The default value of this parameter (indirectly) depends on itself.
+    Cycle(Parameter),
+    /// The expression performs an invalid operation like an integer division by zero.
+    InvalidOperation,
+    /// A function is called with an argument outside of its domain (like `$clog2(0)`).
+    InvalidArgument,
+    /// The genvar loops unrolled by an evaluator have more iterations (in total) than allowed
+    /// by the unroll limit.
+    IterationLimit(u32),
+}
+
+/// Evaluates parameter defaults (and bounds) at compile time.
+///
+/// Defaults may refer to other parameters in any order. These are evaluated on demand
+/// (in topological order) and the results are cached so that every default is only evaluated
+/// once. Parameter overrides are not considered, the results are always the declared defaults.
+///
+/// The evaluator must only be used for modules that compiled without errors.
+pub struct ConstEvaluator<'a> {
+    db: &'a CompilationDB,
+    defaults: HashMap<Parameter, Option<Result<ConstValue, ConstEvalError>>>,
+    pub(crate) genvars: HashMap<Variable, ConstValue>,
+    /// The number of genvar loop iterations unrolled by this evaluator so far.
+    unrolled: u32,
+}
+
+impl<'a> ConstEvaluator<'a> {
+    pub fn new(db: &'a CompilationDB) -> ConstEvaluator<'a> {
+        ConstEvaluator { db, defaults: HashMap::new(), genvars: HashMap::new(), unrolled: 0 }
+    }
+
+    /// Sets the values of the genvars of the genvar loops that enclose the evaluated expressions.
+    pub fn with_genvars(
+        mut self,
+        genvars: impl IntoIterator<Item = (Variable, ConstValue)>,
+    ) -> Self {
+        self.genvars.extend(genvars);
+        self
+    }
+
+    /// Evaluates the default value of `param`.
+    pub fn param_default(&mut self, param: Parameter) -> Result<ConstValue, ConstEvalError> {
+        match self.defaults.entry(param) {
+            Entry::Occupied(entry) => match entry.get() {
+                Some(res) => return res.clone(),
+                // the default of param is currently being evaluated
+                None => return Err(ConstEvalError::Cycle(param)),
+            },
+            Entry::Vacant(entry) => {
+                entry.insert(None);
+            }
+        }
+
+        let res = self.eval_param_expr(param, param.default(self.db));
+        self.defaults.insert(param, Some(res.clone()));
+        res
+    }
+
+    /// Evaluates the element of the array parameter `param` at `index`
+    /// (as written in the source, so relative to the declared bounds).
+    pub fn param_array_element(
+        &mut self,
+        param: Parameter,
+        index: i32,
+    ) -> Result<ConstValue, ConstEvalError> {
+        let elements = param.array_elements(self.db).ok_or(ConstEvalError::NotConstant)?;
+        let element = index
+            .checked_sub(param.array_start(self.db))
+            .and_then(|idx| elements.get(usize::try_from(idx).ok()?))
+            .copied()
+            .ok_or(ConstEvalError::InvalidOperation)?;
+        let ty = match param.ty(self.db) {
+            Type::Array { ty, .. } => *ty,
+            _ => return Err(ConstEvalError::NotConstant),
+        };
+
+        // an element that depends on its own array is a cycle (just like for scalar parameters)
+        if let Some(None) = self.defaults.get(&param) {
+            return Err(ConstEvalError::Cycle(param));
+        }
+        let prev = self.defaults.insert(param, None);
+        let res = self.eval_param_expr(param, element).map(|val| val.cast(&ty));
+        match prev {
+            Some(prev) => self.defaults.insert(param, prev),
+            None => self.defaults.remove(&param),
+        };
+        res
+    }
+
+    /// Evaluates an expression that belongs to the declaration of `param`
+    /// (its default or one of its bounds).
+    pub fn eval_param_expr(
+        &mut self,
+        param: Parameter,
+        expr: ExprId,
+    ) -> Result<ConstValue, ConstEvalError> {
+        let body = param.init(self.db);
+        self.eval_expr(body.borrow(), expr)
+    }
+
+    /// Returns the genvar of a genvar loop (`for (init; cond; incr)`) together with the values
+    /// it takes in each iteration. `None` is returned if `init` does not assign a genvar.
+    pub fn genvar_loop(
+        &mut self,
+        body: BodyRef,
+        init: StmtId,
+        cond: ExprId,
+        incr: StmtId,
+    ) -> Option<Result<(Variable, Vec<ConstValue>), ConstEvalError>> {
+        let genvar_assignment = |stmt| match body.get_stmt(stmt) {
+            Some(Stmt::Assignment { lhs: AssignmentLhs::Variable(var), rhs })
+                if var.is_genvar(self.db) =>
+            {
+                Some((var, rhs))
+            }
+            _ => None,
+        };
+
+        let (var, init) = genvar_assignment(init)?;
+        let incr = match genvar_assignment(incr) {
+            Some((incr_var, incr)) if incr_var == var => incr,
+            _ => return Some(Err(ConstEvalError::NotConstant)),
+        };
+
+        // protects against loops that never terminate (or would exhaust memory when unrolled).
+        // The limit applies to all loops unrolled by this evaluator together, so that nested
+        // loops (whose iteration counts multiply) can not exceed it either.
+        let limit = self.db.limits(self.db.compilation_unit().root_file()).unroll;
+        let res = (|| {
+            let mut vals = Vec::new();
+            let mut val = self.eval_expr(body, init)?;
+            loop {
+                self.genvars.insert(var, val.clone());
+                match self.eval_expr(body, cond)? {
+                    ConstValue::Bool(true) if self.unrolled < limit => self.unrolled += 1,
+                    ConstValue::Bool(true) => return Err(ConstEvalError::IterationLimit(limit)),
+                    ConstValue::Bool(false) => return Ok(vals),
+                    _ => return Err(ConstEvalError::InvalidOperation),
+                }
+                vals.push(val);
+                val = self.eval_expr(body, incr)?;
+            }
+        })();
+        self.genvars.remove(&var);
+        Some(res.map(|vals| (var, vals)))
+    }
+
+    /// Evaluates `expr` (an expression within `body`).
+    pub fn eval_expr(&mut self, body: BodyRef, expr: ExprId) -> Result<ConstValue, ConstEvalError> {
+        let mut res = match body.get_expr(expr) {
+            Expr::Literal(lit) => match *lit {
+                Literal::String(ref val) => ConstValue::Str(val.clone()),
+                Literal::Int(val) => ConstValue::Int(val),
+                Literal::Float(val) => ConstValue::Real(val.into()),
+                Literal::Inf => match body.expr_type(expr) {
+                    Type::Integer => ConstValue::Int(i32::MAX),
+                    _ => ConstValue::Real(f64::INFINITY),
+                },
+            },
+            Expr::Read(Ref::Parameter(param)) => self.param_default(param)?,
+            Expr::Read(Ref::Variable(var)) if self.genvars.contains_key(&var) => {
+                self.genvars[&var].clone()
+            }
+            Expr::Read(Ref::NatureAttr(attr)) => {
+                let body = attr.value(self.db);
+                let body = body.borrow();
+                self.eval_expr(body, body.get_entry_expr(0))?
+            }
+            Expr::Read(_) => return Err(ConstEvalError::NotConstant),
+            Expr::UnaryOp { expr: arg, op } => {
+                if op == UnaryOp::Neg && body.as_literal(arg) == Some(&Literal::Inf) {
+                    match body.expr_type(arg) {
+                        Type::Integer => ConstValue::Int(i32::MIN),
+                        _ => ConstValue::Real(f64::NEG_INFINITY),
+                    }
+                } else {
+                    let arg = self.eval_expr(body, arg)?;
+                    eval_unary(op, arg)?
+                }
+            }
+            Expr::BinaryOp { lhs, rhs, op } => {
+                let lhs = self.eval_expr(body, lhs)?;
+                // only evaluate the rhs if required, just like at runtime
+                match (op, &lhs) {
+                    (BinaryOp::BooleanOr, ConstValue::Bool(true)) => ConstValue::Bool(true),
+                    (BinaryOp::BooleanAnd, ConstValue::Bool(false)) => ConstValue::Bool(false),
+                    _ => {
+                        let rhs = self.eval_expr(body, rhs)?;
+                        eval_binary(op, lhs, rhs)?
+                    }
+                }
+            }
+            Expr::Select { cond, then_val, else_val } => match self.eval_expr(body, cond)? {
+                ConstValue::Bool(true) => self.eval_expr(body, then_val)?,
+                ConstValue::Bool(false) => self.eval_expr(body, else_val)?,
+                _ => return Err(ConstEvalError::InvalidOperation),
+            },
+            Expr::ParamArrayElement { param, index } => match self.eval_expr(body, index)? {
+                ConstValue::Int(index) => self.param_array_element(param, index)?,
+                _ => return Err(ConstEvalError::InvalidOperation),
+            },
+            Expr::Call { fun: ResolvedFun::BuiltIn(builtin), args } => {
+                let args = args
+                    .iter()
+                    .map(|&arg| self.eval_expr(body, arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+                eval_builtin(builtin, &args)?
+            }
+            Expr::Call { .. } | Expr::ArrayElement { .. } | Expr::Array(_) => {
+                return Err(ConstEvalError::NotConstant)
+            }
+        };
+
+        if let Some((_, dst)) = body.needs_cast(expr) {
+            res = res.cast(dst);
+        }
+        Ok(res)
+    }
+}
+
+pub(crate) fn eval_unary(op: UnaryOp, arg: ConstValue) -> Result<ConstValue, ConstEvalError> {
+    let res = match (op, arg) {
+        (UnaryOp::Identity, arg) => arg,
+        (UnaryOp::Neg, ConstValue::Real(val)) => ConstValue::Real(-val),
+        (UnaryOp::Neg, ConstValue::Int(val)) => ConstValue::Int(val.wrapping_neg()),
+        (UnaryOp::BitNegate, ConstValue::Int(val)) => ConstValue::Int(!val),
+        (UnaryOp::Not, ConstValue::Bool(val)) => ConstValue::Bool(!val),
+        _ => return Err(ConstEvalError::InvalidOperation),
+    };
+    Ok(res)
+}
+
+pub(crate) fn eval_binary(
+    op: BinaryOp,
+    lhs: ConstValue,
+    rhs: ConstValue,
+) -> Result<ConstValue, ConstEvalError> {
+    use ConstValue::{Bool, Int, Real};
+
+    let res = match (op, lhs, rhs) {
+        (BinaryOp::BooleanOr, Bool(lhs), Bool(rhs)) => Bool(lhs || rhs),
+        (BinaryOp::BooleanAnd, Bool(lhs), Bool(rhs)) => Bool(lhs && rhs),
+
+        (BinaryOp::EqualityTest, lhs, rhs) => Bool(lhs == rhs),
+        (BinaryOp::NegatedEqualityTest, lhs, rhs) => Bool(lhs != rhs),
+
+        (BinaryOp::LesserTest, Int(lhs), Int(rhs)) => Bool(lhs < rhs),
+        (BinaryOp::LesserEqualTest, Int(lhs), Int(rhs)) => Bool(lhs <= rhs),
+        (BinaryOp::GreaterTest, Int(lhs), Int(rhs)) => Bool(lhs > rhs),
+        (BinaryOp::GreaterEqualTest, Int(lhs), Int(rhs)) => Bool(lhs >= rhs),
+        (BinaryOp::LesserTest, Real(lhs), Real(rhs)) => Bool(lhs < rhs),
+        (BinaryOp::LesserEqualTest, Real(lhs), Real(rhs)) => Bool(lhs <= rhs),
+        (BinaryOp::GreaterTest, Real(lhs), Real(rhs)) => Bool(lhs > rhs),
+        (BinaryOp::GreaterEqualTest, Real(lhs), Real(rhs)) => Bool(lhs >= rhs),
+
+        (BinaryOp::Addition, Int(lhs), Int(rhs)) => Int(lhs.wrapping_add(rhs)),
+        (BinaryOp::Subtraction, Int(lhs), Int(rhs)) => Int(lhs.wrapping_sub(rhs)),
+        (BinaryOp::Multiplication, Int(lhs), Int(rhs)) => Int(lhs.wrapping_mul(rhs)),
+        (BinaryOp::Division, Int(lhs), Int(rhs)) => {
+            Int(lhs.checked_div(rhs).ok_or(ConstEvalError::InvalidOperation)?)
+        }
+        (BinaryOp::Remainder, Int(lhs), Int(rhs)) => {
+            Int(lhs.checked_rem(rhs).ok_or(ConstEvalError::InvalidOperation)?)
+        }
+        (BinaryOp::LeftShift, Int(lhs), Int(rhs)) => Int(lhs.wrapping_shl(rhs as u32)),
+        (BinaryOp::RightShift, Int(lhs), Int(rhs)) => Int(lhs.wrapping_shr(rhs as u32)),
+        (BinaryOp::BitwiseXor, Int(lhs), Int(rhs)) => Int(lhs ^ rhs),
+        (BinaryOp::BitwiseEq, Int(lhs), Int(rhs)) => Int(!(lhs ^ rhs)),
+        (BinaryOp::BitwiseOr, Int(lhs), Int(rhs)) => Int(lhs | rhs),
+        (BinaryOp::BitwiseAnd, Int(lhs), Int(rhs)) => Int(lhs & rhs),
+        (BinaryOp::Power, Int(lhs), Int(rhs)) => Real((lhs as f64).powf(rhs as f64)),
+
+        (BinaryOp::Addition, Real(lhs), Real(rhs)) => Real(lhs + rhs),
+        (BinaryOp::Subtraction, Real(lhs), Real(rhs)) => Real(lhs - rhs),
+        (BinaryOp::Multiplication, Real(lhs), Real(rhs)) => Real(lhs * rhs),
+        (BinaryOp::Division, Real(lhs), Real(rhs)) => Real(lhs / rhs),
+        (BinaryOp::Remainder, Real(lhs), Real(rhs)) => Real(lhs % rhs),
+        (BinaryOp::Power, Real(lhs), Real(rhs)) => Real(lhs.powf(rhs)),
+
+        _ => return Err(ConstEvalError::InvalidOperation),
+    };
+    Ok(res)
+}
+
+pub(crate) fn eval_builtin(
+    builtin: BuiltIn,
+    args: &[ConstValue],
+) -> Result<ConstValue, ConstEvalError> {
+    use ConstValue::{Int, Real};
+
+    let res = match (builtin, args) {
+        (BuiltIn::abs, [Int(val)]) => Int(val.wrapping_abs()),
+        (BuiltIn::sign | BuiltIn::signum, [Int(val)]) => Int(val.signum()),
+        (BuiltIn::min, [Int(lhs), Int(rhs)]) => Int(*lhs.min(rhs)),
+        (BuiltIn::max, [Int(lhs), Int(rhs)]) => Int(*lhs.max(rhs)),
+
+        (BuiltIn::abs, [Real(val)]) => Real(val.abs()),
+        // unlike f64::signum the sign of zero (and NaN) is zero
+        (BuiltIn::sign | BuiltIn::signum, [Real(val)]) => Real(if *val < 0.0 {
+            -1.0
+        } else if *val > 0.0 {
+            1.0
+        } else {
+            0.0
+        }),
+        (BuiltIn::min, [Real(lhs), Real(rhs)]) => Real(lhs.min(*rhs)),
+        (BuiltIn::max, [Real(lhs), Real(rhs)]) => Real(lhs.max(*rhs)),
+        (BuiltIn::pow, [Real(lhs), Real(rhs)]) => Real(lhs.powf(*rhs)),
+        (BuiltIn::hypot, [Real(lhs), Real(rhs)]) => Real(lhs.hypot(*rhs)),
+        (BuiltIn::atan2, [Real(lhs), Real(rhs)]) => Real(lhs.atan2(*rhs)),
+        (BuiltIn::rtoi, [Real(val)]) => Int(val.trunc() as i32),
+        (BuiltIn::itor, [Int(val)]) => Real(*val as f64),
+        (BuiltIn::clog2, [Int(val)]) if *val < 1 => return Err(ConstEvalError::InvalidArgument),
+        (BuiltIn::clog2, [Int(val)]) => Int(32 - (val - 1).leading_zeros() as i32),
+
+        (_, [Real(val)]) => {
+            let fun: fn(f64) -> f64 = match builtin {
+                BuiltIn::sqrt => f64::sqrt,
+                BuiltIn::exp => f64::exp,
+                BuiltIn::ln => f64::ln,
+                BuiltIn::log => f64::log10,
+                BuiltIn::floor => f64::floor,
+                BuiltIn::ceil => f64::ceil,
+                BuiltIn::rint => f64::round_ties_even,
+                BuiltIn::round => f64::round,
+                BuiltIn::trunc => f64::trunc,
+                BuiltIn::sin => f64::sin,
+                BuiltIn::cos => f64::cos,
+                BuiltIn::tan => f64::tan,
+                BuiltIn::asin => f64::asin,
+                BuiltIn::acos => f64::acos,
+                BuiltIn::atan => f64::atan,
+                BuiltIn::sinh => f64::sinh,
+                BuiltIn::cosh => f64::cosh,
+                BuiltIn::tanh => f64::tanh,
+                BuiltIn::asinh => f64::asinh,
+                BuiltIn::acosh => f64::acosh,
+                BuiltIn::atanh => f64::atanh,
+                _ => return Err(ConstEvalError::NotConstant),
+            };
+            Real(fun(*val))
+        }
+
+        _ => return Err(ConstEvalError::NotConstant),
+    };
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,296 @@
+use crate::{
+    CompilationDB, ConstEvalError, ConstEvaluator, ConstValue, Scope, ScopeDef, Stmt, Type,
+};
+
+fn param_results(src: &str) -> Vec<(String, Result<ConstValue, ConstEvalError>)> {
+    let db = CompilationDB::new_virtual(src).unwrap();
+    let module = db.compilation_unit().modules(&db)[0];
+    let mut evaluator = ConstEvaluator::new(&db);
+    Scope::Module(module)
+        .declarations(&db)
+        .into_iter()
+        .filter_map(|(name, def)| match def {
+            // array parameters only have constant elements
+            ScopeDef::Parameter(param) if matches!(param.ty(&db), Type::Array { .. }) => None,
+            ScopeDef::Parameter(param) => Some((name.to_string(), evaluator.param_default(param))),
+            _ => None,
+        })
+        .collect()
+}
+
+fn param_defaults(src: &str) -> Vec<(String, ConstValue)> {
+    param_results(src).into_iter().map(|(name, res)| (name, res.unwrap())).collect()
+}
+
+/// Returns the values the genvar of the first genvar loop in the analog block takes.
+fn genvar_values(src: &str) -> Result<Vec<ConstValue>, ConstEvalError> {
+    let db = CompilationDB::new_virtual(src).unwrap();
+    let module = db.compilation_unit().modules(&db)[0];
+    let body = module.analog_block(&db);
+    let body = body.borrow();
+    let (init, cond, incr) = body
+        .stmts()
+        .find_map(|(_, stmt)| match stmt {
+            Stmt::ForLoop { init, cond, incr, .. } => Some((init, cond, incr)),
+            _ => None,
+        })
+        .unwrap();
+    let res = ConstEvaluator::new(&db).genvar_loop(body, init, cond, incr).unwrap();
+    res.map(|(_, vals)| vals)
+}
+
+#[test]
+fn sign() {
+    let src = r#"
+        module test;
+            parameter real neg = sign(-2.0);
+            parameter real zero = sign(-0.0);
+            parameter real pos = $signum(1e-300);
+            parameter integer neg_int = sign(-5);
+            parameter integer zero_int = $signum(0);
+        endmodule
+    "#;
+    let expected = [
+        ("neg", ConstValue::Real(-1.0)),
+        ("zero", ConstValue::Real(0.0)),
+        ("pos", ConstValue::Real(1.0)),
+        ("neg_int", ConstValue::Int(-1)),
+        ("zero_int", ConstValue::Int(0)),
+    ];
+    let expected: Vec<_> = expected.into_iter().map(|(name, val)| (name.to_owned(), val)).collect();
+    assert_eq!(param_defaults(src), expected);
+}
+
+#[test]
+fn clog2() {
+    let src = r#"
+        module test;
+            parameter integer one = $clog2(1);
+            parameter integer eight = $clog2(8);
+            parameter integer nine = $clog2(9);
+            parameter integer max = $clog2(2147483647);
+        endmodule
+    "#;
+    let expected = [
+        ("one", ConstValue::Int(0)),
+        ("eight", ConstValue::Int(3)),
+        ("nine", ConstValue::Int(4)),
+        ("max", ConstValue::Int(31)),
+    ];
+    let expected: Vec<_> = expected.into_iter().map(|(name, val)| (name.to_owned(), val)).collect();
+    assert_eq!(param_defaults(src), expected);
+}
+
+#[test]
+fn select_unification() {
+    let src = r#"
+        module test;
+            parameter integer sel = 1;
+            parameter int_real = sel ? 1 : 2.0;
+            parameter real_int = sel == 0 ? 2.0 : 3;
+            parameter int_int = sel ? 4 : 5;
+        endmodule
+    "#;
+    let expected = [
+        ("sel", ConstValue::Int(1)),
+        ("int_real", ConstValue::Real(1.0)),
+        ("real_int", ConstValue::Real(3.0)),
+        ("int_int", ConstValue::Int(4)),
+    ];
+    let expected: Vec<_> = expected.into_iter().map(|(name, val)| (name.to_owned(), val)).collect();
+    assert_eq!(param_defaults(src), expected);
+}
+
+#[test]
+fn array_elements() {
+    let src = r#"
+        module test;
+            localparam real r[1:2] = '{1.5, 2};
+            localparam integer second = 2;
+            parameter real first_elem = r[1];
+            parameter real second_elem = r[second];
+        endmodule
+    "#;
+    let expected = [
+        ("second", ConstValue::Int(2)),
+        ("first_elem", ConstValue::Real(1.5)),
+        ("second_elem", ConstValue::Real(2.0)),
+    ];
+    let expected: Vec<_> = expected.into_iter().map(|(name, val)| (name.to_owned(), val)).collect();
+    assert_eq!(param_defaults(src), expected);
+}
+
+#[test]
+fn arithmetic() {
+    let src = r#"
+        module test;
+            parameter integer int_ops = (7 + 3) * 2 - 1;
+            parameter integer int_div = -7 / 2;
+            parameter integer int_rem = 7 % 3;
+            parameter integer shift = (1 << 4) >> 1;
+            parameter real real_ops = 1.5 * 4 - 1 / 4.0;
+            parameter real pow = 2 ** 10;
+            parameter real mixed = 1 + 0.5;
+            parameter integer rounded = 2.5;
+            parameter real real_div = 1.0 / 0.0;
+        endmodule
+    "#;
+    let expected = [
+        ("int_ops", ConstValue::Int(19)),
+        ("int_div", ConstValue::Int(-3)),
+        ("int_rem", ConstValue::Int(1)),
+        ("shift", ConstValue::Int(8)),
+        ("real_ops", ConstValue::Real(5.75)),
+        ("pow", ConstValue::Real(1024.0)),
+        ("mixed", ConstValue::Real(1.5)),
+        ("rounded", ConstValue::Int(3)),
+        ("real_div", ConstValue::Real(f64::INFINITY)),
+    ];
+    let expected: Vec<_> = expected.into_iter().map(|(name, val)| (name.to_owned(), val)).collect();
+    assert_eq!(param_defaults(src), expected);
+}
+
+#[test]
+fn int_overflow() {
+    let src = r#"
+        module test;
+            parameter integer add = 2147483647 + 1;
+            parameter integer sub = -2147483647 - 2;
+            parameter integer mul = 65536 * 65536;
+            parameter integer neg = -(-2147483647 - 1);
+        endmodule
+    "#;
+    let expected = [
+        ("add", ConstValue::Int(i32::MIN)),
+        ("sub", ConstValue::Int(i32::MAX)),
+        ("mul", ConstValue::Int(0)),
+        ("neg", ConstValue::Int(i32::MIN)),
+    ];
+    let expected: Vec<_> = expected.into_iter().map(|(name, val)| (name.to_owned(), val)).collect();
+    assert_eq!(param_defaults(src), expected);
+}
+
+#[test]
+fn invalid_operations() {
+    let src = r#"
+        module test;
+            parameter integer zero = 0;
+            parameter integer div = 1 / zero;
+            parameter integer rem = 5 % 0;
+            parameter integer overflow_div = (-2147483647 - 1) / -1;
+            parameter integer depends_on_div = div + 1;
+            parameter integer clog2 = $clog2(zero);
+        endmodule
+    "#;
+    let expected = [
+        ("zero", Ok(ConstValue::Int(0))),
+        ("div", Err(ConstEvalError::InvalidOperation)),
+        ("rem", Err(ConstEvalError::InvalidOperation)),
+        ("overflow_div", Err(ConstEvalError::InvalidOperation)),
+        ("depends_on_div", Err(ConstEvalError::InvalidOperation)),
+        ("clog2", Err(ConstEvalError::InvalidArgument)),
+    ];
+    let expected: Vec<_> = expected.into_iter().map(|(name, val)| (name.to_owned(), val)).collect();
+    assert_eq!(param_results(src), expected);
+}
+
+#[test]
+fn param_dependencies() {
+    let src = r#"
+        module test;
+            parameter real a = 2 * b;
+            parameter real b = 1.5;
+            localparam integer c = b > 1 ? 10 : 20;
+            parameter real d = max(a, c) + $mfactor;
+        endmodule
+    "#;
+    let results = param_results(src);
+    assert_eq!(results[0], ("a".to_owned(), Ok(ConstValue::Real(3.0))));
+    assert_eq!(results[1], ("b".to_owned(), Ok(ConstValue::Real(1.5))));
+    assert_eq!(results[2], ("c".to_owned(), Ok(ConstValue::Int(10))));
+    assert_eq!(results[3], ("d".to_owned(), Err(ConstEvalError::NotConstant)));
+}
+
+#[test]
+fn param_cycle() {
+    let src = r#"
+        module test;
+            parameter real a = b + 1;
+            parameter real b = a * 2;
+        endmodule
+    "#;
+    let results = param_results(src);
+    assert!(matches!(results[0].1, Err(ConstEvalError::Cycle(_))));
+    assert!(matches!(results[1].1, Err(ConstEvalError::Cycle(_))));
+}
+
+#[test]
+fn genvar_loop() {
+    let src = r#"
+        module test;
+            parameter integer n = 3;
+            genvar i;
+            real x;
+            analog begin
+                for (i = 1; i <= 2 * n; i = i + 2)
+                    x = x + i;
+            end
+        endmodule
+    "#;
+    let expected = vec![ConstValue::Int(1), ConstValue::Int(3), ConstValue::Int(5)];
+    assert_eq!(genvar_values(src), Ok(expected));
+
+    let src = r#"
+        module test;
+            genvar i;
+            real x;
+            analog begin
+                for (i = 0; i >= 0; i = i + 1)
+                    x = x + i;
+            end
+        endmodule
+    "#;
+    assert_eq!(genvar_values(src), Err(ConstEvalError::IterationLimit(1 << 16)));
+}
+
+#[test]
+fn conversions() {
+    let src = r#"
+        module test;
+            parameter integer trunc_pos = $rtoi(2.7);
+            parameter integer trunc_neg = $rtoi(-2.7);
+            parameter integer rounded = 2.7;
+            parameter real half = $itor(7) / 2;
+            parameter real int_half = 7 / 2;
+        endmodule
+    "#;
+    let expected = [
+        ("trunc_pos", ConstValue::Int(2)),
+        ("trunc_neg", ConstValue::Int(-2)),
+        ("rounded", ConstValue::Int(3)),
+        ("half", ConstValue::Real(3.5)),
+        ("int_half", ConstValue::Real(3.0)),
+    ];
+    let expected: Vec<_> = expected.into_iter().map(|(name, val)| (name.to_owned(), val)).collect();
+    assert_eq!(param_defaults(src), expected);
+}
+
+#[test]
+fn rounding() {
+    let src = r#"
+        module test;
+            parameter real rint_even = $rint(2.5);
+            parameter real rint_odd = $rint(-3.5);
+            parameter real round = $round(-2.5);
+            parameter real trunc = $trunc(-2.7);
+        endmodule
+    "#;
+    let expected = [
+        ("rint_even", ConstValue::Real(2.0)),
+        ("rint_odd", ConstValue::Real(-4.0)),
+        ("round", ConstValue::Real(-3.0)),
+        ("trunc", ConstValue::Real(-2.0)),
+    ];
+    let expected: Vec<_> = expected.into_iter().map(|(name, val)| (name.to_owned(), val)).collect();
+    assert_eq!(param_defaults(src), expected);
+}
@@ -0,0 +1,193 @@
+//! Detects constant expressions that evaluate to a non-finite value (or can not be
+//! evaluated at all) at compile time.
+//!
+//! Such expressions are folded during compilation and would silently bake `inf`/`NaN`
+//! into the compiled model. This is almost always caused by an accidental overflow
+//! (for example `pow(1e300, 2)`), a division by zero or a math function that is
+//! called outside of its domain (`ln(0)`, `sqrt(-1)`).
+
+use std::collections::HashMap;
+
+use basedb::diagnostics::{Diagnostic, Label, LabelStyle, Report};
+use basedb::lints::builtin::constant_overflow;
+use basedb::lints::{Lint, LintSrc};
+use basedb::{BaseDB, FileId};
+use hir_def::body::{Body, BodySourceMap};
+use hir_def::{ExprId, Literal, StmtId};
+use hir_ty::inference::{InferenceResult, ResolvedFun};
+use syntax::ast::BinaryOp;
+use syntax::sourcemap::{FileSpan, SourceMap};
+use syntax::{Parse, SourceFile};
+
+use crate::const_eval::{eval_binary, eval_builtin, eval_unary, ConstEvalError, ConstValue};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct NonFiniteConstant {
+    expr: ExprId,
+    stmt: StmtId,
+    val: Result<ConstValue, ConstEvalError>,
+}
+
+impl NonFiniteConstant {
+    pub(crate) fn collect(body: &Body, infer: &InferenceResult) -> Vec<NonFiniteConstant> {
+        let mut folder =
+            ConstFolder { body, infer, vals: HashMap::new(), res: Vec::new(), stmt: None };
+        for (stmt, data) in body.stmts.iter_enumerated() {
+            folder.stmt = Some(stmt);
+            data.walk_child_exprs(|expr| {
+                folder.fold(expr);
+            });
+        }
+        folder.res
+    }
+}
+
+struct ConstFolder<'a> {
+    body: &'a Body,
+    infer: &'a InferenceResult,
+    vals: HashMap<ExprId, Option<ConstValue>>,
+    res: Vec<NonFiniteConstant>,
+    stmt: Option<StmtId>,
+}
+
+impl ConstFolder<'_> {
+    /// Evaluates `expr` if it only consists of literals and pure (math) functions.
+    /// Only the innermost expression that produces a non-finite value is reported.
+    fn fold(&mut self, expr: ExprId) -> Option<ConstValue> {
+        if let Some(val) = self.vals.get(&expr) {
+            return val.clone();
+        }
+        let res = self.fold_impl(expr);
+        self.vals.insert(expr, res.clone());
+        res
+    }
+
+    fn fold_impl(&mut self, expr: ExprId) -> Option<ConstValue> {
+        let body = self.body;
+        let res = match body.exprs[expr] {
+            hir_def::Expr::Literal(ref lit) => match *lit {
+                Literal::Int(val) => Ok(ConstValue::Int(val)),
+                Literal::Float(val) => Ok(ConstValue::Real(val.into())),
+                Literal::String(ref val) => Ok(ConstValue::Str(val.clone())),
+                // inf is intentionally infinite
+                Literal::Inf => return None,
+            },
+            hir_def::Expr::UnaryOp { expr: arg, op } => {
+                let arg = self.fold_finite(arg)?;
+                Ok(eval_unary(op, arg).ok()?)
+            }
+            hir_def::Expr::BinaryOp { lhs, rhs, op: Some(op) } => {
+                let lhs = self.fold_finite(lhs);
+                let rhs = self.fold_finite(rhs);
+                match eval_binary(op, lhs?, rhs?) {
+                    // other invalid operations are type errors that are reported elsewhere
+                    Err(_) if !matches!(op, BinaryOp::Division | BinaryOp::Remainder) => {
+                        return None
+                    }
+                    res => res,
+                }
+            }
+            hir_def::Expr::Call { ref args, .. } => {
+                let builtin = match self.infer.resolved_calls.get(&expr)? {
+                    ResolvedFun::BuiltIn(builtin) => *builtin,
+                    _ => return None,
+                };
+                let args: Vec<_> = args.iter().map(|&arg| self.fold_finite(arg)).collect();
+                let args = args.into_iter().collect::<Option<Vec<_>>>()?;
+                match eval_builtin(builtin, &args) {
+                    // functions that can not be evaluated at compile time are not folded
+                    Err(ConstEvalError::NotConstant) => return None,
+                    res => res,
+                }
+            }
+            hir_def::Expr::Select { cond, then_val, else_val } => {
+                let cond = self.fold(cond);
+                let then_val = self.fold(then_val);
+                let else_val = self.fold(else_val);
+                match cond? {
+                    ConstValue::Bool(true) => return then_val,
+                    ConstValue::Bool(false) => return else_val,
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+
+        let res = match res {
+            Ok(val) => match self.infer.casts.get(&expr) {
+                Some(dst) => val.cast(dst),
+                None => val,
+            },
+            Err(err) => {
+                self.report(expr, Err(err));
+                return None;
+            }
+        };
+
+        if let ConstValue::Real(val) = res {
+            if !val.is_finite() {
+                self.report(expr, Ok(res.clone()))
+            }
+        }
+
+        Some(res)
+    }
+
+    /// Like `fold` but returns `None` for non-finite values to avoid
+    /// reporting the same problem for every enclosing expression.
+    fn fold_finite(&mut self, expr: ExprId) -> Option<ConstValue> {
+        match self.fold(expr)? {
+            ConstValue::Real(val) if !val.is_finite() => None,
+            val => Some(val),
+        }
+    }
+
+    fn report(&mut self, expr: ExprId, val: Result<ConstValue, ConstEvalError>) {
+        if let Some(stmt) = self.stmt {
+            self.res.push(NonFiniteConstant { expr, stmt, val })
+        }
+    }
+}
+
+pub(crate) struct NonFiniteConstantWrapped<'a> {
+    pub diag: &'a NonFiniteConstant,
+    pub body_sm: &'a BodySourceMap,
+    pub parse: &'a Parse<SourceFile>,
+    pub sm: &'a SourceMap,
+}
+
+impl Diagnostic for NonFiniteConstantWrapped<'_> {
+    fn lint(&self, _root_file: FileId, _db: &dyn BaseDB) -> Option<(Lint, LintSrc)> {
+        Some((constant_overflow, self.body_sm.lint_src(self.diag.stmt, constant_overflow)))
+    }
+
+    fn build_report(&self, _root_file: FileId, _db: &dyn BaseDB) -> Report {
+        let expr = self.diag.expr;
+        let FileSpan { range, file } = self
+            .parse
+            .to_file_span(self.body_sm.expr_map_back[expr].as_ref().unwrap().range(), self.sm);
+
+        let message = match self.diag.val {
+            Ok(ConstValue::Real(val)) if val.is_nan() => {
+                "constant expression evaluates to NaN".to_owned()
+            }
+            Ok(ConstValue::Real(val)) => format!("constant expression evaluates to {val}"),
+            Err(ConstEvalError::InvalidArgument) => {
+                "constant function argument is outside of the function's domain".to_owned()
+            }
+            _ => "constant expression divides by zero".to_owned(),
+        };
+
+        Report::error()
+            .with_message(message)
+            .with_labels(vec![Label {
+                style: LabelStyle::Primary,
+                file_id: file,
+                range: range.into(),
+                message: "evaluated at compile time".to_owned(),
+            }])
+            .with_notes(vec!["help: this is usually caused by an overflow, a division by zero\n\
+                 or a math function that is called outside of its domain"
+                .to_owned()])
+    }
+}
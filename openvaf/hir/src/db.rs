@@ -7,12 +7,17 @@ use std::{fs, io};
 use anyhow::{bail, Result};
 use basedb::lints::{Lint, LintLevel};
 use basedb::AbsPathBuf;
-use basedb::{BaseDB, BaseDatabase, FileId, Vfs, VfsPath, VfsStorage, STANDARD_FLAGS};
+use basedb::{
+    BaseDB, BaseDatabase, CompilationLimits, FileId, MinTypMax, Vfs, VfsPath, VfsStorage,
+    STANDARD_FLAGS,
+};
 use hir_def::db::{HirDefDB, HirDefDatabase, InternDatabase};
-use hir_ty::db::HirTyDatabase;
+use hir_def::Type;
+use hir_ty::db::{HirTyDB, HirTyDatabase, HostFunction};
 use parking_lot::RwLock;
 use salsa::ParallelDatabase;
 use stdx::Upcast;
+use syntax::name::sysfun;
 use typed_index_collections::TiSlice;
 
 use crate::CompilationUnit;
@@ -53,6 +58,25 @@ impl CompilationDB {
         )
     }
 
+    /// Creates a database whose root file is not read from the filesystem.
+    /// `name` is the (virtual) path of the root file that is used in diagnostics.
+    /// Files included by `contents` are still resolved from `include_dirs`.
+    pub fn new_str(
+        name: &str,
+        contents: &str,
+        include_dirs: &[AbsPathBuf],
+        macro_flags: &[String],
+        lints: &[(String, LintLevel)],
+    ) -> Result<Self> {
+        CompilationDB::new(
+            VfsPath::new_virtual_path(format!("/{name}")),
+            Ok(contents.as_bytes().to_owned()),
+            include_dirs.iter().map(|path| Ok(VfsPath::from(path.clone()))),
+            macro_flags.iter().map(String::deref),
+            lints.iter().map(|(name, lvl)| (&**name, *lvl)),
+        )
+    }
+
     /// Utility function to create a database with default settings
     /// and a single virtual root file
     pub fn new_virtual(contents: &str) -> Result<Self> {
@@ -92,6 +116,11 @@ impl CompilationDB {
         let macro_flags: Arc<[_]> =
             STANDARD_FLAGS.into_iter().chain(macro_flags).map(Arc::from).collect();
         res.set_macro_flags(root_file, macro_flags);
+        res.set_min_typ_max(root_file, MinTypMax::default());
+        res.set_clamp_sqrt(root_file, false);
+        res.set_limits(root_file, CompilationLimits::default());
+        res.set_implicit_std_includes(root_file, true);
+        res.set_host_functions(Arc::new([]));
 
         res.set_plugin_lints(&[]);
         let mut overwrites = res.empty_global_lint_overwrites();
@@ -134,6 +163,38 @@ impl CompilationDB {
         res.set_global_lint_overwrites(root_file, overwrites);
         Ok(res)
     }
+
+    /// Registers system functions that are implemented by the host (see [`HostFunction`]).
+    /// Models call them like any other system function: `$name(args...)`.
+    pub fn register_host_functions(&mut self, functions: &[HostFunction]) -> Result<()> {
+        for (i, fun) in functions.iter().enumerate() {
+            let name = &fun.name;
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                bail!("invalid host function name '{name}'")
+            }
+            if sysfun::is_known(&format!("${name}")) {
+                bail!("host function ${name} conflicts with a builtin system function")
+            }
+            if functions[..i].iter().any(|other| other.name == fun.name) {
+                bail!("host function ${name} is registered multiple times")
+            }
+            if let Some(ty) =
+                fun.args.iter().find(|ty| !matches!(ty, Type::Real | Type::Integer | Type::String))
+            {
+                bail!("host function ${name} has an argument of unsupported type {ty:?}")
+            }
+            if !matches!(fun.return_ty, Type::Real | Type::Integer) {
+                bail!("host function ${name} has unsupported return type {:?}", fun.return_ty)
+            }
+        }
+        self.set_host_functions(functions.into());
+        Ok(())
+    }
+
+    /// The host function that is called by [`ResolvedFun::HostFunction(id)`](crate::ResolvedFun).
+    pub fn host_function(&self, id: u32) -> HostFunction {
+        self.host_functions()[id as usize].clone()
+    }
 }
 
 impl ParallelDatabase for CompilationDB {
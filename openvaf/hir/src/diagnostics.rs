@@ -14,6 +14,11 @@ use syntax::{Parse, SourceFile};
 pub use basedb::diagnostics::*;
 pub use basedb::{BaseDB, FileId};
 
+use crate::array_index::{
+    ArrayIndexOutOfBounds, ArrayIndexOutOfBoundsWrapped, GenvarLoopErrorWrapped,
+    InvalidArrayIndexWrapped,
+};
+use crate::const_fold::{NonFiniteConstant, NonFiniteConstantWrapped};
 use crate::{CompilationDB, HirDatabase};
 
 pub(crate) fn collect(db: &CompilationDB, root_file: FileId, sink: &mut impl DiagnosticSink) {
@@ -118,7 +123,7 @@ fn collect_def_map(
 }
 
 fn collect_body_diagnostcs(
-    db: &dyn HirDatabase,
+    db: &CompilationDB,
     dst: &mut impl DiagnosticSink,
     def: DefWithBodyId,
     parse: &Parse<SourceFile>,
@@ -127,8 +132,9 @@ fn collect_body_diagnostcs(
     ast_id_map: &AstIdMap,
 ) {
     let body_sm = db.body_source_map(def);
-    let diagnostics = &db.inference_result(def).diagnostics;
-    for diag in diagnostics {
+    let infer = db.inference_result(def);
+    let mut has_errors = !parse.errors().is_empty() || !infer.diagnostics.is_empty();
+    for diag in &infer.diagnostics {
         let diag = InferenceDiagnosticWrapped { body_sm: &body_sm, diag, parse, db, sm };
         let db: &dyn HirDefDB = db.upcast();
         dst.add_diagnostic(&diag, root_file, db.upcast())
@@ -145,6 +151,48 @@ fn collect_body_diagnostcs(
             map: ast_id_map,
         };
         let db: &dyn HirDefDB = db.upcast();
+        has_errors |= diag.lint(root_file, db.upcast()).is_none();
+        dst.add_diagnostic(&diag, root_file, db.upcast())
+    }
+
+    let diagnostics = NonFiniteConstant::collect(&db.body(def), &infer);
+    for diag in &diagnostics {
+        let diag = NonFiniteConstantWrapped { body_sm: &body_sm, diag, parse, sm };
+        let db: &dyn HirDefDB = db.upcast();
+        dst.add_diagnostic(&diag, root_file, db.upcast())
+    }
+
+    // array indices can only be evaluated for valid bodies
+    if has_errors {
+        return;
+    }
+    let (diagnostics, invalid_indices, loop_errors, inline_error) =
+        ArrayIndexOutOfBounds::collect(db, def);
+    for diag in &loop_errors {
+        let diag = GenvarLoopErrorWrapped { diag, body_sm: &body_sm, parse, sm };
+        let db: &dyn HirDefDB = db.upcast();
+        dst.add_diagnostic(&diag, root_file, db.upcast())
+    }
+    for diag in &diagnostics {
+        let diag = ArrayIndexOutOfBoundsWrapped { diag, body_sm: &body_sm, parse, sm };
+        let db: &dyn HirDefDB = db.upcast();
+        dst.add_diagnostic(&diag, root_file, db.upcast())
+    }
+    for diag in &invalid_indices {
+        let diag = InvalidArrayIndexWrapped { diag, body_sm: &body_sm, parse, sm };
+        let db: &dyn HirDefDB = db.upcast();
+        dst.add_diagnostic(&diag, root_file, db.upcast())
+    }
+    if let Some(diag) = &inline_error {
+        let diag = BodyValidationDiagnosticWrapped {
+            body_sm: &body_sm,
+            diag,
+            parse,
+            db,
+            sm,
+            map: ast_id_map,
+        };
+        let db: &dyn HirDefDB = db.upcast();
         dst.add_diagnostic(&diag, root_file, db.upcast())
     }
 }
@@ -16,7 +16,7 @@ use basedb::diagnostics::ConsoleSink;
 use basedb::BaseDB;
 use basedb::FileId;
 use hir_def::db::HirDefDB;
-use hir_def::nameres::{DefMap, LocalScopeId, ScopeDefItem};
+use hir_def::nameres::{DefMap, LocalScopeId, ScopeDefItem, ScopeOrigin};
 use hir_def::DefWithBodyId;
 use hir_def::DisciplineId;
 use hir_def::LocalFunctionArgId;
@@ -38,17 +38,24 @@ pub use hir_def::expr::CaseCond;
 pub use hir_def::nameres::diagnostics::PathResolveError;
 pub use hir_def::{BuiltIn, Case, Literal, ParamSysFun, Path, Type};
 pub use hir_ty::builtin;
+pub use hir_ty::db::HostFunction;
+pub use hir_ty::table_model;
 pub use rec_declarations::RecDeclarations;
 pub use syntax::name::Name;
 
 pub use crate::attributes::AstCache;
 pub use crate::body::{
-    AssignmentLhs, Body, BodyRef, ContributeKind, Expr, ExprId, Ref, ResolvedFun, Stmt, StmtId,
+    AssignmentLhs, Body, BodyRef, ContributeKind, Event, Expr, ExprId, GlobalEvent, Ref,
+    ResolvedFun, Stmt, StmtId,
 };
+pub use crate::const_eval::{ConstEvalError, ConstEvaluator, ConstValue};
 pub use crate::db::CompilationDB;
 
+mod array_index;
 mod attributes;
 mod body;
+mod const_eval;
+mod const_fold;
 mod db;
 pub mod diagnostics;
 mod rec_declarations;
@@ -118,6 +125,75 @@ impl CompilationUnit {
     pub fn preprocess(&self, db: &CompilationDB) -> syntax::Preprocess {
         db.preprocess(self.root_file)
     }
+
+    /// Renders a human readable dump of the HIR of this compilation unit: The item tree
+    /// (natures, disciplines, modules and their attributes), the resolved scopes and the
+    /// statement/expression trees of all analog blocks and functions.
+    pub fn dump_hir(self, db: &CompilationDB) -> String {
+        let mut res = db.item_tree(self.root_file).dump();
+        res.push_str("\n\n");
+        let def_map = db.def_map(self.root_file);
+        res.push_str(&def_map.dump(db));
+        res.push('\n');
+
+        for (label, body) in self.bodies(db) {
+            res.push_str(&format!("// {label}\n"));
+            res.push_str(&db.body(body).dump(db));
+            res.push_str("\n\n");
+        }
+
+        res
+    }
+
+    /// Renders the size of the HIR arenas of all analog blocks and functions.
+    /// Used to find out which part of a (large) model is responsible for slow compilation.
+    pub fn stats(self, db: &CompilationDB) -> String {
+        let mut res = String::new();
+        for (label, id) in self.bodies(db) {
+            let body = db.body(id);
+            let infer = db.inference_result(id);
+            let (mut real, mut int) = (0, 0);
+            for ty in infer.expr_types.iter() {
+                match ty.to_value() {
+                    Some(Type::Real) => real += 1,
+                    Some(Type::Integer) => int += 1,
+                    _ => (),
+                }
+            }
+            res.push_str(&format!(
+                "{label}: {} expressions ({real} real, {int} integer), {} statements\n",
+                body.exprs.len(),
+                body.stmts.len()
+            ));
+        }
+        res
+    }
+
+    /// All analog blocks (that are not empty) and functions of this compilation unit
+    /// together with a human readable label.
+    fn bodies(self, db: &CompilationDB) -> Vec<(String, DefWithBodyId)> {
+        let mut res = Vec::new();
+        let def_map = db.def_map(self.root_file);
+        for (_, scope) in &def_map[def_map.entry()].children {
+            if let ScopeOrigin::Module(module) = def_map[*scope].origin {
+                let name = &db.module_data(module).name;
+                for initial in [true, false] {
+                    let body = DefWithBodyId::ModuleId { initial, module };
+                    if db.body(body).entry_stmts.is_empty() {
+                        continue;
+                    }
+                    let kind = if initial { "analog initial" } else { "analog" };
+                    res.push((format!("{kind} block of {name}"), body));
+                }
+                for (fun_name, scope) in &def_map[*scope].children {
+                    if let ScopeOrigin::Function(func) = def_map[*scope].origin {
+                        res.push((format!("function {name}.{fun_name}"), func.into()));
+                    }
+                }
+            }
+        }
+        res
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
@@ -174,6 +250,15 @@ impl Module {
         Body::new(DefWithBodyId::ModuleId { initial: false, module: self.id }, db)
     }
 
+    /// Returns the attributes attached to a statement of the analog block.
+    pub fn stmt_attrs(&self, db: &CompilationDB, ast: &AstCache, stmt: StmtId) -> Vec<ast::Attr> {
+        let def = DefWithBodyId::ModuleId { initial: false, module: self.id };
+        match db.body_source_map(def).stmt_map_back[stmt] {
+            Some(ref ptr) => ast.stmt_attrs(ptr),
+            None => Vec::new(),
+        }
+    }
+
     // todo: just temporary for VAE, this needs to be cleaned up
     pub fn lookup_var(
         &self,
@@ -439,6 +524,15 @@ impl Variable {
         db.var_data(self.id).ty.clone()
     }
 
+    pub fn is_genvar(self, db: &CompilationDB) -> bool {
+        db.var_data(self.id).is_genvar
+    }
+
+    /// The index of the first element of an array variable (`0` for `real x[0:2]`).
+    pub fn array_start(self, db: &CompilationDB) -> i32 {
+        db.var_data(self.id).array_start
+    }
+
     pub fn init(self, db: &CompilationDB) -> Body {
         Body::new(self.id.into(), db)
     }
@@ -474,6 +568,25 @@ impl Parameter {
         db.param_ty(self.id)
     }
 
+    /// Whether this parameter was declared with `localparam` (and can not be overwritten).
+    pub fn is_local(self, db: &CompilationDB) -> bool {
+        db.param_data(self.id).is_local
+    }
+
+    /// The index of the first element of an array parameter (`0` for `real x[0:1]`).
+    pub fn array_start(self, db: &CompilationDB) -> i32 {
+        db.param_data(self.id).array_start
+    }
+
+    /// The default of every element of an array parameter. Array parameters are validated
+    /// to be initialized with an array literal, so these are expressions within [`Self::init`].
+    pub fn array_elements(self, db: &CompilationDB) -> Option<Vec<ExprId>> {
+        match db.body(self.id.into()).exprs[self.default(db)] {
+            hir_def::Expr::Array(ref elements) => Some(elements.clone()),
+            _ => None,
+        }
+    }
+
     pub fn get_attr(&self, db: &CompilationDB, ast: &AstCache, name: &str) -> Option<ast::Attr> {
         ast.resolve_attribute(name, self.id.lookup(db).ast_id(db).erased())
     }
@@ -635,3 +748,6 @@ pub enum ScopeDef {
     Branch(Branch),
     Function(Function),
 }
+
+#[cfg(test)]
+mod tests;
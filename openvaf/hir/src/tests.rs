@@ -0,0 +1,52 @@
+use crate::CompilationDB;
+
+#[test]
+fn dump_hir() {
+    let src = r#"
+        module test;
+            parameter real p = 1.0;
+            real x;
+            analog function real twice;
+                input val;
+                real val;
+                twice = 2 * val;
+            endfunction
+            analog initial x = p;
+            analog begin
+                x = twice(x);
+            end
+        endmodule
+    "#;
+    let db = CompilationDB::new_virtual(src).unwrap();
+    let dump = db.compilation_unit().dump_hir(&db);
+
+    // every body is dumped once: the analog blocks followed by the functions of the module
+    let headers = [
+        "// analog initial block of test\n",
+        "// analog block of test\n",
+        "// function test.twice\n",
+    ];
+    let positions: Vec<_> = headers
+        .iter()
+        .map(|header| {
+            assert_eq!(dump.matches(header).count(), 1, "{header:?} not found once in:\n{dump}");
+            dump.find(header).unwrap()
+        })
+        .collect();
+    assert!(positions.windows(2).all(|pos| pos[0] < pos[1]), "bodies out of order:\n{dump}");
+    assert!(dump[positions[2]..].contains("val"), "function body missing:\n{dump}");
+}
+
+#[test]
+fn dump_hir_skips_empty_bodies() {
+    let src = r#"
+        module test;
+            real x;
+            analog x = 1.0;
+        endmodule
+    "#;
+    let db = CompilationDB::new_virtual(src).unwrap();
+    let dump = db.compilation_unit().dump_hir(&db);
+    assert!(dump.contains("// analog block of test\n"), "analog block missing:\n{dump}");
+    assert!(!dump.contains("analog initial block"), "empty initial block dumped:\n{dump}");
+}
@@ -132,12 +132,21 @@ impl Body {
                 let expr = if let Some(expr) = ast.default() {
                     ctx.collect_expr(expr)
                 } else {
-                    let default_val = match db.var_data(var).ty {
+                    let zero = |ty: &Type| match ty {
                         Type::Real => Literal::Float(Ieee64::with_float(0.0)),
                         Type::Integer => Literal::Int(0),
-                        _ => unreachable!("invalid var type (TODO arrays)"),
+                        _ => unreachable!("invalid var type {ty}"),
                     };
-                    ctx.alloc_expr_desugared(Expr::Literal(default_val))
+                    match db.var_data(var).ty {
+                        // all elements of an array are initialized to zero
+                        Type::Array { ref ty, len } => {
+                            let vals = (0..len)
+                                .map(|_| ctx.alloc_expr_desugared(Expr::Literal(zero(ty))))
+                                .collect();
+                            ctx.alloc_expr_desugared(Expr::Array(vals))
+                        }
+                        ref ty => ctx.alloc_expr_desugared(Expr::Literal(zero(ty))),
+                    }
                 };
                 let stmt = ctx.alloc_stmt_desugared(Stmt::Expr(expr));
                 body.entry_stmts = vec![stmt].into_boxed_slice();
@@ -1,8 +1,8 @@
 use std::mem;
 
 use basedb::lints::LintRegistry;
-use basedb::{AstIdMap, ErasedAstId, LintAttrs};
-use syntax::ast::{self, ArgListOwner, AttrIter, AttrsOwner, FunctionRef};
+use basedb::{AstIdMap, ErasedAstId, LintAttrs, MinTypMax};
+use syntax::ast::{self, ArgListOwner, AstToken, AttrIter, AttrsOwner, FunctionRef};
 use syntax::name::AsName;
 use syntax::AstPtr;
 
@@ -77,6 +77,16 @@ impl LowerCtx<'_> {
                 Expr::Select { cond, then_val, else_val }
             }
 
+            // only the selected value is lowered, the others are ignored
+            ast::Expr::MinTypMaxExpr(e) => {
+                let val = match self.db.min_typ_max(self.curr_scope.0.root_file) {
+                    MinTypMax::Min => e.min(),
+                    MinTypMax::Typ => e.typ(),
+                    MinTypMax::Max => e.max(),
+                };
+                return self.collect_opt_expr(val);
+            }
+
             // TODO refactor with if let binding and default case is missing expression
             // BLOCK
             ast::Expr::PathExpr(path) => {
@@ -87,6 +97,12 @@ impl LowerCtx<'_> {
                 }
             }
 
+            ast::Expr::IndexExpr(e) => {
+                let base = self.collect_opt_expr(e.base());
+                let index = self.collect_opt_expr(e.index());
+                Expr::Index { base, index }
+            }
+
             ast::Expr::PortFlow(port_flow) => {
                 if let Some(path) = port_flow.port().and_then(Path::resolve) {
                     Expr::Path { path, port: true }
@@ -152,17 +168,28 @@ impl LowerCtx<'_> {
     }
 
     fn collect_event_stmt(&mut self, event_stmt: &ast::EventStmt) -> StmtId {
-        let kind = if event_stmt.initial_step_token().is_some() {
-            GlobalEvent::InitialStep
-        } else if event_stmt.final_step_token().is_some() {
-            GlobalEvent::FinalStep
-        } else {
+        let events: Vec<_> = event_stmt
+            .events()
+            .filter_map(|event| {
+                let global = |kind| {
+                    let phases = event.sim_phases().map(|lit| lit.unescaped_value()).collect();
+                    Event::Global { kind, phases }
+                };
+                let event = if event.initial_step_token().is_some() {
+                    global(GlobalEvent::InitialStep)
+                } else if event.final_step_token().is_some() {
+                    global(GlobalEvent::FinalStep)
+                } else {
+                    Event::Expr(self.collect_expr(event.expr()?))
+                };
+                Some(event)
+            })
+            .collect();
+        if events.is_empty() {
             return self.collect_opt_stmt(event_stmt.stmt());
-        };
+        }
 
-        let phases = event_stmt.sim_phases().map(|lit| lit.unescaped_value()).collect();
-        let event = Event::Global { kind, phases };
-        let stmt = Stmt::EventControl { event, body: self.collect_opt_stmt(event_stmt.stmt()) };
+        let stmt = Stmt::EventControl { events, body: self.collect_opt_stmt(event_stmt.stmt()) };
 
         self.alloc_stmt(stmt, AstPtr::new(event_stmt).cast().unwrap(), event_stmt.attrs())
     }
@@ -279,9 +306,16 @@ impl Literal {
             ast::LiteralKind::String(lit) => {
                 Literal::String(lit.unescaped_value().into_boxed_str())
             }
-            ast::LiteralKind::IntNumber(lit) => Literal::Int(lit.value()),
+            // invalid literals are reported during syntax validation
+            ast::LiteralKind::IntNumber(lit) => lit.value().map_or_else(
+                || Literal::Float(lit.text().replace('_', "").parse::<f64>().unwrap().into()),
+                Literal::Int,
+            ),
             ast::LiteralKind::SiRealNumber(lit) => Literal::Float(lit.value().into()),
-            ast::LiteralKind::StdRealNumber(lit) => Literal::Float(lit.value().into()),
+            ast::LiteralKind::StdRealNumber(lit) => {
+                // invalid literals are reported during syntax validation
+                Literal::Float(lit.value().unwrap_or(0.0).into())
+            }
             ast::LiteralKind::Inf => {
                 // TODO check that this allowed somewhere?
                 Literal::Inf
@@ -3,7 +3,7 @@ use std::fmt::Write;
 
 use super::Body;
 use crate::db::HirDefDB;
-use crate::expr::CaseCond;
+use crate::expr::{CaseCond, Event};
 use crate::nameres::DefMapSource;
 use crate::{Expr, ExprId, Lookup, Stmt, StmtId};
 
@@ -64,8 +64,18 @@ impl Printer<'_> {
                 self.pretty_print_expr(e);
                 wln!(self, ";");
             }
-            Stmt::EventControl { ref event, body } => {
-                wln!(self, "@({:?})", event);
+            Stmt::EventControl { ref events, body } => {
+                w!(self, "@(");
+                for (i, event) in events.iter().enumerate() {
+                    if i != 0 {
+                        w!(self, " or ");
+                    }
+                    match *event {
+                        Event::Expr(event) => self.pretty_print_expr(event),
+                        ref event => w!(self, "{:?}", event),
+                    }
+                }
+                wln!(self, ")");
                 self.pretty_print_stmt(body)
             }
             Stmt::Assignment { dst, val, assignment_kind } => {
@@ -176,6 +186,12 @@ impl Printer<'_> {
                 }
                 w!(self, ")");
             }
+            Expr::Index { base, index } => {
+                self.pretty_print_expr(base);
+                w!(self, "[");
+                self.pretty_print_expr(index);
+                w!(self, "]");
+            }
             Expr::Array(ref vals) => {
                 w!(self, "'{{");
                 for val in vals {
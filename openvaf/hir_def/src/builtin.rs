@@ -2,7 +2,7 @@
 
 use ahash::RandomState;
 use indexmap::IndexMap;
-use syntax::name::{kw, sysfun, Name};
+use syntax::name::{ext, kw, sysfun, Name};
 
 use crate::nameres::ScopeDefItem;
 
@@ -36,91 +36,103 @@ pub enum BuiltIn {
     sqrt = 23u8,
     tan = 24u8,
     tanh = 25u8,
-    display = 26u8,
-    strobe = 27u8,
-    write = 28u8,
-    monitor = 29u8,
-    debug = 30u8,
-    fclose = 31u8,
-    fopen = 32u8,
-    fdisplay = 33u8,
-    fwrite = 34u8,
-    fstrobe = 35u8,
-    fmonitor = 36u8,
-    fgets = 37u8,
-    fscanf = 38u8,
-    swrite = 39u8,
-    sformat = 40u8,
-    sscanf = 41u8,
-    rewind = 42u8,
-    fseek = 43u8,
-    ftell = 44u8,
-    fflush = 45u8,
-    ferror = 46u8,
-    feof = 47u8,
-    fdebug = 48u8,
-    finish = 49u8,
-    stop = 50u8,
-    fatal = 51u8,
-    warning = 52u8,
-    error = 53u8,
-    info = 54u8,
-    abstime = 55u8,
-    dist_chi_square = 56u8,
-    dist_exponential = 57u8,
-    dist_poisson = 58u8,
-    dist_uniform = 59u8,
-    dist_erlang = 60u8,
-    dist_normal = 61u8,
-    dist_t = 62u8,
-    random = 63u8,
-    arandom = 64u8,
-    rdist_chi_square = 65u8,
-    rdist_exponential = 66u8,
-    rdist_poisson = 67u8,
-    rdist_uniform = 68u8,
-    rdist_erlang = 69u8,
-    rdist_normal = 70u8,
-    rdist_t = 71u8,
-    clog2 = 72u8,
-    log10 = 73u8,
-    temperature = 74u8,
-    vt = 75u8,
-    simparam = 76u8,
-    simparam_str = 77u8,
-    simprobe = 78u8,
-    discontinuity = 79u8,
-    param_given = 80u8,
-    port_connected = 81u8,
-    analog_node_alias = 82u8,
-    analog_port_alias = 83u8,
-    test_plusargs = 84u8,
-    value_plusargs = 85u8,
-    bound_step = 86u8,
-    analysis = 87u8,
-    ac_stim = 88u8,
-    noise_table = 89u8,
-    noise_table_log = 90u8,
-    white_noise = 91u8,
-    flicker_noise = 92u8,
-    limit = 93u8,
-    absdelay = 94u8,
-    ddt = 95u8,
-    idt = 96u8,
-    idtmod = 97u8,
-    ddx = 98u8,
-    zi_nd = 99u8,
-    zi_np = 100u8,
-    zi_zd = 101u8,
-    zi_zp = 102u8,
-    laplace_nd = 103u8,
-    laplace_np = 104u8,
-    laplace_zd = 105u8,
-    laplace_zp = 106u8,
-    limexp = 107u8,
-    last_crossing = 108u8,
-    slew = 109u8,
-    transition = 110u8,
+    sign = 26u8,
+    display = 27u8,
+    strobe = 28u8,
+    write = 29u8,
+    monitor = 30u8,
+    debug = 31u8,
+    fclose = 32u8,
+    fopen = 33u8,
+    fdisplay = 34u8,
+    fwrite = 35u8,
+    fstrobe = 36u8,
+    fmonitor = 37u8,
+    fgets = 38u8,
+    fscanf = 39u8,
+    swrite = 40u8,
+    sformat = 41u8,
+    sscanf = 42u8,
+    rewind = 43u8,
+    fseek = 44u8,
+    ftell = 45u8,
+    fflush = 46u8,
+    ferror = 47u8,
+    feof = 48u8,
+    fdebug = 49u8,
+    finish = 50u8,
+    stop = 51u8,
+    fatal = 52u8,
+    warning = 53u8,
+    error = 54u8,
+    info = 55u8,
+    abstime = 56u8,
+    realtime = 57u8,
+    dist_chi_square = 58u8,
+    dist_exponential = 59u8,
+    dist_poisson = 60u8,
+    dist_uniform = 61u8,
+    dist_erlang = 62u8,
+    dist_normal = 63u8,
+    dist_t = 64u8,
+    random = 65u8,
+    arandom = 66u8,
+    rdist_chi_square = 67u8,
+    rdist_exponential = 68u8,
+    rdist_poisson = 69u8,
+    rdist_uniform = 70u8,
+    rdist_erlang = 71u8,
+    rdist_normal = 72u8,
+    rdist_t = 73u8,
+    clog2 = 74u8,
+    log10 = 75u8,
+    rint = 76u8,
+    round = 77u8,
+    trunc = 78u8,
+    rtoi = 79u8,
+    itor = 80u8,
+    signum = 81u8,
+    temperature = 82u8,
+    vt = 83u8,
+    simparam = 84u8,
+    simparam_str = 85u8,
+    simprobe = 86u8,
+    discontinuity = 87u8,
+    param_given = 88u8,
+    port_connected = 89u8,
+    analog_node_alias = 90u8,
+    analog_port_alias = 91u8,
+    table_model = 92u8,
+    test_plusargs = 93u8,
+    value_plusargs = 94u8,
+    bound_step = 95u8,
+    noise_correlation = 96u8,
+    analysis = 97u8,
+    ac_stim = 98u8,
+    noise_table = 99u8,
+    noise_table_log = 100u8,
+    white_noise = 101u8,
+    flicker_noise = 102u8,
+    limit = 103u8,
+    past = 104u8,
+    absdelay = 105u8,
+    ddt = 106u8,
+    idt = 107u8,
+    idtmod = 108u8,
+    ddx = 109u8,
+    zi_nd = 110u8,
+    zi_np = 111u8,
+    zi_zd = 112u8,
+    zi_zp = 113u8,
+    laplace_nd = 114u8,
+    laplace_np = 115u8,
+    laplace_zd = 116u8,
+    laplace_zp = 117u8,
+    limexp = 118u8,
+    last_crossing = 119u8,
+    cross = 120u8,
+    slew = 121u8,
+    transition = 122u8,
 }
 #[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
 #[allow(nonstandard_style, unreachable_pub)]
@@ -157,6 +169,7 @@ impl BuiltIn {
             | BuiltIn::laplace_zp
             | BuiltIn::limexp
             | BuiltIn::last_crossing
+            | BuiltIn::cross
             | BuiltIn::slew
             | BuiltIn::transition => true,
             _ => false,
@@ -165,7 +178,7 @@ impl BuiltIn {
     #[allow(clippy::match_like_matches_macro)]
     pub fn is_analog_operator_sysfun(self) -> bool {
         match self {
-            BuiltIn::limit => true,
+            BuiltIn::limit | BuiltIn::past => true,
             _ => false,
         }
     }
@@ -185,19 +198,11 @@ impl BuiltIn {
             | BuiltIn::laplace_np
             | BuiltIn::laplace_zd
             | BuiltIn::laplace_zp
-            | BuiltIn::last_crossing
             | BuiltIn::slew
             | BuiltIn::transition
-            | BuiltIn::fclose
-            | BuiltIn::fopen
-            | BuiltIn::fdisplay
-            | BuiltIn::fwrite
-            | BuiltIn::fstrobe
-            | BuiltIn::fmonitor
             | BuiltIn::fgets
             | BuiltIn::fscanf
             | BuiltIn::swrite
-            | BuiltIn::sformat
             | BuiltIn::sscanf
             | BuiltIn::rewind
             | BuiltIn::fseek
@@ -213,14 +218,9 @@ impl BuiltIn {
             | BuiltIn::dist_erlang
             | BuiltIn::dist_normal
             | BuiltIn::dist_t
-            | BuiltIn::random
             | BuiltIn::arandom
             | BuiltIn::rdist_chi_square
-            | BuiltIn::rdist_exponential
-            | BuiltIn::rdist_poisson
-            | BuiltIn::rdist_uniform
             | BuiltIn::rdist_erlang
-            | BuiltIn::rdist_normal
             | BuiltIn::rdist_t => true,
             _ => false,
         }
@@ -265,6 +265,7 @@ pub fn insert_builtin_scope(dst: &mut IndexMap<Name, ScopeDefItem, RandomState>)
     dst.insert(kw::sqrt, BuiltIn::sqrt.into());
     dst.insert(kw::tan, BuiltIn::tan.into());
     dst.insert(kw::tanh, BuiltIn::tanh.into());
+    dst.insert(ext::sign, BuiltIn::sign.into());
     dst.insert(sysfun::display, BuiltIn::display.into());
     dst.insert(sysfun::strobe, BuiltIn::strobe.into());
     dst.insert(sysfun::write, BuiltIn::write.into());
@@ -295,6 +296,7 @@ pub fn insert_builtin_scope(dst: &mut IndexMap<Name, ScopeDefItem, RandomState>)
     dst.insert(sysfun::error, BuiltIn::error.into());
     dst.insert(sysfun::info, BuiltIn::info.into());
     dst.insert(sysfun::abstime, BuiltIn::abstime.into());
+    dst.insert(sysfun::realtime, BuiltIn::realtime.into());
     dst.insert(sysfun::dist_chi_square, BuiltIn::dist_chi_square.into());
     dst.insert(sysfun::dist_exponential, BuiltIn::dist_exponential.into());
     dst.insert(sysfun::dist_poisson, BuiltIn::dist_poisson.into());
@@ -319,6 +321,12 @@ pub fn insert_builtin_scope(dst: &mut IndexMap<Name, ScopeDefItem, RandomState>)
     dst.insert(sysfun::pow, BuiltIn::pow.into());
     dst.insert(sysfun::floor, BuiltIn::floor.into());
     dst.insert(sysfun::ceil, BuiltIn::ceil.into());
+    dst.insert(sysfun::rint, BuiltIn::rint.into());
+    dst.insert(sysfun::round, BuiltIn::round.into());
+    dst.insert(sysfun::trunc, BuiltIn::trunc.into());
+    dst.insert(sysfun::rtoi, BuiltIn::rtoi.into());
+    dst.insert(sysfun::itor, BuiltIn::itor.into());
+    dst.insert(sysfun::signum, BuiltIn::signum.into());
     dst.insert(sysfun::sin, BuiltIn::sin.into());
     dst.insert(sysfun::cos, BuiltIn::cos.into());
     dst.insert(sysfun::tan, BuiltIn::tan.into());
@@ -343,9 +351,11 @@ pub fn insert_builtin_scope(dst: &mut IndexMap<Name, ScopeDefItem, RandomState>)
     dst.insert(sysfun::port_connected, BuiltIn::port_connected.into());
     dst.insert(sysfun::analog_node_alias, BuiltIn::analog_node_alias.into());
     dst.insert(sysfun::analog_port_alias, BuiltIn::analog_port_alias.into());
+    dst.insert(sysfun::table_model, BuiltIn::table_model.into());
     dst.insert(sysfun::test_plusargs, BuiltIn::test_plusargs.into());
     dst.insert(sysfun::value_plusargs, BuiltIn::value_plusargs.into());
     dst.insert(sysfun::bound_step, BuiltIn::bound_step.into());
+    dst.insert(sysfun::noise_correlation, BuiltIn::noise_correlation.into());
     dst.insert(kw::analysis, BuiltIn::analysis.into());
     dst.insert(kw::ac_stim, BuiltIn::ac_stim.into());
     dst.insert(kw::noise_table, BuiltIn::noise_table.into());
@@ -353,6 +363,7 @@ pub fn insert_builtin_scope(dst: &mut IndexMap<Name, ScopeDefItem, RandomState>)
     dst.insert(kw::white_noise, BuiltIn::white_noise.into());
     dst.insert(kw::flicker_noise, BuiltIn::flicker_noise.into());
     dst.insert(sysfun::limit, BuiltIn::limit.into());
+    dst.insert(sysfun::past, BuiltIn::past.into());
     dst.insert(kw::absdelay, BuiltIn::absdelay.into());
     dst.insert(kw::ddt, BuiltIn::ddt.into());
     dst.insert(kw::idt, BuiltIn::idt.into());
@@ -368,6 +379,7 @@ pub fn insert_builtin_scope(dst: &mut IndexMap<Name, ScopeDefItem, RandomState>)
     dst.insert(kw::laplace_zp, BuiltIn::laplace_zp.into());
     dst.insert(kw::limexp, BuiltIn::limexp.into());
     dst.insert(kw::last_crossing, BuiltIn::last_crossing.into());
+    dst.insert(kw::cross, BuiltIn::cross.into());
     dst.insert(kw::slew, BuiltIn::slew.into());
     dst.insert(kw::transition, BuiltIn::transition.into());
 }
@@ -121,13 +121,20 @@ impl NatureData {
 pub struct VarData {
     pub name: Name,
     pub ty: Type,
+    pub is_genvar: bool,
+    pub array_start: i32,
 }
 
 impl VarData {
     pub fn var_data_query(db: &dyn HirDefDB, id: VarId) -> Arc<VarData> {
         let loc = id.lookup(db);
         let var = &loc.item_tree(db)[loc.id];
-        Arc::new(VarData { name: var.name.clone(), ty: var.ty.clone() })
+        Arc::new(VarData {
+            name: var.name.clone(),
+            ty: var.ty.clone(),
+            is_genvar: var.is_genvar,
+            array_start: var.array_start,
+        })
     }
 }
 
@@ -135,13 +142,20 @@ impl VarData {
 pub struct ParamData {
     pub name: Name,
     pub ty: Option<Type>,
+    pub is_local: bool,
+    pub array_start: i32,
 }
 
 impl ParamData {
     pub fn param_data_query(db: &dyn HirDefDB, id: ParamId) -> Arc<ParamData> {
         let loc = id.lookup(db);
         let param = &loc.item_tree(db)[loc.id];
-        Arc::new(ParamData { name: param.name.clone(), ty: param.ty.clone() })
+        Arc::new(ParamData {
+            name: param.name.clone(),
+            ty: param.ty.clone(),
+            is_local: param.is_local,
+            array_start: param.array_start,
+        })
     }
 }
 
@@ -9,7 +9,7 @@ use crate::data::{
     ParamData, VarData,
 };
 use crate::item_tree::ItemTree;
-use crate::nameres::{DefMap, ScopeOrigin};
+use crate::nameres::DefMap;
 use crate::{
     AliasParamId, AliasParamLoc, BlockId, BlockLoc, BranchId, BranchLoc, DefWithBodyId,
     DisciplineAttrId, DisciplineAttrLoc, DisciplineId, DisciplineLoc, FunctionArgId,
@@ -101,9 +101,6 @@ pub trait HirDefDB: InternDB + Upcast<dyn BaseDB> {
 
     #[salsa::invoke(ModuleData::module_data_query)]
     fn module_data(&self, module: ModuleId) -> Arc<ModuleData>;
-
-    #[salsa::transparent]
-    fn find_module(&self, root_file: FileId) -> ModuleId;
 }
 
 fn body_source_map(db: &dyn HirDefDB, def: DefWithBodyId) -> Arc<BodySourceMap> {
@@ -117,19 +114,3 @@ fn body(db: &dyn HirDefDB, def: DefWithBodyId) -> Arc<Body> {
 fn param_exprs(db: &dyn HirDefDB, param: ParamId) -> ParamExprs {
     db.param_body_with_sourcemap(param).2
 }
-
-pub fn find_module(db: &dyn HirDefDB, root_file: FileId) -> ModuleId {
-    let def_map = db.def_map(root_file);
-    let root = def_map.entry();
-    def_map[root]
-        .children
-        .values()
-        .find_map(|scope| {
-            if let ScopeOrigin::Module(module) = def_map[*scope].origin {
-                Some(module)
-            } else {
-                None
-            }
-        })
-        .expect("No Module found")
-}
@@ -81,6 +81,11 @@ pub enum Expr {
         fun: Option<Path>,
         args: Vec<ExprId>,
     },
+    /// Access of a single element of an array variable (`x[i]`).
+    Index {
+        base: ExprId,
+        index: ExprId,
+    },
     Array(Vec<ExprId>),
     Literal(Literal),
 }
@@ -95,6 +100,10 @@ impl Expr {
                 f(rhs);
             }
             Expr::UnaryOp { expr, .. } => f(expr),
+            Expr::Index { base, index } => {
+                f(base);
+                f(index);
+            }
             Expr::Select { cond, then_val, else_val } => {
                 f(cond);
                 f(then_val);
@@ -124,7 +133,7 @@ pub enum Stmt {
     Missing,
     Empty,
     Expr(ExprId),
-    EventControl { event: Event, body: StmtId },
+    EventControl { events: Vec<Event>, body: StmtId },
     Assignment { dst: ExprId, val: ExprId, assignment_kind: ast::AssignOp },
     Block { /*scope: Option<BlockId>,*/ body: Vec<StmtId> },
     If { cond: ExprId, then_branch: StmtId, else_branch: StmtId },
@@ -139,11 +148,17 @@ pub enum GlobalEvent {
     FinalStep,
 }
 
+/// An event of an event control statement, `@(a or b)` fires when any of its events fires.
 // non_exhaustive because currently the full standard is not implemented
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
 #[non_exhaustive]
 pub enum Event {
-    Global { kind: GlobalEvent, phases: Vec<String> },
+    Global {
+        kind: GlobalEvent,
+        phases: Vec<String>,
+    },
+    /// An analog event function like `cross(expr, dir)`.
+    Expr(ExprId),
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -162,7 +177,14 @@ impl Stmt {
     #[inline]
     pub fn walk_child_exprs(&self, mut f: impl FnMut(ExprId)) {
         match *self {
-            Stmt::Empty | Stmt::Missing | Stmt::Block { .. } | Stmt::EventControl { .. } => (),
+            Stmt::EventControl { ref events, .. } => {
+                for event in events {
+                    if let Event::Expr(expr) = *event {
+                        f(expr)
+                    }
+                }
+            }
+            Stmt::Empty | Stmt::Missing | Stmt::Block { .. } => (),
             Stmt::If { cond: expr, .. }
             | Stmt::ForLoop { cond: expr, .. }
             | Stmt::WhileLoop { cond: expr, .. }
@@ -284,6 +284,9 @@ pub struct Net {
 pub struct Var {
     pub name: Name,
     pub ty: Type,
+    pub is_genvar: bool,
+    /// The index of the first element of an array variable (`real x[1:3]` starts at 1).
+    pub array_start: i32,
     pub ast_id: AstId<ast::Var>,
 }
 
@@ -292,6 +295,8 @@ pub struct Param {
     pub name: Name,
     pub ty: Option<Type>,
     pub is_local: bool,
+    /// The index of the first element of an array parameter (`localparam real x[1:2]` starts at 1).
+    pub array_start: i32,
     pub ast_id: AstId<ast::Param>,
 }
 
@@ -530,13 +530,23 @@ impl Ctx {
     }
 
     fn lower_var<T: From<ItemTreeId<Var>>>(&mut self, decl: ast::VarDecl, dst: &mut Vec<T>) {
-        let ty = decl.ty().as_type();
+        let is_genvar = decl.genvar_token().is_some();
+        let ty = if is_genvar { Type::Integer } else { decl.ty().as_type() };
         for var in decl.vars() {
             if let Some(name) = var.name() {
+                let (ty, array_start) = match var.array_bounds() {
+                    Some((start, end)) if !is_genvar => {
+                        let len = start.abs_diff(end) + 1;
+                        (Type::Array { ty: Box::new(ty.clone()), len }, start.min(end))
+                    }
+                    _ => (ty.clone(), 0),
+                };
                 let var = Var {
                     name: name.as_name(),
                     ast_id: self.source_ast_id_map.ast_id(&var),
-                    ty: ty.clone(),
+                    ty,
+                    is_genvar,
+                    array_start,
                 };
                 let id = self.tree.data.variables.push_and_get_key(var);
                 dst.push(id.into())
@@ -549,10 +559,18 @@ impl Ctx {
         for param in decl.paras() {
             if let Some(name) = param.name() {
                 let ast_id = self.source_ast_id_map.ast_id(&param);
+                let (ty, array_start) = match (param.array_bounds(), &ty) {
+                    (Some((start, end)), Some(ty)) => {
+                        let len = start.abs_diff(end) + 1;
+                        (Some(Type::Array { ty: Box::new(ty.clone()), len }), start.min(end))
+                    }
+                    _ => (ty.clone(), 0),
+                };
                 let param = Param {
                     name: name.as_name(),
                     is_local: decl.localparam_token().is_some(),
-                    ty: ty.clone(),
+                    ty,
+                    array_start,
                     ast_id,
                 };
                 let id = self.tree.data.parameters.push_and_get_key(param);
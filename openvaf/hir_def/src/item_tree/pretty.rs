@@ -162,7 +162,11 @@ impl<'a> Printer<'a> {
 
     fn print_var(&mut self, var: ItemTreeId<Var>) {
         let var = &self.tree[var];
-        wln!(self, "var {} {}", var.ty, var.name);
+        if var.is_genvar {
+            wln!(self, "genvar {}", var.name);
+        } else {
+            wln!(self, "var {} {}", var.ty, var.name);
+        }
     }
 
     fn print_scope_items(&mut self, items: &[BlockScopeItem]) {
@@ -1,6 +1,6 @@
 use std::ops::Deref;
 
-use basedb::diagnostics::{Diagnostic, Label, LabelStyle, Report};
+use basedb::diagnostics::{missing_std_include_notes, Diagnostic, Label, LabelStyle, Report};
 use basedb::{AstIdMap, BaseDB, FileId};
 use stdx::{impl_display, pretty};
 use syntax::name::Name;
@@ -43,6 +43,13 @@ impl PathResolveError {
             PathResolveError::ExpectedItemKind { expected, .. } => format!("expected {}", expected),
         }
     }
+
+    pub fn notes(&self) -> Vec<String> {
+        match self {
+            PathResolveError::NotFound { name } => missing_std_include_notes(name, name),
+            _ => Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -82,9 +82,9 @@ impl Type {
             ) => Some(Type::EmptyArray),
 
             _ if self == other => Some(self.clone()),
-            _ if self.dim() == other.dim() => {
+            _ if self.dim() == other.dim() && matches!(self, Type::Array { .. }) => {
                 let base_type1 = self.base_type();
-                let base_type2 = self.base_type();
+                let base_type2 = other.base_type();
                 let ty = base_type1.union(base_type2)?;
                 debug_assert_ne!(self.dim(), &[]);
                 Some(ty.to_dim(&self.dim()))
@@ -95,7 +95,7 @@ impl Type {
 
     pub fn base_type(&self) -> &Type {
         let mut curr = self;
-        while let Type::Array { ty, .. } = self {
+        while let Type::Array { ty, .. } = curr {
             curr = ty
         }
         curr
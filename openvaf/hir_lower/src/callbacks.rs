@@ -1,10 +1,10 @@
-use hir::{Node, Parameter};
+use hir::{HostFunction, Node, Parameter};
 use lasso::Spur;
 use mir::{FunctionSignature, Param};
 use stdx::Ieee64;
 
 use crate::fmt::{DisplayKind, FmtArg};
-use crate::LimitState;
+use crate::{CrossingState, LimitState, PastState};
 
 use std::fmt::Display;
 
@@ -37,6 +37,58 @@ impl std::fmt::Display for RetFlag {
     }
 }
 
+/// The random number generators (`$random`/`$rdist_*`) supported by OpenVAF.
+/// All of them are pure functions of the seed that is passed in so that
+/// repeated runs with the same seed are reproducible.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum RandomDist {
+    /// `$random(seed)`
+    Uniform,
+    /// `$rdist_normal(seed, mean, std)`
+    Normal,
+    /// `$rdist_uniform(seed, start, end)`
+    UniformReal,
+    /// `$rdist_exponential(seed, mean)`
+    Exponential,
+    /// `$rdist_poisson(seed, mean)`
+    Poisson,
+}
+
+impl RandomDist {
+    /// number of arguments of the distribution (excluding the seed)
+    pub fn num_args(self) -> u16 {
+        match self {
+            RandomDist::Uniform => 0,
+            RandomDist::Normal | RandomDist::UniformReal => 2,
+            RandomDist::Exponential | RandomDist::Poisson => 1,
+        }
+    }
+
+    /// name of the stdlib function that draws a sample from this distribution
+    pub fn stdlib_fun(self) -> &'static str {
+        match self {
+            RandomDist::Uniform => "va_random",
+            RandomDist::Normal => "va_rdist_normal",
+            RandomDist::UniformReal => "va_rdist_uniform",
+            RandomDist::Exponential => "va_rdist_exponential",
+            RandomDist::Poisson => "va_rdist_poisson",
+        }
+    }
+
+    /// name of the stdlib function that computes the updated seed, it receives the same
+    /// arguments as [`stdlib_fun`](Self::stdlib_fun) because the number of samples drawn
+    /// from the underlying uniform generator may depend on them (e.g. for `$rdist_poisson`)
+    pub fn stdlib_seed_fun(self) -> &'static str {
+        match self {
+            RandomDist::Uniform => "va_random_seed",
+            RandomDist::Normal => "va_rdist_normal_seed",
+            RandomDist::UniformReal => "va_rdist_uniform_seed",
+            RandomDist::Exponential => "va_rdist_exponential_seed",
+            RandomDist::Poisson => "va_rdist_poisson_seed",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub enum CallBackKind {
     Print { kind: DisplayKind, arg_tys: Box<[FmtArg]> },
@@ -56,6 +108,33 @@ pub enum CallBackKind {
     FlickerNoise { name: Spur, idx: u32 },
     NoiseTable(Box<NoiseTable>),
     SetRetFlag(RetFlag), 
+    /// Draws a sample from `dist` (or returns the updated seed if `next_seed` is set).
+    /// The seed is always the first argument.
+    Random { dist: RandomDist, next_seed: bool },
+    /// `last_crossing(expr, dir)` called with the value of `expr`, the direction and `$abstime`.
+    /// Returns the (interpolated) time at which `expr` last crossed zero or a negative value
+    /// if no crossing occurred yet.
+    LastCrossing(CrossingState),
+    /// `@(cross(expr, dir, time_tol, expr_tol))` called with the value of `expr`, the direction,
+    /// `$abstime` and both tolerances (zero if not specified). Returns a non-zero integer if
+    /// `expr` crossed zero (in direction `dir`) between the last accepted timepoint and `$abstime`.
+    CrossEvent(CrossingState),
+    /// `$past(expr)` called with the value of `expr` and `$abstime`. Returns the value of `expr`
+    /// at the last accepted timepoint of a transient analysis (and the value of `expr` otherwise).
+    Past(PastState),
+    /// `$fopen(name, mode)`: returns the descriptor the simulator assigned to the file.
+    FileOpen,
+    /// `$fclose(fd)`
+    FileClose,
+    /// `$fwrite`/`$fdisplay`/`$fstrobe`/`$fmonitor`. Like `Print` but the
+    /// file descriptor is passed as the first argument (before the format literal).
+    FilePrint { arg_tys: Box<[FmtArg]> },
+    /// `$sformat(var, ...)`: formats the arguments like `$fwrite` and returns
+    /// the resulting string which is assigned to `var`.
+    Format { arg_tys: Box<[FmtArg]> },
+    /// A system function registered with `CompilationDB::register_host_functions`. Its
+    /// derivatives are assumed to be zero.
+    HostFunction(Box<HostFunction>),
 }
 
 impl CallBackKind {
@@ -168,6 +247,61 @@ impl CallBackKind {
                 returns: 0,
                 has_sideeffects: true,
             },
+            CallBackKind::Random { dist, next_seed } => FunctionSignature {
+                name: if *next_seed { dist.stdlib_seed_fun() } else { dist.stdlib_fun() }
+                    .to_owned(),
+                params: dist.num_args() + 1,
+                returns: 1,
+                has_sideeffects: false,
+            },
+            CallBackKind::LastCrossing(state) => FunctionSignature {
+                name: format!("last_crossing[{state:?}]"),
+                params: 3,
+                returns: 1,
+                has_sideeffects: false,
+            },
+            CallBackKind::CrossEvent(state) => FunctionSignature {
+                name: format!("cross[{state:?}]"),
+                params: 5,
+                returns: 1,
+                has_sideeffects: false,
+            },
+            CallBackKind::Past(state) => FunctionSignature {
+                name: format!("$past[{state:?}]"),
+                params: 2,
+                returns: 1,
+                has_sideeffects: false,
+            },
+            CallBackKind::FileOpen => FunctionSignature {
+                name: "$fopen".to_owned(),
+                params: 2,
+                returns: 1,
+                has_sideeffects: true,
+            },
+            CallBackKind::FileClose => FunctionSignature {
+                name: "$fclose".to_owned(),
+                params: 1,
+                returns: 0,
+                has_sideeffects: true,
+            },
+            CallBackKind::FilePrint { arg_tys } => FunctionSignature {
+                name: "$fwrite".to_owned(),
+                params: arg_tys.len() as u16 + 2,
+                returns: 0,
+                has_sideeffects: true,
+            },
+            CallBackKind::Format { arg_tys } => FunctionSignature {
+                name: "$sformat".to_owned(),
+                params: arg_tys.len() as u16 + 1,
+                returns: 1,
+                has_sideeffects: true,
+            },
+            CallBackKind::HostFunction(fun) => FunctionSignature {
+                name: format!("${}", fun.name),
+                params: fun.args.len() as u16,
+                returns: 1,
+                has_sideeffects: true,
+            },
         }
     }
     pub fn is_noise(&self) -> bool {
@@ -189,6 +323,10 @@ impl CallBackKind {
                 | CallBackKind::SimParamStr
                 | CallBackKind::LimDiscontinuity
                 | CallBackKind::BuiltinLimit { .. }
+                | CallBackKind::LastCrossing(_)
+                | CallBackKind::CrossEvent(_)
+                | CallBackKind::Past(_)
+                | CallBackKind::HostFunction(_)
         )
     }
 
@@ -197,7 +335,14 @@ impl CallBackKind {
     }
 
     pub fn tracked(&self) -> bool {
-        !matches!(self, CallBackKind::Print { .. })
+        !matches!(
+            self,
+            CallBackKind::Print { .. }
+                | CallBackKind::FileOpen
+                | CallBackKind::FileClose
+                | CallBackKind::FilePrint { .. }
+                | CallBackKind::Format { .. }
+        )
     }
 }
 
@@ -1,8 +1,9 @@
-use ahash::AHashSet;
-use hir::{CompilationDB, Node, Type, Variable};
+use ahash::{AHashMap, AHashSet};
+use hir::{CompilationDB, ConstValue, Node, Type, Variable};
 use mir::builder::{InsertBuilder, InstBuilder};
 use mir::{
     Block, DataFlowGraph, FuncRef, Inst, Opcode, SourceLoc, Value, FALSE, F_ZERO, INFINITY, TRUE,
+    ZERO,
 };
 use mir_build::{FuncInstBuilder, FunctionBuilder, Place};
 use typed_indexmap::TiSet;
@@ -25,6 +26,8 @@ pub struct LoweringCtx<'a, 'c> {
     /// but necessary to avoid accidental correlation/opimization.
     /// For example white_noise(x) - white_noise(x) is not zero.
     pub num_noise_sources: u32,
+    /// The values of the genvars of the genvar loops that are currently unrolled.
+    pub genvars: AHashMap<Variable, ConstValue>,
 }
 
 impl<'a, 'c> LoweringCtx<'a, 'c> {
@@ -43,6 +46,7 @@ impl<'a, 'c> LoweringCtx<'a, 'c> {
             inside_lim: false,
             intern,
             num_noise_sources: 0,
+            genvars: AHashMap::default(),
         }
     }
 
@@ -80,6 +84,11 @@ impl<'a, 'c> LoweringCtx<'a, 'c> {
                 | PlaceKind::ParamMax(_) => return place,
 
                 PlaceKind::Var(var) => self.use_param(ParamKind::HiddenState(var)),
+                // array elements can not be initialized explicitly
+                PlaceKind::ArrayElement { .. } => match kind.ty(self.db) {
+                    Type::Integer => ZERO,
+                    _ => F_ZERO,
+                },
                 PlaceKind::ImplicitResidual { .. } | PlaceKind::Contribute { .. } => F_ZERO,
                 PlaceKind::CollapseImplicitEquation(_) => TRUE,
                 PlaceKind::IsVoltageSrc(_) => FALSE,
@@ -148,14 +157,7 @@ impl<'a, 'c> LoweringCtx<'a, 'c> {
     }
 
     pub fn dec_callback(&mut self, kind: CallBackKind) -> FuncRef {
-        let data = kind.signature();
-        let (func_ref, changed) = self.intern.callbacks.ensure(kind);
-        if changed {
-            self.intern.callback_uses.push(Vec::new());
-            let sig = self.func.func.import_function(data);
-            debug_assert_eq!(func_ref, sig);
-        }
-        func_ref
+        self.intern.ensure_callback(&mut *self.func.func, kind)
     }
 
     pub fn node(&self, node: Node) -> Option<Node> {
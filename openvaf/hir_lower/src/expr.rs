@@ -1,36 +1,54 @@
+use basedb::BaseDB;
 use hir::builtin::{
     FLICKER_NOISE_NAME, NOISE_TABLE_FILE_NAME, NOISE_TABLE_INLINE_NAME, WHITE_NOISE_NAME,
 };
+use hir::diagnostics::{ConsoleSink, Severity};
 use hir::signatures::{
     ABS_INT, ABS_REAL, BOOL_EQ, DDX_POT, IDTMOD_IC, IDTMOD_IC_MODULUS, IDTMOD_IC_MODULUS_OFFSET,
     IDTMOD_IC_MODULUS_OFFSET_NATURE, IDTMOD_IC_MODULUS_OFFSET_TOL, IDTMOD_NO_IC, IDT_IC,
     IDT_IC_ASSERT, IDT_IC_ASSERT_NATURE, IDT_IC_ASSERT_TOL, IDT_NO_IC, INT_EQ, INT_OP,
     LIMIT_BUILTIN_FUNCTION, MAX_INT, MAX_REAL, NATURE_ACCESS_BRANCH, NATURE_ACCESS_NODES,
-    NATURE_ACCESS_NODE_GND, NATURE_ACCESS_PORT_FLOW, REAL_EQ, REAL_OP, SIMPARAM_DEFAULT,
-    SIMPARAM_NO_DEFAULT, STR_EQ,
+    NATURE_ACCESS_NODE_GND, NATURE_ACCESS_PORT_FLOW, REAL_EQ, REAL_OP, SIGN_INT, SIGN_REAL,
+    SIMPARAM_DEFAULT, SIMPARAM_NO_DEFAULT, STR_EQ,
+};
+use hir::table_model::{Piece, TableModel};
+use hir::{
+    Body, BuiltIn, ConstEvaluator, ConstValue, Expr, ExprId, Literal, Parameter,
+    /*ParamSysFun,*/ Ref, ResolvedFun, Type,
 };
-use hir::{Body, BuiltIn, Expr, ExprId, Literal, /*ParamSysFun,*/ Ref, ResolvedFun, Type};
 use mir::builder::InstBuilder;
 use mir::{Opcode, Value, FALSE, F_ZERO, GRAVESTONE, INFINITY, TRUE, ZERO};
 use stdx::iter::zip;
 use syntax::ast::{BinaryOp, UnaryOp};
 
 use crate::body::BodyLoweringCtx;
+use crate::ctx::LoweringCtx;
 use crate::fmt::DisplayKind;
 use crate::{
-    RetFlag, CallBackKind, CurrentKind, IdtKind, ImplicitEquationKind, NoiseTable, ParamKind, PlaceKind,
+    expr_srcloc, RetFlag, CallBackKind, CrossingState, CurrentKind, IdtKind, ImplicitEquationKind, NoiseTable, ParamKind, PastState, PlaceKind,
+    RandomDist,
 };
 
+/// Boltzmann constant used by `$vt` (`P_K_NIST2010` from `constants.vams`)
+// TODO make this a database input
+const P_K: f64 = 1.3806488e-23;
+/// Elementary charge used by `$vt` (`P_Q_NIST2010` from `constants.vams`)
+const P_Q: f64 = 1.602176565e-19;
+
 impl BodyLoweringCtx<'_, '_, '_> {
     pub fn lower_expr(&mut self, expr: ExprId) -> Value {
         let old_loc = self.ctx.get_srcloc();
-        self.ctx.set_srcloc(mir::SourceLoc::new(u32::from(expr) as i32 + 1));
+        self.ctx.set_srcloc(expr_srcloc(expr));
 
         let mut res = match self.body.get_expr(expr) {
             Expr::Read(Ref::Variable(var)) => self.ctx.read_variable(var),
             Expr::Read(Ref::ParamSysFun(param)) => {
                 self.ctx.use_param(ParamKind::ParamSysFun(param))
             }
+            // localparams can not be set by the simulator, so they are lowered like constants
+            Expr::Read(Ref::Parameter(param)) if param.is_local(self.ctx.db) => {
+                self.lower_body(param.init(self.ctx.db), 0)
+            }
             Expr::Read(Ref::Parameter(param)) => self.ctx.use_param(ParamKind::Param(param)),
             Expr::Read(Ref::FunctionReturn(fun)) => {
                 self.ctx.use_place(PlaceKind::FunctionReturn(fun))
@@ -51,7 +69,19 @@ impl BodyLoweringCtx<'_, '_, '_> {
             Expr::Call { args, fun } => match fun {
                 ResolvedFun::User { func, limit } => self.lower_user_fun(func, limit, args),
                 ResolvedFun::BuiltIn(builtin) => self.lower_builtin(expr, builtin, args),
+                ResolvedFun::HostFunction(id) => {
+                    let fun = self.ctx.db.host_function(id);
+                    let args: Vec<_> = args.iter().map(|&arg| self.lower_expr(arg)).collect();
+                    self.ctx.call1(CallBackKind::HostFunction(Box::new(fun)), &args)
+                }
             },
+            Expr::ArrayElement { var, index } => {
+                let place = self.array_element(var, index);
+                self.ctx.use_place(place)
+            }
+            Expr::ParamArrayElement { param, index } => {
+                self.lower_param_array_element(param, index)
+            }
             Expr::Array(vals) => self.lower_array(expr, vals),
             Expr::Literal(lit) => match *lit {
                 Literal::String(ref str) => self.ctx.sconst(str),
@@ -151,6 +181,9 @@ impl BodyLoweringCtx<'_, '_, '_> {
             BinaryOp::Multiplication => {
                 match_signature!(signature: INT_OP => Opcode::Imul, REAL_OP => Opcode::Fmul)
             }
+            // integer division truncates toward zero and the remainder takes the sign of the
+            // dividend (-7 / 2 == -3, -7 % 2 == -1) as required by the LRM. `idiv`/`irem` have
+            // exactly these semantics in the interpreter, const folding and LLVM (`sdiv`/`srem`)
             BinaryOp::Division => {
                 match_signature!(signature: INT_OP => Opcode::Idiv, REAL_OP => Opcode::Fdiv)
             }
@@ -280,6 +313,31 @@ impl BodyLoweringCtx<'_, '_, '_> {
                     |_| val,
                 )
             }
+            BuiltIn::sign | BuiltIn::signum => {
+                let (less, greater, zero) = match_signature!(signature:
+                    SIGN_REAL => (Opcode::Flt, Opcode::Fgt, F_ZERO),
+                    SIGN_INT => (Opcode::Ilt, Opcode::Igt, ZERO)
+                );
+                let (one, neg_one) = if zero == F_ZERO {
+                    (self.ctx.fconst(1.0), self.ctx.fconst(-1.0))
+                } else {
+                    (self.ctx.iconst(1), self.ctx.iconst(-1))
+                };
+                let val = self.lower_expr(args[0]);
+                let (inst, dfg) = self.ctx.ins().binary(less, val, zero);
+                let is_negative = dfg.first_result(inst);
+
+                // the result is piecewise constant so the derivative is zero everywhere
+                self.lower_select_with(
+                    is_negative,
+                    |_| neg_one,
+                    |mut sel| {
+                        let (inst, dfg) = sel.ctx.ins().binary(greater, val, zero);
+                        let is_positive = dfg.first_result(inst);
+                        sel.lower_select_with(is_positive, |_| one, |_| zero)
+                    },
+                )
+            }
             BuiltIn::acos => {
                 let arg0 = self.lower_expr(args[0]);
                 self.ctx.ins().acos(arg0)
@@ -364,7 +422,20 @@ impl BodyLoweringCtx<'_, '_, '_> {
             }
             BuiltIn::sqrt => {
                 let arg0 = self.lower_expr(args[0]);
-                self.ctx.ins().sqrt(arg0)
+                let root_file = self.ctx.db.compilation_unit().root_file();
+                if !self.ctx.db.clamp_sqrt(root_file) {
+                    return self.ctx.ins().sqrt(arg0);
+                }
+                // sqrt(max(x, 0)) where both the value and the derivative are zero for x <= 0
+                // (instead of NaN or the infinite derivative at zero)
+                let positive = self.ctx.ins().fgt(arg0, F_ZERO);
+                self.ctx.make_select(positive, |func, positive| {
+                    if positive {
+                        func.ins().sqrt(arg0)
+                    } else {
+                        F_ZERO
+                    }
+                })
             }
             BuiltIn::tan => {
                 let arg0 = self.lower_expr(args[0]);
@@ -386,6 +457,45 @@ impl BodyLoweringCtx<'_, '_, '_> {
                 let arg0 = self.lower_expr(args[0]);
                 self.ctx.ins().ceil(arg0)
             }
+            BuiltIn::rint => {
+                let arg0 = self.lower_expr(args[0]);
+                self.ctx.ins().rint(arg0)
+            }
+            BuiltIn::round => {
+                let arg0 = self.lower_expr(args[0]);
+                self.ctx.ins().round(arg0)
+            }
+            BuiltIn::trunc => {
+                let arg0 = self.lower_expr(args[0]);
+                self.ctx.ins().trunc(arg0)
+            }
+            BuiltIn::rtoi => {
+                // real to integer casts round, truncate first to make the result exact
+                let arg0 = self.lower_expr(args[0]);
+                let arg0 = self.ctx.ins().trunc(arg0);
+                self.ctx.ins().ficast(arg0)
+            }
+            BuiltIn::itor => {
+                let arg0 = self.lower_expr(args[0]);
+                self.ctx.ins().ifcast(arg0)
+            }
+            BuiltIn::table_model => {
+                // the table was already loaded during validation (with the same file lookup),
+                // it can only fail now if the file changed in the meantime
+                let file = self.body.as_literal(args[1]).unwrap().unwrap_str();
+                let control = self.body.as_literal(args[2]).unwrap().unwrap_str();
+                let root_file = self.body.file(self.ctx.db);
+                let table = match TableModel::load(self.ctx.db, root_file, file, control) {
+                    Ok(table) => table,
+                    Err(err) => {
+                        ConsoleSink::new(self.ctx.db)
+                            .print_simple_message(Severity::Error, err.to_string());
+                        return self.ctx.fconst(f64::NAN);
+                    }
+                };
+                let arg0 = self.lower_expr(args[0]);
+                lower_table_pieces(self.ctx, arg0, &table.pieces())
+            }
 
             BuiltIn::max => {
                 let comparison = match_signature!(signature: MAX_REAL => InstBuilder::fgt, MAX_INT => InstBuilder::igt);
@@ -449,9 +559,45 @@ impl BodyLoweringCtx<'_, '_, '_> {
                 
                 GRAVESTONE
             }
+            BuiltIn::fopen => {
+                let name = self.lower_expr(args[0]);
+                let mode = match args.get(1) {
+                    Some(&mode) => self.lower_expr(mode),
+                    None => self.ctx.sconst("w"),
+                };
+                self.ctx.call1(CallBackKind::FileOpen, &[name, mode])
+            }
+            BuiltIn::fclose => {
+                let fd = self.lower_expr(args[0]);
+                self.ctx.call(CallBackKind::FileClose, &[fd]);
+                ZERO
+            }
+            BuiltIn::fwrite => {
+                self.ins_file_display(false, args);
+                GRAVESTONE
+            }
+            BuiltIn::fdisplay | BuiltIn::fstrobe | BuiltIn::fmonitor => {
+                self.ins_file_display(true, args);
+                GRAVESTONE
+            }
+            BuiltIn::sformat => {
+                let res = self.ins_format(&args[1..]);
+                let dst = self.body.get_expr(args[0]).as_assignment_lhs();
+                self.ctx.def_place(dst.into(), res);
+                GRAVESTONE
+            }
             BuiltIn::analysis => {
-                let arg = self.lower_expr(args[0]);
-                self.ctx.call1(CallBackKind::Analysis, &[arg])
+                // $analysis("ac", "noise") is true if any of the analyses is active
+                let mut res = None;
+                for &arg in args {
+                    let arg = self.lower_expr(arg);
+                    let val = self.ctx.call1(CallBackKind::Analysis, &[arg]);
+                    res = Some(match res {
+                        Some(res) => self.ctx.ins().ior(res, val),
+                        None => val,
+                    });
+                }
+                res.unwrap()
             }
 
             BuiltIn::noise_table
@@ -513,6 +659,17 @@ impl BodyLoweringCtx<'_, '_, '_> {
             }
 
             BuiltIn::abstime => self.ctx.use_param(ParamKind::Abstime),
+            // there is no `timescale so the time unit defaults to one second
+            BuiltIn::realtime => {
+                let abstime = self.ctx.use_param(ParamKind::Abstime);
+                match args.get(0) {
+                    Some(&unit) => {
+                        let unit = self.lower_expr(unit);
+                        self.ctx.ins().fdiv(abstime, unit)
+                    }
+                    None => abstime,
+                }
+            }
 
             BuiltIn::ddt => {
                 if self.ctx.no_equations {
@@ -589,11 +746,9 @@ impl BodyLoweringCtx<'_, '_, '_> {
                 }
             }
             BuiltIn::vt => {
-                // TODO make this a database input
-                const KB: f64 = 1.3806488e-23;
-                const Q: f64 = 1.602176565e-19;
-
-                let fac = self.ctx.fconst(KB / Q);
+                // $vt(T) = P_K * T / P_Q, the constant factor is folded here so that
+                // the derivative with respect to T is simply P_K / P_Q
+                let fac = self.ctx.fconst(P_K / P_Q);
                 let temp = match args.get(0) {
                     Some(temp) => self.lower_expr(*temp),
                     None => self.ctx.use_param(ParamKind::Temperature),
@@ -629,17 +784,71 @@ impl BodyLoweringCtx<'_, '_, '_> {
                 let arg0 = self.lower_expr(args[0]);
                 self.ctx.call1(CallBackKind::SimParamStr, &[arg0])
             }
-            BuiltIn::param_given => self
-                .ctx
-                .use_param(ParamKind::ParamGiven { param: self.body.into_parameter(args[0]) }),
+            BuiltIn::param_given => {
+                let param = self.body.into_parameter(args[0]);
+                if param.is_local(self.ctx.db) {
+                    FALSE
+                } else {
+                    self.ctx.use_param(ParamKind::ParamGiven { param })
+                }
+            }
             BuiltIn::port_connected => {
                 self.ctx.use_param(ParamKind::PortConnected { port: self.body.into_node(args[0]) })
             }
             BuiltIn::bound_step => {
+                // if $bound_step is called multiple times the smallest step is used
                 let step_size = self.lower_expr(args[0]);
+                let prev = self.ctx.use_place(PlaceKind::BoundStep);
+                let cond = self.ctx.ins().flt(step_size, prev);
+                let step_size = self.lower_select_with(cond, |_| step_size, |_| prev);
                 self.ctx.def_place(PlaceKind::BoundStep, step_size);
                 GRAVESTONE
             }
+            // the (constant) correlation is collected into the module info
+            // and does not generate any code
+            BuiltIn::noise_correlation => GRAVESTONE,
+
+            // there is no simulation time without equations
+            BuiltIn::last_crossing if self.ctx.no_equations => self.ctx.fconst(-1.0),
+            BuiltIn::last_crossing => {
+                let val = self.lower_expr(args[0]);
+                let dir = match args.get(1) {
+                    Some(&dir) => self.lower_expr(dir),
+                    None => ZERO,
+                };
+                let abstime = self.ctx.use_param(ParamKind::Abstime);
+                let state = CrossingState::from(self.ctx.intern.num_crossing_states);
+                self.ctx.intern.num_crossing_states += 1;
+                self.ctx.call1(CallBackKind::LastCrossing(state), &[val, dir, abstime])
+            }
+
+            // events can only be triggered during a transient analysis
+            BuiltIn::cross if self.ctx.no_equations => FALSE,
+            BuiltIn::cross => {
+                let val = self.lower_expr(args[0]);
+                let dir = args.get(1).map_or(ZERO, |dir| self.lower_expr(*dir));
+                let abstime = self.ctx.use_param(ParamKind::Abstime);
+                // a tolerance of zero lets the runtime choose the tolerance
+                let time_tol = args.get(2).map_or(F_ZERO, |tol| self.lower_expr(*tol));
+                let expr_tol = args.get(3).map_or(F_ZERO, |tol| self.lower_expr(*tol));
+                let state = CrossingState::from(self.ctx.intern.num_crossing_states);
+                self.ctx.intern.num_crossing_states += 1;
+                let crossed = self.ctx.call1(
+                    CallBackKind::CrossEvent(state),
+                    &[val, dir, abstime, time_tol, expr_tol],
+                );
+                self.ctx.ins().ine(crossed, ZERO)
+            }
+
+            // without a simulation there is no previous timepoint
+            BuiltIn::past if self.ctx.no_equations => self.lower_expr(args[0]),
+            BuiltIn::past => {
+                let val = self.lower_expr(args[0]);
+                let abstime = self.ctx.use_param(ParamKind::Abstime);
+                let state = PastState::from(self.ctx.intern.num_past_states);
+                self.ctx.intern.num_past_states += 1;
+                self.ctx.call1(CallBackKind::Past(state), &[val, abstime])
+            }
 
             BuiltIn::limit if signature == LIMIT_BUILTIN_FUNCTION && !self.ctx.no_equations => {
                 let new_val = self.lower_expr(args[0]);
@@ -719,10 +928,38 @@ impl BodyLoweringCtx<'_, '_, '_> {
                 self.lower_expr(args[0])
             }
 
+            BuiltIn::random => self.lower_random(RandomDist::Uniform, args),
+            BuiltIn::rdist_normal => self.lower_random(RandomDist::Normal, args),
+            BuiltIn::rdist_uniform => self.lower_random(RandomDist::UniformReal, args),
+            BuiltIn::rdist_exponential => self.lower_random(RandomDist::Exponential, args),
+            BuiltIn::rdist_poisson => self.lower_random(RandomDist::Poisson, args),
+
             _ => unreachable!(),
         }
     }
 
+    fn lower_random(&mut self, dist: RandomDist, args: &[ExprId]) -> Value {
+        // $random without a seed always starts from the same (zero) seed
+        let seed = args.first().map_or(ZERO, |seed| self.lower_expr(*seed));
+        let mut call_args = vec![seed];
+        let dist_args = args.get(1..=dist.num_args() as usize).unwrap_or(&[]);
+        call_args.extend(dist_args.iter().map(|arg| self.lower_expr(*arg)));
+        let res = self.ctx.call1(CallBackKind::Random { dist, next_seed: false }, &call_args);
+
+        // the seed is an inout argument, if it is a variable write back the updated seed so
+        // the next call draws a new sample (parameter seeds always produce the same value)
+        if let Some(&seed_expr) = args.first() {
+            let seed_expr_ = self.body.get_expr(seed_expr);
+            if matches!(seed_expr_, Expr::Read(Ref::Variable(_))) {
+                let next_seed =
+                    self.ctx.call1(CallBackKind::Random { dist, next_seed: true }, &call_args);
+                self.ctx.def_place(seed_expr_.as_assignment_lhs().into(), next_seed);
+            }
+        }
+
+        res
+    }
+
     fn lower_integral(&mut self, kind: IdtKind, args: &[ExprId]) -> Value {
         let (equation, val) = self.ctx.implicit_equation(ImplicitEquationKind::Idt(kind));
 
@@ -792,8 +1029,66 @@ impl BodyLoweringCtx<'_, '_, '_> {
             .unwrap_or_else(|| self.body.expr_type(expr))
     }
 
+    /// Array parameters are constants (just like scalar localparams), so the element is
+    /// selected at compile time and only its default is lowered.
+    fn lower_param_array_element(&mut self, param: Parameter, index: ExprId) -> Value {
+        let db = self.ctx.db;
+        let index = ConstEvaluator::new(db)
+            .with_genvars(self.ctx.genvars.clone())
+            .eval_expr(self.body, index);
+        let idx = match index {
+            Ok(ConstValue::Int(idx)) => idx - param.array_start(db),
+            res => {
+                unreachable!("array index {res:?} is not a constant integer (reported as an error)")
+            }
+        };
+        let elements =
+            param.array_elements(db).expect("array parameters are initialized with array literals");
+        let element = usize::try_from(idx)
+            .ok()
+            .and_then(|idx| elements.get(idx).copied())
+            .expect("array indices are validated to be in bounds");
+
+        let body = param.init(db);
+        let mut ctx = BodyLoweringCtx { ctx: self.ctx, body: body.borrow(), path: self.path };
+        let val = ctx.lower_expr(element);
+        let src = ctx.resolved_ty(element);
+        match param.ty(db) {
+            Type::Array { ty, .. } if *ty != src => self.ctx.insert_cast(val, &src, &ty),
+            _ => val,
+        }
+    }
+
     pub fn lower_body(&mut self, body: Body, i: usize) -> Value {
         let expr = body.borrow().get_entry_expr(i);
         BodyLoweringCtx { ctx: self.ctx, body: body.borrow(), path: self.path }.lower_expr(expr)
     }
 }
+
+/// Selects the piece of a table model that contains `x` with a binary search and
+/// evaluates its polynomial.
+fn lower_table_pieces(ctx: &mut LoweringCtx, x: Value, pieces: &[Piece]) -> Value {
+    if let [piece] = pieces {
+        let degree = piece.coeffs.iter().rposition(|&coeff| coeff != 0.0).unwrap_or(0);
+        let mut res = ctx.fconst(piece.coeffs[degree]);
+        if degree != 0 {
+            let x0 = ctx.fconst(piece.x0);
+            let t = ctx.ins().fsub(x, x0);
+            for &coeff in piece.coeffs[..degree].iter().rev() {
+                res = ctx.ins().fmul(res, t);
+                if coeff != 0.0 {
+                    let coeff = ctx.fconst(coeff);
+                    res = ctx.ins().fadd(res, coeff);
+                }
+            }
+        }
+        return res;
+    }
+
+    let (lower, upper) = pieces.split_at(pieces.len() / 2);
+    let start = ctx.fconst(upper[0].start);
+    let cond = ctx.ins().flt(x, start);
+    ctx.make_select(cond, |ctx, is_lower| {
+        lower_table_pieces(ctx, x, if is_lower { lower } else { upper })
+    })
+}
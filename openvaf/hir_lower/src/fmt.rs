@@ -1,5 +1,5 @@
 use hir::{ExprId, Literal, Type};
-use mir::GRAVESTONE;
+use mir::{Value, GRAVESTONE};
 
 use crate::body::BodyLoweringCtx;
 use crate::callbacks::CallBackKind;
@@ -35,19 +35,51 @@ pub struct FmtArg {
 }
 
 impl BodyLoweringCtx<'_, '_, '_> {
-    pub fn ins_display(&mut self, kind: DisplayKind, newline: bool, args: &[ExprId]) {
-        let mut fmt_lit = String::new();
+    pub fn ins_display(&mut self, kind: DisplayKind, newline: bool, mut args: &[ExprId]) {
+        // For $fatal skip the first argument
+        if kind == DisplayKind::Fatal {
+            args = args.get(1..).unwrap_or(&[]);
+        }
         let mut call_args = vec![GRAVESTONE];
+        let arg_tys = self.lower_fmt_args(newline, args, &mut call_args);
+        self.ctx
+            .call(CallBackKind::Print { kind, arg_tys: arg_tys.into_boxed_slice() }, &call_args);
+    }
+
+    /// Lowers `$fwrite(fd, ...)` and friends. The file descriptor is passed
+    /// to the callback before the format literal.
+    pub fn ins_file_display(&mut self, newline: bool, args: &[ExprId]) {
+        let fd = self.lower_expr(args[0]);
+        let mut call_args = vec![fd, GRAVESTONE];
+        let arg_tys = self.lower_fmt_args(newline, &args[1..], &mut call_args);
+        self.ctx.call(CallBackKind::FilePrint { arg_tys: arg_tys.into_boxed_slice() }, &call_args);
+    }
+
+    /// Lowers the format arguments of `$sformat(var, ...)` and returns the
+    /// formatted string.
+    pub fn ins_format(&mut self, args: &[ExprId]) -> Value {
+        let mut call_args = vec![GRAVESTONE];
+        let arg_tys = self.lower_fmt_args(false, args, &mut call_args);
+        self.ctx.call1(CallBackKind::Format { arg_tys: arg_tys.into_boxed_slice() }, &call_args)
+    }
+
+    /// Builds a printf style format literal from the arguments of a display task.
+    /// The formatted values are appended to `call_args` and the format literal
+    /// replaces the last placeholder that was pushed before calling this function.
+    fn lower_fmt_args(
+        &mut self,
+        newline: bool,
+        args: &[ExprId],
+        call_args: &mut Vec<Value>,
+    ) -> Vec<FmtArg> {
+        let fmt_pos = call_args.len() - 1;
+        let mut fmt_lit = String::new();
         let mut arg_tys = Vec::new();
 
         let mut i = 0;
 
         while let Some(&expr) = args.get(i) {
             i += 1;
-            // For $fatal skip the first argument
-            if i==1 && kind==DisplayKind::Fatal {
-                continue;
-            }
             if let Some(Literal::String(ref lit)) = self.body.as_literal(expr) {
                 fmt_lit.reserve(lit.len());
                 let mut chars = lit.chars();
@@ -153,8 +185,7 @@ impl BodyLoweringCtx<'_, '_, '_> {
             fmt_lit.push('\n');
         }
 
-        call_args[0] = self.ctx.sconst(&fmt_lit);
-        self.ctx
-            .call(CallBackKind::Print { kind, arg_tys: arg_tys.into_boxed_slice() }, &call_args);
+        call_args[fmt_pos] = self.ctx.sconst(&fmt_lit);
+        arg_tys
     }
 }
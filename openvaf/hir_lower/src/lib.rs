@@ -3,19 +3,22 @@ use std::iter::FilterMap;
 use ahash::{AHashMap, AHashSet};
 use bitset::HybridBitSet;
 use hir::{
-    Branch, BranchWrite, CompilationDB, Module, Node, ParamSysFun, Parameter, Type, Variable,
+    Branch, BranchWrite, CompilationDB, ExprId, Module, Node, ParamSysFun, Parameter, Type,
+    Variable,
 };
 use indexmap::IndexMap;
 use lasso::Rodeo;
 use mir::builder::InstBuilder;
-use mir::{DataFlowGraph, FuncRef, Function, Inst, KnownDerivatives, Param, Unknown, Value};
+use mir::{
+    DataFlowGraph, FuncRef, Function, Inst, KnownDerivatives, Param, SourceLoc, Unknown, Value,
+};
 use mir_build::{FunctionBuilder, FunctionBuilderContext, RetBuilder};
 use stdx::packed_option::PackedOption;
 use stdx::{impl_debug_display, impl_idx_from};
 use typed_index_collections::TiVec;
 use typed_indexmap::{map, TiMap, TiSet};
 
-pub use callbacks::{RetFlag, CallBackKind, NoiseTable, ParamInfoKind};
+pub use callbacks::{RetFlag, CallBackKind, NoiseTable, ParamInfoKind, RandomDist};
 
 use crate::body::BodyLoweringCtx;
 use crate::ctx::LoweringCtx;
@@ -153,6 +156,11 @@ impl IdtKind {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum PlaceKind {
     Var(Variable),
+    /// An element of an array variable. `idx` is relative to the first element.
+    ArrayElement {
+        var: Variable,
+        idx: u32,
+    },
     FunctionReturn(hir::Function),
     FunctionArg(hir::FunctionArg),
     Contribute {
@@ -177,6 +185,10 @@ impl PlaceKind {
     pub fn ty(&self, db: &CompilationDB) -> Type {
         match *self {
             PlaceKind::Var(var) => var.ty(db),
+            PlaceKind::ArrayElement { var, .. } => match var.ty(db) {
+                Type::Array { ty, .. } => *ty,
+                ty => unreachable!("{ty} is not an array"),
+            },
             PlaceKind::FunctionReturn(fun) => fun.return_ty(db),
             PlaceKind::FunctionArg(arg) => arg.ty(db),
 
@@ -201,6 +213,9 @@ impl From<hir::AssignmentLhs> for PlaceKind {
             hir::AssignmentLhs::Variable(var) => PlaceKind::Var(var),
             hir::AssignmentLhs::FunctionReturn(fun) => PlaceKind::FunctionReturn(fun),
             hir::AssignmentLhs::FunctionArg(arg) => PlaceKind::FunctionArg(arg),
+            hir::AssignmentLhs::ArrayElement { .. } => {
+                unreachable!("the index of an array element must be evaluated during lowering")
+            }
         }
     }
 }
@@ -219,6 +234,31 @@ impl_debug_display! {
     match LimitState {LimitState(i) => "lim_state{}", i;}
 }
 
+/// The simulator managed state of a single `last_crossing` call or `cross` event.
+/// See [`HirInterner::crossing_state_slots`] for the state slots that are used.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CrossingState(u32);
+impl_idx_from!(CrossingState(u32));
+impl_debug_display! {
+    match CrossingState {CrossingState(i) => "crossing_state{}", i;}
+}
+
+/// The simulator managed state of a single `$past` call.
+/// See [`HirInterner::past_state_slot`] for the state slot that is used.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PastState(u32);
+impl_idx_from!(PastState(u32));
+impl_debug_display! {
+    match PastState {PastState(i) => "past_state{}", i;}
+}
+
+/// The source location of the instructions lowered from `expr`.
+/// Derivatives generated by automatic differentiation use the [`derived`](SourceLoc::derived)
+/// version of this location.
+pub(crate) fn expr_srcloc(expr: ExprId) -> SourceLoc {
+    SourceLoc::new(u32::from(expr) as i32 + 1)
+}
+
 /// A mapping between abstractions used in the MIR and the corresponding
 /// information from the HIR. This allows the MIR to remain independent of the frontend/HIR
 #[derive(Debug, PartialEq, Default, Clone)]
@@ -230,6 +270,8 @@ pub struct HirInterner {
     pub tagged_reads: IndexMap<Value, Variable, ahash::RandomState>,
     pub implicit_equations: TiVec<ImplicitEquation, ImplicitEquationKind>,
     pub lim_state: TiMap<LimitState, Value, Vec<(Value, bool)>>,
+    pub num_crossing_states: u32,
+    pub num_past_states: u32,
 }
 
 pub type LiveParams<'a> = FilterMap<
@@ -238,6 +280,34 @@ pub type LiveParams<'a> = FilterMap<
 >;
 
 impl HirInterner {
+    /// The number of simulator managed states (`prev_state`/`next_state`) required by this module.
+    /// The states used by `$limit` come first followed by the states of `last_crossing`/`cross`
+    /// and `$past`.
+    pub fn num_states(&self) -> usize {
+        self.lim_state.len()
+            + 5 * self.num_crossing_states as usize
+            + 3 * self.num_past_states as usize
+    }
+
+    /// The state slots used by a `last_crossing` call (or `cross` event) to store the value of
+    /// its argument, the time and the time of the last crossing at the last accepted timepoint
+    /// as well as the value of its argument and the time of the previous evaluation.
+    /// Just like for `$past` the previous evaluation is only committed once `$abstime` advances.
+    pub fn crossing_state_slots(&self, state: CrossingState) -> [LimitState; 5] {
+        let start = self.lim_state.len() + 5 * usize::from(state);
+        std::array::from_fn(|i| (start + i).into())
+    }
+
+    /// The state slots used by a `$past` call to store the value of its argument at the last
+    /// accepted timepoint as well as the value and time of the previous evaluation.
+    /// `prev_state` only contains the previous Newton iterate, so the value of the previous
+    /// evaluation is only committed once `$abstime` advances (the timepoint was accepted).
+    pub fn past_state_slots(&self, state: PastState) -> [LimitState; 3] {
+        let start =
+            self.lim_state.len() + 5 * self.num_crossing_states as usize + 3 * usize::from(state);
+        [start.into(), (start + 1).into(), (start + 2).into()]
+    }
+
     fn contains_ddx(
         ddx_calls: &mut AHashMap<FuncRef, (HybridBitSet<Unknown>, HybridBitSet<Unknown>)>,
         func: &Function,
@@ -366,6 +436,22 @@ impl HirInterner {
         }
     }
 
+    /// Imports the signature of the callback `kind` into `func` unless it was already declared.
+    pub fn ensure_callback(
+        &mut self,
+        mut func: impl AsMut<Function>,
+        kind: CallBackKind,
+    ) -> FuncRef {
+        let data = kind.signature();
+        let (func_ref, changed) = self.callbacks.ensure(kind);
+        if changed {
+            self.callback_uses.push(Vec::new());
+            let sig = func.as_mut().import_function(data);
+            debug_assert_eq!(func_ref, sig);
+        }
+        func_ref
+    }
+
     pub fn ensure_param(&mut self, func: impl AsMut<Function>, kind: ParamKind) -> Value {
         Self::ensure_param_(&mut self.params, func, kind)
     }
@@ -4,7 +4,7 @@ use std::mem::replace;
 use hir::{CompilationDB, ConstraintValue, ParamConstraint, Parameter, Type};
 use lasso::Rodeo;
 use mir::builder::InstBuilder;
-use mir::{Block, FuncRef, Function, Opcode, Value, FALSE, GRAVESTONE, INFINITY};
+use mir::{Block, FuncRef, Function, Opcode, Value, FALSE, GRAVESTONE, INFINITY, TRUE};
 use mir_build::{FunctionBuilder, FunctionBuilderContext};
 use stdx::packed_option::ReservedValue;
 use syntax::ast::ConstraintKind;
@@ -191,7 +191,7 @@ impl HirInterner {
                                             builder.ins().call(max_inclusive, &[]);
                                             val
                                         } else {
-                                            min
+                                            max
                                         }
                                     });
 
@@ -230,13 +230,14 @@ impl HirInterner {
                                         (ops.lt.unwrap(), max_exclusive)
                                     };
 
+                                    // the upper end is independent of the lower end
                                     let is_max = ctx.ctx.ins().binary1(op, max, end);
                                     let max = ctx.ctx.make_select(is_max, |builder, is_max| {
                                         if is_max {
                                             builder.ins().call(call, &[]);
-                                            start
+                                            end
                                         } else {
-                                            min
+                                            max
                                         }
                                     });
 
@@ -358,17 +359,26 @@ impl BodyLoweringCtx<'_, '_, '_> {
                         |(start, end)| (*start, *end),
                     );
 
-                    let op = ops.in_bound(range.start_inclusive);
-                    let is_lo_ok = self.ctx.ins().binary1(op, start, param_val);
-
-                    let is_ok = self.ctx.make_select(is_lo_ok, |builder, is_ok| {
-                        if is_ok {
-                            let op = ops.in_bound(range.end_inclusive);
-                            builder.ins().binary1(op, param_val, end)
-                        } else {
-                            FALSE
+                    // infinite bounds (`inf`/`-inf`) do not restrict the parameter
+                    let lo_unbounded = self.body.is_infinity(range.start, true);
+                    let hi_unbounded = self.body.is_infinity(range.end, false);
+                    let lo_op = ops.in_bound(range.start_inclusive);
+                    let hi_op = ops.in_bound(range.end_inclusive);
+                    let is_ok = match (lo_unbounded, hi_unbounded) {
+                        (true, true) => TRUE,
+                        (true, false) => self.ctx.ins().binary1(hi_op, param_val, end),
+                        (false, true) => self.ctx.ins().binary1(lo_op, start, param_val),
+                        (false, false) => {
+                            let is_lo_ok = self.ctx.ins().binary1(lo_op, start, param_val);
+                            self.ctx.make_select(is_lo_ok, |builder, is_ok| {
+                                if is_ok {
+                                    builder.ins().binary1(hi_op, param_val, end)
+                                } else {
+                                    FALSE
+                                }
+                            })
                         }
-                    });
+                    };
 
                     let next_bb = self.ctx.create_block();
                     self.ctx.ins().br(is_ok, exit, next_bb);
@@ -1,11 +1,35 @@
-use hir::{BranchWrite, Case, CaseCond, ContributeKind, ExprId, Node, Stmt, StmtId, Type};
+use hir::{
+    AssignmentLhs, BranchWrite, Case, CaseCond, ConstEvaluator, ConstValue, ContributeKind, Event,
+    ExprId, GlobalEvent, Node, Stmt, StmtId, Type, Variable,
+};
 use mir::builder::InstBuilder;
-use mir::{Opcode, F_ZERO};
+use mir::{Opcode, Value, FALSE, F_ZERO, TRUE, ZERO};
 
 use crate::body::BodyLoweringCtx;
-use crate::{CallBackKind, CurrentKind, ParamKind, PlaceKind};
+use crate::{expr_srcloc, CallBackKind, CurrentKind, ParamKind, PlaceKind};
 
 impl BodyLoweringCtx<'_, '_, '_> {
+    /// Lowers an event of an event control statement that also contains a cross event to a
+    /// condition that is true during the evaluations where the event fires.
+    fn lower_event(&mut self, event: &Event) -> Value {
+        match *event {
+            Event::Expr(event) => self.lower_expr(event),
+            // the initial step of an analysis computes its operating point (or the initial
+            // conditions of a transient analysis)
+            Event::Global { kind: GlobalEvent::InitialStep, .. } => {
+                let [static_, ic] = ["static", "ic"].map(|name| {
+                    let name = self.ctx.sconst(name);
+                    self.ctx.call1(CallBackKind::Analysis, &[name])
+                });
+                let initial = self.ctx.ins().ior(static_, ic);
+                self.ctx.ins().ine(initial, ZERO)
+            }
+            // OSDI offers no way to detect the final step of an analysis
+            Event::Global { kind: GlobalEvent::FinalStep, .. } => FALSE,
+            _ => unreachable!("unsupported events are reported as errors"),
+        }
+    }
+
     pub(super) fn lower_stmt(&mut self, stmnt: StmtId) {
         // TODO(msrv): let .. else
         let stmnt = if let Some(stmnt) = self.body.get_stmt(stmnt) {
@@ -17,10 +41,38 @@ impl BodyLoweringCtx<'_, '_, '_> {
             Stmt::Expr(expr) => {
                 self.lower_expr(expr);
             }
-            Stmt::EventControl { body, .. } => {
-                // TODO handle porperly
+            // the values of variables are not kept between evaluations, so a statement that only
+            // waits for global events is executed during every evaluation to make its results
+            // available everywhere
+            Stmt::EventControl { events, body }
+                if events.iter().all(|event| matches!(event, Event::Global { .. })) =>
+            {
                 self.lower_stmt(body);
             }
+            Stmt::EventControl { events, body } => {
+                // every event is lowered (even if an earlier event already fired) so that
+                // the crossing state of each cross event is updated during every evaluation
+                let fired: Vec<_> = events.iter().map(|event| self.lower_event(event)).collect();
+
+                // the body is only executed (once) in the evaluations where at least one of
+                // the events fired
+                let mut fired = fired.into_iter();
+                let first = fired.next().unwrap();
+                let any_fired = fired.fold(first, |lhs, rhs| {
+                    self.ctx.make_select(lhs, |_, lhs| if lhs { TRUE } else { rhs })
+                });
+                self.ctx.make_cond(any_fired, |ctx, branch| {
+                    if branch {
+                        let mut ctx = BodyLoweringCtx { body: self.body, path: self.path, ctx };
+                        ctx.lower_stmt(body);
+                    }
+                });
+            }
+            Stmt::Assignment { lhs: AssignmentLhs::ArrayElement { var, index }, rhs } => {
+                let val_ = self.lower_expr(rhs);
+                let place = self.array_element(var, index);
+                self.ctx.def_place(place, val_);
+            }
             Stmt::Assignment { lhs, rhs } => {
                 let val_ = self.lower_expr(rhs);
                 self.ctx.def_place(lhs.into(), val_);
@@ -43,6 +95,16 @@ impl BodyLoweringCtx<'_, '_, '_> {
                 });
             }
             Stmt::ForLoop { init, cond, incr, body } => {
+                let genvar_loop = ConstEvaluator::new(self.ctx.db)
+                    .with_genvars(self.ctx.genvars.clone())
+                    .genvar_loop(self.body, init, cond, incr);
+                if let Some(iterations) = genvar_loop {
+                    let (var, vals) = iterations
+                        .expect("genvar loops that can not be unrolled are reported as errors");
+                    self.lower_genvar_loop(var, vals, body);
+                    return;
+                }
+
                 self.lower_stmt(init);
                 self.lower_loop(cond, |s| {
                     s.lower_stmt(body);
@@ -85,7 +147,7 @@ impl BodyLoweringCtx<'_, '_, '_> {
                 let val_ = self.lower_expr(*val);
 
                 let old_loc = self.ctx.get_srcloc();
-                self.ctx.set_srcloc(mir::SourceLoc::new(u32::from(*val) as i32 + 1));
+                self.ctx.set_srcloc(expr_srcloc(*val));
                 let cond = self.ctx.ins().binary1(discr_op, val_, discr);
                 self.ctx.set_srcloc(old_loc);
 
@@ -119,6 +181,37 @@ impl BodyLoweringCtx<'_, '_, '_> {
         self.ctx.switch_to_block(end);
     }
 
+    /// Genvar loops are unrolled: the body is lowered once for every iteration
+    /// with the genvar set to a constant.
+    fn lower_genvar_loop(&mut self, var: Variable, vals: Vec<ConstValue>, body: StmtId) {
+        for val in vals {
+            let val_ = match val {
+                ConstValue::Int(val) => self.ctx.iconst(val),
+                ref val => unreachable!("genvar {val:?} is not an integer"),
+            };
+            self.ctx.def_place(PlaceKind::Var(var), val_);
+            self.ctx.genvars.insert(var, val);
+            self.lower_stmt(body);
+        }
+        self.ctx.genvars.remove(&var);
+    }
+
+    /// Array indices are constants (usually depending on genvars) so every element of an
+    /// array is lowered to a separate place (and therefore ends up as a plain SSA value).
+    pub(super) fn array_element(&mut self, var: Variable, index: ExprId) -> PlaceKind {
+        let index = ConstEvaluator::new(self.ctx.db)
+            .with_genvars(self.ctx.genvars.clone())
+            .eval_expr(self.body, index);
+        let idx = match index {
+            Ok(ConstValue::Int(idx)) => idx - var.array_start(self.ctx.db),
+            res => {
+                unreachable!("array index {res:?} is not a constant integer (reported as an error)")
+            }
+        };
+        let idx = u32::try_from(idx).expect("array indices are validated to be in bounds");
+        PlaceKind::ArrayElement { var, idx }
+    }
+
     fn lower_loop(&mut self, cond: ExprId, lower_body: impl FnOnce(&mut Self)) {
         let loop_cond_head = self.ctx.create_block();
         let loop_body_head = self.ctx.create_block();
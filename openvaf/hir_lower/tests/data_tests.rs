@@ -34,16 +34,12 @@ fn integration_test(dir: &Path) -> Result {
     Ok(())
 }
 
-fn mir_test(file: &Path) -> Result {
-    let db = CompilationDB::new_fs(AbsPathBuf::assert(file.canonicalize().unwrap()), &[], &[], &[])
-        .unwrap();
-    assert_eq!(db.compilation_unit().test_diagnostics(&db), "");
-
-    let module = db.compilation_unit().modules(&db)[0];
+fn lower_first_module(db: &CompilationDB) -> String {
+    let module = db.compilation_unit().modules(db)[0];
     let mut empty_iter = [].into_iter();
     let mut literals = Rodeo::new();
     let mir = MirBuilder::new(
-        &db,
+        db,
         module,
         &|kind| {
             matches!(
@@ -56,12 +52,38 @@ fn mir_test(file: &Path) -> Result {
         &mut empty_iter,
     )
     .build(&mut literals);
+    mir.0.to_debug_string()
+}
+
+fn mir_test(file: &Path) -> Result {
+    let db = CompilationDB::new_fs(AbsPathBuf::assert(file.canonicalize().unwrap()), &[], &[], &[])
+        .unwrap();
+    assert_eq!(db.compilation_unit().test_diagnostics(&db), "");
+    expect_file![file.with_extension("mir")].assert_eq(&lower_first_module(&db));
+    Ok(())
+}
 
-    expect_file![file.with_extension("mir")].assert_eq(&mir.0.to_debug_string());
+/// The unary `+` operator does not produce any instructions.
+fn unary_plus() -> Result {
+    let lower = |expr: &str| {
+        let src = format!(
+            "`include \"disciplines.vams\"
+            module unary_plus(inout electrical a);
+                parameter integer i = 1;
+                analog I(a) <+ {expr};
+            endmodule"
+        );
+        let db = CompilationDB::new_str("unary_plus.va", &src, &[], &[], &[]).unwrap();
+        assert_eq!(db.compilation_unit().test_diagnostics(&db), "");
+        lower_first_module(&db)
+    };
+    assert_eq!(lower("+V(a) * (+i)"), lower("V(a) * i"));
+    assert_eq!(lower("-(+V(a))"), lower("-V(a)"));
     Ok(())
 }
 
 harness! {
     Test::from_dir_filtered("integration", &integration_test, &Path::is_dir, &ignore_dev_tests, &project_root().join("integration_tests")),
-    Test::from_dir_filtered("mir", &mir_test, &is_va_file, &ignore_never, &openvaf_test_data("mir"))
+    Test::from_dir_filtered("mir", &mir_test, &is_va_file, &ignore_never, &openvaf_test_data("mir")),
+    [Test::new("unary_plus", &unary_plus)]
 }
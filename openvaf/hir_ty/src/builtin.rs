@@ -173,8 +173,9 @@ bultins! {
         fn ABS_REAL(Val(Real)) -> Real;
     }
 
-    ANALYSIS = const {
-        fn ANALYSIS_SIG(Val(String)) -> Integer;
+    SIGN = const {
+        fn SIGN_INT(Val(Integer)) -> Integer;
+        fn SIGN_REAL(Val(Real)) -> Real;
     }
 
     AC_STIM = const {
@@ -187,7 +188,7 @@ bultins! {
     const fn REAL_INFO() -> Real;
     const fn REAL_MATH_1(Val(Real)) -> Real;
     const fn REAL_MATH_2(Val(Real),Val(Real)) -> Real;
-    const fn INT_MATH_2(Val(Integer),Val(Integer)) -> Integer;
+    const fn CLOG2(Val(Integer)) -> Integer;
 
 
     VT = const {
@@ -195,6 +196,11 @@ bultins! {
         fn VT_ARG(Val(Real)) -> Real;
     }
 
+    REALTIME = const {
+        fn REALTIME_SECONDS() -> Real;
+        fn REALTIME_UNIT(Val(Real)) -> Real;
+    }
+
     FLICKER_NOISE = const{
         fn FLICKER_NOISE_NO_NAME(Val(Real),Val(Real)) -> Real;
         fn FLICKER_NOISE_NAME(Val(Real),Val(Real),Literal(String)) -> Real;
@@ -276,6 +282,13 @@ bultins! {
         fn LAST_CROSSING_DIRECTION(Val(Real),Val(Integer)) -> Real;
     }
 
+    CROSS = {
+        fn CROSS_NO_DIRECTION(Val(Real)) -> Bool;
+        fn CROSS_DIRECTION(Val(Real),Val(Integer)) -> Bool;
+        fn CROSS_TIME_TOL(Val(Real),Val(Integer),Val(Real)) -> Bool;
+        fn CROSS_EXPR_TOL(Val(Real),Val(Integer),Val(Real),Val(Real)) -> Bool;
+    }
+
     fn BASIC_IO(Val(Integer)) -> Integer;
 
      FOPEN = {
@@ -368,9 +381,16 @@ bultins! {
     }
 
     fn BOUND_STEP(Val(Real)) -> Void;
-}
 
-// TODO TABLE_MODEL
+    fn NOISE_CORRELATION(Literal(String), Literal(String), Val(Real), Val(Real)) -> Void;
+
+    fn PAST(Val(Real)) -> Real;
+
+    const fn RTOI(Val(Real)) -> Integer;
+    const fn ITOR(Val(Integer)) -> Real;
+
+    fn TABLE_MODEL(Val(Real), Literal(String), Literal(String)) -> Real;
+}
 
 const DDX: BuiltinInfo = BuiltinInfo::special_cased_pure(2, Some(2));
 pub const DDX_TEMP: Signature = Signature(0);
@@ -395,6 +415,11 @@ const LIMIT: BuiltinInfo = BuiltinInfo::varargs(
     ],
     false,
 );
+/// `$analysis("ac", "noise")` accepts any number of analysis names
+const ANALYSIS: BuiltinInfo = BuiltinInfo::varargs(
+    &[SignatureData { args: Cow::Borrowed(&[Val(String)]), return_ty: Type::Integer }],
+    false,
+);
 const FDISPLAY_FUN: BuiltinInfo = BuiltinInfo::varargs(
     &[SignatureData { args: Cow::Borrowed(&[Val(Integer)]), return_ty: Type::Void }],
     true,
@@ -431,9 +456,12 @@ copied_builtins! {
     FLOOR = REAL_MATH_1
     LN = REAL_MATH_1
     LOG = REAL_MATH_1
-    CLOG2 = INT_MATH_2
+    SIGNUM = SIGN
     LOG10 = REAL_MATH_1
     CEIL = REAL_MATH_1
+    RINT = REAL_MATH_1
+    ROUND = REAL_MATH_1
+    TRUNC = REAL_MATH_1
     LIMEXP = REAL_MATH_1
     SIN = REAL_MATH_1
     SINH = REAL_MATH_1
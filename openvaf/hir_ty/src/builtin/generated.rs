@@ -4,7 +4,7 @@ use hir_def::BuiltIn;
 
 use crate::builtin::*;
 
-const BUILTIN_INFO: [BuiltinInfo; 111usize] = [
+const BUILTIN_INFO: [BuiltinInfo; 123usize] = [
     ABS,
     ACOS,
     ACOSH,
@@ -31,6 +31,7 @@ const BUILTIN_INFO: [BuiltinInfo; 111usize] = [
     SQRT,
     TAN,
     TANH,
+    SIGN,
     DISPLAY,
     STROBE,
     WRITE,
@@ -61,6 +62,7 @@ const BUILTIN_INFO: [BuiltinInfo; 111usize] = [
     ERROR,
     INFO,
     ABSTIME,
+    REALTIME,
     DIST_CHI_SQUARE,
     DIST_EXPONENTIAL,
     DIST_POISSON,
@@ -79,6 +81,12 @@ const BUILTIN_INFO: [BuiltinInfo; 111usize] = [
     RDIST_T,
     CLOG2,
     LOG10,
+    RINT,
+    ROUND,
+    TRUNC,
+    RTOI,
+    ITOR,
+    SIGNUM,
     TEMPERATURE,
     VT,
     SIMPARAM,
@@ -89,9 +97,11 @@ const BUILTIN_INFO: [BuiltinInfo; 111usize] = [
     PORT_CONNECTED,
     ANALOG_NODE_ALIAS,
     ANALOG_PORT_ALIAS,
+    TABLE_MODEL,
     TEST_PLUSARGS,
     VALUE_PLUSARGS,
     BOUND_STEP,
+    NOISE_CORRELATION,
     ANALYSIS,
     AC_STIM,
     NOISE_TABLE,
@@ -99,6 +109,7 @@ const BUILTIN_INFO: [BuiltinInfo; 111usize] = [
     WHITE_NOISE,
     FLICKER_NOISE,
     LIMIT,
+    PAST,
     ABSDELAY,
     DDT,
     IDT,
@@ -114,6 +125,7 @@ const BUILTIN_INFO: [BuiltinInfo; 111usize] = [
     LAPLACE_ZP,
     LIMEXP,
     LAST_CROSSING,
+    CROSS,
     SLEW,
     TRANSITION,
 ];
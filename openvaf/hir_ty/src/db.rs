@@ -1,10 +1,11 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use hir_def::db::HirDefDB;
 use hir_def::nameres::{ResolvedPath, ScopeDefItem};
 use hir_def::{
-    AliasParamId, BranchId, DefWithBodyId, DisciplineId, Lookup, NatureAttrId, NatureId, NodeId,
-    ParamId, ParamSysFun, Type,
+    AliasParamId, BranchId, DefWithBodyId, DisciplineId, Expr, Lookup, NatureAttrId, NatureId,
+    NodeId, ParamId, ParamSysFun, Type,
 };
 use stdx::Upcast;
 
@@ -17,6 +18,20 @@ pub struct LimitSignature {
     pub num_args: u32,
 }
 
+/// A system function `$name` that is not implemented by OpenVAF but by the host
+/// (the simulator or another application embedding OpenVAF). Calls are type checked
+/// against this signature: arguments are converted to the declared types just like
+/// the arguments of user defined functions.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct HostFunction {
+    /// The name of the function without the leading `$`.
+    pub name: String,
+    /// The types of the arguments (`real`, `integer` or `string`), all arguments are inputs.
+    pub args: Box<[Type]>,
+    /// Either `real` or `integer`.
+    pub return_ty: Type,
+}
+
 #[salsa::query_group(HirTyDatabase)]
 pub trait HirTyDB: HirDefDB + Upcast<dyn HirDefDB> {
     #[salsa::invoke(NatureTy::nature_info_query)]
@@ -42,8 +57,20 @@ pub trait HirTyDB: HirDefDB + Upcast<dyn HirDefDB> {
     #[salsa::transparent]
     fn param_ty(&self, param: ParamId) -> Type;
 
+    /// The parameters that are read by the default value of `param`.
+    fn param_dependencies(&self, param: ParamId) -> Arc<[ParamId]>;
+
+    /// The parameters (starting with `param` itself) whose default values depend on each
+    /// other in a cycle. `None` if the default of `param` does not depend on itself.
+    fn param_cycle(&self, param: ParamId) -> Option<Arc<[ParamId]>>;
+
     #[salsa::input]
     fn known_limit_functions(&self) -> Option<Arc<[LimitSignature]>>;
+
+    /// System functions provided by the host, only used for names that
+    /// do not resolve to a builtin system function.
+    #[salsa::input]
+    fn host_functions(&self) -> Arc<[HostFunction]>;
 }
 
 fn nature_attr_ty(db: &dyn HirTyDB, id: NatureAttrId) -> Option<Type> {
@@ -103,6 +130,9 @@ fn node_discipline(db: &dyn HirTyDB, node: NodeId) -> Option<DisciplineId> {
 fn param_ty(db: &dyn HirTyDB, param: ParamId) -> Type {
     match db.param_data(param).ty.clone() {
         Some(ty) => ty,
+        // the type of a default that depends on itself can not be inferred,
+        // the cycle is reported during type validation
+        None if db.param_cycle(param).is_some() => Type::Err,
         None => {
             let default_expr = db.param_exprs(param).default;
             db.inference_result(param.into()).expr_types[default_expr]
@@ -111,3 +141,52 @@ fn param_ty(db: &dyn HirTyDB, param: ParamId) -> Type {
         }
     }
 }
+
+fn param_dependencies(db: &dyn HirTyDB, param: ParamId) -> Arc<[ParamId]> {
+    let scope = param.lookup(db.upcast()).scope;
+    let body = db.body(param.into());
+    let mut res = Vec::new();
+    let mut exprs = vec![db.param_exprs(param).default];
+    while let Some(expr) = exprs.pop() {
+        if let Expr::Path { ref path, port: false } = body.exprs[expr] {
+            match scope.resolve_path(db.upcast(), path) {
+                Ok(ResolvedPath::ScopeDefItem(ScopeDefItem::ParamId(dep))) => res.push(dep),
+                Ok(ResolvedPath::ScopeDefItem(ScopeDefItem::AliasParamId(alias))) => {
+                    if let Some(Alias::Param(dep)) = db.resolve_alias(alias) {
+                        res.push(dep)
+                    }
+                }
+                _ => (),
+            }
+        }
+        body.exprs[expr].walk_child_exprs(|expr| exprs.push(expr));
+    }
+    res.into()
+}
+
+fn param_cycle(db: &dyn HirTyDB, param: ParamId) -> Option<Arc<[ParamId]>> {
+    // depth first search for a path from the dependencies of param back to param
+    let mut visited = HashSet::new();
+    let mut path = vec![param];
+    let mut stack = vec![(db.param_dependencies(param), 0)];
+    while let Some((deps, pos)) = stack.last_mut() {
+        let dep = match deps.get(*pos) {
+            Some(&dep) => dep,
+            None => {
+                stack.pop();
+                path.pop();
+                continue;
+            }
+        };
+        *pos += 1;
+
+        if dep == param {
+            return Some(path.into());
+        }
+        if visited.insert(dep) {
+            path.push(dep);
+            stack.push((db.param_dependencies(dep), 0));
+        }
+    }
+    None
+}
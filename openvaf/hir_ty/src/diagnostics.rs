@@ -115,6 +115,7 @@ impl Diagnostic for InferenceDiagnosticWrapped<'_> {
                         message: err.message(),
                     }])
                     .with_message(err.to_string())
+                    .with_notes(err.notes())
             }
             InferenceDiagnostic::ArgCntMismatch { expected, found, expr, exact } => {
                 let src = self.parse.to_file_span(
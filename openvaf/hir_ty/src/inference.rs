@@ -6,7 +6,7 @@ use ahash::AHashMap;
 use arena::ArenaMap;
 use hir_def::body::Body;
 use hir_def::db::HirDefDB;
-use hir_def::expr::{CaseCond, Literal};
+use hir_def::expr::{CaseCond, Event, Literal};
 use hir_def::nameres::diagnostics::PathResolveError;
 use hir_def::nameres::{NatureAccess, ResolvedPath, ScopeDefItem, ScopeDefItemKind};
 use hir_def::{
@@ -16,6 +16,7 @@ use hir_def::{
 use stdx::impl_from;
 use stdx::iter::zip;
 use syntax::ast::{self, BinaryOp, UnaryOp};
+use syntax::name::AsIdent;
 use syntax::{TextRange, TextSize};
 use typed_index_collections::{TiSlice, TiVec};
 
@@ -37,6 +38,7 @@ pub enum ResolvedFun {
     BuiltIn(BuiltIn),
     Param(ParamSysFun),
     InvalidNatureAccess(NatureId),
+    HostFunction(u32),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
@@ -129,6 +131,13 @@ impl Ctx<'_> {
             Stmt::ForLoop { cond, .. } | Stmt::If { cond, .. } | Stmt::WhileLoop { cond, .. } => {
                 self.infere_cond(stmt, cond)
             }
+            Stmt::EventControl { ref events, .. } => {
+                for event in events {
+                    if let Event::Expr(event) = *event {
+                        self.infere_cond(stmt, event)
+                    }
+                }
+            }
 
             Stmt::Case { discr, ref case_arms } => {
                 if let Some(ty) = self.infere_expr(stmt, discr) {
@@ -374,6 +383,8 @@ impl Ctx<'_> {
                 Ty::Val(Type::Bool)
             }
 
+            // the arms are unified like the operands of a binary operator: if one arm is real
+            // the other one is converted to real (the cast is recorded for that arm)
             Expr::Select { cond, then_val, else_val } => {
                 self.infere_cond(stmt, cond);
                 self.resolve_function_args(
@@ -389,6 +400,7 @@ impl Ctx<'_> {
             Expr::Call { ref fun, ref args } => {
                 self.infere_fun_call(stmt, expr, fun.as_ref()?, args)?
             }
+            Expr::Index { base, index } => self.infere_index(stmt, base, index)?,
             Expr::Array(ref args) if args.is_empty() => Ty::Val(Type::EmptyArray),
             Expr::Array(ref args) => self.infere_array(stmt, args)?,
             Expr::Literal(Literal::Float(_)) => Ty::Literal(Type::Real),
@@ -416,6 +428,9 @@ impl Ctx<'_> {
         fun: &Path,
         args: &[ExprId],
     ) -> Option<Ty> {
+        if let Some(host_fun) = self.host_function(stmt, fun) {
+            return self.infere_host_fun_call(stmt, expr, host_fun, args);
+        }
         let def = self.resolve_path(stmt, expr, fun)?;
         match def {
             ScopeDefItem::NatureAccess(access) => {
@@ -499,6 +514,53 @@ impl Ctx<'_> {
         .0
     }
 
+    /// Returns the index of the host function called `fun` if `fun` is a system function
+    /// that does not resolve to anything else.
+    fn host_function(&self, stmt: StmtId, fun: &Path) -> Option<u32> {
+        let name = fun.as_ident()?;
+        if !name.is_sysfun()
+            || self.body.stmt_scopes[stmt].resolve_path(self.db.upcast(), fun).is_ok()
+        {
+            return None;
+        }
+        let pos = self.db.host_functions().iter().position(|host_fun| host_fun.name == name[1..]);
+        pos.map(|pos| pos as u32)
+    }
+
+    fn infere_host_fun_call(
+        &mut self,
+        stmt: StmtId,
+        expr: ExprId,
+        host_fun: u32,
+        args: &[ExprId],
+    ) -> Option<Ty> {
+        self.result.resolved_calls.insert(expr, ResolvedFun::HostFunction(host_fun));
+        let host_functions = self.db.host_functions();
+        let fun = &host_functions[host_fun as usize];
+        if fun.args.len() != args.len() {
+            self.result.diagnostics.push(InferenceDiagnostic::ArgCntMismatch {
+                expected: fun.args.len(),
+                found: args.len(),
+                expr,
+                exact: true,
+            });
+            return Some(Ty::Val(fun.return_ty.clone()));
+        }
+
+        let signature = fun.args.iter().map(|ty| TyRequirement::Val(ty.clone())).collect();
+        self.resolve_function_args(
+            stmt,
+            expr,
+            args,
+            Cow::Owned(TiVec::from(vec![SignatureData {
+                args: Cow::Owned(signature),
+                return_ty: fun.return_ty.clone(),
+            }])),
+            None,
+        )
+        .0
+    }
+
     fn infere_nature_access(
         &mut self,
         stmt: StmtId,
@@ -605,9 +667,15 @@ impl Ctx<'_> {
             }
 
             _ if info.max_args.is_none() => {
+                // additional analysis names must be strings too
+                let ty = if builtin == BuiltIn::analysis {
+                    TyRequirement::Val(Type::String)
+                } else {
+                    TyRequirement::AnyVal
+                };
                 let mut signatures = Vec::from(info.signatures);
                 for sig in &mut signatures {
-                    sig.args.to_mut().resize(args.len(), TyRequirement::AnyVal)
+                    sig.args.to_mut().resize(args.len(), ty.clone())
                 }
                 Cow::Owned(TiVec::from(signatures))
             }
@@ -635,6 +703,12 @@ impl Ctx<'_> {
             | BuiltIn::error
             | BuiltIn::info
             | BuiltIn::fatal => self.infere_display(stmt, args),
+            // the first argument is the file descriptor (or the destination of $sformat)
+            BuiltIn::fwrite
+            | BuiltIn::fdisplay
+            | BuiltIn::fstrobe
+            | BuiltIn::fmonitor
+            | BuiltIn::sformat => self.infere_display(stmt, &args[1..]),
 
             _ => (),
         }
@@ -907,6 +981,40 @@ impl Ctx<'_> {
         }
     }
 
+    /// An element of an array variable is itself a variable reference (of the element type)
+    /// so that it can be assigned to.
+    fn infere_index(&mut self, stmt: StmtId, base: ExprId, index: ExprId) -> Option<Ty> {
+        let base_ty = self.infere_expr(stmt, base);
+        if let Some(index_ty) = self.infere_expr(stmt, index) {
+            self.expect::<false>(
+                index,
+                None,
+                index_ty,
+                Cow::Borrowed(&[TyRequirement::Val(Type::Integer)]),
+            );
+        }
+
+        match base_ty? {
+            Ty::Var(Type::Array { ty, .. }, var) => Some(Ty::Var(*ty, var)),
+            // elements of array parameters are constants that can only be read
+            Ty::Param(Type::Array { ty, .. }, _) => Some(Ty::Val(*ty)),
+            found_ty => {
+                self.result.diagnostics.push(
+                    TypeMismatch {
+                        expected: Cow::Borrowed(&[
+                            TyRequirement::ArrayVar(Type::Real),
+                            TyRequirement::ArrayVar(Type::Integer),
+                        ]),
+                        found_ty,
+                        expr: base,
+                    }
+                    .into(),
+                );
+                None
+            }
+        }
+    }
+
     fn infere_array(&mut self, stmt: StmtId, args: &[ExprId]) -> Option<Ty> {
         let infere_value_ty = |sel: &mut Self, arg| -> Option<Type> {
             sel.infere_expr(stmt, arg).and_then(|ty| {
@@ -967,7 +1075,7 @@ impl Ctx<'_> {
             }
         }
 
-        Some(Ty::Val(ty))
+        Some(Ty::Val(Type::Array { ty: Box::new(ty), len: args.len() as u32 }))
     }
 
     fn infere_bin_op(
@@ -984,7 +1092,7 @@ impl Ctx<'_> {
             BinaryOp::LesserEqualTest
             | BinaryOp::GreaterEqualTest
             | BinaryOp::LesserTest
-            | BinaryOp::GreaterTest => SignatureData::NUMERIC_COMPARISON,
+            | BinaryOp::GreaterTest => SignatureData::ORDERING_COMPARISON,
 
             BinaryOp::Addition
             | BinaryOp::Multiplication
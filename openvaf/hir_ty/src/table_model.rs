@@ -0,0 +1,276 @@
+//! Support for `$table_model` with one dimensional tables that are read from a data file
+//! at compile time.
+//!
+//! The table is turned into a piecewise polynomial (see [`TableModel::pieces`]) that can
+//! be emitted directly as code. The derivative of the generated code is therefore exactly
+//! the derivative of the interpolant.
+
+use std::fmt;
+use std::io;
+
+use basedb::{BaseDB, FileId, FileReadError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    /// `1`: piecewise linear interpolation
+    Linear,
+    /// `3`: natural cubic spline interpolation
+    CubicSpline,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extrapolation {
+    /// `C`: the value at the end of the table is used
+    Clamp,
+    /// `L`: the interpolant is extended with the slope at the end of the table
+    Linear,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableControl {
+    pub interpolation: Interpolation,
+    pub lower: Extrapolation,
+    pub upper: Extrapolation,
+}
+
+impl Default for TableControl {
+    fn default() -> TableControl {
+        TableControl {
+            interpolation: Interpolation::Linear,
+            lower: Extrapolation::Linear,
+            upper: Extrapolation::Linear,
+        }
+    }
+}
+
+impl TableControl {
+    /// Parses a control string like `"1LL"` or `"3C"`. The first character selects the
+    /// interpolation degree, the following characters the extrapolation below/above the table.
+    /// A single extrapolation character applies to both ends. An empty control string selects
+    /// linear interpolation and extrapolation.
+    pub fn parse(control: &str) -> Result<TableControl, TableModelError> {
+        let invalid = || TableModelError::InvalidControl(control.to_owned());
+        let mut res = TableControl::default();
+        let mut chars = control.trim().chars();
+        res.interpolation = match chars.next() {
+            None => return Ok(res),
+            Some('1') => Interpolation::Linear,
+            Some('3') => Interpolation::CubicSpline,
+            Some(_) => return Err(invalid()),
+        };
+
+        let extrapolation = |c| match c {
+            'C' | 'c' => Ok(Extrapolation::Clamp),
+            'L' | 'l' => Ok(Extrapolation::Linear),
+            _ => Err(invalid()),
+        };
+        match (chars.next(), chars.next(), chars.next()) {
+            (None, _, _) => (),
+            (Some(both), None, _) => {
+                res.lower = extrapolation(both)?;
+                res.upper = res.lower;
+            }
+            (Some(lower), Some(upper), None) => {
+                res.lower = extrapolation(lower)?;
+                res.upper = extrapolation(upper)?;
+            }
+            _ => return Err(invalid()),
+        }
+        Ok(res)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableModelError {
+    Read { file: String, err: io::ErrorKind },
+    InvalidEntry { file: String, line: usize },
+    DuplicatePoint { file: String, line: usize },
+    TooFewPoints { file: String, found: usize },
+    InvalidControl(String),
+}
+
+impl fmt::Display for TableModelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableModelError::Read { file, err } => {
+                write!(f, "failed to read table file '{file}': {}", io::Error::from(*err))
+            }
+            TableModelError::InvalidEntry { file, line } => {
+                write!(f, "{file}:{line}: expected two real numbers per line")
+            }
+            TableModelError::DuplicatePoint { file, line } => {
+                write!(f, "{file}:{line}: duplicate entry for the same input value")
+            }
+            TableModelError::TooFewPoints { file, found } => {
+                write!(f, "table file '{file}' must contain at least 2 points (found {found})")
+            }
+            TableModelError::InvalidControl(control) => {
+                write!(f, "invalid control string \"{control}\"")
+            }
+        }
+    }
+}
+
+/// A polynomial `c[0] + c[1]*t + c[2]*t^2 + c[3]*t^3` with `t = x - x0` that is
+/// valid for all `x >= start` (up to the start of the next piece).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Piece {
+    pub start: f64,
+    pub x0: f64,
+    pub coeffs: [f64; 4],
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableModel {
+    pub xs: Vec<f64>,
+    pub ys: Vec<f64>,
+    pub control: TableControl,
+}
+
+impl TableModel {
+    /// Loads the table `file` (relative to the directory of `root_file`).
+    pub fn load(
+        db: &dyn BaseDB,
+        root_file: FileId,
+        file: &str,
+        control: &str,
+    ) -> Result<TableModel, TableModelError> {
+        let control = TableControl::parse(control)?;
+        let read_err = |err| TableModelError::Read { file: file.to_owned(), err };
+        let path = db
+            .file_path(root_file)
+            .parent()
+            .and_then(|dir| dir.join(file))
+            .ok_or_else(|| read_err(io::ErrorKind::NotFound))?;
+        let text = db.file_text(db.file_id(path)).map_err(|err| match err {
+            FileReadError::Io(err) => read_err(err),
+            FileReadError::InvalidTextFormat(_) => read_err(io::ErrorKind::InvalidData),
+        })?;
+        TableModel::parse(file, &text, control)
+    }
+
+    /// Parses a table with one `x y` pair per line. Empty lines and comments (starting with `#`)
+    /// are ignored. The entries do not need to be sorted.
+    pub fn parse(
+        file: &str,
+        text: &str,
+        control: TableControl,
+    ) -> Result<TableModel, TableModelError> {
+        let mut points = Vec::new();
+        for (line, src) in text.lines().enumerate() {
+            let src = src.split('#').next().unwrap_or("");
+            let mut vals =
+                src.split(|c: char| c.is_whitespace() || c == ',').filter(|it| !it.is_empty());
+            let point = match (vals.next(), vals.next(), vals.next()) {
+                (None, _, _) => continue,
+                (Some(x), Some(y), None) => x.parse::<f64>().ok().zip(y.parse::<f64>().ok()),
+                _ => None,
+            };
+            match point {
+                Some((x, y)) if x.is_finite() && y.is_finite() => points.push((x, y, line + 1)),
+                _ => {
+                    return Err(TableModelError::InvalidEntry {
+                        file: file.to_owned(),
+                        line: line + 1,
+                    })
+                }
+            }
+        }
+
+        if points.len() < 2 {
+            return Err(TableModelError::TooFewPoints {
+                file: file.to_owned(),
+                found: points.len(),
+            });
+        }
+
+        points.sort_by(|(x1, ..), (x2, ..)| x1.total_cmp(x2));
+        if let Some(window) = points.windows(2).find(|window| window[0].0 == window[1].0) {
+            let line = window[0].2.max(window[1].2);
+            return Err(TableModelError::DuplicatePoint { file: file.to_owned(), line });
+        }
+
+        let (xs, ys) = points.into_iter().map(|(x, y, _)| (x, y)).unzip();
+        Ok(TableModel { xs, ys, control })
+    }
+
+    /// Returns the interpolant (including extrapolation) as a list of polynomials
+    /// sorted by [`Piece::start`]. The first piece starts at negative infinity.
+    pub fn pieces(&self) -> Vec<Piece> {
+        let n = self.xs.len();
+        let mut segments: Vec<Piece> = match self.control.interpolation {
+            Interpolation::Linear => (0..n - 1)
+                .map(|i| {
+                    let slope = (self.ys[i + 1] - self.ys[i]) / (self.xs[i + 1] - self.xs[i]);
+                    Piece {
+                        start: self.xs[i],
+                        x0: self.xs[i],
+                        coeffs: [self.ys[i], slope, 0.0, 0.0],
+                    }
+                })
+                .collect(),
+            Interpolation::CubicSpline => self.spline_segments(),
+        };
+
+        let lower_slope = segments[0].coeffs[1];
+        let last = segments[n - 2];
+        let h = self.xs[n - 1] - last.x0;
+        let [_, b, c, d] = last.coeffs;
+        let upper_slope = b + 2.0 * c * h + 3.0 * d * h * h;
+
+        let extrapolate = |kind, y0, slope| {
+            let slope = if kind == Extrapolation::Linear { slope } else { 0.0 };
+            [y0, slope, 0.0, 0.0]
+        };
+
+        segments.insert(
+            0,
+            Piece {
+                start: f64::NEG_INFINITY,
+                x0: self.xs[0],
+                coeffs: extrapolate(self.control.lower, self.ys[0], lower_slope),
+            },
+        );
+        segments.push(Piece {
+            start: self.xs[n - 1],
+            x0: self.xs[n - 1],
+            coeffs: extrapolate(self.control.upper, self.ys[n - 1], upper_slope),
+        });
+        segments
+    }
+
+    /// Natural cubic spline (vanishing second derivative at both ends).
+    fn spline_segments(&self) -> Vec<Piece> {
+        let (xs, ys) = (&self.xs, &self.ys);
+        let n = xs.len();
+        let h: Vec<f64> = xs.windows(2).map(|x| x[1] - x[0]).collect();
+
+        // solve the tridiagonal system for the second derivatives (Thomas algorithm)
+        let mut m = vec![0.0; n];
+        if n > 2 {
+            let mut diag = vec![0.0; n];
+            let mut rhs = vec![0.0; n];
+            for i in 1..n - 1 {
+                diag[i] = 2.0 * (h[i - 1] + h[i]);
+                rhs[i] = 6.0 * ((ys[i + 1] - ys[i]) / h[i] - (ys[i] - ys[i - 1]) / h[i - 1]);
+            }
+            for i in 2..n - 1 {
+                let w = h[i - 1] / diag[i - 1];
+                diag[i] -= w * h[i - 1];
+                rhs[i] -= w * rhs[i - 1];
+            }
+            for i in (1..n - 1).rev() {
+                m[i] = (rhs[i] - h[i] * m[i + 1]) / diag[i];
+            }
+        }
+
+        (0..n - 1)
+            .map(|i| {
+                let b = (ys[i + 1] - ys[i]) / h[i] - h[i] * (2.0 * m[i] + m[i + 1]) / 6.0;
+                let c = m[i] / 2.0;
+                let d = (m[i + 1] - m[i]) / (6.0 * h[i]);
+                Piece { start: xs[i], x0: xs[i], coeffs: [ys[i], b, c, d] }
+            })
+            .collect()
+    }
+}
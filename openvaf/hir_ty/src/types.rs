@@ -17,6 +17,7 @@ pub enum TyRequirement {
     PortFlow,
     Nature,
     Var(Type),
+    ArrayVar(Type),
     Param(Type),
     AnyParam,
     Branch,
@@ -60,6 +61,7 @@ impl_display! {
         TyRequirement::Node => "net reference";
         TyRequirement:: Nature => "nature reference";
         TyRequirement::Var(ty) => "{} variable reference", ty;
+        TyRequirement::ArrayVar(ty) => "{} array variable reference", ty;
         TyRequirement::Param(ty) => "{} parameter ref", ty;
         TyRequirement::AnyParam => "parameter reference";
         TyRequirement::Literal(ty) => "{} literal", ty;
@@ -214,6 +216,10 @@ impl Ty {
             )
             | (Ty::Param(ty1, _), TyRequirement::Param(ty2)) => ty1 == ty2,
 
+            (Ty::Var(Type::Array { ty: ref ty1, .. }, _), TyRequirement::ArrayVar(ty2)) => {
+                **ty1 == *ty2
+            }
+
             _ => false,
         }
     }
@@ -300,6 +306,13 @@ impl SignatureData {
         &[SignatureData::INT_BIN_OP, SignatureData::REAL_BIN_OP];
     pub const NUMERIC_COMPARISON: &'static [SignatureData] =
         &[SignatureData::INT_COMPARISON, SignatureData::REAL_COMPARISON];
+    /// Strings can not be ordered. They are only accepted here so that validation can
+    /// report a clear error (see [`STR_ORDERING`]).
+    pub const ORDERING_COMPARISON: &'static [SignatureData] = &[
+        SignatureData::INT_COMPARISON,
+        SignatureData::REAL_COMPARISON,
+        SignatureData::STR_COMPARISON,
+    ];
     pub const ANY_COMPARISON: &'static [SignatureData] = &[
         SignatureData::BOOL_COMPARISON,
         SignatureData::INT_COMPARISON,
@@ -318,6 +331,7 @@ pub const STR_EQ: Signature = Signature(3);
 
 pub const INT_OP: Signature = Signature(0);
 pub const REAL_OP: Signature = Signature(1);
+pub const STR_ORDERING: Signature = Signature(2);
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct Signature(pub u32);
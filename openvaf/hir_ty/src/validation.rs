@@ -1,21 +1,28 @@
 use basedb::diagnostics::{Diagnostic, Label, LabelStyle, Report};
-use basedb::lints::builtin::{const_simparam, trivial_probe, variant_const_simparam};
+use basedb::lints::builtin::{
+    celsius_temperature, charge_conservation, conflicting_contribution, const_simparam,
+    file_io_in_analog_block, last_crossing_derivative, nature_mismatch, no_contributions,
+    rounding_derivative, sign_derivative, sqrt_domain, time_dependent_contribution, trivial_probe,
+    variant_const_simparam,
+};
 use basedb::lints::{self, Lint, LintSrc};
 use basedb::{AstIdMap, BaseDB, FileId};
-pub use body::BodyValidationDiagnostic;
+pub use body::{inlined_size, BodyValidationDiagnostic};
 use hir_def::body::BodySourceMap;
 use hir_def::{
-    DisciplineAttr, ExprId, ItemLoc, ItemTree, ItemTreeNode, Lookup, NatureAttr, NodeId,
-    NodeTypeDecl,
+    BuiltIn, DisciplineAttr, ExprId, ItemLoc, ItemTree, ItemTreeNode, Lookup, NatureAttr, NodeId,
+    NodeTypeDecl, StmtId,
 };
 use syntax::name::Name;
 use syntax::sourcemap::{FileSpan, SourceMap};
-use syntax::{Parse, SourceFile, TextRange};
+use syntax::{AstNode, Parse, SourceFile, TextRange};
 pub use types::TypeValidationDiagnostic;
 
 use crate::db::HirTyDB;
 use crate::inference::BranchWrite;
-use crate::validation::body::{BodyCtx, IllegalCtxAccess, IllegalCtxAccessKind};
+use crate::validation::body::{
+    BodyCtx, IllegalCtxAccess, IllegalCtxAccessKind, NoiseCorrelationError,
+};
 use crate::validation::types::DuplicateItem;
 
 mod body;
@@ -94,6 +101,24 @@ impl BodyValidationDiagnosticWrapped<'_> {
         self.parse.to_file_span(self.body_sm.expr_map_back[expr].as_ref().unwrap().range(), self.sm)
     }
 
+    fn stmt_src(&self, stmt: StmtId) -> FileSpan {
+        self.parse.to_file_span(self.body_sm.stmt_map_back[stmt].as_ref().unwrap().range(), self.sm)
+    }
+
+    fn branch_name(&self, branch: BranchWrite) -> String {
+        let db = self.db.upcast();
+        match branch {
+            BranchWrite::Named(branch) => {
+                let branch = branch.lookup(db).name(db);
+                branch.to_string()
+            }
+            BranchWrite::Unnamed { hi, lo: Some(lo) } => {
+                format!("({}, {})", db.node_data(hi).name, db.node_data(lo).name)
+            }
+            BranchWrite::Unnamed { hi, lo: None } => format!("({})", db.node_data(hi).name),
+        }
+    }
+
     fn lookup<I, T>(&self, id: I) -> (Name, FileSpan)
     where
         I: Lookup<Data = ItemLoc<T>>,
@@ -130,6 +155,50 @@ impl Diagnostic for BodyValidationDiagnosticWrapped<'_> {
                 let src = self.body_sm.lint_src(stmt, trivial_probe);
                 Some((trivial_probe, src))
             }
+            BodyValidationDiagnostic::NatureMismatch { stmt, .. } => {
+                let src = self.body_sm.lint_src(stmt, nature_mismatch);
+                Some((nature_mismatch, src))
+            }
+            BodyValidationDiagnostic::LastCrossingDerivative { stmt, .. } => {
+                let src = self.body_sm.lint_src(stmt, last_crossing_derivative);
+                Some((last_crossing_derivative, src))
+            }
+            BodyValidationDiagnostic::SignDerivative { stmt, .. } => {
+                let src = self.body_sm.lint_src(stmt, sign_derivative);
+                Some((sign_derivative, src))
+            }
+            BodyValidationDiagnostic::RoundingDerivative { stmt, .. } => {
+                let src = self.body_sm.lint_src(stmt, rounding_derivative);
+                Some((rounding_derivative, src))
+            }
+            BodyValidationDiagnostic::FileIoInAnalogBlock { stmt, .. } => {
+                let src = self.body_sm.lint_src(stmt, file_io_in_analog_block);
+                Some((file_io_in_analog_block, src))
+            }
+            BodyValidationDiagnostic::CelsiusTemperature { stmt, .. } => {
+                let src = self.body_sm.lint_src(stmt, celsius_temperature);
+                Some((celsius_temperature, src))
+            }
+            BodyValidationDiagnostic::ConflictingContribution { stmt, .. } => {
+                let src = self.body_sm.lint_src(stmt, conflicting_contribution);
+                Some((conflicting_contribution, src))
+            }
+            BodyValidationDiagnostic::TimeDependentContribution { stmt, .. } => {
+                let src = self.body_sm.lint_src(stmt, time_dependent_contribution);
+                Some((time_dependent_contribution, src))
+            }
+            BodyValidationDiagnostic::NonConservativeCharge { stmt, .. } => {
+                let src = self.body_sm.lint_src(stmt, charge_conservation);
+                Some((charge_conservation, src))
+            }
+            BodyValidationDiagnostic::SqrtDomain { stmt, .. } => {
+                let src = self.body_sm.lint_src(stmt, sqrt_domain);
+                Some((sqrt_domain, src))
+            }
+            BodyValidationDiagnostic::NoContributions { module } => {
+                let ast_id = module.lookup(self.db.upcast()).ast_id(self.db.upcast());
+                Some((no_contributions, LintSrc::item(ast_id.into())))
+            }
             _ => None,
         }
     }
@@ -337,6 +406,14 @@ impl Diagnostic for BodyValidationDiagnosticWrapped<'_> {
                         "analysis function '{}' is not allowed in constants",
                         name
                     )),
+                    IllegalCtxAccessKind::RandomFun { name } => res
+                        .with_message(format!("'{}' is not allowed in {}", name, ctx))
+                        .with_notes(vec![
+                            "help: random numbers are drawn once when an instance is set up"
+                                .to_owned(),
+                            "help: use them in an analog initial block and store the result in a variable"
+                                .to_owned(),
+                        ]),
                     IllegalCtxAccessKind::Var(var) => {
                         let name = var.lookup(self.db.upcast()).name(self.db.upcast());
                         let def = var.lookup(self.db.upcast()).ast_ptr(self.db.upcast()).range();
@@ -455,6 +532,41 @@ impl Diagnostic for BodyValidationDiagnosticWrapped<'_> {
                     .with_message(format!("'{name}' access of branch without {name}"))
                     .with_notes(vec![format!("help: this branches nodes have a discipline without the '{name}' attribute")])
             }
+            BodyValidationDiagnostic::DiscreteNatureAccess { discipline, access_expr } => {
+                let name = &self.db.discipline_data(discipline).name;
+                let src = self.expr_src(access_expr);
+                Report::error()
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: src.file,
+                        range: src.range.into(),
+                        message: format!("access of net with discrete discipline '{name}'"),
+                    }])
+                    .with_message("discrete disciplines are currently not supported by OpenVAF")
+                    .with_notes(vec![
+                        "help: only disciplines with 'domain continuous' can be accessed in analog code".to_owned(),
+                    ])
+            }
+            BodyValidationDiagnostic::NatureMismatch {
+                op, nature, expected, found, expr, ..
+            } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                let nature = &self.db.nature_data(nature).name;
+                let expected = &self.db.nature_data(expected).name;
+                let found = &self.db.nature_data(found).name;
+                let attr = if op == BuiltIn::ddt { "ddt_nature" } else { "idt_nature" };
+                Report::error()
+                    .with_message(format!("'{op:?}' of '{nature}' contributed to '{found}'"))
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: format!("expected '{expected}' found '{found}'"),
+                    }])
+                    .with_notes(vec![format!(
+                        "help: the {attr} of '{nature}' is declared as '{expected}'"
+                    )])
+            }
             BodyValidationDiagnostic::IncompatibleImplicitBranch { access, node1, node2 } => {
                 let node1_ = self.db.node_data(node1);
                 let node2_ = self.db.node_data(node2);
@@ -466,21 +578,381 @@ impl Diagnostic for BodyValidationDiagnosticWrapped<'_> {
                 }
                 .into_report(self.db, self.parse, self.map, self.sm)
             }
-            BodyValidationDiagnostic::TrivialBranchAccess { branch, expr, .. } => {
+            BodyValidationDiagnostic::InvalidTableModel { expr, ref err } => {
                 let FileSpan { range, file } = self.expr_src(expr);
-                let db = self.db.upcast();
-                let branch_name = match branch {
-                    BranchWrite::Named(branch) => {
-                        let branch = branch.lookup(db).name(db);
-                        branch.to_string()
-                    }
-                    BranchWrite::Unnamed { hi, lo: Some(lo) } => {
-                        format!("({}, {})", db.node_data(hi).name, db.node_data(lo).name)
-                    }
-                    BranchWrite::Unnamed { hi, lo: None } => {
-                        format!("({})", db.node_data(hi).name)
-                    }
+                Report::error().with_message(err.to_string()).with_labels(vec![Label {
+                    style: LabelStyle::Primary,
+                    file_id: file,
+                    range: range.into(),
+                    message: "failed to load table model".to_owned(),
+                }])
+            }
+            BodyValidationDiagnostic::GenvarAssignment { expr } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                Report::error()
+                    .with_message("genvars can only be assigned in the header of a for loop")
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "invalid assignment".to_owned(),
+                    }])
+            }
+            BodyValidationDiagnostic::NonConstGenvarLoop { expr } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                Report::error()
+                    .with_message("genvar loops must have constant bounds")
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "not known at compile time".to_owned(),
+                    }])
+                    .with_notes(vec![
+                        "help: only literals, genvars and localparams that do not depend\n\
+                         on parameters can be used here\n\
+                         parameters can be overwritten at runtime"
+                            .to_owned(),
+                    ])
+            }
+            BodyValidationDiagnostic::NonConstArrayIndex { expr } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                Report::error()
+                    .with_message("array index must be known at compile time")
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "not known at compile time".to_owned(),
+                    }])
+                    .with_notes(vec![
+                        "help: only literals, genvars and localparams that do not depend\n\
+                         on parameters can be used here\n\
+                         use a genvar loop to iterate over the elements of an array"
+                            .to_owned(),
+                    ])
+            }
+            BodyValidationDiagnostic::ArrayWithoutIndex { expr, var } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                let name = &self.db.var_data(var).name;
+                Report::error()
+                    .with_message(format!("array '{name}' can only be accessed element-wise"))
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "missing index".to_owned(),
+                    }])
+                    .with_notes(vec![format!("help: select an element like {name}[0]")])
+            }
+            BodyValidationDiagnostic::InvalidGenvarIncrement { stmt, var } => {
+                let FileSpan { range, file } = self.parse.to_file_span(
+                    self.body_sm.stmt_map_back[stmt].as_ref().unwrap().range(),
+                    self.sm,
+                );
+                let name = &self.db.var_data(var).name;
+                Report::error()
+                    .with_message(format!("genvar loop does not increment '{name}'"))
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: format!("expected an assignment to '{name}'"),
+                    }])
+            }
+            BodyValidationDiagnostic::LastCrossingDerivative { expr, .. } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                Report::error()
+                    .with_message("derivative of 'last_crossing' is assumed to be zero")
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "not differentiable".to_owned(),
+                    }])
+                    .with_notes(vec![
+                        "help: the time of the last crossing only changes at discrete events"
+                            .to_owned(),
+                    ])
+            }
+            BodyValidationDiagnostic::SignDerivative { expr, .. } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                Report::error()
+                    .with_message("derivative of 'sign' is assumed to be zero")
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "not differentiable at zero".to_owned(),
+                    }])
+                    .with_notes(vec![
+                        "help: the discontinuity at zero is ignored by the derivative".to_owned(),
+                    ])
+            }
+            BodyValidationDiagnostic::RoundingDerivative { expr, fun, .. } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                let name = match fun {
+                    BuiltIn::floor => "floor",
+                    BuiltIn::ceil => "ceil",
+                    BuiltIn::rint => "$rint",
+                    BuiltIn::round => "$round",
+                    BuiltIn::trunc => "$trunc",
+                    _ => unreachable!("{fun:?} is not a rounding function"),
                 };
+                Report::error()
+                    .with_message(format!("derivative of '{name}' is assumed to be zero"))
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "not differentiable at integers".to_owned(),
+                    }])
+                    .with_notes(vec![
+                        "help: the jumps at integer values are ignored by the derivative"
+                            .to_owned(),
+                    ])
+            }
+            BodyValidationDiagnostic::FileIoInAnalogBlock { expr, .. } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                Report::error()
+                    .with_message("file I/O is executed for every newton iteration")
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "called inside the analog block".to_owned(),
+                    }])
+                    .with_notes(vec![
+                        "help: move file I/O into an 'analog initial' block".to_owned()
+                    ])
+            }
+            BodyValidationDiagnostic::CrossOutsideEvent { expr } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                Report::error()
+                    .with_message("'cross' can only be used as an event")
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "used as an expression".to_owned(),
+                    }])
+                    .with_notes(vec![
+                        "help: use '@(cross(...))' to execute a statement at the crossing\n\
+                         or 'last_crossing' to obtain the time of the crossing"
+                            .to_owned(),
+                    ])
+            }
+            BodyValidationDiagnostic::InvalidEvent { expr } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                Report::error()
+                    .with_message("unsupported event")
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "expected 'cross(...)'".to_owned(),
+                    }])
+                    .with_notes(vec![
+                        "help: OpenVAF supports 'initial_step', 'final_step' and 'cross' events"
+                            .to_owned(),
+                    ])
+            }
+            BodyValidationDiagnostic::CelsiusTemperature { literal, .. } => {
+                let FileSpan { range, file } = self.expr_src(literal);
+                Report::error()
+                    .with_message("'$temperature' is compared to a temperature in celsius")
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "this looks like a temperature in celsius".to_owned(),
+                    }])
+                    .with_notes(vec![
+                        "help: '$temperature' is in kelvin, use '$temperature - `P_CELSIUS0'\n\
+                         to obtain the temperature in celsius"
+                            .to_owned(),
+                    ])
+            }
+            BodyValidationDiagnostic::ConflictingContribution { branch, first, stmt } => {
+                let FileSpan { range, file } = self.stmt_src(stmt);
+                let first = self.stmt_src(first);
+                Report::error()
+                    .with_message(format!(
+                        "both the potential and the flow of branch {} are contributed \
+                         unconditionally",
+                        self.branch_name(branch)
+                    ))
+                    .with_labels(vec![
+                        Label {
+                            style: LabelStyle::Primary,
+                            file_id: file,
+                            range: range.into(),
+                            message: "this contribution discards the previous one".to_owned(),
+                        },
+                        Label {
+                            style: LabelStyle::Secondary,
+                            file_id: first.file,
+                            range: first.range.into(),
+                            message: "discarded".to_owned(),
+                        },
+                    ])
+                    .with_notes(vec![
+                        "help: the last contribution determines whether a branch is a potential \
+                         or flow source\nuse a condition to switch between the two"
+                            .to_owned(),
+                    ])
+            }
+            BodyValidationDiagnostic::TimeDependentContribution { expr, .. } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                Report::error()
+                    .with_message("contribution depends on the simulation time")
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "depends on time".to_owned(),
+                    }])
+                    .with_notes(vec![
+                        "help: time dependent contributions are meaningless during a DC analysis\n\
+                         guard them with 'if (analysis(\"tran\"))'"
+                            .to_owned(),
+                    ])
+            }
+            BodyValidationDiagnostic::StringOrdering { expr } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                Report::error()
+                    .with_message("strings can only be compared for equality")
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "unsupported comparison".to_owned(),
+                    }])
+                    .with_notes(vec!["help: use '==' or '!=' instead".to_owned()])
+            }
+            BodyValidationDiagnostic::NonConservativeCharge { expr, .. } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                Report::error()
+                    .with_message("capacitance multiplied with a voltage does not conserve charge")
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "voltage dependent capacitance".to_owned(),
+                    }])
+                    .with_notes(vec!["help: contribute the charge Q(V) instead\n\
+                         the capacitance C(V) = dQ/dV is computed automatically"
+                        .to_owned()])
+            }
+            BodyValidationDiagnostic::SqrtDomain { expr, .. } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                Report::error()
+                    .with_message("argument of 'sqrt' may be negative")
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "depends on a potential".to_owned(),
+                    }])
+                    .with_notes(vec![
+                        "help: sqrt is NaN for negative arguments and its derivative \
+                         is infinite at zero\n\
+                         use sqrt(max(x, 0)) or compile with --clamp-sqrt"
+                            .to_owned(),
+                    ])
+            }
+            BodyValidationDiagnostic::NoContributions { module } => {
+                let loc = module.lookup(self.db.upcast());
+                let name = loc.name(self.db.upcast());
+                let range = loc.source(self.db.upcast()).name().unwrap().syntax().text_range();
+                let FileSpan { range, file } = self.parse.to_file_span(range, self.sm);
+                Report::error()
+                    .with_message(format!("module '{name}' does not contain any contributions"))
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "no contributions".to_owned(),
+                    }])
+                    .with_notes(vec![
+                        "help: the compiled model does not have any equations".to_owned(),
+                        "note: modules that only compute variables can allow this lint\n\
+                         with (* openvaf_allow=\"no_contributions\" *)"
+                            .to_owned(),
+                    ])
+            }
+            BodyValidationDiagnostic::RecursiveFunction { expr, fun } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                let name = &self.db.function_data(fun).name;
+                Report::error()
+                    .with_message(format!("function '{name}' is called recursively"))
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "recursive call".to_owned(),
+                    }])
+                    .with_notes(vec!["help: analog functions are inlined during compilation\n\
+                         and can therefore not be recursive"
+                        .to_owned()])
+            }
+            BodyValidationDiagnostic::InlineLimit { expr, limit } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                Report::error()
+                    .with_message(format!(
+                        "inlining analog functions exceeds the maximum of {limit} expressions"
+                    ))
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "limit exceeded while inlining this call".to_owned(),
+                    }])
+                    .with_notes(vec!["help: analog functions are inlined during compilation\n\
+                         the limit can be increased with --expression-limit"
+                        .to_owned()])
+            }
+            BodyValidationDiagnostic::InvalidNoiseCorrelation { expr, ref err } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                let (message, label, note) = match err {
+                    NoiseCorrelationError::IllegalCtx(ctx) => (
+                        format!("'$noise_correlation' is not allowed in {ctx}"),
+                        "not allowed here",
+                        "help: noise correlations are constant, declare them \
+                         unconditionally in the analog block",
+                    ),
+                    NoiseCorrelationError::NonLiteral => (
+                        "noise correlation coefficients must be numeric literals".to_owned(),
+                        "expected a literal",
+                        "help: the correlation is stored in the model descriptor \
+                         and can not depend on parameters",
+                    ),
+                    NoiseCorrelationError::Magnitude => (
+                        "the magnitude of a noise correlation coefficient must not exceed one"
+                            .to_owned(),
+                        "invalid correlation",
+                        "help: the coefficient is real + j*imag with sqrt(real^2 + imag^2) <= 1",
+                    ),
+                    NoiseCorrelationError::UnknownSource(name) => (
+                        format!("no noise source named \"{name}\""),
+                        "unknown noise source",
+                        "help: noise sources are named by the last argument of \
+                         white_noise, flicker_noise and noise_table",
+                    ),
+                };
+                Report::error()
+                    .with_message(message)
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: label.to_owned(),
+                    }])
+                    .with_notes(vec![note.to_owned()])
+            }
+            BodyValidationDiagnostic::TrivialBranchAccess { branch, expr, .. } => {
+                let FileSpan { range, file } = self.expr_src(expr);
+                let branch_name = self.branch_name(branch);
                 let branch_probe = match branch {
                     BranchWrite::Named(_) => &branch_name,
                     BranchWrite::Unnamed { .. } => &branch_name[1..branch_name.len() - 1],
@@ -586,6 +1058,7 @@ impl Diagnostic for TypeValidationDiagnosticWrapped<'_> {
                         message: err.message(),
                     }])
                     .with_message(err.to_string())
+                    .with_notes(err.notes())
             }
             TypeValidationDiagnostic::DuplicateDisciplineAttr(ref info) => {
                 let discipline = &self.item_tree[info.src.lookup(self.db.upcast()).id];
@@ -714,6 +1187,62 @@ impl Diagnostic for TypeValidationDiagnosticWrapped<'_> {
                 }
                 .into_report(self.db, self.parse, self.map, self.sm)
             }
+            TypeValidationDiagnostic::DegenerateBranch { branch, node } => {
+                let branch = branch.lookup(self.db.upcast());
+                let branch_range = branch.ast_ptr(self.db.upcast()).range();
+                let FileSpan { range, file } = self.parse.to_file_span(branch_range, self.sm);
+                let branch_name = branch.name(self.db.upcast());
+                let node_name = &self.db.node_data(node).name;
+                Report::error()
+                    .with_message(format!(
+                        "branch '{branch_name}' connects net '{node_name}' to itself"
+                    ))
+                    .with_labels(vec![Label {
+                        style: LabelStyle::Primary,
+                        file_id: file,
+                        range: range.into(),
+                        message: "both terminals are the same net".to_owned(),
+                    }])
+                    .with_notes(vec![format!(
+                        "help: the potential of such a branch is always zero\n\
+                         use 'branch ({node_name}) {branch_name};' for a branch to ground"
+                    )])
+            }
+            TypeValidationDiagnostic::ParamCycle(ref cycle) => {
+                let labels = cycle
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &param)| {
+                        let dep = cycle[(i + 1) % cycle.len()];
+                        let default = self.db.param_exprs(param).default;
+                        let src = self.db.body_source_map(param.into()).expr_map_back[default]
+                            .as_ref()
+                            .unwrap()
+                            .range();
+                        let src = self.parse.to_file_span(src, self.sm);
+                        Label {
+                            style: if i == 0 { LabelStyle::Primary } else { LabelStyle::Secondary },
+                            file_id: src.file,
+                            range: src.range.into(),
+                            message: format!(
+                                "default of '{}' depends on '{}'",
+                                self.db.param_data(param).name,
+                                self.db.param_data(dep).name
+                            ),
+                        }
+                    })
+                    .collect();
+
+                Report::error()
+                    .with_labels(labels)
+                    .with_message(format!(
+                        "default value of parameter '{}' depends on itself",
+                        self.db.param_data(cycle[0]).name
+                    ))
+                    .with_notes(vec![
+                        "help: replace one of the parameter references with a constant".to_owned(),
+                    ])
+            }
         }
     }
 
@@ -1,13 +1,17 @@
 use std::mem::replace;
 
 use ahash::{HashMap, HashSet};
+use basedb::BaseDB;
 use hir_def::body::Body;
+use hir_def::db::HirDefDB;
+use hir_def::expr::Event;
 use hir_def::{
-    BranchId, BuiltIn, DefWithBodyId, DisciplineId, Expr, ExprId, FunctionArgLoc, Literal, Lookup,
-    NatureId, NodeId, ParamId, Path, Stmt, StmtId, VarId,
+    BranchId, BuiltIn, DefWithBodyId, DisciplineId, Domain, Expr, ExprId, FunctionArgLoc,
+    FunctionId, Literal, Lookup, ModuleId, NatureId, NodeId, ParamId, Path, Stmt, StmtId, Type,
+    VarId,
 };
 use stdx::impl_display;
-use syntax::ast::AssignOp;
+use syntax::ast::{AssignOp, BinaryOp, UnaryOp};
 use syntax::name::{AsIdent, Name};
 
 use crate::builtin::{
@@ -16,15 +20,17 @@ use crate::builtin::{
     TRANSITION_DELAY_RISET_FALLT_TOL,
 };
 use crate::db::HirTyDB;
-use crate::inference::{BranchWrite, InferenceResult, ResolvedFun};
-use crate::lower::BranchKind;
-use crate::types::{Signature, Ty};
+use crate::inference::{AssignDst, BranchWrite, InferenceResult, ResolvedFun};
+use crate::lower::{BranchKind, NatureTy};
+use crate::table_model::{TableModel, TableModelError};
+use crate::types::{Signature, Ty, STR_ORDERING};
 
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub enum IllegalCtxAccessKind {
     NatureAccess,
     AnalogOperator { name: Name, is_standard: bool, non_const_dominator: Box<[ExprId]> },
     AnalysisFun { name: Name },
+    RandomFun { name: Name },
     Var(VarId),
 }
 
@@ -91,11 +97,156 @@ pub enum BodyValidationDiagnostic {
         access_expr: ExprId,
     },
 
+    DiscreteNatureAccess {
+        discipline: DisciplineId,
+        access_expr: ExprId,
+    },
+
+    NatureMismatch {
+        op: BuiltIn,
+        nature: NatureId,
+        expected: NatureId,
+        found: NatureId,
+        expr: ExprId,
+        stmt: StmtId,
+    },
+
     IncompatibleImplicitBranch {
         access: ExprId,
         node1: NodeId,
         node2: NodeId,
     },
+
+    InvalidTableModel {
+        expr: ExprId,
+        err: TableModelError,
+    },
+
+    GenvarAssignment {
+        expr: ExprId,
+    },
+
+    NonConstGenvarLoop {
+        expr: ExprId,
+    },
+
+    InvalidGenvarIncrement {
+        stmt: StmtId,
+        var: VarId,
+    },
+
+    LastCrossingDerivative {
+        expr: ExprId,
+        stmt: StmtId,
+    },
+
+    SignDerivative {
+        expr: ExprId,
+        stmt: StmtId,
+    },
+
+    RoundingDerivative {
+        expr: ExprId,
+        stmt: StmtId,
+        fun: BuiltIn,
+    },
+
+    FileIoInAnalogBlock {
+        expr: ExprId,
+        stmt: StmtId,
+    },
+
+    CrossOutsideEvent {
+        expr: ExprId,
+    },
+
+    InvalidEvent {
+        expr: ExprId,
+    },
+
+    CelsiusTemperature {
+        literal: ExprId,
+        stmt: StmtId,
+    },
+
+    /// Array elements are resolved at compile time so the index must be a constant.
+    NonConstArrayIndex {
+        expr: ExprId,
+    },
+
+    /// An array variable is used without selecting one of its elements.
+    ArrayWithoutIndex {
+        expr: ExprId,
+        var: VarId,
+    },
+
+    /// Both the potential and the flow of `branch` are contributed outside of any condition.
+    ConflictingContribution {
+        branch: BranchWrite,
+        /// The first contribution (which is discarded by `stmt`)
+        first: StmtId,
+        stmt: StmtId,
+    },
+
+    /// A contribution depends on the simulation time but is not guarded by `analysis`.
+    TimeDependentContribution {
+        expr: ExprId,
+        stmt: StmtId,
+    },
+
+    /// Strings are compared with `<`, `<=`, `>` or `>=`.
+    StringOrdering {
+        expr: ExprId,
+    },
+
+    /// `ddt(C*V)` where `C` depends on a potential (a capacitance instead of a charge).
+    NonConservativeCharge {
+        expr: ExprId,
+        stmt: StmtId,
+    },
+
+    /// The argument of `sqrt` depends on a potential and may become negative (or zero).
+    SqrtDomain {
+        expr: ExprId,
+        stmt: StmtId,
+    },
+
+    /// A `$noise_correlation` call that can not be resolved at compile time.
+    InvalidNoiseCorrelation {
+        expr: ExprId,
+        err: NoiseCorrelationError,
+    },
+
+    /// The analog block of a module does not contain a single contribution.
+    NoContributions {
+        module: ModuleId,
+    },
+
+    /// A call (within an analog function) that (indirectly) calls the function itself.
+    /// Functions are inlined during lowering so recursion would never terminate.
+    RecursiveFunction {
+        expr: ExprId,
+        fun: FunctionId,
+    },
+
+    /// The call of an analog function after which the body has more expressions than allowed
+    /// by the expression limit once all function calls are inlined.
+    InlineLimit {
+        expr: ExprId,
+        limit: u32,
+    },
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum NoiseCorrelationError {
+    /// `$noise_correlation` is only allowed (unconditionally) in the analog block.
+    IllegalCtx(BodyCtx),
+    /// The real or imaginary part of the coefficient is not a numeric literal.
+    NonLiteral,
+    /// The magnitude of the coefficient is larger than one.
+    Magnitude,
+    /// None of the noise sources of the analog block has this name.
+    UnknownSource(Box<str>),
 }
 
 impl BodyValidationDiagnostic {
@@ -118,8 +269,13 @@ impl BodyValidationDiagnostic {
             diagnostics: Vec::new(),
             ctx,
             non_const_dominator: Box::default(),
+            event: None,
             non_trivial_branches: HashSet::default(),
             trivial_probes: HashMap::default(),
+            conditional: false,
+            unconditional_contributions: HashMap::default(),
+            analysis_guard: false,
+            potential_vars: HashSet::default(),
         };
 
         for stmt in &*body.entry_stmts {
@@ -136,10 +292,178 @@ impl BodyValidationDiagnostic {
             }
         }
 
+        if let DefWithBodyId::ModuleId { initial: false, module } = def {
+            let contributes = body.stmts.iter().any(|stmt| {
+                matches!(stmt, Stmt::Assignment { assignment_kind: AssignOp::Contribute, .. })
+            });
+            // modules without an analog block (for example netlists) are not linted
+            if !contributes && !body.entry_stmts.is_empty() {
+                validator.diagnostics.push(BodyValidationDiagnostic::NoContributions { module })
+            }
+        }
+
+        let mut calls: Vec<_> = infere
+            .resolved_calls
+            .iter()
+            .filter_map(|(&expr, call)| match *call {
+                ResolvedFun::User { func, .. } => Some((expr, func)),
+                _ => None,
+            })
+            .collect();
+        calls.sort_unstable_by_key(|&(expr, _)| expr);
+
+        if let DefWithBodyId::FunctionId(fun) = def {
+            for &(expr, callee) in &calls {
+                if calls_function(db, callee, fun, &mut HashSet::default()) {
+                    validator
+                        .diagnostics
+                        .push(BodyValidationDiagnostic::RecursiveFunction { expr, fun })
+                }
+            }
+        }
+
+        // functions are inlined during lowering, even without recursion the inlined body can
+        // grow exponentially (for example if every function calls the previous one twice).
+        // Calls within genvar loops are inlined for every iteration, therefore the analog
+        // blocks of modules are checked while unrolling these loops (in the hir crate)
+        if !calls.is_empty() && !matches!(def, DefWithBodyId::ModuleId { .. }) {
+            let def_db: &dyn HirDefDB = db.upcast();
+            let base_db: &dyn BaseDB = def_db.upcast();
+            let limit = base_db.limits(def.file(def_db)).expressions;
+            let mut sizes = HashMap::default();
+            let call_sizes: Vec<_> = calls
+                .iter()
+                .map(|&(expr, callee)| (expr, inlined_size(db, callee, &mut sizes)))
+                .collect();
+            // functions that exceed the limit by themselves are reported within that function
+            if call_sizes.iter().all(|&(_, size)| size <= limit as u64) {
+                let mut size = body.exprs.len() as u64;
+                for (expr, call_size) in call_sizes {
+                    size += call_size;
+                    if size > limit as u64 {
+                        validator
+                            .diagnostics
+                            .push(BodyValidationDiagnostic::InlineLimit { expr, limit });
+                        break;
+                    }
+                }
+            }
+        }
+
         validator.diagnostics
     }
 }
 
+/// Returns the number of expressions of the body of `fun` once all function calls are inlined.
+/// Recursive calls are reported separately and do not count towards the size.
+pub fn inlined_size(db: &dyn HirTyDB, fun: FunctionId, sizes: &mut HashMap<FunctionId, u64>) -> u64 {
+    if let Some(&size) = sizes.get(&fun) {
+        return size;
+    }
+    sizes.insert(fun, 0);
+    let def = DefWithBodyId::FunctionId(fun);
+    let infer = db.inference_result(def);
+    let mut size = db.body(def).exprs.len() as u64;
+    for call in infer.resolved_calls.values() {
+        if let ResolvedFun::User { func, .. } = *call {
+            size = size.saturating_add(inlined_size(db, func, sizes));
+        }
+    }
+    sizes.insert(fun, size);
+    size
+}
+
+/// Returns whether `fun` is `target` or (indirectly) calls `target`.
+fn calls_function(
+    db: &dyn HirTyDB,
+    fun: FunctionId,
+    target: FunctionId,
+    visited: &mut HashSet<FunctionId>,
+) -> bool {
+    if fun == target {
+        return true;
+    }
+    if !visited.insert(fun) {
+        return false;
+    }
+    let infer = db.inference_result(DefWithBodyId::FunctionId(fun));
+    infer.resolved_calls.values().any(|call| match *call {
+        ResolvedFun::User { func, .. } => calls_function(db, func, target, visited),
+        _ => false,
+    })
+}
+
+/// Returns a subexpression of `expr` (an expression within `body`) that can not be
+/// evaluated at compile time (if any). Parameters can be overwritten at runtime so only
+/// `localparam`s whose defaults are themselves constant are constants here.
+fn non_const_expr(
+    db: &dyn HirTyDB,
+    body: &Body,
+    infer: &InferenceResult,
+    expr: ExprId,
+    visited: &mut HashSet<ParamId>,
+) -> Option<ExprId> {
+    let is_const = match body.exprs[expr] {
+        Expr::Literal(_) => true,
+        Expr::Path { port: false, .. } => match infer.expr_types[expr] {
+            Ty::Var(_, var) => db.var_data(var).is_genvar,
+            Ty::Param(_, param) => is_const_param(db, param, visited),
+            _ => false,
+        },
+        Expr::Call { .. } => match infer.resolved_calls.get(&expr) {
+            // pure math functions that the constant evaluator supports
+            Some(&ResolvedFun::BuiltIn(call)) => matches!(
+                call,
+                BuiltIn::abs
+                    | BuiltIn::sign
+                    | BuiltIn::signum
+                    | BuiltIn::min
+                    | BuiltIn::max
+                    | BuiltIn::pow
+                    | BuiltIn::sqrt
+                    | BuiltIn::exp
+                    | BuiltIn::ln
+                    | BuiltIn::log
+                    | BuiltIn::floor
+                    | BuiltIn::ceil
+                    | BuiltIn::rtoi
+                    | BuiltIn::itor
+            ),
+            _ => false,
+        },
+        Expr::BinaryOp { .. } | Expr::UnaryOp { .. } | Expr::Select { .. } => true,
+        _ => false,
+    };
+
+    if !is_const {
+        return Some(expr);
+    }
+    let mut res = None;
+    body.exprs[expr].walk_child_exprs(|child| {
+        if res.is_none() {
+            res = non_const_expr(db, body, infer, child, visited)
+        }
+    });
+    res
+}
+
+/// Returns whether `param` is a `localparam` whose default (indirectly) only depends
+/// on literals and other `localparam`s. A `localparam` that is derived from a
+/// parameter changes when that parameter is overwritten.
+fn is_const_param(db: &dyn HirTyDB, param: ParamId, visited: &mut HashSet<ParamId>) -> bool {
+    if !db.param_data(param).is_local {
+        return false;
+    }
+    // cyclic defaults are reported elsewhere
+    if !visited.insert(param) {
+        return true;
+    }
+    let def = DefWithBodyId::ParamId(param);
+    let body = db.body(def);
+    let infer = db.inference_result(def);
+    non_const_expr(db, &body, &infer, db.param_exprs(param).default, visited).is_none()
+}
+
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum BodyCtx {
     AnalogBlock,
@@ -168,6 +492,10 @@ impl BodyCtx {
         !matches!(self, Self::Const)
     }
 
+    fn allow_random_fun(self) -> bool {
+        matches!(self, Self::AnalogInitialBlock)
+    }
+
     fn allow_var_ref(self) -> bool {
         !matches!(self, Self::Const | Self::ConstOrAnalysis)
     }
@@ -193,8 +521,58 @@ struct BodyValidator<'a> {
     diagnostics: Vec<BodyValidationDiagnostic>,
     ctx: BodyCtx,
     non_const_dominator: Box<[ExprId]>,
+    /// The `cross` call that is currently validated as the event of an event statement.
+    event: Option<ExprId>,
     non_trivial_branches: HashSet<BranchWrite>,
     trivial_probes: HashMap<BranchWrite, Vec<(StmtId, ExprId)>>,
+    /// Whether the statement that is currently validated is only executed conditionally
+    /// (inside an `if`, `case` or loop).
+    conditional: bool,
+    /// The first potential and flow contribution of each branch that is not inside a condition.
+    unconditional_contributions: HashMap<BranchWrite, [Option<StmtId>; 2]>,
+    /// Whether the statement that is currently validated is inside an `if` whose
+    /// condition calls `analysis`.
+    analysis_guard: bool,
+    /// Variables that have been assigned a value that depends on a potential.
+    potential_vars: HashSet<VarId>,
+}
+
+impl<'a> BodyValidator<'a> {
+    /// The names of all named noise sources (`white_noise`, `flicker_noise` and
+    /// `noise_table` calls with a string literal as last argument) in the body.
+    fn noise_sources(&self) -> HashSet<&'a str> {
+        let body = self.body;
+        body.exprs
+            .iter_enumerated()
+            .filter_map(|(expr, data)| {
+                let named_args = match self.infer.resolved_calls.get(&expr)? {
+                    ResolvedFun::BuiltIn(BuiltIn::white_noise) => 2,
+                    ResolvedFun::BuiltIn(BuiltIn::flicker_noise) => 3,
+                    ResolvedFun::BuiltIn(BuiltIn::noise_table | BuiltIn::noise_table_log) => 2,
+                    _ => return None,
+                };
+                match data {
+                    Expr::Call { args, .. } if args.len() == named_args => {
+                        match &body.exprs[args[named_args - 1]] {
+                            Expr::Literal(Literal::String(name)) => Some(&**name),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// The value of a (possibly negated) integer or real literal.
+    fn real_literal(&self, expr: ExprId) -> Option<f64> {
+        match self.body.exprs[expr] {
+            Expr::Literal(Literal::Float(val)) => Some(val.into()),
+            Expr::Literal(Literal::Int(val)) => Some(val as f64),
+            Expr::UnaryOp { expr, op: UnaryOp::Neg } => self.real_literal(expr).map(|val| -val),
+            _ => None,
+        }
+    }
 }
 
 impl BodyValidator<'_> {
@@ -210,11 +588,28 @@ impl BodyValidator<'_> {
                 // avoid duplicate errors
                 else if self.infer.assignment_destination.contains_key(&stmt) {
                     self.validate_assignment_dst(dst, stmt);
+                    if assignment_kind == AssignOp::Contribute {
+                        self.validate_contribute_nature(dst, val, stmt);
+                        self.lint_conflicting_contribution(stmt);
+                        self.lint_time_dependent_contribution(val, stmt);
+                        self.lint_charge_conservation(val, stmt);
+                    } else if let Some(&AssignDst::Var(var)) =
+                        self.infer.assignment_destination.get(&stmt)
+                    {
+                        if self.depends_on_potential(val) {
+                            self.potential_vars.insert(var);
+                        }
+                    }
                 }
 
                 return;
             }
-            Stmt::EventControl { body, .. } => {
+            Stmt::EventControl { ref events, body } => {
+                for event in events {
+                    if let Event::Expr(event) = *event {
+                        self.validate_event(event, stmt);
+                    }
+                }
                 let old = replace(&mut self.ctx, BodyCtx::EventControl);
                 self.validate_stmt(body);
                 self.ctx = old;
@@ -227,6 +622,11 @@ impl BodyValidator<'_> {
 
             Stmt::Missing | Stmt::Empty => return,
 
+            Stmt::ForLoop { init, cond, incr, body } if self.genvar_loop_var(init).is_some() => {
+                self.validate_genvar_loop(init, cond, incr, body);
+                return;
+            }
+
             Stmt::Expr(e) => {
                 self.validate_expr(e, stmt);
                 return;
@@ -238,9 +638,194 @@ impl BodyValidator<'_> {
             | Stmt::Case { discr: cond, .. } => cond,
         };
 
+        let analysis_guard = matches!(self.body.stmts[stmt], Stmt::If { .. })
+            && self.find_builtin_call(cond, &|fun| fun == BuiltIn::analysis).is_some();
         self.validate_condition(cond, stmt, |s| {
-            s.body.stmts[stmt].walk_child_stmts(|stmt| s.validate_stmt(stmt))
+            let conditional = replace(&mut s.conditional, true);
+            let old_guard = s.analysis_guard;
+            s.analysis_guard |= analysis_guard;
+            s.body.stmts[stmt].walk_child_stmts(|stmt| s.validate_stmt(stmt));
+            s.conditional = conditional;
+            s.analysis_guard = old_guard;
+        });
+    }
+
+    /// Returns the first call (within `expr`) of a builtin function for which `filter` returns true.
+    fn find_builtin_call(&self, expr: ExprId, filter: &dyn Fn(BuiltIn) -> bool) -> Option<ExprId> {
+        if let Some(&ResolvedFun::BuiltIn(fun)) = self.infer.resolved_calls.get(&expr) {
+            if filter(fun) {
+                return Some(expr);
+            }
+        }
+        let mut res = None;
+        self.body.exprs[expr].walk_child_exprs(|child| {
+            if res.is_none() {
+                res = self.find_builtin_call(child, filter)
+            }
         });
+        res
+    }
+
+    /// Contributions that depend on the simulation time have no meaningful value during a DC
+    /// analysis. Models usually guard them with `analysis("tran")` (opt-in lint).
+    fn lint_time_dependent_contribution(&mut self, val: ExprId, stmt: StmtId) {
+        if self.analysis_guard || self.ctx != BodyCtx::AnalogBlock {
+            return;
+        }
+
+        let time_dependent = |fun: BuiltIn| {
+            matches!(
+                fun,
+                BuiltIn::abstime
+                    | BuiltIn::realtime
+                    | BuiltIn::ddt
+                    | BuiltIn::idt
+                    | BuiltIn::idtmod
+            )
+        };
+        if let Some(expr) = self.find_builtin_call(val, &time_dependent) {
+            self.diagnostics
+                .push(BodyValidationDiagnostic::TimeDependentContribution { expr, stmt })
+        }
+    }
+
+    /// Returns whether `expr` reads a potential, either directly or through a variable that
+    /// was previously assigned a value depending on a potential.
+    fn depends_on_potential(&self, expr: ExprId) -> bool {
+        match self.infer.expr_types[expr] {
+            Ty::Var(_, var) if self.potential_vars.contains(&var) => return true,
+            _ => (),
+        }
+        if let Some(ResolvedFun::BuiltIn(BuiltIn::potential)) = self.infer.resolved_calls.get(&expr)
+        {
+            return true;
+        }
+        let mut res = false;
+        self.body.exprs[expr]
+            .walk_child_exprs(|child| res = res || self.depends_on_potential(child));
+        res
+    }
+
+    /// Contributing `ddt(C*V)` with a voltage dependent capacitance `C` does not conserve charge
+    /// as `C = dQ/dV` is not the charge. This is a heuristic so the lint is opt-in.
+    fn lint_charge_conservation(&mut self, val: ExprId, stmt: StmtId) {
+        let mut exprs = vec![val];
+        while let Some(expr) = exprs.pop() {
+            self.body.exprs[expr].walk_child_exprs(|child| exprs.push(child));
+            if self.infer.resolved_calls.get(&expr) != Some(&ResolvedFun::BuiltIn(BuiltIn::ddt)) {
+                continue;
+            }
+            let arg = match self.body.exprs[expr] {
+                Expr::Call { ref args, .. } => args[0],
+                _ => continue,
+            };
+            let (lhs, rhs) = match self.body.exprs[arg] {
+                Expr::BinaryOp { lhs, rhs, op: Some(BinaryOp::Multiplication) } => (lhs, rhs),
+                _ => continue,
+            };
+            let is_potential = |expr| {
+                self.infer.resolved_calls.get(&expr)
+                    == Some(&ResolvedFun::BuiltIn(BuiltIn::potential))
+            };
+            if is_potential(lhs) && self.depends_on_potential(rhs)
+                || is_potential(rhs) && self.depends_on_potential(lhs)
+            {
+                self.diagnostics
+                    .push(BodyValidationDiagnostic::NonConservativeCharge { expr, stmt })
+            }
+        }
+    }
+
+    /// Contributing to both the potential and the flow of a branch turns it into a switch branch.
+    /// That only makes sense if the contributions are in different branches of a condition,
+    /// otherwise one of them is always discarded.
+    fn lint_conflicting_contribution(&mut self, stmt: StmtId) {
+        if self.conditional || self.ctx != BodyCtx::AnalogBlock {
+            return;
+        }
+
+        let (branch, is_flow) = match self.infer.assignment_destination[&stmt] {
+            AssignDst::Potential(branch) => (branch, false),
+            AssignDst::Flow(branch) => (branch, true),
+            _ => return,
+        };
+
+        let contributions = self.unconditional_contributions.entry(branch).or_default();
+        if contributions[is_flow as usize].is_some() {
+            return;
+        }
+        contributions[is_flow as usize] = Some(stmt);
+        if let Some(first) = contributions[!is_flow as usize] {
+            self.diagnostics.push(BodyValidationDiagnostic::ConflictingContribution {
+                branch,
+                first,
+                stmt,
+            })
+        }
+    }
+
+    /// `cross` is currently the only analog event function. It is validated like an analog
+    /// operator so events are only allowed where analog operators are allowed.
+    fn validate_event(&mut self, event: ExprId, stmt: StmtId) {
+        match self.infer.resolved_calls.get(&event) {
+            Some(ResolvedFun::BuiltIn(BuiltIn::cross)) => {
+                let old = replace(&mut self.event, Some(event));
+                self.validate_expr(event, stmt);
+                self.event = old;
+            }
+            // syntax errors are already reported
+            _ if matches!(self.body.exprs[event], Expr::Missing) => (),
+            _ => self.diagnostics.push(BodyValidationDiagnostic::InvalidEvent { expr: event }),
+        }
+    }
+
+    /// Returns the genvar assigned by `stmt` (the initialization of a genvar loop).
+    fn genvar_loop_var(&self, stmt: StmtId) -> Option<VarId> {
+        match self.body.stmts[stmt] {
+            Stmt::Assignment { assignment_kind: AssignOp::Assign, .. } => {
+                match self.infer.assignment_destination.get(&stmt)? {
+                    &AssignDst::Var(var) if self.db.var_data(var).is_genvar => Some(var),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Genvar loops are unrolled at compile time. Therefore the loop header may only depend
+    /// on values that are known at compile time and the loop body may contain analog operators
+    /// and contributions (just like straight line code).
+    fn validate_genvar_loop(&mut self, init: StmtId, cond: ExprId, incr: StmtId, body: StmtId) {
+        if let Stmt::Assignment { val, .. } = self.body.stmts[init] {
+            self.validate_genvar_bound(val, init);
+        }
+        self.validate_genvar_bound(cond, init);
+
+        let var = self.genvar_loop_var(init).unwrap();
+        match self.body.stmts[incr] {
+            Stmt::Assignment { val, .. } if self.genvar_loop_var(incr) == Some(var) => {
+                self.validate_genvar_bound(val, incr)
+            }
+            _ => self
+                .diagnostics
+                .push(BodyValidationDiagnostic::InvalidGenvarIncrement { stmt: incr, var }),
+        }
+
+        let conditional = replace(&mut self.conditional, true);
+        self.validate_stmt(body);
+        self.conditional = conditional;
+    }
+
+    fn validate_genvar_bound(&mut self, expr: ExprId, stmt: StmtId) {
+        self.validate_expr(expr, stmt);
+        if let Some(expr) = self.non_const_genvar_expr(expr) {
+            self.diagnostics.push(BodyValidationDiagnostic::NonConstGenvarLoop { expr })
+        }
+    }
+
+    /// Returns a subexpression of `expr` that can not be evaluated at compile time (if any).
+    fn non_const_genvar_expr(&self, expr: ExprId) -> Option<ExprId> {
+        non_const_expr(self.db, self.body, self.infer, expr, &mut HashSet::default())
     }
 
     fn validate_condition(
@@ -275,6 +860,72 @@ impl BodyValidator<'_> {
         None
     }
 
+    /// Returns the nature of a `potential`/`flow` access expression.
+    fn access_nature(&self, expr: ExprId) -> Option<NatureId> {
+        let arg = match self.body.exprs[expr] {
+            Expr::Call { ref args, .. } => *args.first()?,
+            _ => return None,
+        };
+        let is_pot = match self.infer.resolved_calls.get(&expr)? {
+            ResolvedFun::BuiltIn(BuiltIn::potential) => true,
+            ResolvedFun::BuiltIn(BuiltIn::flow) => false,
+            _ => return None,
+        };
+        let discipline = match *self.infer.resolved_signatures.get(&expr)? {
+            NATURE_ACCESS_BRANCH => {
+                self.db.branch_info(self.infer.expr_types[arg].unwrap_branch())?.discipline
+            }
+            NATURE_ACCESS_NODES | NATURE_ACCESS_NODE_GND => {
+                self.db.node_discipline(self.infer.expr_types[arg].unwrap_node())?
+            }
+            NATURE_ACCESS_PORT_FLOW => {
+                self.db.node_discipline(self.infer.expr_types[arg].unwrap_port_flow())?
+            }
+            _ => return None,
+        };
+        let discipline = self.db.discipline_info(discipline);
+        if is_pot {
+            discipline.potential
+        } else {
+            discipline.flow
+        }
+    }
+
+    /// Contributing `ddt`/`idt` of a probe directly to a branch must yield the
+    /// `ddt_nature`/`idt_nature` declared for the probed nature.
+    fn validate_contribute_nature(&mut self, dst: ExprId, val: ExprId, stmt: StmtId) {
+        let (op, arg) = match self.body.exprs[val] {
+            Expr::Call { ref args, .. } if !args.is_empty() => {
+                match self.infer.resolved_calls.get(&val) {
+                    Some(&ResolvedFun::BuiltIn(op @ (BuiltIn::ddt | BuiltIn::idt))) => {
+                        (op, args[0])
+                    }
+                    _ => return,
+                }
+            }
+            _ => return,
+        };
+
+        let (found, nature) = match (self.access_nature(dst), self.access_nature(arg)) {
+            (Some(found), Some(nature)) => (found, nature),
+            _ => return,
+        };
+
+        let info = self.db.nature_info(nature);
+        let expected = if op == BuiltIn::ddt { info.ddt_nature } else { info.idt_nature };
+        // natures without an explicit ddt_nature/idt_nature resolve to themselves
+        if expected != nature && !NatureTy::compatible(self.db, expected, found) {
+            self.diagnostics.push(BodyValidationDiagnostic::NatureMismatch {
+                op,
+                nature,
+                expected,
+                found,
+                expr: val,
+                stmt,
+            })
+        }
+    }
+
     fn validate_expr(&mut self, expr: ExprId, stmt: StmtId) {
         ExprValidator { parent: self, cond_diagnostic_sink: None, write: false, stmt }
             .validate_expr(expr)
@@ -325,6 +976,10 @@ impl ExprValidator<'_, '_> {
         access_nature: Option<NatureId>,
         access_expr: ExprId,
     ) {
+        if self.validate_continuous(access_expr, discipline) {
+            return;
+        }
+
         let db = self.parent.db;
         let discipline = db.discipline_info(discipline);
 
@@ -377,7 +1032,24 @@ impl ExprValidator<'_, '_> {
         }
     }
 
+    /// Discrete (digital) disciplines are not supported. Accessing a net with such a
+    /// discipline would silently be treated as an analog quantity so it is rejected here.
+    ///
+    /// # Returns
+    /// whether an error was reported
+    fn validate_continuous(&mut self, access_expr: ExprId, discipline: DisciplineId) -> bool {
+        let discrete = self.parent.db.discipline_data(discipline).domain == Some(Domain::Discrete);
+        if discrete {
+            self.report(BodyValidationDiagnostic::DiscreteNatureAccess { discipline, access_expr })
+        }
+        discrete
+    }
+
     fn validate_flow_or_pot(&mut self, expr: ExprId, call: BuiltIn, discipline: DisciplineId) {
+        if self.validate_continuous(expr, discipline) {
+            return;
+        }
+
         let is_pot = call == BuiltIn::potential;
         let discipline_ = self.parent.db.discipline_info(discipline);
         if discipline_.potential.is_none() && is_pot || discipline_.flow.is_none() && !is_pot {
@@ -419,7 +1091,7 @@ impl ExprValidator<'_, '_> {
 
             Some(NATURE_ACCESS_NODES) => {
                 let node1 = self.parent.infer.expr_types[args[0]].unwrap_node();
-                let node2 = self.parent.infer.expr_types[args[0]].unwrap_node();
+                let node2 = self.parent.infer.expr_types[args[1]].unwrap_node();
                 if let Some(discipline1) = self.parent.db.node_discipline(node1) {
                     if let Some(discipline2) = self.parent.db.node_discipline(node2) {
                         let discipline2 = self.parent.db.discipline_info(discipline2);
@@ -477,6 +1149,30 @@ impl ExprValidator<'_, '_> {
                 }
             }
 
+            Expr::BinaryOp {
+                lhs,
+                rhs,
+                op:
+                    Some(
+                        op @ (BinaryOp::EqualityTest
+                        | BinaryOp::NegatedEqualityTest
+                        | BinaryOp::LesserEqualTest
+                        | BinaryOp::GreaterEqualTest
+                        | BinaryOp::LesserTest
+                        | BinaryOp::GreaterTest),
+                    ),
+            } => {
+                self.lint_celsius_temperature(lhs, rhs);
+                self.lint_celsius_temperature(rhs, lhs);
+                let is_ordering =
+                    !matches!(op, BinaryOp::EqualityTest | BinaryOp::NegatedEqualityTest);
+                if is_ordering
+                    && self.parent.infer.resolved_signatures.get(&expr) == Some(&STR_ORDERING)
+                {
+                    self.report(BodyValidationDiagnostic::StringOrdering { expr })
+                }
+            }
+
             Expr::Select { cond, then_val, else_val } => {
                 if let Some(non_const_dominators) =
                     self.parent.validate_condition(cond, self.stmt, |s| {
@@ -496,6 +1192,21 @@ impl ExprValidator<'_, '_> {
                 }
             }
 
+            Expr::Index { base, index } => {
+                match self.parent.infer.expr_types[base] {
+                    Ty::Var(_, var) => self.validate_var_ref(base, var),
+                    _ => self.validate_expr(base),
+                }
+
+                let write = replace(&mut self.write, false);
+                self.validate_expr(index);
+                self.write = write;
+                if let Some(expr) = self.parent.non_const_genvar_expr(index) {
+                    self.report(BodyValidationDiagnostic::NonConstArrayIndex { expr })
+                }
+                return;
+            }
+
             Expr::Path { port: false, .. } => {
                 match self.parent.infer.expr_types[expr] {
                     Ty::FunctionVar { arg: Some(arg), fun, .. } => {
@@ -508,13 +1219,10 @@ impl ExprValidator<'_, '_> {
                         }
                     }
 
-                    Ty::Var(_, var) => {
-                        self.check_access(
-                            |__| IllegalCtxAccessKind::Var(var),
-                            expr,
-                            self.parent.ctx.allow_var_ref(),
-                        );
+                    Ty::Var(Type::Array { .. }, var) => {
+                        self.report(BodyValidationDiagnostic::ArrayWithoutIndex { expr, var })
                     }
+                    Ty::Var(_, var) => self.validate_var_ref(expr, var),
                     Ty::Param(_, param) => {
                         if let DefWithBodyId::ParamId(def) = self.parent.owner {
                             if def.lookup(self.parent.db.upcast()).id
@@ -539,6 +1247,41 @@ impl ExprValidator<'_, '_> {
         self.parent.body.exprs[expr].walk_child_exprs(|child| self.validate_expr(child))
     }
 
+    fn validate_var_ref(&mut self, expr: ExprId, var: VarId) {
+        // genvar loops are unrolled so genvars are constants
+        let is_genvar = self.parent.db.var_data(var).is_genvar;
+        if is_genvar && self.write {
+            self.report(BodyValidationDiagnostic::GenvarAssignment { expr })
+        } else if !is_genvar || !self.parent.ctx.allow_var_ref() {
+            self.check_access(
+                |__| IllegalCtxAccessKind::Var(var),
+                expr,
+                self.parent.ctx.allow_var_ref(),
+            );
+        }
+    }
+
+    /// `$temperature` is in kelvin, comparing it to a literal within the usual range of
+    /// operating temperatures in celsius (-55°C to 150°C) is most likely a mistake.
+    fn lint_celsius_temperature(&mut self, temperature: ExprId, literal: ExprId) {
+        if !matches!(
+            self.parent.infer.resolved_calls.get(&temperature),
+            Some(ResolvedFun::BuiltIn(BuiltIn::temperature))
+        ) {
+            return;
+        }
+
+        let val = match self.parent.body.exprs[literal] {
+            Expr::Literal(Literal::Int(val)) => val as f64,
+            Expr::Literal(Literal::Float(val)) => val.into(),
+            _ => return,
+        };
+
+        if (-55.0..=150.0).contains(&val) {
+            self.report(BodyValidationDiagnostic::CelsiusTemperature { literal, stmt: self.stmt })
+        }
+    }
+
     fn validate_builtin(
         &mut self,
         name: &Option<Path>,
@@ -552,6 +1295,9 @@ impl ExprValidator<'_, '_> {
                 .parent
                 .diagnostics
                 .push(BodyValidationDiagnostic::UnsupportedFunction { expr, func: call }),
+            BuiltIn::cross if self.parent.event != Some(expr) => {
+                self.report(BodyValidationDiagnostic::CrossOutsideEvent { expr })
+            }
             BuiltIn::potential | BuiltIn::flow => self.check_access(
                 |_| IllegalCtxAccessKind::NatureAccess,
                 expr,
@@ -585,6 +1331,34 @@ impl ExprValidator<'_, '_> {
                     },
                     expr,
                 ),
+            BuiltIn::random
+            | BuiltIn::rdist_normal
+            | BuiltIn::rdist_uniform
+            | BuiltIn::rdist_exponential
+            | BuiltIn::rdist_poisson
+                if !self.parent.ctx.allow_random_fun() =>
+            {
+                self.report_illegal_access(
+                    IllegalCtxAccessKind::RandomFun {
+                        name: name.as_ref().and_then(|p| p.as_ident()).unwrap(),
+                    },
+                    expr,
+                )
+            }
+            // the analog block (including events) is evaluated for every newton iteration
+            BuiltIn::fopen
+            | BuiltIn::fclose
+            | BuiltIn::fwrite
+            | BuiltIn::fdisplay
+            | BuiltIn::fstrobe
+            | BuiltIn::fmonitor
+                if matches!(
+                    self.parent.ctx,
+                    BodyCtx::AnalogBlock | BodyCtx::Conditional | BodyCtx::EventControl
+                ) =>
+            {
+                self.report(BodyValidationDiagnostic::FileIoInAnalogBlock { expr, stmt: self.stmt })
+            }
             _ => (),
         }
 
@@ -671,6 +1445,23 @@ impl ExprValidator<'_, '_> {
                 }
             }
 
+            (BuiltIn::table_model, _) => {
+                let literal = |arg: ExprId| match &self.parent.body.exprs[arg] {
+                    Expr::Literal(Literal::String(val)) => Some(val.clone()),
+                    _ => None,
+                };
+                if let (Some(file), Some(control)) = (literal(args[1]), literal(args[2])) {
+                    let root_file = self.parent.owner.file(self.parent.db.upcast());
+                    if let Err(err) =
+                        TableModel::load(self.parent.db.upcast(), root_file, &file, &control)
+                    {
+                        self.report(BodyValidationDiagnostic::InvalidTableModel { expr, err })
+                    }
+                }
+            }
+
+            (BuiltIn::noise_correlation, _) => self.validate_noise_correlation(expr, args),
+
             (BuiltIn::port_connected, _) => {
                 let node = self.parent.infer.expr_types[args[0]].unwrap_node();
                 let node_data = self.parent.db.node_data(node);
@@ -748,11 +1539,77 @@ impl ExprValidator<'_, '_> {
             _ => (),
         }
 
+        if call == BuiltIn::ddx {
+            self.lint_nondifferentiable_derivative(args[0]);
+        }
+
+        if call == BuiltIn::sqrt && self.parent.depends_on_potential(args[0]) {
+            let stmt = self.stmt;
+            self.report(BodyValidationDiagnostic::SqrtDomain { expr, stmt })
+        }
+
         for arg in args {
             self.validate_expr(*arg)
         }
     }
 
+    /// `last_crossing` only changes at discrete events while `sign` and the rounding functions
+    /// jump so their derivatives are not defined (and assumed to be zero).
+    fn lint_nondifferentiable_derivative(&mut self, expr: ExprId) {
+        let stmt = self.stmt;
+        match self.parent.infer.resolved_calls.get(&expr) {
+            Some(ResolvedFun::BuiltIn(BuiltIn::last_crossing)) => {
+                self.report(BodyValidationDiagnostic::LastCrossingDerivative { expr, stmt })
+            }
+            Some(ResolvedFun::BuiltIn(BuiltIn::sign | BuiltIn::signum)) => {
+                self.report(BodyValidationDiagnostic::SignDerivative { expr, stmt })
+            }
+            Some(&ResolvedFun::BuiltIn(
+                fun @ (BuiltIn::floor
+                | BuiltIn::ceil
+                | BuiltIn::rint
+                | BuiltIn::round
+                | BuiltIn::trunc),
+            )) => self.report(BodyValidationDiagnostic::RoundingDerivative { expr, stmt, fun }),
+            _ => (),
+        }
+        self.parent.body.exprs[expr].walk_child_exprs(|child| {
+            self.lint_nondifferentiable_derivative(child);
+        })
+    }
+
+    fn validate_noise_correlation(&mut self, expr: ExprId, args: &[ExprId]) {
+        let ctx = if self.parent.conditional { BodyCtx::Conditional } else { self.parent.ctx };
+        if ctx != BodyCtx::AnalogBlock {
+            self.report_noise_correlation(expr, NoiseCorrelationError::IllegalCtx(ctx));
+            return;
+        }
+
+        let body = self.parent.body;
+        let sources = self.parent.noise_sources();
+        for &arg in &args[..2] {
+            if let Expr::Literal(Literal::String(name)) = &body.exprs[arg] {
+                if !sources.contains(&**name) {
+                    let err = NoiseCorrelationError::UnknownSource(name.clone());
+                    self.report_noise_correlation(arg, err);
+                }
+            }
+        }
+
+        match (self.parent.real_literal(args[2]), self.parent.real_literal(args[3])) {
+            (Some(real), Some(imag)) if real.hypot(imag) > 1.0 => {
+                self.report_noise_correlation(expr, NoiseCorrelationError::Magnitude)
+            }
+            (Some(_), Some(_)) => (),
+            (None, _) => self.report_noise_correlation(args[2], NoiseCorrelationError::NonLiteral),
+            (_, None) => self.report_noise_correlation(args[3], NoiseCorrelationError::NonLiteral),
+        }
+    }
+
+    fn report_noise_correlation(&mut self, expr: ExprId, err: NoiseCorrelationError) {
+        self.report(BodyValidationDiagnostic::InvalidNoiseCorrelation { expr, err })
+    }
+
     fn validate_const_expr(&mut self, expr: ExprId) {
         let old = replace(&mut self.parent.ctx, BodyCtx::Const);
         let sink = self.cond_diagnostic_sink.take();
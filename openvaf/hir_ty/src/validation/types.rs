@@ -1,4 +1,6 @@
+use std::collections::HashSet;
 use std::iter::once;
+use std::sync::Arc;
 
 use basedb::{AstId, ErasedAstId, FileId};
 use hir_def::nameres::diagnostics::PathResolveError;
@@ -6,7 +8,7 @@ use hir_def::nameres::{DefMap, ScopeDefItem};
 use hir_def::{
     AliasParamId, Branch, BranchId, BranchKind, DisciplineId, ItemLoc, ItemTree,
     LocalDisciplineAttrId, LocalNatureAttrId, Lookup, ModuleId, ModuleLoc, NatureId, NodeId,
-    NodeTypeDecl, Path, ScopeId,
+    NodeTypeDecl, ParamId, Path, ScopeId,
 };
 use syntax::ast::ArgListOwner;
 use syntax::name::Name;
@@ -34,6 +36,8 @@ pub enum TypeValidationDiagnostic {
     NodeWithoutDiscipline { decl: ErasedAstId, name: Name },
     ExpectedPort { node: NodeId, src: ErasedAstId },
     IncompatibleBranch { branch: BranchId, node1: NodeId, node2: NodeId },
+    DegenerateBranch { branch: BranchId, node: NodeId },
+    ParamCycle(Arc<[ParamId]>),
 }
 
 impl TypeValidationDiagnostic {
@@ -73,11 +77,21 @@ impl TypeValidationCtx<'_> {
     fn verify_module(&mut self, module: ModuleId) {
         let loc = module.lookup(self.db.upcast());
         let scope = loc.scope.local_scope;
+        let mut cyclic_params = HashSet::new();
         for item in self.def_map[scope].declarations.values() {
             match item {
                 ScopeDefItem::NodeId(node) => self.verify_node(*node, loc),
                 ScopeDefItem::BranchId(branch) => self.verify_branch(*branch),
                 ScopeDefItem::AliasParamId(alias) => self.verify_alias(*alias),
+                ScopeDefItem::ParamId(param) => {
+                    // every cycle is only reported once (for the first parameter)
+                    if let Some(cycle) = self.db.param_cycle(*param) {
+                        if cyclic_params.insert(*param) {
+                            cyclic_params.extend(cycle.iter().copied());
+                            self.report(TypeValidationDiagnostic::ParamCycle(cycle));
+                        }
+                    }
+                }
                 _ => (),
             }
         }
@@ -171,6 +185,14 @@ impl TypeValidationCtx<'_> {
                     return;
                 };
 
+                if node1 == node2 {
+                    self.report(TypeValidationDiagnostic::DegenerateBranch {
+                        branch: branch_,
+                        node: node1,
+                    });
+                    return;
+                }
+
                 let discipline1 = self.db.node_discipline(node1);
                 let discipline2 = self.db.node_discipline(node2);
                 // fast path
@@ -3,7 +3,7 @@ use libc::c_char;
 /**
  * Create a string attribute.
  */
-use crate::{Attribute, Context, Value};
+use crate::{Attribute, Context, LLVMAttributeIndex, Value};
 
 extern "C" {
     fn LLVMCreateStringAttribute(
@@ -15,6 +15,21 @@ extern "C" {
     ) -> &Attribute;
 
     pub fn LLVMPurgeAttrs(val: &Value);
+
+    fn LLVMGetEnumAttributeKindForName(name: *const c_char, len: libc::size_t) -> u32;
+    fn LLVMCreateEnumAttribute(ctx: &Context, kind_id: u32, val: u64) -> &Attribute;
+    pub fn LLVMAddAttributeAtIndex(fun: &Value, idx: LLVMAttributeIndex, attr: &Attribute);
+}
+
+/**
+ * Create an enum attribute (like `nounwind`) without a value.
+ */
+pub fn create_enum_attr<'ll>(llcx: &'ll Context, attr: &str) -> &'ll Attribute {
+    unsafe {
+        let kind = LLVMGetEnumAttributeKindForName(attr.as_ptr().cast(), attr.len());
+        assert_ne!(kind, 0, "unknown LLVM attribute {attr}");
+        LLVMCreateEnumAttribute(llcx, kind, 0)
+    }
 }
 
 pub fn create_attr_string_value<'ll>(
@@ -32,6 +32,7 @@ pub mod context;
 pub mod initialization;
 // pub mod lld;
 pub mod module;
+pub mod passes;
 pub mod support;
 pub mod targets;
 pub mod types;
@@ -44,6 +45,7 @@ pub use builder::*;
 pub use context::*;
 pub use initialization::*;
 pub use module::*;
+pub use passes::*;
 pub use targets::*;
 pub use types::*;
 pub use values::*;
@@ -155,13 +157,28 @@ pub enum TargetData {}
 #[derive(Debug)]
 pub enum TargetMachine {}
 
-#[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum OptLevel {
-    None = 0,
-    Less = 1,
-    Default = 2,
-    Aggressive = 3,
+    None,
+    /// Only promotes stack slots to registers (SROA/mem2reg) and performs basic
+    /// simplifications without inlining. Compiles almost as fast as `None` but
+    /// avoids the memory traffic of unoptimized code.
+    DebugFast,
+    Less,
+    Default,
+    Aggressive,
+}
+
+impl OptLevel {
+    /// The pass pipeline (in the textual format of `opt -passes=`) used for this level.
+    /// Only `OptLevel::DebugFast` runs an LLVM pass pipeline, the other levels only
+    /// affect code generation.
+    pub fn pass_pipeline(self) -> Option<&'static str> {
+        match self {
+            OptLevel::DebugFast => Some("function(sroa,mem2reg,early-cse,instcombine,simplifycfg)"),
+            OptLevel::None | OptLevel::Less | OptLevel::Default | OptLevel::Aggressive => None,
+        }
+    }
 }
 
 // Only allow default CodeModel/RelocMode
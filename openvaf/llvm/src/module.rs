@@ -18,6 +18,10 @@ extern "C" {
 
     pub fn LLVMSetDataLayout(module: &Module, DataLayoutStr: *const c_char);
     pub fn LLVMLinkModules2(dst: &Module, src: &Module) -> Bool;
+    /// Return an exact copy of the specified module.
+    pub fn LLVMCloneModule(module: &Module) -> &Module;
+    /// Destroy a module instance.
+    pub fn LLVMDisposeModule(module: &Module);
     // /// Returns the module flags as an array of flag-key-value triples.  The caller is responsible for freeing this array by calling LLVMDisposeModuleFlagsMetadata.
     // pub fn LLVMCopyModuleFlagsMetadata(
     //     module: &Module,
@@ -117,6 +121,10 @@ extern "C" {
         OutMessage: Option<&mut MaybeUninit<LLVMString>>,
     ) -> Bool;
 
+    /// Verify that a single function is valid, taking the specified action. Useful
+    /// for debugging.
+    pub fn LLVMVerifyFunction(fun: &Value, Action: VerifierFailureAction) -> Bool;
+
 }
 
 pub fn function_iter(module: &Module) -> impl Iterator<Item = &Value> + '_ {
@@ -0,0 +1,48 @@
+use std::ffi::{CStr, CString};
+
+use libc::c_char;
+
+use crate::{Module, TargetMachine};
+
+pub enum PassBuilderOptions {}
+pub enum Error {}
+
+// Transforms->PassBuilder (new pass manager)
+extern "C" {
+    fn LLVMCreatePassBuilderOptions() -> &'static mut PassBuilderOptions;
+    fn LLVMDisposePassBuilderOptions(options: &'static mut PassBuilderOptions);
+    fn LLVMRunPasses(
+        module: &Module,
+        passes: *const c_char,
+        target_machine: &TargetMachine,
+        options: &PassBuilderOptions,
+    ) -> Option<&'static mut Error>;
+    fn LLVMGetErrorMessage(err: &'static mut Error) -> *mut c_char;
+    fn LLVMDisposeErrorMessage(msg: *mut c_char);
+}
+
+/// Runs the pass pipeline described by `passes` (in the textual format of `opt -passes=`)
+/// on `module`.
+///
+/// # Safety
+///
+/// This function calls the LLVM C interface and may emit unsafety for invalid inputs.
+pub unsafe fn run_passes(
+    module: &Module,
+    passes: &str,
+    target_machine: &TargetMachine,
+) -> Result<(), String> {
+    let passes = CString::new(passes).unwrap();
+    let options = LLVMCreatePassBuilderOptions();
+    let err = LLVMRunPasses(module, passes.as_ptr(), target_machine, options);
+    LLVMDisposePassBuilderOptions(options);
+    match err {
+        Some(err) => {
+            let msg = LLVMGetErrorMessage(err);
+            let res = CStr::from_ptr(msg).to_string_lossy().into_owned();
+            LLVMDisposeErrorMessage(msg);
+            Err(res)
+        }
+        None => Ok(()),
+    }
+}
@@ -21,7 +21,7 @@ extern "C" {
         Triple: *const c_char,
         CPU: *const c_char,
         Features: *const c_char,
-        Level: OptLevel,
+        Level: CodeGenOptLevel,
         Reloc: RelocMode,
         CodeModel: CodeModel,
     ) -> Option<&'static mut TargetMachine>;
@@ -57,6 +57,27 @@ extern "C" {
     pub fn LLVMABIAlignmentOfType(data: &TargetData, ty: &Type) -> c_uint;
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CodeGenOptLevel {
+    None = 0,
+    Less = 1,
+    Default = 2,
+    Aggressive = 3,
+}
+
+impl From<OptLevel> for CodeGenOptLevel {
+    fn from(level: OptLevel) -> CodeGenOptLevel {
+        match level {
+            OptLevel::None => CodeGenOptLevel::None,
+            // same as clang -Og: the register allocator of -O0 spills too much
+            OptLevel::DebugFast | OptLevel::Less => CodeGenOptLevel::Less,
+            OptLevel::Default => CodeGenOptLevel::Default,
+            OptLevel::Aggressive => CodeGenOptLevel::Aggressive,
+        }
+    }
+}
+
 /// # Safety
 ///
 /// This function calls the LLVM C interface and may emit unsafety for invalid inputs.
@@ -89,7 +110,7 @@ pub unsafe fn create_target(
         triple_.as_ptr(),
         cpu.as_ptr(),
         features.as_ptr(),
-        level,
+        level.into(),
         reloc_mode,
         code_model,
     );
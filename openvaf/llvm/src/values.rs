@@ -1,4 +1,4 @@
-use libc::{c_char, c_double, c_uint, c_ulonglong};
+use libc::{c_char, c_double, c_uint, c_ulonglong, size_t};
 
 use crate::{
     BasicBlock, Bool, CallConv, Context, DLLStorageClass, Linkage, Module, Type, UnnamedAddr,
@@ -11,7 +11,7 @@ extern "C" {
     // Get the enumerated kind of a Value instance.
     pub fn LLVMTypeOf(val: &Value) -> &Type;
 
-    // pub fn LLVMGetValueName2(val: &'a Value, Length: *mut ::libc::size_t) -> *const ::libc::c_char;
+    pub fn LLVMGetValueName2(val: &Value, length: *mut size_t) -> *const c_char;
     // pub fn LLVMSetValueName2(val: &'a Value, Name: *const ::libc::c_char, NameLen: ::libc::size_t);
 
     // pub fn LLVMDumpValue(Val: &'a Value);
@@ -329,7 +329,7 @@ extern "C" {
     //) -> &'a Value;
 
     //..->Function Values
-    //// pub fn LLVMDeleteFunction(Fn: &'a Value);
+    pub fn LLVMDeleteFunction(fun: &Value);
     ///// Check whether the given function has a personality function.
     //// pub fn LLVMHasPersonalityFn(Fn: &'a Value) -> LLVMBool;
     ///// Obtain the personality function attached to the function.
@@ -423,6 +423,7 @@ extern "C" {
     // pub fn LLVMSetParamAlignment(Arg: &'a Value, Align: ::libc::c_uint);
     pub fn LLVMSetPartialFastMath(val: &Value);
     pub fn LLVMSetFastMath(val: &Value);
+    pub fn LLVMSetFastMathFlags(val: &Value, relaxed: bool, contract: bool);
 
     // Instruction->PHI Nodes
     pub fn LLVMAddIncoming<'a>(
@@ -2,6 +2,7 @@
 #include "llvm/Support/CrashRecoveryContext.h"
 #include <llvm/IR/Attributes.h>
 #include <llvm/IR/Function.h>
+#include <llvm/IR/Operator.h>
 
 #include <iostream>
 #include <mutex>
@@ -33,6 +34,26 @@ void LLVMSetFastMath(LLVMValueRef V) {
   }
 }
 
+// Enable user selected fast-math flags for an operation. `Relaxed` enables all
+// flags except `contract` which is controlled separately because fusing
+// operations (FMA) changes the rounding of the result
+// https://llvm.org/docs/LangRef.html#fast-math-flags
+void LLVMSetFastMathFlags(LLVMValueRef V, bool Relaxed, bool Contract) {
+  if (auto I = dyn_cast<Instruction>(unwrap<Value>(V))) {
+    if (!isa<FPMathOperator>(I)) {
+      return;
+    }
+    if (Relaxed) {
+      FastMathFlags FMF;
+      FMF.setFast(true);
+      FMF.setAllowContract(Contract);
+      I->setFastMathFlags(FMF);
+    } else if (Contract) {
+      I->setHasAllowContract(true);
+    }
+  }
+}
+
 void LLVMPurgeAttrs(LLVMValueRef V) {
   if (auto func = dyn_cast<Function>(unwrap<Value>(V))) {
     func->setAttributes(AttributeList());
@@ -162,6 +162,18 @@ pub trait InstBuilder<'f>: InstBuilderBase<'f> {
         let (inst, dfg) = self.unary(Opcode::Ceil, arg0);
         dfg.first_result(inst)
     }
+    fn rint(self, arg0: Value) -> Value {
+        let (inst, dfg) = self.unary(Opcode::Rint, arg0);
+        dfg.first_result(inst)
+    }
+    fn round(self, arg0: Value) -> Value {
+        let (inst, dfg) = self.unary(Opcode::Round, arg0);
+        dfg.first_result(inst)
+    }
+    fn trunc(self, arg0: Value) -> Value {
+        let (inst, dfg) = self.unary(Opcode::Trunc, arg0);
+        dfg.first_result(inst)
+    }
     fn sin(self, arg0: Value) -> Value {
         let (inst, dfg) = self.unary(Opcode::Sin, arg0);
         dfg.first_result(inst)
@@ -1,6 +1,6 @@
 use crate::builder::InstBuilder;
 use crate::cursor::{Cursor, FuncCursor};
-use crate::{Function, ValueDef};
+use crate::{Function, FunctionStats, ValueDef};
 
 #[test]
 fn reuse_results() {
@@ -22,3 +22,25 @@ fn reuse_results() {
     assert!(imul != iadd);
     assert_eq!(pos.func.dfg.value_def(v1), ValueDef::Result(iadd, 0));
 }
+
+#[test]
+fn stats() {
+    let mut func = Function::new();
+    let block0 = func.layout.append_new_block();
+    let block1 = func.layout.append_new_block();
+    let arg0 = func.dfg.make_param(0u32.into());
+    let mut pos = FuncCursor::new(&mut func).at_bottom(block0);
+    let v0 = pos.ins().fmul(arg0, arg0);
+    pos.ins().jump(block1);
+    pos.goto_bottom(block1);
+    pos.ins().fadd(v0, arg0);
+    let fadd = pos.prev_inst().unwrap();
+    let values = func.dfg.num_values();
+
+    let stats = FunctionStats { blocks: 2, insts: 3, dead_insts: 0, values };
+    assert_eq!(func.stats(), stats);
+    func.layout.remove_inst(fadd);
+    let stats = FunctionStats { blocks: 2, insts: 2, dead_insts: 1, values };
+    assert_eq!(func.stats(), stats);
+    assert_eq!(stats.to_string(), format!("2 blocks, 2 instructions (1 removed), {values} values"));
+}
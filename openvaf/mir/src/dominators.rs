@@ -115,6 +115,11 @@ impl DominatorTree {
         block == dominator
     }
 
+    /// Returns the closest block that dominates both `block1` and `block2`.
+    pub fn nearest_common_dominator(&self, block1: Block, block2: Block) -> Block {
+        Self::common_dominator(&self.nodes, block1, block2)
+    }
+
     pub fn ipdom(&self, block: Block) -> Option<Block> {
         self.reverse_nodes[block].idom.expand()
     }
@@ -32,59 +32,62 @@ pub enum Opcode {
     Clog2 = 16u8,
     Floor = 17u8,
     Ceil = 18u8,
-    Sin = 19u8,
-    Cos = 20u8,
-    Tan = 21u8,
-    Asin = 22u8,
-    Acos = 23u8,
-    Atan = 24u8,
-    Sinh = 25u8,
-    Cosh = 26u8,
-    Tanh = 27u8,
-    Asinh = 28u8,
-    Acosh = 29u8,
-    Atanh = 30u8,
-    Iadd = 31u8,
-    Isub = 32u8,
-    Imul = 33u8,
-    Idiv = 34u8,
-    Irem = 35u8,
-    Ishl = 36u8,
-    Ishr = 37u8,
-    Ixor = 38u8,
-    Iand = 39u8,
-    Ior = 40u8,
-    Fadd = 41u8,
-    Fsub = 42u8,
-    Fmul = 43u8,
-    Fdiv = 44u8,
-    Frem = 45u8,
-    Ilt = 46u8,
-    Igt = 47u8,
-    Ige = 48u8,
-    Ile = 49u8,
-    Flt = 50u8,
-    Fgt = 51u8,
-    Fge = 52u8,
-    Fle = 53u8,
-    Ieq = 54u8,
-    Feq = 55u8,
-    Seq = 56u8,
-    Beq = 57u8,
-    Ine = 58u8,
-    Fne = 59u8,
-    Sne = 60u8,
-    Bne = 61u8,
-    Hypot = 62u8,
-    Atan2 = 63u8,
-    Pow = 64u8,
-    Br = 65u8,
-    Jmp = 66u8,
-    Exit = 67u8,
-    Call = 68u8,
-    Phi = 69u8,
+    Rint = 19u8,
+    Round = 20u8,
+    Trunc = 21u8,
+    Sin = 22u8,
+    Cos = 23u8,
+    Tan = 24u8,
+    Asin = 25u8,
+    Acos = 26u8,
+    Atan = 27u8,
+    Sinh = 28u8,
+    Cosh = 29u8,
+    Tanh = 30u8,
+    Asinh = 31u8,
+    Acosh = 32u8,
+    Atanh = 33u8,
+    Iadd = 34u8,
+    Isub = 35u8,
+    Imul = 36u8,
+    Idiv = 37u8,
+    Irem = 38u8,
+    Ishl = 39u8,
+    Ishr = 40u8,
+    Ixor = 41u8,
+    Iand = 42u8,
+    Ior = 43u8,
+    Fadd = 44u8,
+    Fsub = 45u8,
+    Fmul = 46u8,
+    Fdiv = 47u8,
+    Frem = 48u8,
+    Ilt = 49u8,
+    Igt = 50u8,
+    Ige = 51u8,
+    Ile = 52u8,
+    Flt = 53u8,
+    Fgt = 54u8,
+    Fge = 55u8,
+    Fle = 56u8,
+    Ieq = 57u8,
+    Feq = 58u8,
+    Seq = 59u8,
+    Beq = 60u8,
+    Ine = 61u8,
+    Fne = 62u8,
+    Sne = 63u8,
+    Bne = 64u8,
+    Hypot = 65u8,
+    Atan2 = 66u8,
+    Pow = 67u8,
+    Br = 68u8,
+    Jmp = 69u8,
+    Exit = 70u8,
+    Call = 71u8,
+    Phi = 72u8,
 }
-pub(super) const OPCODE_CONSTRAINTS: [OpcodeConstraints; 69usize + 1] = [
+pub(super) const OPCODE_CONSTRAINTS: [OpcodeConstraints; 72usize + 1] = [
     OpcodeConstraints::new(0, 0),
     OpcodeConstraints::new(1u8, 1u8),
     OpcodeConstraints::new(1u8, 1u8),
@@ -116,6 +119,9 @@ pub(super) const OPCODE_CONSTRAINTS: [OpcodeConstraints; 69usize + 1] = [
     OpcodeConstraints::new(1u8, 1u8),
     OpcodeConstraints::new(1u8, 1u8),
     OpcodeConstraints::new(1u8, 1u8),
+    OpcodeConstraints::new(1u8, 1u8),
+    OpcodeConstraints::new(1u8, 1u8),
+    OpcodeConstraints::new(1u8, 1u8),
     OpcodeConstraints::new(2u8, 1u8),
     OpcodeConstraints::new(2u8, 1u8),
     OpcodeConstraints::new(2u8, 1u8),
@@ -156,7 +162,7 @@ pub(super) const OPCODE_CONSTRAINTS: [OpcodeConstraints; 69usize + 1] = [
     OpcodeConstraints::new(0u8, 0u8),
     OpcodeConstraints::new(0u8, 1u8),
 ];
-pub(super) const OPCODE_NAMES: [&str; 69usize + 1] = [
+pub(super) const OPCODE_NAMES: [&str; 72usize + 1] = [
     "",
     "inot",
     "bnot",
@@ -176,6 +182,9 @@ pub(super) const OPCODE_NAMES: [&str; 69usize + 1] = [
     "clog2",
     "floor",
     "ceil",
+    "rint",
+    "round",
+    "trunc",
     "sin",
     "cos",
     "tan",
@@ -228,7 +237,7 @@ pub(super) const OPCODE_NAMES: [&str; 69usize + 1] = [
     "call",
     "phi",
 ];
-pub(super) const OPCODE_FORMAT: [InstructionFormat; 69usize + 1] = [
+pub(super) const OPCODE_FORMAT: [InstructionFormat; 72usize + 1] = [
     InstructionFormat::Binary,
     InstructionFormat::Unary,
     InstructionFormat::Unary,
@@ -260,6 +269,9 @@ pub(super) const OPCODE_FORMAT: [InstructionFormat; 69usize + 1] = [
     InstructionFormat::Unary,
     InstructionFormat::Unary,
     InstructionFormat::Unary,
+    InstructionFormat::Unary,
+    InstructionFormat::Unary,
+    InstructionFormat::Unary,
     InstructionFormat::Binary,
     InstructionFormat::Binary,
     InstructionFormat::Binary,
@@ -322,6 +334,9 @@ impl std::str::FromStr for Opcode {
             "clog2" => Ok(Opcode::Clog2),
             "floor" => Ok(Opcode::Floor),
             "ceil" => Ok(Opcode::Ceil),
+            "rint" => Ok(Opcode::Rint),
+            "round" => Ok(Opcode::Round),
+            "trunc" => Ok(Opcode::Trunc),
             "sin" => Ok(Opcode::Sin),
             "cos" => Ok(Opcode::Cos),
             "tan" => Ok(Opcode::Tan),
@@ -134,6 +134,20 @@ impl Function {
         format!("{:?}", self)
     }
 
+    /// Counts the blocks and instructions that are currently part of the layout.
+    /// Instructions that were removed (but still occupy the arena) are reported separately
+    /// to make excessive growth of the arenas visible.
+    pub fn stats(&self) -> FunctionStats {
+        let mut res =
+            FunctionStats { blocks: 0, insts: 0, dead_insts: 0, values: self.dfg.num_values() };
+        for bb in self.layout.blocks() {
+            res.blocks += 1;
+            res.insts += self.layout.block_insts(bb).count();
+        }
+        res.dead_insts = self.dfg.num_insts() - res.insts;
+        res
+    }
+
     /// Adds a signature which can later be used to declare an external function import.
     pub fn import_function(&mut self, signature: FunctionSignature) -> FuncRef {
         self.dfg.signatures.push_and_get_key(signature)
@@ -190,6 +204,21 @@ impl Function {
     }
 }
 
+/// Size of a [`Function`], see [`Function::stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FunctionStats {
+    pub blocks: usize,
+    pub insts: usize,
+    pub dead_insts: usize,
+    pub values: usize,
+}
+
+impl_display! {
+    match FunctionStats{
+        FunctionStats{blocks, insts, dead_insts, values} => "{} blocks, {} instructions ({} removed), {} values", blocks, insts, dead_insts, values;
+    }
+}
+
 #[doc(hidden)]
 pub struct PrintableFunction<'a> {
     fun: &'a Function,
@@ -217,6 +246,11 @@ pub type SourceLocs = TiVec<Inst, SourceLoc>;
 ///
 /// The default source location uses the all-ones bit pattern `!0`. It is used for instructions
 /// that can't be given a real source location.
+///
+/// Instructions that are synthesized from another instruction (for example the derivatives
+/// generated by automatic differentiation) are marked as [`derived`](SourceLoc::derived) from the
+/// location of the original instruction. Derived locations are stored as negative numbers and
+/// printed as `@^<origin>`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 pub struct SourceLoc(pub i32);
 
@@ -235,12 +269,29 @@ impl SourceLoc {
     pub fn bits(self) -> i32 {
         self.0
     }
+
+    /// Returns the location of an instruction that was synthesized from the instruction at `self`.
+    pub fn derived(self) -> Self {
+        Self(-self.0.abs())
+    }
+
+    /// Was the instruction synthesized from the instruction at [`origin`](SourceLoc::origin)?
+    pub fn is_derived(self) -> bool {
+        self.0 < 0
+    }
+
+    /// The location of the original instruction a derived instruction was synthesized from.
+    pub fn origin(self) -> Self {
+        Self(self.0.abs())
+    }
 }
 
 impl fmt::Display for SourceLoc {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if self.is_default() {
             write!(f, "@-")
+        } else if self.is_derived() {
+            write!(f, "@^{:04x}", self.origin().0)
         } else {
             write!(f, "@{:04x}", self.0)
         }
@@ -1,3 +1,4 @@
+use std::collections::hash_map::Entry;
 use std::ops::Range;
 
 use ahash::AHashMap;
@@ -33,6 +34,7 @@ pub fn build_derivatives(
         }
     }
 
+    let first_new_inst = Inst::from(func.dfg.num_insts() as u32);
     let mut builder = DerivativeBuilder {
         func,
         live_derivatives,
@@ -45,6 +47,7 @@ pub fn build_derivatives(
     };
 
     builder.run(post_order);
+    builder.merge_duplicate_insts(first_new_inst);
     builder.derivative_values
 }
 
@@ -113,9 +116,8 @@ impl<'a, 'u> DerivativeBuilder<'a, 'u> {
         for bb in post_order.iter().rev() {
             let mut cursor = self.func.layout.block_inst_cursor(*bb);
             while let Some(inst) = cursor.next(&self.func.layout) {
-                let mut srcloc = self.func.srclocs.get(inst).copied().unwrap_or_default();
-                srcloc.0 *= -1;
-                self.dst = (inst, srcloc);
+                let srcloc = self.func.srclocs.get(inst).copied().unwrap_or_default();
+                self.dst = (inst, srcloc.derived());
                 self.build_inst_derivatives(&mut cache);
             }
         }
@@ -131,6 +133,63 @@ impl<'a, 'u> DerivativeBuilder<'a, 'u> {
         }
     }
 
+    /// The derivative rules are applied separately for every unknown (and every order), so the
+    /// same subterm (for example the `x*x` cached for every division) is often generated many
+    /// times. This function merges identical pure instructions within a block so that each term
+    /// is only computed once. Only instructions generated by the builder are removed.
+    ///
+    /// This can not be done while the derivatives are generated because higher order derivatives
+    /// are generated by differentiating the instructions of the lower order derivative.
+    fn merge_duplicate_insts(&mut self, first_new_inst: Inst) {
+        let mut replacements = AHashMap::new();
+        let mut known_insts = AHashMap::new();
+
+        let mut bb_cursor = self.func.layout.blocks_cursor();
+        while let Some(bb) = bb_cursor.next(&self.func.layout) {
+            known_insts.clear();
+            let mut cursor = self.func.layout.block_inst_cursor(bb);
+            while let Some(inst) = cursor.next(&self.func.layout) {
+                let key = match self.func.dfg.insts[inst] {
+                    InstructionData::Unary { opcode: Opcode::OptBarrier, .. } => continue,
+                    InstructionData::Unary { opcode, arg } => {
+                        (opcode, [arg, Value::reserved_value()])
+                    }
+                    InstructionData::Binary {
+                        opcode: opcode @ (Opcode::Fadd | Opcode::Fmul),
+                        args: [lhs, rhs],
+                    } if rhs < lhs => (opcode, [rhs, lhs]),
+                    InstructionData::Binary { opcode, args } => (opcode, args),
+                    _ => continue,
+                };
+
+                let res = self.func.dfg.first_result(inst);
+                match known_insts.entry(key) {
+                    Entry::Occupied(known) if inst >= first_new_inst => {
+                        let known = *known.get();
+                        self.func.dfg.replace_uses(res, known);
+                        self.func.dfg.zap_inst(inst);
+                        self.func.layout.remove_inst(inst);
+                        replacements.insert(res, known);
+                    }
+                    Entry::Occupied(_) => (),
+                    Entry::Vacant(entry) => {
+                        entry.insert(res);
+                    }
+                }
+            }
+        }
+
+        if replacements.is_empty() {
+            return;
+        }
+
+        let resolve = |val| replacements.get(&val).copied().unwrap_or(val);
+        let derivative_values = std::mem::take(&mut self.derivative_values);
+        for ((val, unknown), derivative) in derivative_values {
+            self.derivative_values.entry((resolve(val), unknown)).or_insert(resolve(derivative));
+        }
+    }
+
     fn ddx(
         &mut self,
         arg: Value,
@@ -743,6 +802,9 @@ impl<'a, 'u> DerivativeBuilder<'a, 'u> {
             | Opcode::Frem
             | Opcode::Floor
             | Opcode::Ceil
+            | Opcode::Rint
+            | Opcode::Round
+            | Opcode::Trunc
             | Opcode::Bnot
             | Opcode::Ilt
             | Opcode::Igt
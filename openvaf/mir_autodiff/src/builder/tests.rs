@@ -117,24 +117,89 @@ fn phi() {
 
         block1:
             v13 = exp v10
-            v102 = exp v10
             jmp block3
 
         block2:
             v14 = exp v11
-            v101 = exp v11
             jmp block3
 
         block3:
             v15 = phi [v13, block1], [v14, block2]
-            v103 = phi [v102, block1], [v3, block2]
-            v104 = phi [v3, block1], [v101, block2]
+            v103 = phi [v13, block1], [v3, block2]
+            v104 = phi [v3, block1], [v14, block2]
             v100 = optbarrier v3
         }
     "#]];
     check_simple(src, expect);
 }
 
+/// Derivatives of variables that are updated in a loop (like a `while` loop that sums a
+/// series) are accumulated across iterations by the phis in the loop header.
+#[test]
+fn loop_accumulation() {
+    let src = r##"
+        function %bar(v10) {
+            fn0 = const fn %ddx_v10(1) -> 1
+            v30 = iconst 3
+
+        block0:
+            jmp block1
+
+        block1:
+            v20 = phi [v3, block0], [v22, block2]
+            v21 = phi [v4, block0], [v23, block2]
+            v24 = ilt v21, v30
+            br v24, block2, block3
+
+        block2:
+            v25 = fmul v20, v10
+            v22 = fadd v25, v6
+            v23 = iadd v21, v5
+            jmp block1
+
+        block3:
+            v26 = call fn0 (v20)
+            v100 = optbarrier v26
+        }"##;
+    let expect = expect![[r#"
+        function %bar(v10) {
+            inst0 = const fn %ddx_v10(1) -> 1
+            v3 = fconst 0.0
+            v4 = iconst 0
+            v5 = iconst 1
+            v6 = fconst 0x1.0000000000000p0
+            v30 = iconst 3
+
+        block0:
+            jmp block1
+
+        block1:
+            v20 = phi [v3, block0], [v22, block2]
+            v101 = phi [v3, block0], [v104, block2]
+            v21 = phi [v4, block0], [v23, block2]
+            v24 = ilt v21, v30
+            br v24, block2, block3
+
+        block2:
+            v25 = fmul v20, v10
+            v102 = fmul v101, v10
+            v103 = fadd v102, v20
+            v22 = fadd v25, v6
+            v104 = fadd v103, v3
+            v23 = iadd v21, v5
+            jmp block1
+
+        block3:
+            v100 = optbarrier v101
+        }
+    "#]];
+
+    // horner scheme: x^2 + x + 1
+    let v10 = 2f64;
+    let res = 2.0 * v10 + 1.0;
+    check_num(src, expect, &[v10], res);
+}
+
 #[test]
 fn exp_second_order() {
     let src = r##"
@@ -172,18 +237,13 @@ fn exp_second_order() {
 
         block0:
             v13 = exp v10
-            v101 = exp v10
-            v102 = exp v10
             v16 = fmul v10, v11
             v17 = exp v16
-            v103 = exp v16
-            v104 = fmul v11, v103
-            v105 = exp v16
-            v106 = fmul v11, v105
-            v107 = fmul v106, v11
+            v104 = fmul v11, v17
+            v107 = fmul v104, v11
             v18 = fadd v13, v17
-            v108 = fadd v101, v104
-            v109 = fadd v102, v107
+            v108 = fadd v13, v104
+            v109 = fadd v13, v107
             v100 = optbarrier v109
         }
     "#]];
@@ -212,8 +272,7 @@ fn sin_second_order() {
             v13 = sin v12
             v101 = cos v12
             v102 = fmul v11, v101
-            v103 = sin v12
-            v104 = fneg v103
+            v104 = fneg v13
             v105 = fmul v11, v104
             v106 = fmul v105, v11
             v100 = optbarrier v106
@@ -243,22 +302,14 @@ fn sin_exp_second_order() {
         block0:
             v12 = sin v10
             v101 = cos v10
-            v102 = sin v10
-            v103 = fneg v102
+            v103 = fneg v12
             v13 = exp v10
-            v104 = exp v10
-            v105 = exp v10
             v16 = fmul v12, v13
             v106 = fmul v101, v13
-            v107 = fmul v104, v12
-            v108 = fadd v106, v107
+            v108 = fadd v106, v16
             v109 = fmul v103, v13
-            v110 = fmul v104, v101
-            v111 = fadd v109, v110
-            v112 = fmul v105, v12
-            v113 = fmul v101, v104
-            v114 = fadd v112, v113
-            v115 = fadd v111, v114
+            v111 = fadd v109, v106
+            v115 = fadd v111, v108
             v100 = optbarrier v115
         }
     "#]];
@@ -288,40 +339,19 @@ fn third_order_ln_sin_exp() {
         block0:
             v12 = sin v10
             v101 = cos v10
-            v102 = sin v10
-            v103 = fneg v102
-            v104 = cos v10
-            v105 = fneg v104
+            v103 = fneg v12
+            v105 = fneg v101
             v13 = exp v10
-            v106 = exp v10
-            v107 = exp v10
-            v108 = exp v10
             v16 = fmul v12, v13
             v109 = fmul v101, v13
-            v110 = fmul v106, v12
-            v111 = fadd v109, v110
+            v111 = fadd v109, v16
             v112 = fmul v103, v13
-            v113 = fmul v106, v101
-            v114 = fadd v112, v113
-            v115 = fmul v107, v12
-            v116 = fmul v101, v106
-            v117 = fadd v115, v116
-            v118 = fadd v114, v117
+            v114 = fadd v112, v109
+            v118 = fadd v114, v111
             v119 = fmul v105, v13
-            v120 = fmul v106, v103
-            v121 = fadd v119, v120
-            v122 = fmul v107, v101
-            v123 = fmul v103, v106
-            v124 = fadd v122, v123
-            v125 = fadd v121, v124
-            v126 = fmul v108, v12
-            v127 = fmul v101, v107
-            v128 = fadd v126, v127
-            v129 = fmul v103, v106
-            v130 = fmul v107, v101
-            v131 = fadd v129, v130
-            v132 = fadd v128, v131
-            v133 = fadd v125, v132
+            v121 = fadd v119, v112
+            v125 = fadd v121, v114
+            v133 = fadd v125, v118
             v17 = ln v16
             v134 = fdiv v111, v16
             v135 = fmul v16, v16
@@ -329,18 +359,14 @@ fn third_order_ln_sin_exp() {
             v137 = fmul v111, v111
             v138 = fdiv v137, v135
             v139 = fsub v136, v138
-            v140 = fmul v16, v16
             v141 = fmul v135, v135
             v142 = fmul v111, v16
-            v143 = fmul v111, v16
-            v144 = fadd v142, v143
+            v144 = fadd v142, v142
             v145 = fdiv v133, v16
             v146 = fmul v111, v118
-            v147 = fdiv v146, v140
+            v147 = fdiv v146, v135
             v148 = fsub v145, v147
-            v149 = fmul v118, v111
-            v150 = fmul v118, v111
-            v151 = fadd v149, v150
+            v151 = fadd v146, v146
             v152 = fdiv v151, v135
             v153 = fmul v144, v137
             v154 = fdiv v153, v141
@@ -379,38 +405,12 @@ fn third_order_ln_sinh_exp() {
         block0:
             v12 = sinh v10
             v101 = cosh v10
-            v102 = sinh v10
-            v103 = cosh v10
             v13 = exp v10
-            v104 = exp v10
-            v105 = exp v10
-            v106 = exp v10
             v16 = fmul v12, v13
             v107 = fmul v101, v13
-            v108 = fmul v104, v12
-            v109 = fadd v107, v108
-            v110 = fmul v102, v13
-            v111 = fmul v104, v101
-            v112 = fadd v110, v111
-            v113 = fmul v105, v12
-            v114 = fmul v101, v104
-            v115 = fadd v113, v114
-            v116 = fadd v112, v115
-            v117 = fmul v103, v13
-            v118 = fmul v104, v102
-            v119 = fadd v117, v118
-            v120 = fmul v105, v101
-            v121 = fmul v102, v104
-            v122 = fadd v120, v121
-            v123 = fadd v119, v122
-            v124 = fmul v106, v12
-            v125 = fmul v101, v105
-            v126 = fadd v124, v125
-            v127 = fmul v102, v104
-            v128 = fmul v105, v101
-            v129 = fadd v127, v128
-            v130 = fadd v126, v129
-            v131 = fadd v123, v130
+            v109 = fadd v107, v16
+            v116 = fadd v109, v109
+            v131 = fadd v116, v116
             v17 = ln v16
             v132 = fdiv v109, v16
             v133 = fmul v16, v16
@@ -418,18 +418,14 @@ fn third_order_ln_sinh_exp() {
             v135 = fmul v109, v109
             v136 = fdiv v135, v133
             v137 = fsub v134, v136
-            v138 = fmul v16, v16
             v139 = fmul v133, v133
             v140 = fmul v109, v16
-            v141 = fmul v109, v16
-            v142 = fadd v140, v141
+            v142 = fadd v140, v140
             v143 = fdiv v131, v16
             v144 = fmul v109, v116
-            v145 = fdiv v144, v138
+            v145 = fdiv v144, v133
             v146 = fsub v143, v145
-            v147 = fmul v116, v109
-            v148 = fmul v116, v109
-            v149 = fadd v147, v148
+            v149 = fadd v144, v144
             v150 = fdiv v149, v133
             v151 = fmul v142, v135
             v152 = fdiv v151, v139
@@ -482,8 +478,7 @@ fn third_order_asin() {
             v114 = fmul v106, v106
             v115 = fmul v109, v11
             v116 = fmul v109, v103
-            v117 = fmul v109, v103
-            v118 = fadd v116, v117
+            v118 = fadd v116, v116
             v119 = fadd v6, v6
             v120 = fsub v3, v119
             v121 = fdiv v120, v105
@@ -545,8 +540,7 @@ fn third_order_acos() {
             v116 = fmul v107, v107
             v117 = fmul v110, v11
             v118 = fmul v111, v104
-            v119 = fmul v111, v104
-            v120 = fadd v118, v119
+            v120 = fadd v118, v118
             v121 = fadd v6, v6
             v122 = fsub v3, v121
             v123 = fdiv v122, v106
@@ -607,8 +601,7 @@ fn third_order_acosh() {
             v114 = fmul v106, v106
             v115 = fmul v109, v11
             v116 = fmul v109, v103
-            v117 = fmul v109, v103
-            v118 = fadd v116, v117
+            v118 = fadd v116, v116
             v119 = fadd v6, v6
             v120 = fsub v119, v3
             v121 = fdiv v120, v105
@@ -654,16 +647,12 @@ fn third_order_tan() {
             v101 = fmul v12, v12
             v102 = fadd v6, v101
             v103 = fmul v102, v12
-            v104 = fmul v102, v12
-            v105 = fadd v103, v104
+            v105 = fadd v103, v103
             v106 = fadd v3, v105
             v107 = fmul v106, v12
             v108 = fmul v102, v102
             v109 = fadd v107, v108
-            v110 = fmul v106, v12
-            v111 = fmul v102, v102
-            v112 = fadd v110, v111
-            v113 = fadd v109, v112
+            v113 = fadd v109, v109
             v114 = fadd v3, v113
             v100 = optbarrier v114
         }
@@ -698,16 +687,12 @@ fn third_order_tanh() {
             v101 = fmul v12, v12
             v102 = fsub v6, v101
             v103 = fmul v102, v12
-            v104 = fmul v102, v12
-            v105 = fadd v103, v104
+            v105 = fadd v103, v103
             v106 = fsub v3, v105
             v107 = fmul v106, v12
             v108 = fmul v102, v102
             v109 = fadd v107, v108
-            v110 = fmul v106, v12
-            v111 = fmul v102, v102
-            v112 = fadd v110, v111
-            v113 = fadd v109, v112
+            v113 = fadd v109, v109
             v114 = fsub v3, v113
             v100 = optbarrier v114
         }
@@ -796,8 +781,7 @@ fn third_order_atan() {
             v109 = fsub v3, v108
             v110 = fmul v104, v104
             v111 = fmul v106, v102
-            v112 = fmul v106, v102
-            v113 = fadd v111, v112
+            v113 = fadd v111, v111
             v114 = fadd v6, v6
             v115 = fadd v3, v114
             v116 = fmul v115, v6
@@ -847,8 +831,7 @@ fn third_order_atanh() {
             v109 = fsub v3, v108
             v110 = fmul v104, v104
             v111 = fmul v106, v102
-            v112 = fmul v106, v102
-            v113 = fadd v111, v112
+            v113 = fadd v111, v111
             v114 = fadd v6, v6
             v115 = fsub v3, v114
             v116 = fmul v115, v6
@@ -954,10 +937,7 @@ fn subgraph() {
         block0:
             v22 = fadd v10, v11
             v106 = fadd v6, v3
-            v107 = fadd v3, v6
             v14 = fadd v22, v12
-            v108 = fadd v3, v6
-            v109 = fadd v6, v3
             v15 = fmul v14, v20
             v16 = fmul v15, v20
             v110 = fmul v20, v20
@@ -965,32 +945,25 @@ fn subgraph() {
             v111 = fmul v110, v20
             v23 = fmul v22, v20
             v24 = fmul v23, v20
-            v112 = fmul v20, v20
             v25 = fmul v24, v20
-            v113 = fmul v112, v20
             v26 = fmul v25, v20
-            v114 = fmul v113, v20
+            v114 = fmul v111, v20
             v27 = fmul v26, v20
             v115 = fmul v114, v20
             v116 = fmul v106, v115
-            v117 = fmul v107, v115
             v18 = fmul v17, v20
-            v118 = fmul v111, v20
             v19 = fmul v18, v20
-            v119 = fmul v118, v20
             v40 = fmul v19, v20
-            v120 = fmul v119, v20
+            v120 = fmul v115, v20
             v41 = fmul v40, v20
             v121 = fmul v120, v20
-            v122 = fmul v108, v121
-            v123 = fmul v109, v121
-            v124 = fmul v106, v123
-            v125 = fmul v107, v123
+            v122 = fmul v106, v121
+            v124 = fmul v106, v122
             v101 = optbarrier v124
             v102 = optbarrier v29
             v103 = optbarrier v122
             v104 = optbarrier v116
-            v105 = optbarrier v117
+            v105 = optbarrier v116
         }
     "#]];
 
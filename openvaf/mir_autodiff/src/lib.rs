@@ -58,6 +58,9 @@ fn zero_derivative(dfg: &DataFlowGraph, inst: Inst) -> bool {
             | Opcode::Frem
             | Opcode::Floor
             | Opcode::Ceil
+            | Opcode::Rint
+            | Opcode::Round
+            | Opcode::Trunc
             | Opcode::Bnot
             | Opcode::Ilt
             | Opcode::Igt
@@ -1,5 +1,4 @@
 use std::ffi::c_void;
-use std::mem::size_of_val;
 use std::slice;
 
 use mir::{Block, FuncRef, Function, Inst, Opcode, Param, Value, ValueDef};
@@ -130,13 +129,15 @@ impl<'a> Interpreter<'a> {
             mir::Opcode::Exp => f64::exp(args(0).f64()).into(),
             mir::Opcode::Ln => f64::ln(args(0).f64()).into(),
             mir::Opcode::Log => f64::log10(args(0).f64()).into(),
-            mir::Opcode::Clog2 => {
-                let val = args(0).i32();
-                let val = 8 * size_of_val(&val) as i32 - val.leading_zeros() as i32;
-                val.into()
-            }
+            mir::Opcode::Clog2 => match args(0).i32() {
+                val if val < 1 => 0i32.into(),
+                val => (32 - (val - 1).leading_zeros() as i32).into(),
+            },
             mir::Opcode::Floor => f64::floor(args(0).f64()).into(),
             mir::Opcode::Ceil => f64::ceil(args(0).f64()).into(),
+            mir::Opcode::Rint => f64::round_ties_even(args(0).f64()).into(),
+            mir::Opcode::Round => f64::round(args(0).f64()).into(),
+            mir::Opcode::Trunc => f64::trunc(args(0).f64()).into(),
             mir::Opcode::Sin => f64::sin(args(0).f64()).into(),
             mir::Opcode::Cos => f64::cos(args(0).f64()).into(),
             mir::Opcode::Tan => f64::tan(args(0).f64()).into(),
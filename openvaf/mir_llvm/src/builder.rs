@@ -221,9 +221,28 @@ impl Drop for Builder<'_, '_, '_> {
 pub enum FastMathMode {
     Full,
     Partial,
+    User(FastMathOpts),
     Disabled,
 }
 
+/// Fast-math flags the user allows for the floating point operations of a model.
+/// Derivatives generated by OpenVAF always use [`FastMathMode::Partial`] instead.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FastMathOpts {
+    /// Allow all fast-math optimizations (reassociation, reciprocals, ignoring NaN/inf and the
+    /// sign of zero, approximate functions) except contraction.
+    pub relaxed: bool,
+    /// Allow contracting multiplications and additions into fused operations (FMA). This is
+    /// controlled separately because it slightly changes the rounding of results.
+    pub contract: bool,
+}
+
+impl FastMathOpts {
+    pub fn is_enabled(self) -> bool {
+        self.relaxed || self.contract
+    }
+}
+
 impl<'a, 'cx, 'll> Builder<'a, 'cx, 'll> {
     pub fn new(
         cx: &'a CodegenCx<'cx, 'll>,
@@ -473,11 +492,15 @@ impl<'ll> Builder<'_, '_, 'll> {
         self.select_bb(bb);
 
         for inst in self.func.layout.block_insts(bb) {
-            let fast_math = self.func.srclocs.get(inst).map_or(false, |loc| loc.0 < 0);
-            self.build_inst(
-                inst,
-                if fast_math { FastMathMode::Partial } else { FastMathMode::Disabled },
-            )
+            let fast_math = self.func.srclocs.get(inst).map_or(false, |loc| loc.is_derived());
+            let mode = if fast_math {
+                FastMathMode::Partial
+            } else if self.cx.fast_math.is_enabled() {
+                FastMathMode::User(self.cx.fast_math)
+            } else {
+                FastMathMode::Disabled
+            };
+            self.build_inst(inst, mode)
         }
     }
 
@@ -661,6 +684,7 @@ impl<'ll> Builder<'_, '_, 'll> {
                 let rhs = self.values[args[1]].get(self);
                 llvm::LLVMBuildMul(self.llbuilder, lhs, rhs, UNNAMED)
             }
+            // Verilog-A integer division truncates toward zero, just like sdiv/srem
             Opcode::Idiv => {
                 let lhs = self.values[args[0]].get(self);
                 let rhs = self.values[args[1]].get(self);
@@ -748,13 +772,28 @@ impl<'ll> Builder<'_, '_, 'll> {
             Opcode::Exp => self.intrinsic(args, "llvm.exp.f64"),
             Opcode::Ln => self.intrinsic(args, "llvm.log.f64"),
             Opcode::Log => self.intrinsic(args, "llvm.log10.f64"),
+            // clog2(n) = 32 - ctlz(n - 1), arguments smaller than one produce zero
             Opcode::Clog2 => {
-                let leading_zeros = self.intrinsic(&[args[0], true.into()], "llvm.ctlz");
+                let arg = self.values[args[0]].get(self);
+                let one = self.cx.const_int(1);
+                let (ty, fun) = self.cx.intrinsic("llvm.ctlz").unwrap();
+                let ctlz_args = [
+                    llvm::LLVMBuildSub(self.llbuilder, arg, one, UNNAMED),
+                    self.cx.const_bool(false),
+                ];
+                let leading_zeros =
+                    llvm::LLVMBuildCall2(self.llbuilder, ty, fun, ctlz_args.as_ptr(), 2, UNNAMED);
                 let total_bits = self.cx.const_int(32);
-                llvm::LLVMBuildSub(self.llbuilder, total_bits, leading_zeros, UNNAMED)
+                let res = llvm::LLVMBuildSub(self.llbuilder, total_bits, leading_zeros, UNNAMED);
+                let positive = self.int_cmp(arg, one, llvm::IntPredicate::IntSGE);
+                self.select(positive, res, self.cx.const_int(0))
             }
             Opcode::Floor => self.intrinsic(args, "llvm.floor.f64"),
             Opcode::Ceil => self.intrinsic(args, "llvm.ceil.f64"),
+            // the default floating point environment rounds half-way cases to even
+            Opcode::Rint => self.intrinsic(args, "llvm.rint.f64"),
+            Opcode::Round => self.intrinsic(args, "llvm.round.f64"),
+            Opcode::Trunc => self.intrinsic(args, "llvm.trunc.f64"),
             Opcode::Sin => self.intrinsic(args, "llvm.sin.f64"),
             Opcode::Cos => self.intrinsic(args, "llvm.cos.f64"),
             Opcode::Tan => self.intrinsic(args, "tan"),
@@ -798,6 +837,9 @@ impl<'ll> Builder<'_, '_, 'll> {
                 | Opcode::Clog2
                 | Opcode::Floor
                 | Opcode::Ceil
+                | Opcode::Rint
+                | Opcode::Round
+                | Opcode::Trunc
                 | Opcode::Sin
                 | Opcode::Cos
                 | Opcode::Tan
@@ -817,6 +859,9 @@ impl<'ll> Builder<'_, '_, 'll> {
             match fast_math_mode {
                 FastMathMode::Full => llvm::LLVMSetFastMath(val),
                 FastMathMode::Partial => llvm::LLVMSetPartialFastMath(val),
+                FastMathMode::User(opts) => {
+                    llvm::LLVMSetFastMathFlags(val, opts.relaxed, opts.contract)
+                }
                 FastMathMode::Disabled => (),
             }
         }
@@ -12,6 +12,7 @@ use llvm::{
 use target::spec::Target;
 
 use crate::types::Types;
+use crate::FastMathOpts;
 
 pub struct CodegenCx<'a, 'll> {
     pub llmod: &'ll llvm::Module,
@@ -20,6 +21,7 @@ pub struct CodegenCx<'a, 'll> {
     pub target: &'a Target,
     // pub target_cpu: &'a str,
     pub literals: &'a Rodeo,
+    pub fast_math: FastMathOpts,
     str_lit_cache: RefCell<AHashMap<Spur, &'ll Value>>,
     pub(crate) intrinsics: RefCell<AHashMap<&'static str, (&'ll Type, &'ll Value)>>,
     pub(crate) local_gen_sym_counter: Cell<u32>,
@@ -31,6 +33,7 @@ impl<'a, 'll> CodegenCx<'a, 'll> {
         literals: &'a Rodeo,
         llvm_module: &'ll crate::ModuleLlvm,
         target: &'a Target,
+        fast_math: FastMathOpts,
         // target_cpu: &'a str,
     ) -> CodegenCx<'a, 'll> {
         // let ty_isize =
@@ -40,6 +43,7 @@ impl<'a, 'll> CodegenCx<'a, 'll> {
             llcx: llvm_module.llcx,
             str_lit_cache: RefCell::new(AHashMap::with_capacity(literals.len())),
             literals,
+            fast_math,
             intrinsics: RefCell::new(AHashMap::new()),
             local_gen_sym_counter: Cell::new(0),
             // target_cpu,
@@ -5,6 +5,30 @@ use llvm::{False, LLVMTypeOf, Type, Value};
 
 use crate::CodegenCx;
 
+/// Returns the symbol suffix used for all functions generated for the module `name`.
+///
+/// Exported functions are named `<function>_<symbol>` (for example `eval_diode`) so that
+/// they are predictable for simulators. The result is always a valid C identifier and
+/// distinct module names always produce distinct symbols:
+///
+/// * ASCII letters and digits are kept as is,
+/// * `_` is escaped as `__`,
+/// * any other character is escaped as `_u<hex code point>_`.
+///
+/// As a single `_` is never followed by a digit, the `<function>_<symbol>_<idx>` names of
+/// helper functions can not collide with the functions of another module either.
+pub fn symbol_name(name: &str) -> String {
+    let mut res = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' => res.push(c),
+            '_' => res.push_str("__"),
+            _ => res.push_str(&format!("_u{:x}_", c as u32)),
+        }
+    }
+    res
+}
+
 /// Declare a function.
 ///
 /// If there’s a value with the same name already declared, the function will
@@ -47,6 +71,16 @@ impl<'a, 'll> CodegenCx<'a, 'll> {
         declare_raw_fn(self, name, llvm::CallConv::CCallConv, llvm::UnnamedAddr::No, fn_type)
     }
 
+    /// Marks a function as pure: It does not access memory, never unwinds and always returns.
+    /// This allows LLVM to hoist, CSE and eliminate calls to `fun` just like arithmetic
+    /// instructions.
+    pub fn set_pure_fn(&self, fun: &'ll Value) {
+        for attr in ["readnone", "nounwind", "willreturn"] {
+            let attr = llvm::create_enum_attr(self.llcx, attr);
+            unsafe { llvm::LLVMAddAttributeAtIndex(fun, llvm::LLVMAttributeFunctionIndex, attr) }
+        }
+    }
+
     /// Declare a internal function.
     pub fn declare_int_fn(&self, name: &str, fn_type: &'ll Type) -> &'ll Value {
         // Function addresses are never significant, allowing functions to be merged.
@@ -119,6 +153,18 @@ impl<'a, 'll> CodegenCx<'a, 'll> {
         })
     }
 
+    /// Makes `global` visible to the simulator that loads the compiled library.
+    /// Windows only exports symbols from a DLL that are marked with `dllexport`.
+    pub fn export_global(&self, global: &'ll Value) {
+        unsafe {
+            llvm::LLVMSetLinkage(global, llvm::Linkage::ExternalLinkage);
+            llvm::LLVMSetUnnamedAddress(global, llvm::UnnamedAddr::No);
+            if self.target.options.is_like_windows {
+                llvm::LLVMSetDLLStorageClass(global, llvm::DLLStorageClass::Export);
+            }
+        }
+    }
+
     pub fn export_val(
         &self,
         name: &str,
@@ -132,9 +178,7 @@ impl<'a, 'll> CodegenCx<'a, 'll> {
                 .unwrap_or_else(|| unreachable!("symbol '{}' already defined", name));
 
             llvm::LLVMSetInitializer(res, val);
-            llvm::LLVMSetLinkage(res, llvm::Linkage::ExternalLinkage);
-            llvm::LLVMSetUnnamedAddress(res, llvm::UnnamedAddr::No);
-            llvm::LLVMSetDLLStorageClass(res, llvm::DLLStorageClass::Export);
+            self.export_global(res);
 
             if is_const {
                 llvm::LLVMSetGlobalConstant(res, llvm::True);
@@ -217,8 +261,8 @@ impl<'a, 'll> CodegenCx<'a, 'll> {
         unsafe {
             let init = llvm::LLVMConstNull(ty);
             llvm::LLVMSetInitializer(arr, init);
-            llvm::LLVMSetLinkage(arr, llvm::Linkage::ExternalLinkage);
         }
+        self.export_global(arr);
 
         if add_cnt {
             let name = format!("{}.cnt", name);
@@ -230,8 +274,8 @@ impl<'a, 'll> CodegenCx<'a, 'll> {
                 let init = self.const_usize(len);
                 llvm::LLVMSetInitializer(arr_len, init);
                 llvm::LLVMSetGlobalConstant(arr_len, llvm::True);
-                llvm::LLVMSetLinkage(arr_len, llvm::Linkage::ExternalLinkage);
             }
+            self.export_global(arr_len);
         }
 
         arr
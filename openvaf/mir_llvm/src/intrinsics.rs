@@ -9,6 +9,13 @@ impl<'a, 'll> CodegenCx<'a, 'll> {
         }
 
         macro_rules! ifn {
+            (pure $name:expr, fn($($arg:expr),*) -> $ret:expr) => (
+                if name == $name {
+                    let res = self.insert_intrinsic($name, &[$($arg),*], $ret, false);
+                    self.set_pure_fn(res.1);
+                    return Some(res);
+                }
+            );
             ($name:expr, fn($($arg:expr),* ;...) -> $ret:expr) => (
                 if name == $name {
                     return Some(self.insert_intrinsic($name, &[$($arg),*], $ret, true));
@@ -37,26 +44,35 @@ impl<'a, 'll> CodegenCx<'a, 'll> {
         ifn!("llvm.log10.f64", fn(t_f64) -> t_f64);
         ifn!("llvm.log2.f64", fn(t_f64) -> t_f64);
         ifn!("llvm.floor.f64", fn(t_f64) -> t_f64);
+        ifn!("llvm.ceil.f64", fn(t_f64) -> t_f64);
+        ifn!("llvm.rint.f64", fn(t_f64) -> t_f64);
+        ifn!("llvm.round.f64", fn(t_f64) -> t_f64);
+        ifn!("llvm.trunc.f64", fn(t_f64) -> t_f64);
         ifn!("llvm.ctlz", fn(t_i32, t_bool) -> t_i32);
 
         // not technically intrinsics but part of the C standard library
         // TODO link custom mathematical functions
-        ifn!("tan", fn(t_f64) -> t_f64);
-        ifn!("acos", fn(t_f64) -> t_f64);
-        ifn!("asin", fn(t_f64) -> t_f64);
-        ifn!("atan", fn(t_f64) -> t_f64);
-        ifn!("atan2", fn(t_f64, t_f64) -> t_f64);
-        ifn!("sqrt", fn(t_f64) -> t_f64);
-        ifn!("cosh", fn(t_f64) -> t_f64);
-        ifn!("sinh", fn(t_f64) -> t_f64);
-        ifn!("tanh", fn(t_f64) -> t_f64);
-        ifn!("acosh", fn(t_f64) -> t_f64);
-        ifn!("asinh", fn(t_f64) -> t_f64);
-        ifn!("atanh", fn(t_f64) -> t_f64);
+        // LLVM intrinsics receive their attributes automatically but these are plain libm
+        // functions. Mark them as pure so that repeated calls can be deduplicated. Strictly
+        // speaking they may set `errno` but generated code never reads it.
+        ifn!(pure "tan", fn(t_f64) -> t_f64);
+        ifn!(pure "acos", fn(t_f64) -> t_f64);
+        ifn!(pure "asin", fn(t_f64) -> t_f64);
+        ifn!(pure "atan", fn(t_f64) -> t_f64);
+        ifn!(pure "atan2", fn(t_f64, t_f64) -> t_f64);
+        ifn!(pure "sqrt", fn(t_f64) -> t_f64);
+        ifn!(pure "cosh", fn(t_f64) -> t_f64);
+        ifn!(pure "sinh", fn(t_f64) -> t_f64);
+        ifn!(pure "tanh", fn(t_f64) -> t_f64);
+        ifn!(pure "acosh", fn(t_f64) -> t_f64);
+        ifn!(pure "asinh", fn(t_f64) -> t_f64);
+        ifn!(pure "atanh", fn(t_f64) -> t_f64);
 
         if name == "hypot" {
             let name = if self.target.options.is_like_windows { "_hypot" } else { "hypot" };
-            return Some(self.insert_intrinsic(name, &[t_f64], t_f64, false));
+            let res = self.insert_intrinsic(name, &[t_f64, t_f64], t_f64, false);
+            self.set_pure_fn(res.1);
+            return Some(res);
         }
 
         ifn!("strcmp", fn(t_str, t_str) -> t_i32);
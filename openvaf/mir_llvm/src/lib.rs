@@ -24,16 +24,18 @@ mod callbacks;
 #[cfg(test)]
 mod tests;
 
-pub use builder::{Builder, BuilderVal, MemLoc};
+pub use builder::{Builder, BuilderVal, FastMathOpts, MemLoc};
 pub use callbacks::InlineCallbackBuilder;
 pub use callbacks::BuiltCallbackFun;
 pub use callbacks::CallbackFun;
 pub use context::CodegenCx;
+pub use declarations::symbol_name;
 
 pub struct LLVMBackend<'t> {
     target: &'t Target,
     target_cpu: String,
     features: String,
+    fast_math: FastMathOpts,
 }
 
 impl<'t> LLVMBackend<'t> {
@@ -97,7 +99,19 @@ impl<'t> LLVMBackend<'t> {
 
         // TODO add target options here if we ever have any
         llvm::initialization::init(cg_opts, &[]);
-        LLVMBackend { target, target_cpu, features: features.join(",") }
+        LLVMBackend {
+            target,
+            target_cpu,
+            features: features.join(","),
+            fast_math: FastMathOpts::default(),
+        }
+    }
+
+    /// Enables fast-math flags for all floating point operations in the generated code.
+    /// By default the generated code strictly follows IEEE semantics.
+    pub fn with_fast_math(mut self, fast_math: FastMathOpts) -> Self {
+        self.fast_math = fast_math;
+        self
     }
 
     /// # Safety
@@ -121,7 +135,7 @@ impl<'t> LLVMBackend<'t> {
         literals: &'a Rodeo,
         module: &'ll ModuleLlvm,
     ) -> CodegenCx<'a, 'll> {
-        CodegenCx::new(literals, module, self.target)
+        CodegenCx::new(literals, module, self.target, self.fast_math)
     }
     pub fn target(&self) -> &'t Target {
         self.target
@@ -184,7 +198,10 @@ impl ModuleLlvm {
         Ok(ModuleLlvm { llcx, llmod_raw, tm, opt_lvl })
     }
 
-    pub fn to_str(&self) -> LLVMString {
+    /// Returns the textual LLVM IR of this module (as it is at the time of the call,
+    /// so calling it before and after [`optimize`](Self::optimize) shows the effect of
+    /// the optimization pipeline).
+    pub fn print_ir(&self) -> LLVMString {
         unsafe { LLVMString::new(llvm::LLVMPrintModuleToString(self.llmod())) }
     }
 
@@ -192,7 +209,13 @@ impl ModuleLlvm {
         unsafe { &*self.llmod_raw }
     }
 
+    /// Runs the optimization pipeline of the `OptLevel` this module was created with.
     pub fn optimize(&self) {
+        if let Some(passes) = self.opt_lvl.pass_pipeline() {
+            if let Err(err) = unsafe { llvm::run_passes(self.llmod(), passes, self.tm) } {
+                unreachable!("invalid pass pipeline {passes:?}: {err}")
+            }
+        }
     }
 
     /// Verifies this module and prints out  any errors
@@ -226,6 +249,68 @@ impl ModuleLlvm {
         }
     }
 
+    /// Verifies this module and attributes the errors to the individual functions that are
+    /// invalid. Each invalid function is verified in isolation (in a copy of this module
+    /// where all other functions were removed) so that the error message only contains
+    /// the problems found within that function.
+    ///
+    /// # Returns
+    /// A list of `(function_name, error_message)` pairs. Errors that can not be attributed to
+    /// any function (for example invalid global variables) are reported with an empty
+    /// function name. An empty list is returned if this module is valid.
+    pub fn verify_and_collect(&self) -> Vec<(String, String)> {
+        let err = match self.verify() {
+            Some(err) => err,
+            None => return Vec::new(),
+        };
+
+        let invalid: Vec<_> = llvm::function_iter(self.llmod())
+            .filter(|&fun| unsafe {
+                llvm::LLVMIsDeclaration(fun) == llvm::False
+                    && llvm::LLVMVerifyFunction(fun, llvm::VerifierFailureAction::ReturnStatus)
+                        == llvm::True
+            })
+            .map(value_name)
+            .collect();
+
+        if invalid.is_empty() {
+            return vec![(String::new(), err.to_string())];
+        }
+
+        invalid
+            .into_iter()
+            .map(|name| {
+                let err = unsafe { self.verify_function_isolated(&name) };
+                (name, err)
+            })
+            .collect()
+    }
+
+    unsafe fn verify_function_isolated(&self, name: &str) -> String {
+        let llmod = llvm::LLVMCloneModule(self.llmod());
+        let others: Vec<_> = llvm::function_iter(llmod)
+            .filter(|&fun| llvm::LLVMIsDeclaration(fun) == llvm::False && value_name(fun) != name)
+            .collect();
+        for fun in others {
+            llvm::LLVMReplaceAllUsesWith(fun, llvm::LLVMGetUndef(llvm::LLVMTypeOf(fun)));
+            llvm::LLVMDeleteFunction(fun);
+        }
+
+        let mut res = MaybeUninit::uninit();
+        let err = if llvm::LLVMVerifyModule(
+            llmod,
+            llvm::VerifierFailureAction::ReturnStatus,
+            Some(&mut res),
+        ) == llvm::True
+        {
+            res.assume_init().to_string()
+        } else {
+            String::new()
+        };
+        llvm::LLVMDisposeModule(llmod);
+        err
+    }
+
     pub fn emit_object(&self, dst: &Path) -> Result<(), LLVMString> {
         let path = CString::new(dst.to_str().unwrap()).unwrap();
 
@@ -260,3 +345,12 @@ impl Drop for ModuleLlvm {
         }
     }
 }
+
+fn value_name(val: &llvm::Value) -> String {
+    unsafe {
+        let mut len = 0;
+        let ptr = llvm::LLVMGetValueName2(val, &mut len);
+        let name = std::slice::from_raw_parts(ptr as *const u8, len);
+        String::from_utf8_lossy(name).into_owned()
+    }
+}
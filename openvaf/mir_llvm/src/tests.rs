@@ -1 +1,154 @@
+use lasso::Rodeo;
+use target::spec::Target;
 
+use crate::{symbol_name, LLVMBackend, OptLevel};
+
+#[test]
+fn pure_math_functions() {
+    let target = Target::host_target().unwrap();
+    let backend = LLVMBackend::new(&[], &target, "generic".to_owned(), &[]);
+    let literals = Rodeo::new();
+    let module = unsafe { backend.new_module("pure_math", OptLevel::Aggressive).unwrap() };
+    let cx = unsafe { backend.new_ctx(&literals, &module) };
+    cx.intrinsic("tan").unwrap();
+    cx.intrinsic("strcmp").unwrap();
+
+    let ir = module.print_ir().to_string();
+    let attrs = |fun: &str| {
+        let decl = ir.lines().find(|line| line.contains(&format!("@{fun}("))).unwrap();
+        let group = decl.rsplit_once('#').map_or("", |(_, group)| group.trim());
+        ir.lines()
+            .find(|line| line.starts_with(&format!("attributes #{group} ")))
+            .unwrap_or("")
+            .to_owned()
+    };
+    let tan = attrs("tan");
+    assert!(tan.contains("readnone") && tan.contains("nounwind"), "{ir}");
+    assert!(!attrs("strcmp").contains("readnone"), "{ir}");
+
+    // f(x) = exp(x) + exp(x) + tan(x) + tan(x): LLVM may only merge the duplicate calls
+    // if it knows that they are pure
+    let fn_ty = cx.ty_func(&[cx.ty_double()], cx.ty_double());
+    let fun = cx.declare_ext_fn("f", fn_ty);
+    unsafe {
+        let bb = llvm::LLVMAppendBasicBlockInContext(cx.llcx, fun, llvm::UNNAMED);
+        let builder = llvm::LLVMCreateBuilderInContext(cx.llcx);
+        llvm::LLVMPositionBuilderAtEnd(builder, bb);
+        let x = llvm::LLVMGetParam(fun, 0);
+        let mut sum = cx.const_real(0.0);
+        for name in ["llvm.exp.f64", "tan", "llvm.exp.f64", "tan"] {
+            let (ty, callee) = cx.intrinsic(name).unwrap();
+            let val = llvm::LLVMBuildCall2(builder, ty, callee, [x].as_ptr(), 1, llvm::UNNAMED);
+            sum = llvm::LLVMBuildFAdd(builder, sum, val, llvm::UNNAMED);
+        }
+        llvm::LLVMBuildRet(builder, sum);
+        llvm::LLVMDisposeBuilder(builder);
+    }
+    assert_eq!(module.verify_and_collect(), Vec::new());
+
+    // OptLevel::Aggressive does not run a pipeline of its own (see OptLevel::pass_pipeline),
+    // so run the LLVM pipeline of the same level
+    unsafe { llvm::run_passes(module.llmod(), "default<O3>", module.tm).unwrap() };
+    let ir = module.print_ir().to_string();
+    assert_eq!(ir.matches("call double @llvm.exp.f64(").count(), 1, "{ir}");
+    assert_eq!(ir.matches("call double @tan(").count(), 1, "{ir}");
+}
+
+#[test]
+fn math_intrinsics() {
+    let target = Target::host_target().unwrap();
+    let backend = LLVMBackend::new(&[], &target, "generic".to_owned(), &[]);
+    let literals = Rodeo::new();
+    let module = unsafe { backend.new_module("math_intrinsics", OptLevel::None).unwrap() };
+    let cx = unsafe { backend.new_ctx(&literals, &module) };
+
+    // LLVM only provides intrinsics for these functions, everything else calls libm
+    let intrinsics = [
+        ("llvm.sqrt.f64", 1),
+        ("llvm.exp.f64", 1),
+        ("llvm.log.f64", 1),
+        ("llvm.log10.f64", 1),
+        ("llvm.sin.f64", 1),
+        ("llvm.cos.f64", 1),
+        ("llvm.pow.f64", 2),
+        ("llvm.floor.f64", 1),
+        ("llvm.ceil.f64", 1),
+        ("llvm.rint.f64", 1),
+        ("llvm.round.f64", 1),
+        ("llvm.trunc.f64", 1),
+    ];
+    let libm = [("tan", 1), ("atan2", 2), ("hypot", 2), ("tanh", 1)];
+    for (name, arity) in intrinsics.into_iter().chain(libm) {
+        cx.intrinsic(name).unwrap_or_else(|| panic!("{name} is not declared"));
+        let ir = module.print_ir().to_string();
+        let decl = ir
+            .lines()
+            .find(|line| line.starts_with("declare") && line.contains(&format!(" @{name}(")))
+            .or_else(|| ir.lines().find(|line| line.contains("@_hypot(")))
+            .unwrap_or_else(|| panic!("{name} missing in\n{ir}"));
+        assert_eq!(decl.matches("double").count(), arity + 1, "{decl}");
+    }
+}
+
+#[test]
+fn dll_export() {
+    let literals = Rodeo::new();
+    let exported_ir = |target: &Target| {
+        let backend = LLVMBackend::new(&[], target, "generic".to_owned(), &[]);
+        let module = unsafe { backend.new_module("dll_export", OptLevel::None).unwrap() };
+        let cx = unsafe { backend.new_ctx(&literals, &module) };
+        cx.export_val("OSDI_NUM_DESCRIPTORS", cx.ty_int(), cx.const_unsigned_int(1), true);
+        let ir = module.print_ir().to_string();
+        ir.lines().find(|line| line.starts_with("@OSDI_NUM_DESCRIPTORS")).unwrap().to_owned()
+    };
+
+    let windows = Target::search("x86_64-pc-windows").unwrap();
+    assert!(exported_ir(&windows).contains("dllexport"), "{}", exported_ir(&windows));
+    let linux = Target::search("x86_64-unknown-linux").unwrap();
+    assert!(!exported_ir(&linux).contains("dllexport"), "{}", exported_ir(&linux));
+}
+
+#[test]
+fn symbol_names() {
+    assert_eq!(symbol_name("diode"), "diode");
+    assert_eq!(symbol_name("bsim_4"), "bsim__4");
+    assert_eq!(symbol_name("\\weird+model"), "_u5c_weird_u2b_model");
+    assert_eq!(symbol_name("µ"), "_ub5_");
+
+    let names = ["a_b", "a__b", "a+b", "a_u2b_b", "a\\_u2b_b", "a"];
+    let symbols: Vec<_> = names.iter().map(|name| symbol_name(name)).collect();
+    for (i, sym) in symbols.iter().enumerate() {
+        assert!(sym.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'), "{sym}");
+        assert!(!symbols[..i].contains(sym), "{sym} is not unique");
+    }
+}
+
+#[test]
+fn verify_and_collect() {
+    let target = Target::host_target().unwrap();
+    let backend = LLVMBackend::new(&[], &target, "generic".to_owned(), &[]);
+    let literals = Rodeo::new();
+    let module = unsafe { backend.new_module("verify", OptLevel::None).unwrap() };
+    let cx = unsafe { backend.new_ctx(&literals, &module) };
+    assert_eq!(module.verify_and_collect(), Vec::new());
+
+    let fn_ty = cx.ty_func(&[], cx.ty_void());
+    for name in ["valid", "invalid"] {
+        let fun = cx.declare_ext_fn(name, fn_ty);
+        unsafe {
+            let bb = llvm::LLVMAppendBasicBlockInContext(cx.llcx, fun, llvm::UNNAMED);
+            // the block of "invalid" lacks a terminator
+            if name == "valid" {
+                let builder = llvm::LLVMCreateBuilderInContext(cx.llcx);
+                llvm::LLVMPositionBuilderAtEnd(builder, bb);
+                llvm::LLVMBuildRetVoid(builder);
+                llvm::LLVMDisposeBuilder(builder);
+            }
+        }
+    }
+
+    let errors = module.verify_and_collect();
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert_eq!(errors[0].0, "invalid");
+    assert!(errors[0].1.contains("terminator"), "{}", errors[0].1);
+}
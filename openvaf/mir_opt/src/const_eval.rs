@@ -1,5 +1,3 @@
-use std::mem::size_of_val;
-
 use mir::{Const, Function, Opcode, Value, FALSE, F_ONE, F_ZERO, ONE, TRUE, ZERO};
 
 pub fn eval_binary(func: &mut Function, op: Opcode, lhs: Const, rhs: Const) -> Value {
@@ -72,6 +70,10 @@ pub fn eval_unary(func: &mut Function, op: Opcode, val: Const) -> Option<Value>
                 Opcode::Log => func.dfg.f64const(val.log10()),
                 Opcode::Floor => func.dfg.f64const(val.floor()),
                 Opcode::Ceil => func.dfg.f64const(val.ceil()),
+                // $rint rounds half-way cases to even just like the default IEEE rounding mode
+                Opcode::Rint => func.dfg.f64const(val.round_ties_even()),
+                Opcode::Round => func.dfg.f64const(val.round()),
+                Opcode::Trunc => func.dfg.f64const(val.trunc()),
                 Opcode::Sin => func.dfg.f64const(val.sin()),
                 Opcode::Cos => func.dfg.f64const(val.cos()),
                 Opcode::Tan => func.dfg.f64const(val.tan()),
@@ -95,10 +97,9 @@ pub fn eval_unary(func: &mut Function, op: Opcode, val: Const) -> Option<Value>
             Opcode::Ineg => func.dfg.iconst(-val),
             Opcode::IFcast => func.dfg.f64const(val as f64),
             Opcode::IBcast => (val != 0).into(),
-            Opcode::Clog2 => {
-                let val = 8 * size_of_val(&val) as i32 - val.leading_zeros() as i32;
-                func.dfg.iconst(val)
-            }
+            // arguments smaller than one produce zero (just like the generated code)
+            Opcode::Clog2 if val < 1 => ZERO,
+            Opcode::Clog2 => func.dfg.iconst(32 - (val - 1).leading_zeros() as i32),
             _ => unreachable!("invalid int operation {}", op),
         },
         mir::Const::Str(_) => unreachable!(),
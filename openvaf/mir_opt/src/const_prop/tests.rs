@@ -136,3 +136,82 @@ pub fn const_backward_edge() {
 
     check(raw, expect)
 }
+
+#[test]
+pub fn const_rounding() {
+    let raw = r##"
+        function %bar(v20) {
+            v30 = fconst 0x1.0000000000000p-1
+            v31 = fconst 0x1.8000000000000p0
+            v32 = fconst 0x1.4000000000000p1
+        block0:
+            v40 = rint v30
+            v41 = rint v31
+            v42 = rint v32
+            v43 = round v30
+            v44 = round v31
+            v45 = round v32
+            v46 = trunc v31
+            v50 = fmul v40, v20
+            v51 = fmul v41, v20
+            v52 = fmul v42, v20
+            v53 = fmul v43, v20
+            v54 = fmul v44, v20
+            v55 = fmul v45, v20
+            v56 = fmul v46, v20
+        }
+    "##;
+
+    let expect = expect![[r#"
+        function %bar(v20) {
+            v6 = fconst 0x1.0000000000000p0
+            v11 = fconst 0x1.0000000000000p1
+            v14 = fconst 0x1.8000000000000p1
+        block0:
+            v51 = fmul v11, v20
+            v52 = fmul v11, v20
+            v53 = fmul v6, v20
+            v54 = fmul v11, v20
+            v55 = fmul v14, v20
+            v56 = fmul v6, v20
+        }
+    "#]];
+
+    check(raw, expect)
+}
+
+#[test]
+pub fn const_int_div_truncates() {
+    let raw = r##"
+        function %bar(v20) {
+            v30 = iconst -7
+            v31 = iconst 2
+            v32 = iconst 7
+            v33 = iconst -2
+        block0:
+            v40 = idiv v30, v31
+            v41 = irem v30, v31
+            v42 = idiv v32, v33
+            v43 = irem v32, v33
+            v50 = imul v40, v20
+            v51 = imul v41, v20
+            v52 = imul v42, v20
+            v53 = imul v43, v20
+        }
+    "##;
+
+    let expect = expect![[r#"
+        function %bar(v20) {
+            v5 = iconst 1
+            v12 = iconst -1
+            v54 = iconst -3
+        block0:
+            v50 = imul v54, v20
+            v51 = imul v12, v20
+            v52 = imul v54, v20
+            v53 = imul v5, v20
+        }
+    "#]];
+
+    check(raw, expect)
+}
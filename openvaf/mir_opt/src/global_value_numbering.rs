@@ -7,14 +7,17 @@ use std::ops::{Index, IndexMut};
 use ahash::RandomState;
 use bitset::{BitSet, HybridBitSet};
 use hashbrown::raw::RawTable;
-use mir::DominatorTree;
 use mir::{Block, FuncRef, Function, Inst, InstructionData, Opcode, Value, ValueDef, ValueList};
+use mir::{ControlFlowGraph, DominatorTree};
 use stdx::packed_option::PackedOption;
 use stdx::{impl_idx_from, impl_idx_math};
 use typed_index_collections::TiVec;
 
 use crate::simplify::SimplifyCtx;
 
+#[cfg(test)]
+mod test;
+
 struct GVNExpression {
     opcode: Opcode,
     payload: GVNExprPayLoad,
@@ -526,30 +529,126 @@ impl GVN {
                 continue;
             }
 
-            let mut leader = None;
+            // instructions are visited in reverse postorder, so the most recent leader may not
+            // dominate `inst` even if an earlier one does (for example the same `exp` used by
+            // contributions in different branches)
+            let mut leader_insts: Vec<Inst> = Vec::new();
             let mut leaders = HybridBitSet::new_empty();
             for dfs_id in class.insts.iter() {
                 let inst = self.dfs_map.dfs_to_inst[dfs_id];
+                let block = func.layout.inst_block(inst).unwrap();
 
-                if let Some(leader_inst) = leader {
-                    let block = func.layout.inst_block(inst).unwrap();
+                let leader = leader_insts.iter().rev().find(|&&leader_inst| {
                     let dominator = func.layout.inst_block(leader_inst).unwrap();
-                    if dom_tree.dominates(block, dominator) {
-                        // a equivalent instruction dominates `inst` we can remove it
+                    dom_tree.dominates(block, dominator)
+                });
+                if let Some(&leader_inst) = leader {
+                    // a equivalent instruction dominates `inst` we can remove it
+                    let dest = func.dfg.first_result(inst);
+                    let src = func.dfg.first_result(leader_inst);
+                    func.dfg.replace_uses(dest, src);
+                    func.dfg.zap_inst(inst);
+                    func.layout.remove_inst(inst);
+                    continue;
+                }
+
+                leaders.insert(dfs_id, self.dfs_map.dfs_to_inst.len());
+                leader_insts.push(inst);
+            }
+
+            class.insts = leaders
+        }
+    }
+
+    /// Hoists pure instructions that are computed in multiple blocks (none of which dominates
+    /// the others) into the closest block that dominates all of them, for example the same `exp`
+    /// used by contributions in both branches of an `if`. An instruction is only hoisted if it
+    /// is computed on every path leaving that block (so no path evaluates it more often than
+    /// before) and one of the equivalent instructions has all arguments available there.
+    ///
+    /// Must be called after [`remove_unnecessary_insts`](GVN::remove_unnecessary_insts).
+    pub fn hoist_common_insts(
+        &mut self,
+        func: &mut Function,
+        cfg: &ControlFlowGraph,
+        dom_tree: &DominatorTree,
+    ) {
+        let mut visited = BitSet::new_empty(func.layout.num_blocks());
+        let mut stack = Vec::new();
+        let mut changed = true;
+        // hoisting an instruction can make the arguments of other instructions available
+        while changed {
+            changed = false;
+            for class in self.class_map.classes.iter_mut() {
+                if matches!(class.expr.opcode, Opcode::OptBarrier | Opcode::Phi | Opcode::Call)
+                    || matches!(&class.insts, HybridBitSet::Sparse(insts) if insts.len() < 2)
+                {
+                    continue;
+                }
+
+                let insts: Vec<_> =
+                    class.insts.iter().map(|dfs_id| self.dfs_map.dfs_to_inst[dfs_id]).collect();
+                let mut blocks = insts.iter().map(|&inst| func.layout.inst_block(inst).unwrap());
+                let first = blocks.next().unwrap();
+                let dst =
+                    blocks.fold(first, |dst, block| dom_tree.nearest_common_dominator(dst, block));
+
+                let available = |val: Value| match func.dfg.value_def(val) {
+                    ValueDef::Result(inst, _) => {
+                        dom_tree.dominates(dst, func.layout.inst_block(inst).unwrap())
+                    }
+                    _ => true,
+                };
+                let hoisted = insts
+                    .iter()
+                    .copied()
+                    .find(|&inst| func.dfg.instr_args(inst).iter().all(|&arg| available(arg)));
+                let hoisted = match hoisted {
+                    Some(hoisted) => hoisted,
+                    None => continue,
+                };
+
+                // check that every path from `dst` to the exit computes the instruction
+                visited.clear();
+                stack.clear();
+                stack.extend(cfg.succ_iter(dst));
+                for &inst in &insts {
+                    visited.insert(func.layout.inst_block(inst).unwrap());
+                }
+                let mut anticipated = true;
+                while let Some(block) = stack.pop() {
+                    if !visited.insert(block) {
+                        continue;
+                    }
+                    let mut succ = cfg.succ_iter(block).peekable();
+                    if succ.peek().is_none() {
+                        anticipated = false;
+                        break;
+                    }
+                    stack.extend(succ);
+                }
+                if !anticipated {
+                    continue;
+                }
+
+                let term = func.layout.block_terminator(dst).unwrap();
+                func.layout.remove_inst(hoisted);
+                func.layout.prepend_inst(hoisted, term);
+                let src = func.dfg.first_result(hoisted);
+                for inst in insts {
+                    if inst != hoisted {
                         let dest = func.dfg.first_result(inst);
-                        let src = func.dfg.first_result(leader_inst);
                         func.dfg.replace_uses(dest, src);
                         func.dfg.zap_inst(inst);
                         func.layout.remove_inst(inst);
-                        continue;
                     }
                 }
 
-                leaders.insert(dfs_id, self.dfs_map.dfs_to_inst.len());
-                leader = Some(inst);
+                let dfs_id = self.dfs_map.inst_to_dfs[hoisted].unwrap_unchecked();
+                class.insts = HybridBitSet::new_empty();
+                class.insts.insert(dfs_id, self.dfs_map.dfs_to_inst.len());
+                changed = true;
             }
-
-            class.insts = leaders
         }
     }
 
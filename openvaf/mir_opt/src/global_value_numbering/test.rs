@@ -13,6 +13,7 @@ fn check(src: &str, data_flow_result: Expect) {
     gvn.init(&func, &dom_tree, 3);
     gvn.solve(&mut func);
     gvn.remove_unnecessary_insts(&mut func, &dom_tree);
+    gvn.hoist_common_insts(&mut func, &cfg, &dom_tree);
     gvn.clear(&mut func);
     data_flow_result.assert_eq(&func.to_debug_string());
 }
@@ -71,3 +72,117 @@ fn diode() {
     "#]];
     check(raw, expect)
 }
+
+#[test]
+fn shared_exp_across_branches() {
+    // the same exp is used by multiple contributions, the computation in block5 does
+    // not dominate block3 but it is visited first so the exp in block3 must still be
+    // replaced with the exp in block1. Every path computes the exp so the remaining
+    // computations are hoisted into block0
+    let raw = r#"
+        function %bar(v10, v11, v12) {
+        block0:
+            br v11, block1, block2
+        block1:
+            v20 = exp v10
+            v30 = optbarrier v20
+            br v12, block3, block5
+        block2:
+            jmp block5
+        block3:
+            v22 = exp v10
+            v32 = optbarrier v22
+            jmp block6
+        block5:
+            v21 = exp v10
+            v31 = optbarrier v21
+            jmp block6
+        block6:
+            v23 = exp v10
+            v33 = optbarrier v23
+        }
+    "#;
+    let expect = expect![[r#"
+        function %bar(v10, v11, v12) {
+        block0:
+            v20 = exp v10
+            br v11, block1, block2
+
+        block1:
+            v30 = optbarrier v20
+            br v12, block3, block5
+
+        block2:
+            jmp block5
+
+        block3:
+            v32 = optbarrier v20
+            jmp block6
+
+        block5:
+            v31 = optbarrier v20
+            jmp block6
+
+        block6:
+            v33 = optbarrier v20
+        }
+    "#]];
+    check(raw, expect)
+}
+
+#[test]
+fn hoist_shared_exp() {
+    // a (heavily simplified) Gummel-Poon like model: the forward and reverse mode compute the
+    // same exponentials. exp(v10) and exp(v10 * v11) are needed in both modes and are hoisted
+    // into block0, exp(v11) is only needed in the forward mode and is not hoisted.
+    // This reduces the number of exp calls from five to three
+    let raw = r#"
+        function %gp(v10, v11, v12) {
+        block0:
+            br v12, block1, block2
+        block1:
+            v20 = exp v10
+            v21 = exp v11
+            v22 = fmul v10, v11
+            v23 = exp v22
+            v24 = fsub v20, v21
+            v25 = fadd v24, v23
+            v30 = optbarrier v25
+            jmp block3
+        block2:
+            v40 = fmul v10, v11
+            v41 = exp v40
+            v42 = exp v10
+            v43 = fsub v41, v42
+            v31 = optbarrier v43
+            jmp block3
+        block3:
+            v32 = optbarrier v10
+        }
+    "#;
+    let expect = expect![[r#"
+        function %gp(v10, v11, v12) {
+        block0:
+            v40 = fmul v10, v11
+            v41 = exp v40
+            v42 = exp v10
+            br v12, block1, block2
+
+        block1:
+            v21 = exp v11
+            v24 = fsub v42, v21
+            v25 = fadd v24, v41
+            v30 = optbarrier v25
+            jmp block3
+
+        block2:
+            v43 = fsub v41, v42
+            v31 = optbarrier v43
+            jmp block3
+
+        block3:
+            v32 = optbarrier v10
+        }
+    "#]];
+    check(raw, expect)
+}
@@ -129,10 +129,19 @@ impl<'a, FP: Arithmetic, M: Fn(Value, &Function) -> Value> SimplifyCtx<'a, FP, M
 
                 return None;
             }
-            Opcode::Floor | Opcode::Ceil => {
+            Opcode::Floor | Opcode::Ceil | Opcode::Rint | Opcode::Round | Opcode::Trunc => {
                 if matches!(
                     self.as_any_unary(arg),
-                    Some((Opcode::IFcast | Opcode::BFcast | Opcode::Ceil | Opcode::Floor, _))
+                    Some((
+                        Opcode::IFcast
+                            | Opcode::BFcast
+                            | Opcode::Ceil
+                            | Opcode::Floor
+                            | Opcode::Rint
+                            | Opcode::Round
+                            | Opcode::Trunc,
+                        _
+                    ))
                 ) {
                     return Some(arg);
                 } else {
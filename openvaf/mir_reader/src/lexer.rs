@@ -365,10 +365,13 @@ impl<'a> Lexer<'a> {
 
         assert_eq!(self.lookahead, Some('@'));
 
-        while let Some(c) = self.next_ch() {
-            if !char::is_digit(c, 16) {
-                break;
-            }
+        let mut next = self.next_ch();
+        // derived source locations are prefixed with `^`
+        if next == Some('^') {
+            next = self.next_ch();
+        }
+        while next.map_or(false, |c| c.is_digit(16)) {
+            next = self.next_ch();
         }
 
         let end = self.pos;
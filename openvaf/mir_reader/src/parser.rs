@@ -325,10 +325,15 @@ impl<'a> Parser<'a> {
     /// Return an optional source location if no real location is present.
     fn optional_srcloc(&mut self) -> ParseResult<SourceLoc> {
         if let Some(Token::SourceLoc(text)) = self.token() {
-            match i32::from_str_radix(text, 16) {
+            let (derived, hex) = match text.strip_prefix('^') {
+                Some(hex) => (true, hex),
+                None => (false, text),
+            };
+            match i32::from_str_radix(hex, 16) {
                 Ok(num) => {
                     self.consume();
-                    Ok(SourceLoc::new(num))
+                    let loc = SourceLoc::new(num);
+                    Ok(if derived { loc.derived() } else { loc })
                 }
                 Err(_) => err!(self.loc, "invalid source location: {}", text),
             }
@@ -58,3 +58,20 @@ fn roundtrip() {
     let printed = fun.print(&interner).to_string();
     expected.assert_eq(&printed)
 }
+
+#[test]
+fn derived_srcloc() {
+    let src = "function %foo(v1) {
+        block0:
+            @0007 v2 = fneg v1
+            @^0007 v3 = fneg v2
+        }";
+    let (fun, interner) = parse_function(src).unwrap();
+    let insts: Vec<_> = fun.layout.block_insts(fun.layout.entry_block().unwrap()).collect();
+    let (loc, derived) = (fun.srclocs[insts[0]], fun.srclocs[insts[1]]);
+    assert!(!loc.is_derived());
+    assert!(derived.is_derived());
+    assert_eq!(derived, loc.derived());
+    assert_eq!(derived.origin(), loc);
+    assert!(fun.print(&interner).to_string().contains("@^0007"));
+}
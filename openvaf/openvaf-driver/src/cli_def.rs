@@ -26,11 +26,22 @@ pub fn main_command() -> Command {
             output(),
             batchmode(),
             dry_run(),
+            dump_hir(),
             dump_mir(), 
             dump_unopt_mir(), 
             dump_ir(), 
+            emit(),
+            print_stats(),
+            print_timing(),
+            gmin(),
             cache_dir(),
             opt_lvl(),
+            fast_math(),
+            min_typ_max(),
+            clamp_sqrt(),
+            macro_depth_limit(),
+            unroll_limit(),
+            expression_limit(),
             target(),
             supported_targets(),
             target_cpu(),
@@ -47,9 +58,18 @@ pub fn main_command() -> Command {
 pub const INTERFACE: &str = "interface";
 pub const BATCHMODE: &str = "batchmode";
 pub const DRYRUN: &str = "dry-run";
+pub const DUMPHIR: &str = "dump-hir";
 pub const DUMPMIR: &str = "dump-mir";
 pub const DUMPUNOPTMIR: &str = "dump-unopt-mir";
 pub const DUMPIR: &str = "dump-ir";
+pub const EMIT: &str = "emit";
+pub const PRINT_STATS: &str = "print-stats";
+pub const PRINT_TIMING: &str = "print-timing";
+pub const GMIN: &str = "gmin";
+pub const CLAMP_SQRT: &str = "clamp-sqrt";
+pub const MACRO_DEPTH_LIMIT: &str = "macro-depth-limit";
+pub const UNROLL_LIMIT: &str = "unroll-limit";
+pub const EXPRESSION_LIMIT: &str = "expression-limit";
 pub const TARGET: &str = "target";
 pub const SUPPORTED_TARGETS: &str = "supported-targets";
 pub const LINTS: &str = "lints";
@@ -60,6 +80,8 @@ pub const INCLUDE: &str = "include";
 pub const OUTPUT: &str = "output";
 pub const CACHE_DIR: &str = "cache-dir";
 pub const OPT_LVL: &str = "opt_lvl";
+pub const FAST_MATH: &str = "fast-math";
+pub const MIN_TYP_MAX: &str = "mintypmax";
 pub const DEFINE: &str = "define";
 pub const PRINT_EXPANSION: &str = "print-expansion";
 pub const DUMP_JSON: &str = "dump-json";
@@ -91,6 +113,12 @@ fn dry_run() -> Arg {
         .long_help("Run compilation without producing any output.\nUsed for testing.")
 }
 
+fn dump_hir() -> Arg {
+    flag(DUMPHIR, "dump-hir")
+        .help("Dump HIR during compilation.")
+        .long_help("Dump HIR (item tree, resolved scopes and analog blocks) during compilation.\nUsed for debugging.")
+}
+
 fn dump_mir() -> Arg {
     flag(DUMPMIR, "dump-mir")
         .help("Dump MIR during compilation.")
@@ -109,6 +137,37 @@ fn dump_ir() -> Arg {
         .long_help("Dump LLVM IR during compilation.\nUsed for debugging.")
 }
 
+fn emit() -> Arg {
+    Arg::new(EMIT)
+        .long(EMIT)
+        .help("Write additional compilation artifacts next to the output file.")
+        .long_help("Write additional compilation artifacts next to the output file.\nCan be passed multiple times.\n\npossible values\n\nllvm-ir - the LLVM IR of every codegen unit before (<output>.<unit>.ll)\n          and after (<output>.<unit>.opt.ll) optimization.\n          Used for debugging and for reporting codegen bugs.")
+        .value_name("KIND")
+        .value_hint(ValueHint::Other)
+        .value_parser(["llvm-ir"])
+        .hide_possible_values(true)
+        .action(ArgAction::Append)
+        .required(false)
+}
+
+fn print_stats() -> Arg {
+    flag(PRINT_STATS, "print-stats")
+        .help("Print the size of the HIR and MIR of all modules.")
+        .long_help("Print the number of expressions and statements of every analog block and function\nand the number of blocks, instructions and values of the MIR after each compilation phase.\nUsed to profile the compilation of large models.")
+}
+
+fn print_timing() -> Arg {
+    flag(PRINT_TIMING, "print-timing")
+        .help("Print the time spent in each compilation phase.")
+        .long_help("Print the wall-clock time of parsing, lowering to HIR, lowering to MIR, automatic differentiation,\nMIR optimization, LLVM code generation and linking.\nUsed to find out where the time is spent when compiling large models.")
+}
+
+fn gmin() -> Arg {
+    flag(GMIN, "gmin")
+        .help("Add a gmin conductance to all nonlinear branches.")
+        .long_help("Add a conductance in parallel to all nonlinear branches.\nIts value is read from the simulator parameter gmin (0 if the simulator does not provide it)\nso that the simulator can use gmin stepping to improve convergence.")
+}
+
 fn target() -> Arg {
     let vals = get_target_names().fold(String::new(), |mut dst, it| {
         dst.push('\n');
@@ -273,14 +332,79 @@ fn opt_lvl() -> Arg {
         .long(OPT_LVL)
         .short('O')
         .help("Set how much the code is optimized.")
-        .long_help("Set how much the generated machine code is optimized:\nA higher optimization level means slower compile times but faster simulations.\n\npossible values\n\n0 - no optimizations\n1 - optimize minimally\n2 - optimize more\n3 - optimize even more")
+        .long_help("Set how much the generated machine code is optimized:\nA higher optimization level means slower compile times but faster simulations.\n\npossible values\n\n0 - no optimizations\ng - only promote stack slots to registers and simplify (compiles fast, runs much faster than 0)\n1 - optimize minimally\n2 - optimize more\n3 - optimize even more")
         .value_name("LEVEL")
         .value_hint(ValueHint::Other)
-        .value_parser(["0","1","2","3"])
+        .value_parser(["0","g","1","2","3"])
         .hide_possible_values(true)
         .default_value("3").required(false)
 }
 
+fn fast_math() -> Arg {
+    Arg::new(FAST_MATH)
+        .long(FAST_MATH)
+        .help("Allow optimizations that do not strictly follow IEEE floating point semantics.")
+        .long_help("Allow optimizations that do not strictly follow IEEE floating point semantics.\nBy default all floating point operations are strict.\nCan be passed multiple times.\n\npossible values\n\nrelaxed - allow reassociation, reciprocals and ignoring NaN/inf/signed zeros\ncontract - allow fusing multiplications and additions (FMA)\nfast - both of the above")
+        .value_name("FLAGS")
+        .value_hint(ValueHint::Other)
+        .value_parser(["relaxed", "contract", "fast"])
+        .hide_possible_values(true)
+        .action(ArgAction::Append)
+        .required(false)
+}
+
+fn min_typ_max() -> Arg {
+    Arg::new(MIN_TYP_MAX)
+        .long(MIN_TYP_MAX)
+        .help("Select which value of (min:typ:max) expressions is used.")
+        .long_help("Select which value of (min:typ:max) expressions is used.\n\npossible values\n\nmin - use the minimum value\ntyp - use the typical value\nmax - use the maximum value")
+        .value_name("VALUE")
+        .value_hint(ValueHint::Other)
+        .value_parser(["min", "typ", "max"])
+        .hide_possible_values(true)
+        .default_value("typ")
+        .required(false)
+}
+
+fn clamp_sqrt() -> Arg {
+    flag(CLAMP_SQRT, "clamp-sqrt")
+        .help("Evaluate sqrt(x) as sqrt(max(x, 0)).")
+        .long_help("Evaluate sqrt(x) as sqrt(max(x, 0)).\nThe value and the derivative of sqrt are zero for arguments smaller than or equal to zero\ninstead of NaN (negative arguments) or an infinite derivative (at zero).")
+}
+
+fn macro_depth_limit() -> Arg {
+    Arg::new(MACRO_DEPTH_LIMIT)
+        .long(MACRO_DEPTH_LIMIT)
+        .help("Maximum nesting depth of macro expansions.")
+        .long_help("Maximum nesting depth of macro expansions (default 256).\nMacros that are nested deeper produce an error.")
+        .value_name("DEPTH")
+        .value_hint(ValueHint::Other)
+        .value_parser(clap::value_parser!(u32))
+        .required(false)
+}
+
+fn unroll_limit() -> Arg {
+    Arg::new(UNROLL_LIMIT)
+        .long(UNROLL_LIMIT)
+        .help("Maximum number of unrolled genvar loop iterations per analog block.")
+        .long_help("Maximum number of unrolled genvar loop iterations per analog block (default 65536).\nGenvar loops are unrolled during compilation, the iterations of all loops count towards the limit\n(for nested loops every iteration of the inner loop is counted). Exceeding the limit produces an error.")
+        .value_name("ITERATIONS")
+        .value_hint(ValueHint::Other)
+        .value_parser(clap::value_parser!(u32))
+        .required(false)
+}
+
+fn expression_limit() -> Arg {
+    Arg::new(EXPRESSION_LIMIT)
+        .long(EXPRESSION_LIMIT)
+        .help("Maximum number of expressions of a block after inlining analog functions.")
+        .long_help("Maximum number of expressions of a block after inlining analog functions (default 4194304).\nAnalog functions are inlined during compilation, blocks that grow larger produce an error.")
+        .value_name("EXPRESSIONS")
+        .value_hint(ValueHint::Other)
+        .value_parser(clap::value_parser!(u32))
+        .required(false)
+}
+
 fn expand() -> Arg {
     flag(PRINT_EXPANSION, "print-expansion")
         .help("Abort after preprocessing and print expanded sourcecode.")
@@ -4,12 +4,15 @@ use std::process::exit;
 use anyhow::{bail, Context, Result};
 use camino::Utf8PathBuf;
 use clap::ArgMatches;
-use openvaf::{builtin_lints, get_target_names, host_triple, AbsPathBuf, LintLevel, OptLevel};
+use openvaf::{
+    builtin_lints, get_target_names, host_triple, AbsPathBuf, CompilationLimits, FastMathOpts,
+    LintLevel, MinTypMax, OptLevel,
+};
 use termcolor::{Color, ColorChoice, ColorSpec, WriteColor};
 
 use crate::cli_def::{
-    ALLOW, BATCHMODE, CACHE_DIR, CODEGEN, DEFINE, DENY, DRYRUN, DUMPMIR, DUMPUNOPTMIR, DUMPIR, INCLUDE, INPUT, LINTS, OPT_LVL,
-    OUTPUT, SUPPORTED_TARGETS, TARGET, TARGET_CPU, WARN,
+    ALLOW, BATCHMODE, CACHE_DIR, CLAMP_SQRT, CODEGEN, DEFINE, DENY, DRYRUN, DUMPHIR, DUMPMIR, DUMPUNOPTMIR, DUMPIR, EMIT, EXPRESSION_LIMIT, FAST_MATH, GMIN, INCLUDE, INPUT, LINTS, MACRO_DEPTH_LIMIT, MIN_TYP_MAX, OPT_LVL,
+    OUTPUT, PRINT_STATS, PRINT_TIMING, SUPPORTED_TARGETS, TARGET, TARGET_CPU, UNROLL_LIMIT, WARN,
 };
 use crate::{CompilationDestination, Opts};
 
@@ -84,12 +87,49 @@ pub fn matches_to_opts(matches: ArgMatches) -> Result<Opts> {
 
     let opt_lvl = match &**matches.get_one::<String>(OPT_LVL).unwrap() {
         "0" => OptLevel::None,
+        "g" => OptLevel::DebugFast,
         "1" => OptLevel::Less,
         "2" => OptLevel::Default,
         "3" => OptLevel::Aggressive,
         lvl => bail!("unknown opt lvl {lvl}"),
     };
 
+    let mut fast_math = FastMathOpts::default();
+    for flags in matches.get_many::<String>(FAST_MATH).into_iter().flatten() {
+        match &**flags {
+            "relaxed" => fast_math.relaxed = true,
+            "contract" => fast_math.contract = true,
+            "fast" => fast_math = FastMathOpts { relaxed: true, contract: true },
+            flags => bail!("unknown fast-math flags {flags}"),
+        }
+    }
+
+    let mut emit_llvm_ir = false;
+    for kind in matches.get_many::<String>(EMIT).into_iter().flatten() {
+        match &**kind {
+            "llvm-ir" => emit_llvm_ir = true,
+            kind => bail!("unknown emit kind {kind}"),
+        }
+    }
+
+    let min_typ_max = match &**matches.get_one::<String>(MIN_TYP_MAX).unwrap() {
+        "min" => MinTypMax::Min,
+        "typ" => MinTypMax::Typ,
+        "max" => MinTypMax::Max,
+        val => bail!("unknown mintypmax value {val}"),
+    };
+
+    let mut limits = CompilationLimits::default();
+    if let Some(&depth) = matches.get_one::<u32>(MACRO_DEPTH_LIMIT) {
+        limits.macro_depth = depth;
+    }
+    if let Some(&iterations) = matches.get_one::<u32>(UNROLL_LIMIT) {
+        limits.unroll = iterations;
+    }
+    if let Some(&expressions) = matches.get_one::<u32>(EXPRESSION_LIMIT) {
+        limits.expressions = expressions;
+    }
+
     let host = host_triple();
     let target = matches.get_one::<String>(TARGET).cloned().unwrap_or_else(|| host.to_owned());
     let default_cpu = if host != target { "generic" } else { "native" };
@@ -112,11 +152,21 @@ pub fn matches_to_opts(matches: ArgMatches) -> Result<Opts> {
         include,
         output,
         opt_lvl,
+        fast_math,
+        min_typ_max,
         target,
         target_cpu,
+        dump_hir: matches.get_flag(DUMPHIR),
         dump_mir: matches.get_flag(DUMPMIR), 
         dump_unopt_mir: matches.get_flag(DUMPUNOPTMIR), 
         dump_ir: matches.get_flag(DUMPIR), 
+        emit_llvm_ir,
+        print_stats: matches.get_flag(PRINT_STATS),
+        print_timing: matches.get_flag(PRINT_TIMING),
+        gmin: matches.get_flag(GMIN),
+        clamp_sqrt: matches.get_flag(CLAMP_SQRT),
+        limits,
+        host_functions: Vec::new(),
         dry_run: matches.get_flag(DRYRUN),
     })
 }
@@ -8,17 +8,18 @@ use hir::CompilationDB;
 use crate::Opts;
 
 // TODO: use high level hir API instead of low leve database API
-fn hash(db: &CompilationDB, defines: &[String]) -> md5::Digest {
+fn hash(db: &CompilationDB, opts: &Opts) -> md5::Digest {
     let mut hash_builder = md5::Context::new();
     let cu = db.compilation_unit();
 
     // hash settings
     hash_builder.consume(cu.root_file().0.to_ne_bytes());
 
-    hash_builder.consume(defines.len().to_ne_bytes());
-    for def in defines {
+    hash_builder.consume(opts.defines.len().to_ne_bytes());
+    for def in &opts.defines {
         hash_builder.consume(def)
     }
+    hash_builder.consume(format!("{:?}", opts.host_functions));
 
     hash_builder.consume(env!("CARGO_PKG_VERSION"));
     let lints = db.global_lint_overwrites(cu.root_file());
@@ -32,6 +33,12 @@ fn hash(db: &CompilationDB, defines: &[String]) -> md5::Digest {
         )
     };
     hash_builder.consume(lints);
+    hash_builder.consume([db.min_typ_max(cu.root_file()) as u8]);
+    hash_builder.consume([db.clamp_sqrt(cu.root_file()) as u8]);
+    let limits = db.limits(cu.root_file());
+    hash_builder.consume(limits.macro_depth.to_ne_bytes());
+    hash_builder.consume(limits.unroll.to_ne_bytes());
+    hash_builder.consume(limits.expressions.to_ne_bytes());
 
     // Hash the full preprocessor result
     let preprocess = cu.preprocess(db);
@@ -49,7 +56,7 @@ fn hash(db: &CompilationDB, defines: &[String]) -> md5::Digest {
 }
 
 pub fn file_name(db: &CompilationDB, opts: &Opts) -> String {
-    let hash = u128::from_ne_bytes(*hash(db, &opts.defines));
+    let hash = u128::from_ne_bytes(*hash(db, opts));
     let hash = base_n::encode(hash, base_n::CASE_INSENSITIVE);
     format!("{}.osdi", hash)
 }
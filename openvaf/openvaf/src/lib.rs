@@ -12,12 +12,17 @@ use linker::link;
 use mir_llvm::LLVMBackend;
 use sim_back::collect_modules;
 use sim_back::{print_module, print_intern};
+use stdx::timing::PhaseTimer;
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
 pub use basedb::lints::builtin as builtin_lints;
 pub use basedb::lints::LintLevel;
+pub use basedb::{CompilationLimits, MinTypMax};
+pub use hir::{HostFunction, Type};
 pub use llvm::OptLevel;
+pub use mir_llvm::FastMathOpts;
 pub use paths::AbsPathBuf;
+pub use target::data_layout::{DataLayout, Endian};
 pub use target::host_triple;
 pub use target::spec::{get_target_names, Target};
 
@@ -44,11 +49,34 @@ pub struct Opts {
     pub output: CompilationDestination,
     pub include: Vec<AbsPathBuf>,
     pub opt_lvl: OptLevel,
+    pub fast_math: FastMathOpts,
     pub target: Target,
     pub target_cpu: String,
+    pub dump_hir: bool,
     pub dump_mir: bool, 
     pub dump_unopt_mir: bool, 
     pub dump_ir: bool, 
+    /// Write the LLVM IR of every codegen unit before and after optimization next to the
+    /// library (`<lib>.<unit>.ll` and `<lib>.<unit>.opt.ll`).
+    pub emit_llvm_ir: bool,
+    /// Print the size of the HIR and MIR (after each compilation phase) of all modules.
+    pub print_stats: bool,
+    /// Print the wall-clock time of each compilation phase.
+    pub print_timing: bool,
+    /// Add a simulator controlled conductance (`$simparam("gmin")`) in parallel to all
+    /// nonlinear branches.
+    pub gmin: bool,
+    /// Which value of `(min:typ:max)` expressions is used.
+    pub min_typ_max: MinTypMax,
+    /// Lower `sqrt(x)` as `sqrt(max(x, 0))`: its value and derivative are zero for `x <= 0`.
+    pub clamp_sqrt: bool,
+    /// Limits that turn pathological models (deeply nested macros, huge genvar loops)
+    /// into errors instead of exhausting memory.
+    pub limits: CompilationLimits,
+    /// System functions implemented by the simulator. The compiled library exports a
+    /// function pointer `osdi_host_<name>` for each function that is called by a model,
+    /// the simulator must set it after loading the library (like `osdi_log`).
+    pub host_functions: Vec<HostFunction>,
 }
 // pub fn dump_json(opts: &Opts) -> Result<CompilationTermination> {
 //     let input =
@@ -157,6 +185,26 @@ pub fn expand(opts: &Opts) -> Result<CompilationTermination> {
     Ok(CompilationTermination::Compiled { lib_file: Utf8PathBuf::default() })
 }
 
+/// Compiles multiple (independent) model libraries in parallel.
+///
+/// Every library is compiled by a separate worker of the global thread pool.
+/// This is safe because each compilation uses its own [`CompilationDB`] and
+/// every LLVM module is created within its own LLVM context
+/// (no LLVM state is shared between workers).
+///
+/// # Returns
+///
+/// The result of compiling each entry of `opts` (in the same order).
+pub fn compile_all(opts: &[Opts]) -> Vec<Result<CompilationTermination>> {
+    let mut res: Vec<_> = opts.iter().map(|_| None).collect();
+    rayon_core::scope(|scope| {
+        for (opts, res) in opts.iter().zip(&mut res) {
+            scope.spawn(move |_| *res = Some(compile(opts)));
+        }
+    });
+    res.into_iter().map(|res| res.expect("compilation finished")).collect()
+}
+
 pub fn compile(opts: &Opts) -> Result<CompilationTermination> {
     let start = Instant::now();
 
@@ -164,6 +212,37 @@ pub fn compile(opts: &Opts) -> Result<CompilationTermination> {
         opts.input.canonicalize().with_context(|| format!("failed to resolve {}", opts.input))?;
     let input = AbsPathBuf::assert(input);
     let db = CompilationDB::new_fs(input, &opts.include, &opts.defines, &opts.lints)?;
+    compile_db(db, opts, start)
+}
+
+/// Compiles the Verilog-A `source` instead of reading `opts.input` from the filesystem.
+///
+/// `opts.input` only provides the (synthetic) file name used in diagnostics,
+/// all other options behave just like for [`compile`].
+pub fn compile_str(source: &str, opts: &Opts) -> Result<CompilationTermination> {
+    let start = Instant::now();
+
+    let name = opts.input.file_name().unwrap_or("source.va");
+    let db = CompilationDB::new_str(name, source, &opts.include, &opts.defines, &opts.lints)?;
+    compile_db(db, opts, start)
+}
+
+fn compile_db(
+    mut db: CompilationDB,
+    opts: &Opts,
+    start: Instant,
+) -> Result<CompilationTermination> {
+    let root_file = db.compilation_unit().root_file();
+    db.set_min_typ_max(root_file, opts.min_typ_max);
+    db.set_clamp_sqrt(root_file, opts.clamp_sqrt);
+    db.set_limits(root_file, opts.limits);
+    db.register_host_functions(&opts.host_functions)?;
+    let mut timer = PhaseTimer::new(opts.print_timing);
+    if opts.print_timing {
+        // parsing is otherwise done lazily while lowering to HIR
+        db.parse(root_file);
+        timer.finish("preprocessing and parsing");
+    }
 
     let lib_file = match &opts.output {
         CompilationDestination::Cache { cache_dir } => {
@@ -178,17 +257,29 @@ pub fn compile(opts: &Opts) -> Result<CompilationTermination> {
         CompilationDestination::Path { lib_file } => lib_file.clone(),
     };
 
+    if opts.dump_hir {
+        println!("{}", db.compilation_unit().dump_hir(&db));
+    }
+
+    if opts.print_stats {
+        print!("{}", db.compilation_unit().stats(&db));
+    }
+
     let modules = if let Some(modules) = collect_modules(&db, false, &mut ConsoleSink::new(&db)) {
         modules
     } else {
         return Ok(CompilationTermination::FatalDiagnostic);
     };
+    timer.finish("lowering to HIR and validation");
 
-    let back = LLVMBackend::new(&opts.codegen_opts, &opts.target, opts.target_cpu.clone(), &[]);
+    let back = LLVMBackend::new(&opts.codegen_opts, &opts.target, opts.target_cpu.clone(), &[])
+        .with_fast_math(opts.fast_math);
     if opts.dry_run {
         return Ok(CompilationTermination::Compiled { lib_file });
     }
-    let (paths, compiled_modules, literals) = osdi::compile(&db, &modules, &lib_file, &opts.target, &back, true, opts.opt_lvl, opts.dump_mir, opts.dump_unopt_mir, opts.dump_ir);
+    let (paths, compiled_modules, literals) = osdi::compile(&db, &modules, &lib_file, &opts.target, &back, true, opts.opt_lvl, opts.dump_mir, opts.dump_unopt_mir, opts.dump_ir, opts.emit_llvm_ir, opts.print_stats, opts.print_timing, opts.gmin);
+    // the individual phases are reported by osdi::compile, this is their total
+    timer.finish("lowering to MIR and LLVM code generation (total)");
 
     // Dump MIR of compiled modules
     if opts.dump_mir || opts.dump_unopt_mir {
@@ -230,13 +321,15 @@ pub fn compile(opts: &Opts) -> Result<CompilationTermination> {
     for obj_file in paths {
         remove_file(obj_file).context("failed to delete intermediate compile artifact")?;
     }
+    timer.finish("linking");
 
     let seconds = Instant::elapsed(&start).as_secs_f64();
     let mut stderr = StandardStream::stderr(ColorChoice::Auto);
     stderr.set_color(ColorSpec::new().set_fg(Some(Color::Green)).set_bold(true))?;
     write!(&mut stderr, "Finished")?;
     stderr.set_color(&ColorSpec::new())?;
-    writeln!(&mut stderr, " building {} in {:.2}s", opts.input.file_name().unwrap(), seconds)?;
+    let name = db.compilation_unit().name(&db);
+    writeln!(&mut stderr, " building {} in {:.2}s", name, seconds)?;
 
     Ok(CompilationTermination::Compiled { lib_file })
 }
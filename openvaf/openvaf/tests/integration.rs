@@ -1,38 +1,59 @@
 use std::f64::consts;
+use std::ffi::CStr;
 use std::path::Path;
+use std::time::Instant;
 
-use camino::Utf8Path;
+use camino::{Utf8Path, Utf8PathBuf};
 use expect_test::expect_file;
 use float_cmp::assert_approx_eq;
 use llvm::OptLevel;
 use mini_harness::{harness, Result};
-use openvaf::{CompilationDestination, CompilationTermination};
+use openvaf::{
+    CompilationDestination, CompilationLimits, CompilationTermination, FastMathOpts, HostFunction,
+    LintLevel, MinTypMax, Type,
+};
 use stdx::{ignore_dev_tests, openvaf_test_data, project_root};
 use target::spec::Target;
 
-use crate::load::{load_osdi_lib, EvalFlags, OsdiDescriptor};
-use crate::mock_sim::{MockSimulation, ALPHA};
+use crate::load::{
+    file_contents, load_osdi_lib, osdi_str, EvalFlags, OsdiDescriptor, JACOBIAN_ENTRY_REACT_CONST,
+    JACOBIAN_ENTRY_RESIST_CONST,
+};
+use crate::mock_sim::{evaluate_point, MockSimulation, ALPHA};
 
 mod load;
 mod mock_sim;
 
-fn compile_and_load(root_file: &Utf8Path) -> &'static OsdiDescriptor {
-    let openvaf_opts = openvaf::Opts {
+fn openvaf_opts(root_file: &Utf8Path, lib_file: Utf8PathBuf) -> openvaf::Opts {
+    openvaf::Opts {
         defines: Vec::new(),
         codegen_opts: Vec::new(),
         lints: Vec::new(),
         input: root_file.to_path_buf(),
-        output: CompilationDestination::Path { lib_file: root_file.with_extension("osdi") },
+        output: CompilationDestination::Path { lib_file },
         include: Vec::new(),
         opt_lvl: OptLevel::Aggressive,
+        fast_math: FastMathOpts::default(),
         target: Target::host_target().unwrap(),
         target_cpu: "native".to_owned(),
         dry_run: false,
+        dump_hir: false,
         dump_mir: false, 
         dump_unopt_mir: false, 
         dump_ir: false, 
-    };
+        emit_llvm_ir: false,
+        print_stats: false,
+        print_timing: false,
+        gmin: false,
+        min_typ_max: MinTypMax::Typ,
+        clamp_sqrt: false,
+        limits: CompilationLimits::default(),
+        host_functions: Vec::new(),
+    }
+}
 
+fn compile_and_load(root_file: &Utf8Path) -> &'static OsdiDescriptor {
+    let openvaf_opts = openvaf_opts(root_file, root_file.with_extension("osdi"));
     let res = openvaf::compile(&openvaf_opts).unwrap();
     let lib_file = match res {
         CompilationTermination::Compiled { lib_file } => lib_file,
@@ -169,6 +190,53 @@ fn test_limit() -> Result<()> {
     Ok(())
 }
 
+/// The limited voltage is compared to the limited voltage of the previous iteration (stored in
+/// the instance state) so every step is limited until the solution is reached.
+fn test_limit_steps() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const KB: f64 = 1.3806488e-23;
+    const Q: f64 = 1.602176565e-19;
+    const VT: f64 = KB * 300.0 / Q;
+    const IS: f64 = 1e-12;
+    let vcrit = VT * f64::ln(VT / (consts::SQRT_2 * IS));
+    let vd = 4.0 * vcrit;
+
+    let desc = test_descriptor(&openvaf_test_data("osdi").join("diode_lim.va"))?;
+    let model = desc.new_model();
+    model.set_real_param(1, IS);
+    model.process_params()?;
+    let mut instance = model.new_instance();
+    let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+
+    // the derivative of the diode current is evaluated at the limited voltage
+    let mut eval = |sim: &mut MockSimulation, flags| {
+        instance.eval(&model, sim, flags);
+        instance.load_dae(&model, sim);
+        let (conductance, _) = sim.read_jacobian("A", "A");
+        VT * f64::ln(conductance * VT / IS)
+    };
+
+    let mut vd_lim = eval(&mut sim, EvalFlags::INIT_LIM | EvalFlags::ENABLE_LIM);
+    float_cmp::assert_approx_eq!(f64, vd_lim, vcrit, epsilon = 1e-9);
+    let mut iterations = 0;
+    while (vd_lim - vd).abs() > 1e-9 {
+        sim.next_iter();
+        sim.set_voltage("A", vd);
+        let vd_lim_ = eval(&mut sim, EvalFlags::ENABLE_LIM);
+        // the test implementation of pnjlim halves all steps larger than 2*VT
+        let max_step = f64::max((vd - vd_lim) / 2.0, 2.0 * VT);
+        assert!(vd_lim_ > vd_lim && vd_lim_ - vd_lim <= max_step + 1e-9, "{vd_lim} -> {vd_lim_}");
+        vd_lim = vd_lim_;
+        iterations += 1;
+        assert!(iterations < 32, "limiting did not converge");
+    }
+    assert!(iterations > 1);
+    Ok(())
+}
+
 macro_rules! assert_approx_eq {
     ($val: expr, $expect: expr) => {
         let resist = $val;
@@ -218,8 +286,1367 @@ fn test_noise() -> Result<()> {
     Ok(())
 }
 
+fn test_flicker_noise() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const R: f64 = 1.5;
+    const KF: f64 = 2.0;
+    const V: f64 = 3.0;
+
+    let root_file = openvaf_test_data("osdi").join("flicker_noise.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    // the default exponent is used when af is not given
+    for af in [None, Some(1.7), Some(0.0)] {
+        let model = desc.new_model();
+        model.set_real_param(desc.param_index("r").unwrap(), R);
+        model.set_real_param(desc.param_index("kf").unwrap(), KF);
+        if let Some(af) = af {
+            model.set_real_param(desc.param_index("af").unwrap(), af);
+        }
+        model.process_params()?;
+        let mut instance = model.new_instance();
+        let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+
+        sim.set_voltage("d", V);
+        instance.eval(&model, &mut sim, EvalFlags::empty());
+        for freq in [1.0, 10.0, 1e3] {
+            instance.load_noise(&model, &mut sim, freq);
+            let expect = KF * V / R / freq.powf(af.unwrap_or(1.0));
+            assert_approx_eq!(sim.read_noise(0), expect);
+        }
+    }
+    Ok(())
+}
+
+fn test_noise_correlation() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("noise_correlation.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let names: Vec<_> = desc.noise().iter().map(|src| unsafe { osdi_str(src.name) }).collect();
+    assert_eq!(names, ["drain", "flicker", "gate"]);
+
+    // the correlation refers to the noise sources by their index
+    let correlations = desc.noise_correlations();
+    assert_eq!(correlations.len(), 1);
+    assert_eq!((correlations[0].noise_1, correlations[0].noise_2), (0, 2));
+    assert_eq!(correlations[0].real, 0.0);
+    assert_eq!(correlations[0].imag, 0.5);
+    Ok(())
+}
+
+fn test_last_crossing() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("last_crossing.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    assert_eq!(desc.num_states, 5);
+    let model = desc.new_model();
+    model.process_params()?;
+    let mut instance = model.new_instance();
+    let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+
+    let step = |sim: &mut MockSimulation, abstime: f64, vin: f64, flags: EvalFlags| {
+        sim.next_iter();
+        sim.abstime = abstime;
+        sim.set_voltage("inp", vin);
+        instance.eval(&model, sim, flags);
+        instance.load_dae(&model, sim);
+        sim.read_residual("out").0
+    };
+
+    // no crossing during the operating point, not even if the input rises between the Newton
+    // iterations (the local assert_approx_eq! can not compare negative values)
+    for vin in [-1.0, 2.0, -1.0] {
+        let t_rise = step(&mut sim, 0.0, vin, EvalFlags::ANALYSIS_DC);
+        float_cmp::assert_approx_eq!(f64, t_rise, -1.0);
+    }
+    // rising edge between t=0 and t=1 (interpolated), every Newton iteration of a timepoint
+    // interpolates between the last accepted timepoint and the current solution
+    assert_approx_eq!(step(&mut sim, 1.0, 0.5, EvalFlags::ANALYSIS_TRAN), 2.0 / 3.0);
+    assert_approx_eq!(step(&mut sim, 1.0, 1.0, EvalFlags::ANALYSIS_TRAN), 0.5);
+    // falling edges are ignored
+    assert_approx_eq!(step(&mut sim, 2.0, -1.0, EvalFlags::ANALYSIS_TRAN), 0.5);
+    assert_approx_eq!(step(&mut sim, 3.0, 3.0, EvalFlags::ANALYSIS_TRAN), 2.25);
+    // the crossing of a rejected timestep is forgotten
+    assert_approx_eq!(step(&mut sim, 2.5, -0.5, EvalFlags::ANALYSIS_TRAN), 0.5);
+    assert_approx_eq!(step(&mut sim, 3.0, 2.0, EvalFlags::ANALYSIS_TRAN), 2.6);
+    assert_approx_eq!(step(&mut sim, 4.0, 3.0, EvalFlags::ANALYSIS_TRAN), 2.6);
+    Ok(())
+}
+
+fn test_cross_event() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("cross_event.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    assert_eq!(desc.num_states, 5);
+    let model = desc.new_model();
+    model.process_params()?;
+    let mut instance = model.new_instance();
+    let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+
+    let step = |sim: &mut MockSimulation, abstime: f64, vin: f64, flags: EvalFlags| {
+        sim.next_iter();
+        sim.abstime = abstime;
+        sim.set_voltage("inp", vin);
+        instance.eval(&model, sim, flags);
+        instance.load_dae(&model, sim);
+        sim.read_residual("out").0
+    };
+
+    // events are not triggered during the operating point
+    assert_approx_eq!(step(&mut sim, 0.0, -1.0, EvalFlags::ANALYSIS_DC), 0.5);
+    // the body is executed in every Newton iteration of the first timepoint after a rising edge
+    assert_approx_eq!(step(&mut sim, 1.0, 0.5, EvalFlags::ANALYSIS_TRAN), 2.0);
+    assert_approx_eq!(step(&mut sim, 1.0, 1.0, EvalFlags::ANALYSIS_TRAN), 2.0);
+    assert_approx_eq!(step(&mut sim, 1.0, 1.0, EvalFlags::ANALYSIS_TRAN), 2.0);
+    // but not at the following timepoints or for falling edges
+    assert_approx_eq!(step(&mut sim, 2.0, 2.0, EvalFlags::ANALYSIS_TRAN), 0.5);
+    assert_approx_eq!(step(&mut sim, 3.0, -1.0, EvalFlags::ANALYSIS_TRAN), 0.5);
+    assert_approx_eq!(step(&mut sim, 4.0, 3.0, EvalFlags::ANALYSIS_TRAN), 2.0);
+    // the timestep is rejected and retried with a smaller step that does not cross zero
+    assert_approx_eq!(step(&mut sim, 3.5, -0.5, EvalFlags::ANALYSIS_TRAN), 0.5);
+    assert_approx_eq!(step(&mut sim, 4.0, 2.0, EvalFlags::ANALYSIS_TRAN), 2.0);
+    assert_approx_eq!(step(&mut sim, 4.0, 1.0, EvalFlags::ANALYSIS_TRAN), 2.0);
+    assert_approx_eq!(step(&mut sim, 5.0, 2.0, EvalFlags::ANALYSIS_TRAN), 0.5);
+    Ok(())
+}
+
+fn test_event_or() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("event_or.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let model = desc.new_model();
+    model.process_params()?;
+    let mut instance = model.new_instance();
+    let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+
+    let step = |sim: &mut MockSimulation, abstime: f64, vin: f64, flags: EvalFlags| {
+        sim.next_iter();
+        sim.abstime = abstime;
+        sim.set_voltage("inp", vin);
+        instance.eval(&model, sim, flags);
+        instance.load_dae(&model, sim);
+        sim.read_residual("out").0
+    };
+
+    float_cmp::assert_approx_eq!(f64, step(&mut sim, 0.0, -1.0, EvalFlags::ANALYSIS_DC), 0.0);
+    // either event triggers the body
+    assert_approx_eq!(step(&mut sim, 1.0, 0.2, EvalFlags::ANALYSIS_TRAN), 1.0);
+    assert_approx_eq!(step(&mut sim, 2.0, 0.7, EvalFlags::ANALYSIS_TRAN), 1.0);
+    float_cmp::assert_approx_eq!(f64, step(&mut sim, 3.0, -1.0, EvalFlags::ANALYSIS_TRAN), 0.0);
+    // the body is only executed once if both events trigger in the same evaluation
+    assert_approx_eq!(step(&mut sim, 4.0, 1.0, EvalFlags::ANALYSIS_TRAN), 1.0);
+    Ok(())
+}
+
+fn test_event_initial_step() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("event_initial_step.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let model = desc.new_model();
+    model.process_params()?;
+    let mut instance = model.new_instance();
+    let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+
+    let step = |sim: &mut MockSimulation, abstime: f64, vin: f64, flags: EvalFlags| {
+        sim.next_iter();
+        sim.abstime = abstime;
+        sim.set_voltage("inp", vin);
+        instance.eval(&model, sim, flags);
+        instance.load_dae(&model, sim);
+        sim.read_residual("out").0
+    };
+
+    // initial_step fires while computing the operating point and the initial conditions
+    let op = EvalFlags::ANALYSIS_DC | EvalFlags::ANALYSIS_STATIC;
+    assert_approx_eq!(step(&mut sim, 0.0, -1.0, op), 2.0);
+    let ic = EvalFlags::ANALYSIS_TRAN | EvalFlags::ANALYSIS_IC;
+    assert_approx_eq!(step(&mut sim, 0.0, -1.0, ic), 2.0);
+    // but not during the following timepoints, where only the cross event triggers the body
+    assert_approx_eq!(step(&mut sim, 1.0, -0.5, EvalFlags::ANALYSIS_TRAN), 0.5);
+    assert_approx_eq!(step(&mut sim, 2.0, 1.0, EvalFlags::ANALYSIS_TRAN), 2.0);
+    assert_approx_eq!(step(&mut sim, 3.0, 2.0, EvalFlags::ANALYSIS_TRAN), 0.5);
+    Ok(())
+}
+
+fn test_past() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("past.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    assert_eq!(desc.num_states, 3);
+    let model = desc.new_model();
+    model.process_params()?;
+    let mut instance = model.new_instance();
+    let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+
+    let step = |sim: &mut MockSimulation, abstime: f64, vin: f64, flags: EvalFlags| {
+        sim.next_iter();
+        sim.abstime = abstime;
+        sim.set_voltage("inp", vin);
+        instance.eval(&model, sim, flags);
+        instance.load_dae(&model, sim);
+        sim.read_residual("out").0
+    };
+
+    // there is no previous timepoint during the operating point
+    assert_approx_eq!(step(&mut sim, 0.0, 1.0, EvalFlags::ANALYSIS_DC), 1.0);
+    // several Newton iterations per timepoint all see the value of the operating point
+    assert_approx_eq!(step(&mut sim, 1.0, 5.0, EvalFlags::ANALYSIS_TRAN), 1.0);
+    assert_approx_eq!(step(&mut sim, 1.0, 3.0, EvalFlags::ANALYSIS_TRAN), 1.0);
+    assert_approx_eq!(step(&mut sim, 1.0, 2.0, EvalFlags::ANALYSIS_TRAN), 1.0);
+    // only the converged value of the accepted timepoint is kept
+    assert_approx_eq!(step(&mut sim, 2.0, 7.0, EvalFlags::ANALYSIS_TRAN), 2.0);
+    assert_approx_eq!(step(&mut sim, 2.0, 4.0, EvalFlags::ANALYSIS_TRAN), 2.0);
+    // a rejected timestep (retried with a smaller step) does not become the past value
+    assert_approx_eq!(step(&mut sim, 1.5, 3.0, EvalFlags::ANALYSIS_TRAN), 2.0);
+    assert_approx_eq!(step(&mut sim, 1.5, 2.5, EvalFlags::ANALYSIS_TRAN), 2.0);
+    assert_approx_eq!(step(&mut sim, 2.0, 6.0, EvalFlags::ANALYSIS_TRAN), 2.5);
+    Ok(())
+}
+
+fn test_array() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("array.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let model = desc.new_model();
+    model.process_params()?;
+    let mut instance = model.new_instance();
+    let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+
+    let mut step = |x: f64| {
+        sim.next_iter();
+        sim.set_voltage("inp", x);
+        instance.eval(&model, &mut sim, EvalFlags::ANALYSIS_DC);
+        instance.load_dae(&model, &mut sim);
+        sim.read_residual("out").0
+    };
+
+    assert_approx_eq!(step(2.0), 17.0);
+    assert_approx_eq!(step(0.5), 2.75);
+    Ok(())
+}
+
+fn test_rdist() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("rdist.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let draw = |seed: i32| -> Result<[f64; 3]> {
+        let model = desc.new_model();
+        model.set_int_param(1, seed);
+        model.process_params()?;
+        let mut instance = model.new_instance();
+        let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+        sim.next_iter();
+        instance.eval(&model, &mut sim, EvalFlags::ANALYSIS_DC);
+        instance.load_dae(&model, &mut sim);
+        Ok(["u", "e", "p"].map(|node| sim.read_residual(node).0))
+    };
+
+    let [u, e, p] = draw(42)?;
+    assert!((1.0..=2.0).contains(&u), "uniform sample {u} out of range");
+    assert!(e >= 0.0, "exponential sample {e} is negative");
+    assert!(p >= 0.0 && p.fract() == 0.0, "poisson sample {p} is not a natural number");
+
+    // the same seed reproduces the samples while a different seed draws new ones
+    assert_eq!(draw(42)?, [u, e, p]);
+    let other = draw(1234)?;
+    assert_ne!(other[0], u);
+    assert_ne!(other[1], e);
+    Ok(())
+}
+
+/// The random number generators follow the reference implementation of the Verilog standard,
+/// so a seed always produces the same sequence of samples.
+fn test_random_sequence() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("random.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let model = desc.new_model();
+    model.process_params()?;
+    let mut instance = model.new_instance();
+    let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+    sim.next_iter();
+    instance.eval(&model, &mut sim, EvalFlags::ANALYSIS_DC);
+    instance.load_dae(&model, &mut sim);
+
+    let random = ["r1", "r2", "r3"].map(|node| sim.read_residual(node).0);
+    assert_eq!(random, [-2144582656.0, 646214477.0, 38602500.0]);
+    let normal = ["n1", "n2"].map(|node| sim.read_residual(node).0);
+    float_cmp::assert_approx_eq!(f64, normal[0], 1.0350871437396174, ulps = 4);
+    float_cmp::assert_approx_eq!(f64, normal[1], 1.303866325397284, ulps = 4);
+    Ok(())
+}
+
+fn test_bound_step() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("bound_step.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let bound_step = |s1: f64, s2: f64, voltage: f64| -> Result<Option<f64>> {
+        let model = desc.new_model();
+        model.set_real_param(1, s1);
+        model.set_real_param(2, s2);
+        model.process_params()?;
+        let mut instance = model.new_instance();
+        let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+        sim.next_iter();
+        sim.set_voltage("a", voltage);
+        instance.eval(&model, &mut sim, EvalFlags::ANALYSIS_TRAN);
+        Ok(instance.bound_step())
+    };
+
+    assert_eq!(bound_step(2e-9, 1e-9, 0.0)?, Some(2e-9));
+    // the smallest requested step is reported regardless of the call order
+    assert_eq!(bound_step(2e-9, 1e-9, 1.0)?, Some(1e-9));
+    assert_eq!(bound_step(2e-9, 5e-9, 1.0)?, Some(2e-9));
+    Ok(())
+}
+
+fn test_realtime() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const UNIT: f64 = 1e-6;
+    let root_file = openvaf_test_data("osdi").join("realtime.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let model = desc.new_model();
+    model.set_real_param(1, UNIT);
+    model.process_params()?;
+    let mut instance = model.new_instance();
+    let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+    let mut charges = |abstime: f64| {
+        sim.next_iter();
+        sim.abstime = abstime;
+        instance.eval(&model, &mut sim, EvalFlags::ANALYSIS_TRAN);
+        instance.load_dae(&model, &mut sim);
+        (sim.read_residual("a").1, sim.read_residual("b").1)
+    };
+
+    let (a0, b0) = charges(1e-3);
+    let (a1, b1) = charges(2e-3);
+    // the derivative of $realtime with respect to time is the scale factor 1/unit
+    float_cmp::assert_approx_eq!(f64, (a1 - a0) / 1e-3, 1.0 / UNIT, epsilon = 1e-3);
+    float_cmp::assert_approx_eq!(f64, (b1 - b0) / 1e-3, 1.0, epsilon = 1e-9);
+    float_cmp::assert_approx_eq!(f64, a0, 1e-3 / UNIT, epsilon = 1e-9);
+    Ok(())
+}
+
+fn test_inf_bounds() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("inf_bounds.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let is_valid = |x: f64, y: f64| {
+        let model = desc.new_model();
+        model.set_real_param(1, x);
+        model.set_real_param(2, y);
+        model.process_params().is_ok()
+    };
+
+    assert!(is_valid(1.0, 0.0));
+    assert!(!is_valid(0.0, 0.0));
+    assert!(!is_valid(-1.0, 0.0));
+    assert!(!is_valid(1.0, 2.0));
+    // an infinite bound does not restrict the parameter at all
+    assert!(is_valid(f64::MAX, 1.0));
+    assert!(is_valid(f64::INFINITY, f64::NEG_INFINITY));
+    Ok(())
+}
+
+fn test_mixed_bounds() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("mixed_bounds.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let is_valid = |x: f64, y: f64, n: i32| {
+        let model = desc.new_model();
+        model.set_real_param(desc.param_index("x").unwrap(), x);
+        model.set_real_param(desc.param_index("y").unwrap(), y);
+        model.set_int_param(desc.param_index("n").unwrap(), n);
+        model.process_params().is_ok()
+    };
+
+    assert!(is_valid(0.5, 0.5, 1));
+    // `from (0:1]` excludes the lower end but includes the upper end
+    assert!(!is_valid(0.0, 0.5, 1));
+    assert!(is_valid(1.0, 0.5, 1));
+    assert!(!is_valid(1.5, 0.5, 1));
+    // `from [0:1)` includes the lower end but excludes the upper end
+    assert!(is_valid(0.5, 0.0, 1));
+    assert!(!is_valid(0.5, 1.0, 1));
+    assert!(!is_valid(0.5, -0.5, 1));
+    assert!(!is_valid(0.5, 0.5, 0));
+    assert!(is_valid(0.5, 0.5, 3));
+    assert!(!is_valid(0.5, 0.5, 4));
+    Ok(())
+}
+
+fn test_point_eval() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const IS: f64 = 2e-14;
+    const CJ: f64 = 1e-12;
+    const V: f64 = 0.5;
+    let root_file = openvaf_test_data("osdi").join("point_eval.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let sim = evaluate_point(desc, &[("is", IS), ("cj", CJ)], &[("a", V)])?;
+
+    let current = IS * ((V / 0.025).exp() - 1.0);
+    let conductance = IS * (V / 0.025).exp() / 0.025;
+    let (resist, react) = sim.read_residual("a");
+    float_cmp::assert_approx_eq!(f64, resist, current, ulps = 16);
+    float_cmp::assert_approx_eq!(f64, react, CJ * V * V, ulps = 16);
+    float_cmp::assert_approx_eq!(f64, sim.read_residual("c").0, -current, ulps = 16);
+    let (resist, react) = sim.read_jacobian("a", "c");
+    float_cmp::assert_approx_eq!(f64, resist, -conductance, ulps = 16);
+    float_cmp::assert_approx_eq!(f64, react, -2.0 * CJ * V, ulps = 16);
+
+    // one line for each node and each (non ground) Jacobian entry
+    let dump = sim.to_string();
+    assert_eq!(dump.lines().count(), 2 + 4, "{dump}");
+    assert!(dump.contains("jacobian (a, c): "), "{dump}");
+    Ok(())
+}
+
+fn test_ground() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const G: f64 = 1e-3;
+    const V: f64 = 0.7;
+    let root_file = openvaf_test_data("osdi").join("ground.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    // the ground net is the reference node and not an unknown of the system
+    assert_eq!(desc.num_nodes, 1);
+    assert_eq!(desc.num_jacobian_entries, 1);
+
+    let sim = evaluate_point(desc, &[("g", G)], &[("a", V)])?;
+    float_cmp::assert_approx_eq!(f64, sim.read_residual("a").0, 2.0 * G * V, ulps = 4);
+    float_cmp::assert_approx_eq!(f64, sim.read_jacobian("a", "a").0, 2.0 * G, ulps = 4);
+    Ok(())
+}
+
+fn test_safe_log() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("safe_log.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+
+    // only the taken arm of the conditional is evaluated (and differentiated)
+    let sim = evaluate_point(desc, &[], &[("a", -1.0)])?;
+    assert_eq!(sim.read_residual("a").0, 0.0);
+    assert_eq!(sim.read_jacobian("a", "a").0, 0.0);
+
+    let sim = evaluate_point(desc, &[], &[("a", consts::E)])?;
+    float_cmp::assert_approx_eq!(f64, sim.read_residual("a").0, 1.0, ulps = 4);
+    float_cmp::assert_approx_eq!(f64, sim.read_jacobian("a", "a").0, 1.0 / consts::E, ulps = 4);
+    Ok(())
+}
+
+fn test_clamp_sqrt() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file: Utf8PathBuf =
+        openvaf_test_data("osdi").join("clamp_sqrt.va").try_into().unwrap();
+    let compile = |clamp_sqrt: bool| -> Result<Utf8PathBuf> {
+        let lib_file = root_file.with_file_name(format!("clamp_sqrt_{clamp_sqrt}.osdi"));
+        let opts = openvaf::Opts { clamp_sqrt, ..openvaf_opts(&root_file, lib_file) };
+        match openvaf::compile(&opts)? {
+            CompilationTermination::Compiled { lib_file } => Ok(lib_file),
+            CompilationTermination::FatalDiagnostic => {
+                panic!("openvaf: compilation of {root_file} failed");
+            }
+        }
+    };
+
+    // by default sqrt follows IEEE semantics
+    let libs = unsafe { load_osdi_lib(&compile(false)?)? };
+    let sim = evaluate_point(&libs[0], &[], &[("a", -1.0)])?;
+    assert!(sim.read_residual("a").0.is_nan());
+    let sim = evaluate_point(&libs[0], &[], &[("a", 0.0)])?;
+    assert_eq!(sim.read_jacobian("a", "a").0, f64::INFINITY);
+
+    // clamped: value and derivative are zero at and below zero
+    let libs = unsafe { load_osdi_lib(&compile(true)?)? };
+    let desc = &libs[0];
+    for v in [-1.0, 0.0] {
+        let sim = evaluate_point(desc, &[], &[("a", v)])?;
+        assert_eq!(sim.read_residual("a").0, 0.0);
+        assert_eq!(sim.read_jacobian("a", "a").0, 0.0);
+    }
+    let sim = evaluate_point(desc, &[], &[("a", 4.0)])?;
+    float_cmp::assert_approx_eq!(f64, sim.read_residual("a").0, 2.0, ulps = 4);
+    float_cmp::assert_approx_eq!(f64, sim.read_jacobian("a", "a").0, 0.25, ulps = 4);
+    Ok(())
+}
+
+fn test_conversion() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("conversion.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let model = desc.new_model();
+    model.process_params()?;
+    let mut instance = model.new_instance();
+    let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+
+    // $rtoi truncates towards zero instead of rounding like an implicit conversion
+    for (vin, trunc, half) in [(3.7, 3.0, 1.5), (-3.7, -3.0, -1.5), (0.5, 0.0, 0.0)] {
+        sim.next_iter();
+        sim.set_voltage("inp", vin);
+        instance.eval(&model, &mut sim, EvalFlags::ANALYSIS_DC);
+        instance.load_dae(&model, &mut sim);
+        float_cmp::assert_approx_eq!(f64, sim.read_residual("trunc").0, trunc);
+        float_cmp::assert_approx_eq!(f64, sim.read_residual("half").0, half);
+    }
+    Ok(())
+}
+
+fn test_int_div() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("int_div.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let eval = |a: i32, b: i32| -> Result<(f64, f64)> {
+        let model = desc.new_model();
+        model.set_int_param(desc.param_index("a").unwrap(), a);
+        model.set_int_param(desc.param_index("b").unwrap(), b);
+        model.process_params()?;
+        let mut instance = model.new_instance();
+        let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+        sim.next_iter();
+        instance.eval(&model, &mut sim, EvalFlags::ANALYSIS_DC);
+        instance.load_dae(&model, &mut sim);
+        Ok((sim.read_residual("q").0, sim.read_residual("r").0))
+    };
+
+    // the parameters are not known at compile time so this tests the generated code
+    assert_eq!(eval(7, 2)?, (3.0, 1.0));
+    assert_eq!(eval(-7, 2)?, (-3.0, -1.0));
+    assert_eq!(eval(7, -2)?, (-3.0, 1.0));
+    assert_eq!(eval(-7, -2)?, (3.0, -1.0));
+    Ok(())
+}
+
+fn test_param_array() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const RS: f64 = 500.0;
+    let root_file = openvaf_test_data("osdi").join("param_array.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    // array parameters are constants and therefore not visible to the simulator
+    assert!(desc.param_index("r").is_none());
+
+    let sim = evaluate_point(desc, &[("rs", RS)], &[("a", 1.0), ("b", 3.0)])?;
+    float_cmp::assert_approx_eq!(f64, sim.read_residual("a").0, 1.0 / RS, ulps = 4);
+    float_cmp::assert_approx_eq!(f64, sim.read_residual("b").0, 2.0 * 3.0 / (2.0 * RS), ulps = 4);
+    float_cmp::assert_approx_eq!(f64, sim.read_jacobian("a", "a").0, 1.0 / RS, ulps = 4);
+    float_cmp::assert_approx_eq!(f64, sim.read_jacobian("b", "b").0, 1.0 / RS, ulps = 4);
+    Ok(())
+}
+
+fn test_model_evaluate() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const R: f64 = 2e3;
+    const V: f64 = 1.5;
+    let root_file = project_root().join("integration_tests/RESISTOR/resistor.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let model = desc.new_model();
+    model.set_real_param(desc.param_index("R").unwrap(), R);
+    model.process_params()?;
+
+    let eval = model.evaluate(&[("A", V)])?;
+    // nodes (A, B) and Jacobian entries (A, A), (A, B), (B, A), (B, B)
+    assert_eq!(eval.residual().len(), 2);
+    float_cmp::assert_approx_eq!(f64, eval.residual()[0], V / R, ulps = 4);
+    float_cmp::assert_approx_eq!(f64, eval.residual()[1], -V / R, ulps = 4);
+    assert_eq!(eval.jacobian().len(), 4);
+    for (&val, sign) in eval.jacobian().iter().zip([1.0, -1.0, -1.0, 1.0]) {
+        float_cmp::assert_approx_eq!(f64, val, sign / R, ulps = 4);
+    }
+    assert!(model.evaluate(&[("C", V)]).is_err());
+    Ok(())
+}
+
+fn test_rc_stages() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const R: f64 = 2e3;
+    const C: f64 = 3e-12;
+    const V: f64 = 0.3;
+    let root_file = openvaf_test_data("osdi").join("rc_stages.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let sim = evaluate_point(desc, &[("r", R), ("cap", C)], &[("a", V)])?;
+
+    // stage i contributes a conductance of 1/(R*2^i) and a capacitance of C/2^i
+    let scale = 1.0 + 0.5 + 0.25;
+    let (resist, react) = sim.read_residual("a");
+    float_cmp::assert_approx_eq!(f64, resist, scale * V / R, ulps = 16);
+    float_cmp::assert_approx_eq!(f64, react, scale * C * V, ulps = 16);
+    let (resist, react) = sim.read_jacobian("a", "a");
+    float_cmp::assert_approx_eq!(f64, resist, scale / R, ulps = 16);
+    float_cmp::assert_approx_eq!(f64, react, scale * C, ulps = 16);
+    Ok(())
+}
+
+fn test_ddt_expr() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const CAP: f64 = 3e-12;
+    const CNL: f64 = 2e-12;
+    const V: f64 = 0.7;
+    let root_file = openvaf_test_data("osdi").join("ddt_expr.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let sim = evaluate_point(desc, &[("cap", CAP), ("cnl", CNL)], &[("a", V)])?;
+
+    // the arguments of ddt are integrated as a whole and are purely reactive
+    let (resist, react) = sim.read_residual("a");
+    assert_eq!(resist, 0.0);
+    float_cmp::assert_approx_eq!(f64, react, CAP * V + CNL * V * V, ulps = 16);
+    let (resist, react) = sim.read_jacobian("a", "a");
+    assert_eq!(resist, 0.0);
+    float_cmp::assert_approx_eq!(f64, react, CAP + 2.0 * CNL * V, ulps = 16);
+    Ok(())
+}
+
+fn test_string_mode() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const G: f64 = 2e-3;
+    const V: f64 = 0.5;
+    let root_file = openvaf_test_data("osdi").join("string_mode.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let current = |mode: Option<&'static [u8]>| -> Result<f64> {
+        let model = desc.new_model();
+        if let Some(mode) = mode {
+            let mode = CStr::from_bytes_with_nul(mode).unwrap();
+            model.set_str_param(desc.param_index("mode").unwrap(), mode);
+        }
+        model.set_real_param(desc.param_index("g").unwrap(), G);
+        model.process_params()?;
+        let mut instance = model.new_instance();
+        let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+        sim.next_iter();
+        sim.set_voltage("a", V);
+        instance.eval(&model, &mut sim, EvalFlags::ANALYSIS_DC);
+        instance.load_dae(&model, &mut sim);
+        Ok(sim.read_residual("a").0)
+    };
+
+    float_cmp::assert_approx_eq!(f64, current(None)?, G * V, ulps = 4);
+    float_cmp::assert_approx_eq!(f64, current(Some(b"linear\0"))?, G * V, ulps = 4);
+    float_cmp::assert_approx_eq!(f64, current(Some(b"quadratic\0"))?, G * V * V, ulps = 4);
+    assert_eq!(current(Some(b"off\0"))?, 0.0);
+    Ok(())
+}
+
+fn test_localparam() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const R: f64 = 4.0;
+    const V: f64 = 2.0;
+    let root_file = openvaf_test_data("osdi").join("localparam.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    // only $mfactor and r can be set by the simulator
+    assert_eq!(desc.num_params, 2);
+    let model = desc.new_model();
+    model.set_real_param(1, R);
+    model.process_params()?;
+    let mut instance = model.new_instance();
+    let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+
+    sim.set_voltage("a", V);
+    instance.eval(&model, &mut sim, EvalFlags::ANALYSIS_DC);
+    instance.load_dae(&model, &mut sim);
+    assert_approx_eq!(sim.read_residual("a").0, V / R);
+    Ok(())
+}
+
+fn test_port_flow() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("port_flow.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    assert_eq!(desc.num_states, 0);
+    let model = desc.new_model();
+    model.process_params()?;
+    let mut instance = model.new_instance();
+    let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+
+    // I(<a>) is an unknown of the system (with an equation that equates it to the current
+    // flowing into a), solve for it with newton iterations while V(a) = 2V is held fixed
+    let mut flow = 0.0;
+    for _ in 0..2 {
+        sim.next_iter();
+        sim.set_voltage("a", 2.0);
+        sim.set_voltage("flow(<a>)", flow);
+        instance.eval(&model, &mut sim, EvalFlags::ANALYSIS_DC);
+        instance.load_dae(&model, &mut sim);
+        let (residual, _) = sim.read_residual("flow(<a>)");
+        let (ddx, _) = sim.read_jacobian("flow(<a>)", "flow(<a>)");
+        float_cmp::assert_approx_eq!(f64, ddx, -1.0);
+        flow -= residual / ddx;
+    }
+
+    // the port flow is linear in the unknowns so the first iteration already converged
+    assert_approx_eq!(flow, 2e-3);
+    float_cmp::assert_approx_eq!(f64, sim.read_residual("flow(<a>)").0, 0.0, epsilon = 1e-15);
+    assert_approx_eq!(sim.read_residual("sense").0, 2e-3);
+    Ok(())
+}
+
+fn test_analysis() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const C0: f64 = 1e-12;
+    let root_file = openvaf_test_data("osdi").join("analysis.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let model = desc.new_model();
+    model.process_params()?;
+    let mut instance = model.new_instance();
+    let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+
+    for (flags, cap) in [
+        (EvalFlags::ANALYSIS_DC, 0.0),
+        (EvalFlags::ANALYSIS_STATIC, 0.0),
+        (EvalFlags::ANALYSIS_AC, C0),
+        (EvalFlags::ANALYSIS_TRAN, C0),
+    ] {
+        sim.next_iter();
+        sim.set_voltage("a", 1.0);
+        instance.eval(&model, &mut sim, flags);
+        instance.load_dae(&model, &mut sim);
+        let (resist, react) = sim.read_jacobian("a", "a");
+        float_cmp::assert_approx_eq!(f64, resist, 0.0, ulps = 16);
+        float_cmp::assert_approx_eq!(f64, react, cap, ulps = 16);
+        let (resist, react) = sim.read_residual("a");
+        float_cmp::assert_approx_eq!(f64, resist, 0.0, ulps = 16);
+        float_cmp::assert_approx_eq!(f64, react, cap, ulps = 16);
+    }
+    Ok(())
+}
+
+fn test_linear_hint() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("linear_hint.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let node = |name: &str| {
+        desc.nodes().iter().position(|node| unsafe { osdi_str(node.name) } == name).unwrap() as u32
+    };
+    let flags = |hi: &str, lo: &str| {
+        let (hi, lo) = (node(hi), node(lo));
+        let entry = desc
+            .matrix_entries()
+            .iter()
+            .find(|entry| entry.nodes.node_1 == hi && entry.nodes.node_2 == lo);
+        entry.unwrap().flags
+    };
+
+    let is_const = JACOBIAN_ENTRY_RESIST_CONST | JACOBIAN_ENTRY_REACT_CONST;
+    assert_eq!(flags("a", "a") & is_const, is_const);
+    assert_eq!(flags("a", "b") & is_const, is_const);
+    // b also receives the (nonlinear) diode current
+    assert_eq!(flags("b", "b") & JACOBIAN_ENTRY_RESIST_CONST, 0);
+    assert_eq!(flags("b", "c") & JACOBIAN_ENTRY_RESIST_CONST, 0);
+    Ok(())
+}
+
+fn test_symbol_names() -> Result<()> {
+    // only the OSDI_* symbols are exported from DLLs
+    if cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file: Utf8PathBuf =
+        openvaf_test_data("osdi").join("escaped_name.va").try_into().unwrap();
+    let desc = compile_and_load(&root_file);
+    assert_eq!(unsafe { osdi_str(desc.name) }, "\\weird+model");
+    let lib = unsafe { libloading::Library::new(root_file.with_extension("osdi"))? };
+    for fun in ["access", "setup_model", "setup_instance", "eval"] {
+        let sym = format!("{fun}__u5c_weird_u2b_model\0");
+        unsafe { lib.get::<*const libc::c_void>(sym.as_bytes())? };
+    }
+    Ok(())
+}
+
+fn test_compile_all() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let test_dir = openvaf_test_data("osdi");
+    let opts: Vec<_> = ["last_crossing", "linear_hint"]
+        .iter()
+        .map(|name| {
+            let root_file: Utf8PathBuf = test_dir.join(format!("{name}.va")).try_into().unwrap();
+            let lib_file = root_file.with_file_name(format!("{name}_parallel.osdi"));
+            openvaf_opts(&root_file, lib_file)
+        })
+        .collect();
+
+    for (opts, res) in opts.iter().zip(openvaf::compile_all(&opts)) {
+        let lib_file = match res? {
+            CompilationTermination::Compiled { lib_file } => lib_file,
+            CompilationTermination::FatalDiagnostic => {
+                panic!("openvaf: compilation of {} failed", opts.input);
+            }
+        };
+        let libs = unsafe { load_osdi_lib(&lib_file)? };
+        assert_eq!(libs.len(), 1);
+    }
+    Ok(())
+}
+
+/// Compares the evaluation throughput of the diode model at different optimization levels
+/// (run with `--nocapture` to see the results).
+fn test_opt_levels() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const ITERATIONS: u32 = 100_000;
+    let root_file: Utf8PathBuf = openvaf_test_data("osdi").join("diode_lim.va").try_into().unwrap();
+    let mut reference = None;
+    for (name, opt_lvl) in
+        [("O0", OptLevel::None), ("Og", OptLevel::DebugFast), ("O2", OptLevel::Default)]
+    {
+        let lib_file = root_file.with_file_name(format!("diode_lim_{name}.osdi"));
+        let opts = openvaf::Opts { opt_lvl, ..openvaf_opts(&root_file, lib_file) };
+        let lib_file = match openvaf::compile(&opts)? {
+            CompilationTermination::Compiled { lib_file } => lib_file,
+            CompilationTermination::FatalDiagnostic => {
+                panic!("openvaf: compilation of {root_file} failed");
+            }
+        };
+        let libs = unsafe { load_osdi_lib(&lib_file)? };
+        let desc = &libs[0];
+        let model = desc.new_model();
+        model.process_params()?;
+        let mut instance = model.new_instance();
+        let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+        sim.set_voltage("A", 0.5);
+
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            instance.eval(&model, &mut sim, EvalFlags::empty());
+        }
+        let elapsed = start.elapsed().as_secs_f64();
+        println!("-{name}: {:.0} evaluations/s", f64::from(ITERATIONS) / elapsed);
+
+        // all optimization levels must produce the same results
+        instance.load_dae(&model, &mut sim);
+        let (resist, react) = sim.read_residual("A");
+        if let Some((resist_ref, react_ref)) = reference {
+            assert_approx_eq!(resist, resist_ref);
+            assert_approx_eq!(react, react_ref);
+        } else {
+            reference = Some((resist, react));
+        }
+    }
+    Ok(())
+}
+
+fn test_multiple_modules() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file: Utf8PathBuf =
+        openvaf_test_data("osdi").join("multiple_modules.va").try_into().unwrap();
+    let opts = openvaf_opts(&root_file, root_file.with_extension("osdi"));
+    let lib_file = match openvaf::compile(&opts)? {
+        CompilationTermination::Compiled { lib_file } => lib_file,
+        CompilationTermination::FatalDiagnostic => {
+            panic!("openvaf: compilation of {root_file} failed");
+        }
+    };
+    let libs = unsafe { load_osdi_lib(&lib_file)? };
+    let modules: Vec<_> = libs
+        .iter()
+        .map(|desc| {
+            let terminals = &desc.nodes()[..desc.num_terminals as usize];
+            let terminals: Vec<_> =
+                terminals.iter().map(|node| unsafe { osdi_str(node.name) }).collect();
+            (unsafe { osdi_str(desc.name) }, terminals)
+        })
+        .collect();
+    assert_eq!(modules, [("heated_res", vec!["a", "b", "t"]), ("heat_sink", vec!["t", "sense"])]);
+    for desc in libs {
+        let model = desc.new_model();
+        model.process_params()?;
+        let mut instance = model.new_instance();
+        instance.process_params(&model, desc.num_terminals, 300.0)?;
+    }
+    Ok(())
+}
+
+fn test_compile_str() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const SRC: &str = r#"
+        `include "disciplines.vams"
+        module str_res(inout electrical a, inout electrical b);
+            parameter real r = 2.0;
+            analog I(a, b) <+ V(a, b) / r;
+        endmodule
+    "#;
+
+    // the input file does not exist, only its name is used for diagnostics
+    let root_file: Utf8PathBuf =
+        openvaf_test_data("osdi").join("compile_str.va").try_into().unwrap();
+    let opts = openvaf_opts(&root_file, root_file.with_extension("osdi"));
+    let lib_file = match openvaf::compile_str(SRC, &opts)? {
+        CompilationTermination::Compiled { lib_file } => lib_file,
+        CompilationTermination::FatalDiagnostic => {
+            panic!("openvaf: compilation of {root_file} failed");
+        }
+    };
+    let libs = unsafe { load_osdi_lib(&lib_file)? };
+    assert_eq!(libs.len(), 1);
+    let desc = &libs[0];
+    assert_eq!(unsafe { osdi_str(desc.name) }, "str_res");
+
+    let model = desc.new_model();
+    model.process_params()?;
+    let mut instance = model.new_instance();
+    let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+    sim.next_iter();
+    sim.set_voltage("a", 4.0);
+    instance.eval(&model, &mut sim, EvalFlags::ANALYSIS_DC);
+    instance.load_dae(&model, &mut sim);
+    assert_approx_eq!(sim.read_residual("a").0, 2.0);
+
+    // invalid sources produce diagnostics just like invalid files
+    let res = openvaf::compile_str("module broken(", &opts)?;
+    assert!(matches!(res, CompilationTermination::FatalDiagnostic));
+    Ok(())
+}
+
+fn test_implicit_std_includes() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    // neither disciplines.vams nor constants.vams are included
+    const SRC: &str = r#"
+        module std_res(inout electrical a, inout electrical b);
+            analog I(a, b) <+ V(a, b) * `P_Q / 1e-19;
+        endmodule
+    "#;
+
+    let root_file: Utf8PathBuf =
+        openvaf_test_data("osdi").join("implicit_std_includes.va").try_into().unwrap();
+    let opts = openvaf_opts(&root_file, root_file.with_extension("osdi"));
+    let lib_file = match openvaf::compile_str(SRC, &opts)? {
+        CompilationTermination::Compiled { lib_file } => lib_file,
+        CompilationTermination::FatalDiagnostic => {
+            panic!("openvaf: compilation of {root_file} failed");
+        }
+    };
+    let libs = unsafe { load_osdi_lib(&lib_file)? };
+    let desc = &libs[0];
+    let model = desc.new_model();
+    model.process_params()?;
+    let mut instance = model.new_instance();
+    let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+    sim.next_iter();
+    sim.set_voltage("a", 4.0);
+    instance.eval(&model, &mut sim, EvalFlags::ANALYSIS_DC);
+    instance.load_dae(&model, &mut sim);
+    float_cmp::assert_approx_eq!(
+        f64,
+        sim.read_residual("a").0,
+        4.0 * 1.602176462e-19 / 1e-19,
+        ulps = 16
+    );
+    Ok(())
+}
+
+fn test_emit_llvm_ir() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const SRC: &str = r#"
+        `include "disciplines.vams"
+        module ir_res(inout electrical a, inout electrical b);
+            parameter real r = 1.0;
+            analog I(a, b) <+ V(a, b) / r;
+        endmodule
+    "#;
+
+    let root_file: Utf8PathBuf =
+        openvaf_test_data("osdi").join("emit_llvm_ir.va").try_into().unwrap();
+    let opts = openvaf::Opts {
+        emit_llvm_ir: true,
+        ..openvaf_opts(&root_file, root_file.with_extension("osdi"))
+    };
+    let res = openvaf::compile_str(SRC, &opts)?;
+    assert!(matches!(res, CompilationTermination::Compiled { .. }));
+
+    // the IR of the eval codegen unit is written before and after optimization
+    for extension in ["eval_ir__res.ll", "eval_ir__res.opt.ll"] {
+        let ir_file = root_file.with_extension(extension);
+        let ir = std::fs::read_to_string(&ir_file)?;
+        assert!(
+            ir.lines().any(|line| line.starts_with("define") && line.contains("@eval_ir__res(")),
+            "{ir_file} does not define the eval function:\n{ir}"
+        );
+    }
+
+    for entry in std::fs::read_dir(root_file.parent().unwrap())? {
+        let path = entry?.path();
+        let name = path.file_name().unwrap().to_string_lossy();
+        if name.starts_with("emit_llvm_ir.") && name.ends_with(".ll") {
+            std::fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+fn test_fast_math() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const SRC: &str = r#"
+        `include "disciplines.vams"
+        module fast_res(inout electrical a, inout electrical b);
+            parameter real r = 1.0;
+            analog I(a, b) <+ V(a, b) / r;
+        endmodule
+    "#;
+
+    // derivatives always use `reassoc arcp contract`, the flags of the division in the model
+    // itself depend on the options
+    let configs = [
+        ("strict", FastMathOpts::default(), "fdiv double"),
+        (
+            "relaxed",
+            FastMathOpts { relaxed: true, contract: false },
+            "fdiv reassoc nnan ninf nsz arcp afn double",
+        ),
+        ("contract", FastMathOpts { relaxed: false, contract: true }, "fdiv contract double"),
+        ("fast", FastMathOpts { relaxed: true, contract: true }, "fdiv fast double"),
+    ];
+    for (name, fast_math, expected) in configs {
+        let root_file: Utf8PathBuf =
+            openvaf_test_data("osdi").join(format!("fast_math_{name}.va")).try_into().unwrap();
+        let opts = openvaf::Opts {
+            emit_llvm_ir: true,
+            fast_math,
+            ..openvaf_opts(&root_file, root_file.with_extension("osdi"))
+        };
+        let res = openvaf::compile_str(SRC, &opts)?;
+        assert!(matches!(res, CompilationTermination::Compiled { .. }));
+
+        let ir_file = root_file.with_extension("eval_fast__res.ll");
+        let ir = std::fs::read_to_string(&ir_file)?;
+        assert!(
+            ir.lines().any(|line| line.contains(&format!("= {expected} "))),
+            "{ir_file} does not contain `{expected}`:\n{ir}"
+        );
+        if !fast_math.relaxed {
+            assert!(!ir.contains("nnan"), "{ir_file} contains relaxed fast-math flags:\n{ir}");
+        }
+
+        for entry in std::fs::read_dir(root_file.parent().unwrap())? {
+            let path = entry?.path();
+            let file_name = path.file_name().unwrap().to_string_lossy();
+            if file_name.starts_with(&format!("fast_math_{name}.")) {
+                std::fs::remove_file(&path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn test_deny_lint() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const SRC: &str = r#"
+        `include "disciplines.vams"
+        module quantizer(inout electrical a, inout electrical b);
+            analog I(a, b) <+ ddx(floor(V(a, b)), V(a));
+        endmodule
+    "#;
+
+    let root_file: Utf8PathBuf = openvaf_test_data("osdi").join("deny_lint.va").try_into().unwrap();
+    let mut opts = openvaf_opts(&root_file, root_file.with_extension("osdi"));
+    // rounding_derivative only warns by default
+    let res = openvaf::compile_str(SRC, &opts)?;
+    assert!(matches!(res, CompilationTermination::Compiled { .. }));
+
+    opts.lints = vec![("rounding_derivative".to_owned(), LintLevel::Deny)];
+    let res = openvaf::compile_str(SRC, &opts)?;
+    assert!(matches!(res, CompilationTermination::FatalDiagnostic));
+
+    opts.lints = vec![("rounding_derivative".to_owned(), LintLevel::Allow)];
+    let res = openvaf::compile_str(SRC, &opts)?;
+    assert!(matches!(res, CompilationTermination::Compiled { .. }));
+    Ok(())
+}
+
+fn test_param_attrs() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    const SRC: &str = r#"
+        `include "disciplines.vams"
+        module attr_res(inout electrical a, inout electrical b);
+            (* units="m", desc="channel length", foo="bar" *) parameter real l = 1e-6;
+            parameter real w = 1e-6;
+            analog I(a, b) <+ V(a, b) * w / l;
+        endmodule
+    "#;
+
+    let root_file: Utf8PathBuf =
+        openvaf_test_data("osdi").join("param_attrs.va").try_into().unwrap();
+    let opts = openvaf_opts(&root_file, root_file.with_extension("osdi"));
+    let lib_file = match openvaf::compile_str(SRC, &opts)? {
+        CompilationTermination::Compiled { lib_file } => lib_file,
+        CompilationTermination::FatalDiagnostic => {
+            panic!("openvaf: compilation of {root_file} failed");
+        }
+    };
+    let libs = unsafe { load_osdi_lib(&lib_file)? };
+    let desc = &libs[0];
+
+    // unknown attributes (foo) are ignored
+    let l = &desc.params()[desc.param_index("l").unwrap() as usize];
+    assert_eq!(unsafe { osdi_str(l.units) }, "m");
+    assert_eq!(unsafe { osdi_str(l.description) }, "channel length");
+
+    // parameters without attributes have empty units and descriptions
+    let w = &desc.params()[desc.param_index("w").unwrap() as usize];
+    assert_eq!(unsafe { osdi_str(w.units) }, "");
+    assert_eq!(unsafe { osdi_str(w.description) }, "");
+    Ok(())
+}
+
+fn test_host_function() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    extern "C" fn scale(x: f64, n: i32) -> f64 {
+        x * n as f64
+    }
+
+    let root_file: Utf8PathBuf =
+        openvaf_test_data("osdi").join("host_function.va").try_into().unwrap();
+    let scale_fun = HostFunction {
+        name: "scale".to_owned(),
+        args: Box::new([Type::Real, Type::Integer]),
+        return_ty: Type::Real,
+    };
+    let opts = openvaf::Opts {
+        host_functions: vec![scale_fun.clone()],
+        ..openvaf_opts(&root_file, root_file.with_extension("osdi"))
+    };
+    let lib_file = match openvaf::compile(&opts)? {
+        CompilationTermination::Compiled { lib_file } => lib_file,
+        CompilationTermination::FatalDiagnostic => {
+            panic!("openvaf: compilation of {root_file} failed");
+        }
+    };
+    let libs = unsafe { load_osdi_lib(&lib_file)? };
+    // the simulator provides the implementation after loading the library
+    unsafe {
+        let lib = libloading::Library::new(&lib_file)?;
+        let ptr = lib.get::<*mut extern "C" fn(f64, i32) -> f64>(b"osdi_host_scale\0")?;
+        ptr.write(scale);
+    }
+    let sim = evaluate_point(&libs[0], &[], &[("a", 0.5)])?;
+    float_cmp::assert_approx_eq!(f64, sim.read_residual("a").0, 3.0 * 0.5 + 2.0, ulps = 16);
+
+    // calls are checked against the registered signature
+    let src = r#"
+        `include "disciplines.vams"
+        module wrong_args(inout electrical a);
+            analog I(a) <+ $scale(V(a));
+        endmodule
+    "#;
+    let res = openvaf::compile_str(src, &opts)?;
+    assert!(matches!(res, CompilationTermination::FatalDiagnostic));
+
+    // builtin system functions can not be replaced
+    let display = HostFunction { name: "display".to_owned(), ..scale_fun };
+    let opts = openvaf::Opts { host_functions: vec![display], ..opts };
+    assert!(openvaf::compile(&opts).is_err());
+    Ok(())
+}
+
+fn test_file_io() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("file_io.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let model = desc.new_model();
+    model.process_params()?;
+    let mut instance = model.new_instance();
+    let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+    assert_eq!(file_contents("file_io.log").as_deref(), Some(""));
+
+    for vin in [1.5, 2.0] {
+        sim.next_iter();
+        sim.set_voltage("a", vin);
+        instance.eval(&model, &mut sim, EvalFlags::ANALYSIS_DC);
+    }
+    assert_eq!(file_contents("file_io.log").as_deref(), Some("V=1.5\nV=2\n"));
+    Ok(())
+}
+
+fn test_table_model() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("table_model.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let model = desc.new_model();
+    model.process_params()?;
+    let mut instance = model.new_instance();
+    let mut sim = instance.mock_simulation(&model, desc.num_terminals, 300.0)?;
+
+    // the table contains the points (0, 0), (1, 2) and (2, 3)
+    let mut eval = |node: &str, v: f64| {
+        sim.next_iter();
+        sim.set_voltage(node, v);
+        instance.eval(&model, &mut sim, EvalFlags::ANALYSIS_DC);
+        instance.load_dae(&model, &mut sim);
+        (sim.read_residual(node).0, sim.read_jacobian(node, node).0)
+    };
+    let check = |(val, ddx): (f64, f64), (val_ref, ddx_ref): (f64, f64)| {
+        float_cmp::assert_approx_eq!(f64, val, val_ref, epsilon = 1e-12);
+        float_cmp::assert_approx_eq!(f64, ddx, ddx_ref, epsilon = 1e-12);
+    };
+
+    check(eval("lin", 0.5), (1.0, 2.0));
+    check(eval("lin", 1.5), (2.5, 1.0));
+    // extrapolated with the slopes at the ends of the table
+    check(eval("lin", -1.0), (-2.0, 2.0));
+    check(eval("lin", 3.0), (4.0, 1.0));
+
+    check(eval("clamp", 1.5), (2.5, 1.0));
+    check(eval("clamp", -1.0), (0.0, 0.0));
+    check(eval("clamp", 3.0), (3.0, 0.0));
+
+    // the spline passes through the points and has the slope 2.0625 at x = 0.5
+    check(eval("spline", 1.0), (2.0, 1.5));
+    check(eval("spline", 0.5), (1.09375, 2.0625));
+    check(eval("spline", 1.5), (2.59375, 0.9375));
+    Ok(())
+}
+
+fn test_sformat() -> Result<()> {
+    if stdx::IS_CI && cfg!(windows) {
+        return Ok(());
+    }
+
+    let root_file = openvaf_test_data("osdi").join("sformat.va");
+    let desc = compile_and_load(root_file.as_path().try_into().unwrap());
+    let model = desc.new_model();
+    model.process_params()?;
+    // every instance owns the strings it formatted, evaluating
+    // another instance in between must not invalidate them
+    let mut instances = [model.new_instance(), model.new_instance()];
+    let mut sims = instances
+        .iter_mut()
+        .map(|instance| instance.mock_simulation(&model, desc.num_terminals, 300.0))
+        .collect::<Result<Vec<_>>>()?;
+
+    for vin in [1.5, 2.0] {
+        for (i, (instance, sim)) in instances.iter().zip(&mut sims).enumerate() {
+            sim.next_iter();
+            sim.set_voltage("a", if i == 0 { vin } else { -vin });
+            instance.eval(&model, sim, EvalFlags::ANALYSIS_DC);
+        }
+    }
+    assert_eq!(
+        file_contents("sformat.log").as_deref(),
+        Some(
+            "[sformat: V=1.5 (3)]\n[sformat: V=-1.5 (3)]\n[sformat: V=2 (3)]\n[sformat: V=-2 (3)]\n"
+        )
+    );
+    Ok(())
+}
+
 harness! {
     // TODO: run this in CI, somehow this test is flakey tough regarding the linker invocation (and really slow)
     Test::from_dir("integration", &integration_test, &ignore_dev_tests, &project_root().join("integration_tests")),
-    [Test::new("$limit", &test_limit),Test::new("noise", &test_noise)]
+    [Test::new("$limit", &test_limit),Test::new("noise", &test_noise),Test::new("flicker_noise", &test_flicker_noise),Test::new("noise_correlation", &test_noise_correlation),Test::new("last_crossing", &test_last_crossing),Test::new("cross_event", &test_cross_event),Test::new("event_or", &test_event_or),Test::new("event_initial_step", &test_event_initial_step),Test::new("port_flow", &test_port_flow),Test::new("bound_step", &test_bound_step),Test::new("realtime", &test_realtime),Test::new("inf_bounds", &test_inf_bounds),Test::new("mixed_bounds", &test_mixed_bounds),Test::new("point_eval", &test_point_eval),Test::new("rc_stages", &test_rc_stages),Test::new("ddt_expr", &test_ddt_expr),Test::new("ground", &test_ground),Test::new("safe_log", &test_safe_log),Test::new("clamp_sqrt", &test_clamp_sqrt),Test::new("int_div", &test_int_div),Test::new("conversion", &test_conversion),Test::new("param_array", &test_param_array),Test::new("model_evaluate", &test_model_evaluate),Test::new("limit_steps", &test_limit_steps),Test::new("string_mode", &test_string_mode),Test::new("localparam", &test_localparam),Test::new("$past", &test_past),Test::new("array", &test_array),Test::new("rdist", &test_rdist),Test::new("random_sequence", &test_random_sequence),Test::new("analysis", &test_analysis),Test::new("linear_hint", &test_linear_hint),Test::new("compile_all", &test_compile_all),Test::new("opt_levels", &test_opt_levels),Test::new("symbol_names", &test_symbol_names),Test::new("multiple_modules", &test_multiple_modules),Test::new("compile_str", &test_compile_str),Test::new("implicit_std_includes", &test_implicit_std_includes),Test::new("emit_llvm_ir", &test_emit_llvm_ir),Test::new("fast_math", &test_fast_math),Test::new("deny_lint", &test_deny_lint),Test::new("param_attrs", &test_param_attrs),Test::new("host_function", &test_host_function),Test::new("file_io", &test_file_io),Test::new("sformat", &test_sformat),Test::new("table_model", &test_table_model)]
 }
@@ -5,6 +5,7 @@ use std::fmt::Debug;
 use std::mem::{align_of, swap};
 use std::panic::catch_unwind;
 use std::process::exit;
+use std::sync::Mutex;
 use std::{ptr, slice};
 
 use anyhow::{bail, Result};
@@ -16,9 +17,9 @@ use stdx::format_to;
 use stdx::iter::zip;
 
 #[allow(warnings)]
-mod osdi_0_4;
+mod osdi_0_5;
 
-pub use osdi_0_4::*;
+pub use osdi_0_5::*;
 
 impl OsdiDescriptor {
     pub fn nodes(&self) -> &[OsdiNode] {
@@ -33,6 +34,12 @@ impl OsdiDescriptor {
         unsafe { slice::from_raw_parts(self.param_opvar, self.num_params as usize) }
     }
 
+    /// Returns the index of the parameter called `name`.
+    pub fn param_index(&self, name: &str) -> Option<u32> {
+        let pos = self.params().iter().position(|param| unsafe { osdi_str(*param.name) } == name);
+        pos.map(|pos| pos as u32)
+    }
+
     pub fn collapsible(&self) -> &[OsdiNodePair] {
         // SAFETY: self.data is a valid allocation and the descriptor is assumed valid
         unsafe { slice::from_raw_parts(self.collapsible, self.num_collapsible as usize) }
@@ -43,6 +50,13 @@ impl OsdiDescriptor {
         unsafe { slice::from_raw_parts(self.noise_sources, self.num_noise_src as usize) }
     }
 
+    pub fn noise_correlations(&self) -> &[OsdiNoiseCorrelation] {
+        // SAFETY: self.data is a valid allocation and the descriptor is assumed valid
+        unsafe {
+            slice::from_raw_parts(self.noise_correlations, self.num_noise_correlations as usize)
+        }
+    }
+
     pub fn matrix_entries(&self) -> &[OsdiJacobianEntry] {
         // SAFETY: self.data is a valid allocation and the descriptor is assumed valid
         unsafe { slice::from_raw_parts(self.jacobian_entries, self.num_jacobian_entries as usize) }
@@ -176,6 +190,25 @@ impl OsdiModel {
         unsafe { ptr.write(val) };
     }
 
+    pub fn set_int_param(&self, param: u32, val: i32) {
+        let ptr = self.descriptor.access(ptr::null_mut(), self.data, param, ACCESS_FLAG_SET);
+        let ptr = ptr as *mut i32;
+        if ptr.is_null() {
+            unreachable!("invalid parameter access")
+        }
+        unsafe { ptr.write(val) };
+    }
+
+    /// The string is not copied by the model so it must outlive the model.
+    pub fn set_str_param(&self, param: u32, val: &'static CStr) {
+        let ptr = self.descriptor.access(ptr::null_mut(), self.data, param, ACCESS_FLAG_SET);
+        let ptr = ptr as *mut *const c_char;
+        if ptr.is_null() {
+            unreachable!("invalid parameter access")
+        }
+        unsafe { ptr.write(val.as_ptr()) };
+    }
+
     pub fn new_instance(&self) -> OsdiInstance {
         OsdiInstance {
             descriptor: self.descriptor,
@@ -191,6 +224,7 @@ pub struct OsdiInstance {
 
 impl Drop for OsdiInstance {
     fn drop(&mut self) {
+        self.descriptor.free_instance(self.data, ptr::null_mut());
         unsafe { dealloc(self.data, self.descriptor.instance_size as usize) }
     }
 }
@@ -215,6 +249,16 @@ impl OsdiInstance {
         }
     }
 
+    /// The maximum timestep requested with `$bound_step` during the last evaluation.
+    pub fn bound_step(&self) -> Option<f64> {
+        let offset = self.descriptor.bound_step_offset;
+        if offset == u32::MAX {
+            return None;
+        }
+        // SAFETY: self.data is a valid allocation and the descriptor is assumed valid
+        unsafe { Some(*((self.data as *mut u8).add(offset as usize) as *const f64)) }
+    }
+
     pub fn collapsed(&self) -> &[bool] {
         let ptr = self.data as *mut u8;
         // SAFETY: self.data is a valid allocation and the descriptor is assumed valid
@@ -328,7 +372,7 @@ pub unsafe fn load_osdi_lib(path: &Utf8Path) -> Result<&'static [OsdiDescriptor]
     let major_version: &u32 = *lib.get(b"OSDI_VERSION_MAJOR\0")?;
     let minor_version: &u32 = *lib.get(b"OSDI_VERSION_MINOR\0")?;
 
-    if *major_version != 0 || *minor_version != 4 {
+    if *major_version != OSDI_VERSION_MAJOR_CURR || *minor_version != OSDI_VERSION_MINOR_CURR {
         bail!("invalid version v{major_version}.{minor_version}",);
     }
 
@@ -343,6 +387,18 @@ pub unsafe fn load_osdi_lib(path: &Utf8Path) -> Result<&'static [OsdiDescriptor]
     {
         osdi_log_ptr.write(osdi_log)
     }
+    if let Ok(osdi_fopen_ptr) =
+        lib.get::<*mut unsafe extern "C" fn(*mut c_void, *const c_char, *const c_char) -> u32>(
+            b"osdi_fopen\0",
+        )
+    {
+        osdi_fopen_ptr.write(osdi_fopen)
+    }
+    if let Ok(osdi_fwrite_ptr) =
+        lib.get::<*mut unsafe extern "C" fn(*mut c_void, u32, *const c_char, u32)>(b"osdi_fwrite\0")
+    {
+        osdi_fwrite_ptr.write(osdi_fwrite)
+    }
     if let Ok(osdi_lim_table) = lib.get(b"OSDI_LIM_TABLE\0") {
         let lim_table_base: *mut OsdiLimFunction = *osdi_lim_table;
         let lim_table_len: &u32 = *lib.get(b"OSDI_LIM_TABLE_LEN\0")?;
@@ -363,6 +419,41 @@ unsafe extern "C" fn osdi_log(handle: *mut c_void, msg: *const c_char, lvl: u32)
     let _ = catch_unwind(|| osdi_log_impl(handle, msg, lvl));
 }
 
+/// Files written by the model are only kept in memory so that tests can inspect them.
+/// The file descriptor is the index into this table (plus one).
+static FILES: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+
+/// Returns everything that was written to the file `name` so far.
+pub fn file_contents(name: &str) -> Option<String> {
+    let files = FILES.lock().unwrap();
+    files.iter().find(|(file, _)| file == name).map(|(_, contents)| contents.clone())
+}
+
+unsafe extern "C" fn osdi_fopen(
+    _handle: *mut c_void,
+    name: *const c_char,
+    _mode: *const c_char,
+) -> u32 {
+    let name = CStr::from_ptr(name).to_str().expect("all OSDI strings must be valid utf-8");
+    let mut files = FILES.lock().unwrap();
+    // the model may be set up multiple times, reuse the descriptor in that case
+    let fd = match files.iter().position(|(file, _)| file == name) {
+        Some(fd) => fd,
+        None => {
+            files.push((name.to_owned(), String::new()));
+            files.len() - 1
+        }
+    };
+    fd as u32 + 1
+}
+
+unsafe extern "C" fn osdi_fwrite(_handle: *mut c_void, fd: u32, msg: *const c_char, flags: u32) {
+    let msg = CStr::from_ptr(msg).to_str().expect("all OSDI strings must be valid utf-8");
+    assert_eq!(flags & LOG_FMT_ERR, 0, "failed to format \"{msg}\"");
+    let mut files = FILES.lock().unwrap();
+    files[fd as usize - 1].1.push_str(msg);
+}
+
 unsafe extern "C" fn osdi_pnjlim(
     init: bool,
     check: *mut bool,
@@ -489,6 +580,15 @@ impl Debug for OsdiDescriptor {
                 };
                 wn!("noise {:?} ({}, {})", osdi_str(*name), osdi_str(hi), lo);
             }
+            for OsdiNoiseCorrelation { noise_1, noise_2, real, imag } in self.noise_correlations() {
+                let name_1 = self.noise()[*noise_1 as usize].name;
+                let name_2 = self.noise()[*noise_2 as usize].name;
+                wn!(
+                    "noise correlation ({:?}, {:?}) {real} + {imag}j",
+                    osdi_str(name_1),
+                    osdi_str(name_2)
+                );
+            }
             wn!("{} states", self.num_states);
             wn!("has bound_step {}", self.bound_step_offset != u32::MAX);
             wn!("instance size {}", self.instance_size);
@@ -3,7 +3,7 @@
 use std::os::raw::{c_char, c_void};
 
 pub const OSDI_VERSION_MAJOR_CURR: u32 = 0;
-pub const OSDI_VERSION_MINOR_CURR: u32 = 4;
+pub const OSDI_VERSION_MINOR_CURR: u32 = 5;
 pub const PARA_TY_MASK: u32 = 3;
 pub const PARA_TY_REAL: u32 = 0;
 pub const PARA_TY_INT: u32 = 1;
@@ -124,6 +124,13 @@ pub struct OsdiNoiseSource {
     pub nodes: OsdiNodePair,
 }
 #[repr(C)]
+pub struct OsdiNoiseCorrelation {
+    pub noise_1: u32,
+    pub noise_2: u32,
+    pub real: f64,
+    pub imag: f64,
+}
+#[repr(C)]
 #[non_exhaustive]
 pub struct OsdiDescriptor {
     pub name: *mut c_char,
@@ -173,6 +180,11 @@ pub struct OsdiDescriptor {
     pub inputs: *mut OsdiNodePair,
     pub load_jacobian_with_offset_resist: fn(*mut c_void, *mut c_void, usize),
     pub load_jacobian_with_offset_react: fn(*mut c_void, *mut c_void, usize),
+    pub num_gmin_branches: u32,
+    pub gmin_branches: *mut OsdiNodePair,
+    pub num_noise_correlations: u32,
+    pub noise_correlations: *mut OsdiNoiseCorrelation,
+    pub free_instance: fn(*mut c_void, *mut c_void),
 }
 impl OsdiDescriptor {
     pub fn access(
@@ -301,4 +313,37 @@ impl OsdiDescriptor {
     ) {
         (self.load_jacobian_with_offset_react)(inst, model, offset)
     }
+    pub fn free_instance(&self, inst: *mut c_void, model: *mut c_void) {
+        (self.free_instance)(inst, model)
+    }
+}
+#[cfg(all(test, target_pointer_width = "64"))]
+#[test]
+fn layout() {
+    assert_eq!(std::mem::size_of::<OsdiLimFunction>(), 24usize);
+    assert_eq!(std::mem::size_of::<OsdiSimParas>(), 32usize);
+    assert_eq!(std::mem::size_of::<OsdiSimInfo>(), 72usize);
+    assert_eq!(std::mem::size_of::<OsdiInitErrorPayload>(), 4usize);
+    assert_eq!(std::mem::size_of::<OsdiInitError>(), 8usize);
+    assert_eq!(std::mem::size_of::<OsdiInitInfo>(), 16usize);
+    assert_eq!(std::mem::size_of::<OsdiNodePair>(), 8usize);
+    assert_eq!(std::mem::size_of::<OsdiJacobianEntry>(), 16usize);
+    assert_eq!(std::mem::size_of::<OsdiNode>(), 48usize);
+    assert_eq!(std::mem::size_of::<OsdiParamOpvar>(), 40usize);
+    assert_eq!(std::mem::size_of::<OsdiNoiseSource>(), 16usize);
+    assert_eq!(std::mem::size_of::<OsdiNoiseCorrelation>(), 24usize);
+    assert_eq!(std::mem::size_of::<OsdiDescriptor>(), 352usize);
+    assert_eq!(std::mem::align_of::<OsdiLimFunction>(), 8usize);
+    assert_eq!(std::mem::align_of::<OsdiSimParas>(), 8usize);
+    assert_eq!(std::mem::align_of::<OsdiSimInfo>(), 8usize);
+    assert_eq!(std::mem::align_of::<OsdiInitErrorPayload>(), 4usize);
+    assert_eq!(std::mem::align_of::<OsdiInitError>(), 4usize);
+    assert_eq!(std::mem::align_of::<OsdiInitInfo>(), 8usize);
+    assert_eq!(std::mem::align_of::<OsdiNodePair>(), 4usize);
+    assert_eq!(std::mem::align_of::<OsdiJacobianEntry>(), 4usize);
+    assert_eq!(std::mem::align_of::<OsdiNode>(), 8usize);
+    assert_eq!(std::mem::align_of::<OsdiParamOpvar>(), 8usize);
+    assert_eq!(std::mem::align_of::<OsdiNoiseSource>(), 8usize);
+    assert_eq!(std::mem::align_of::<OsdiNoiseCorrelation>(), 8usize);
+    assert_eq!(std::mem::align_of::<OsdiDescriptor>(), 8usize);
 }
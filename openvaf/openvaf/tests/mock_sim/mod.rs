@@ -1,8 +1,9 @@
 use std::cell::UnsafeCell;
+use std::fmt;
 use std::mem::swap;
 use std::ptr;
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use indexmap::IndexSet;
 use libc::c_void;
 use stdx::iter::zip;
@@ -10,7 +11,8 @@ use stdx::iter::zip;
 pub const ALPHA: f64 = 0.172;
 
 use crate::load::{
-    osdi_str, EvalFlags, EvalRetFlags, OsdiInstance, OsdiModel, OsdiSimInfo, OsdiSimParas,
+    osdi_str, EvalFlags, EvalRetFlags, OsdiDescriptor, OsdiInstance, OsdiModel, OsdiSimInfo,
+    OsdiSimParas,
 };
 
 #[derive(Debug, Default)]
@@ -25,6 +27,7 @@ pub struct MockSimulation {
     pub state_1: Vec<f64>,
     pub state_2: Vec<f64>,
     pub noise_dense: Vec<f64>,
+    pub abstime: f64,
 }
 impl MockSimulation {
     fn new() -> MockSimulation {
@@ -47,6 +50,7 @@ impl MockSimulation {
             state_1: Vec::new(),
             state_2: Vec::new(),
             noise_dense: Vec::new(),
+            abstime: 0.0,
         }
     }
 
@@ -65,7 +69,7 @@ impl MockSimulation {
         }
     }
 
-    fn get_jacobian_entry(&mut self, hi: u32, lo: u32) -> usize {
+    fn get_jacobian_entry(&self, hi: u32, lo: u32) -> usize {
         if hi == 0 || lo == 0 {
             0
         } else {
@@ -119,6 +123,113 @@ impl MockSimulation {
     }
 }
 
+/// Dumps the residual of every node and every Jacobian entry (resistive and reactive part).
+impl fmt::Display for MockSimulation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, node) in self.nodes.iter().enumerate().skip(1) {
+            let (resist, react) = (self.residual_resist[i], self.residual_react[i]);
+            writeln!(f, "residual {node}: {resist:e} {react:e}")?;
+        }
+        for &(hi, lo) in self.jacobian_info.iter().skip(1) {
+            let (hi, lo) = (self.nodes[hi as usize], self.nodes[lo as usize]);
+            let (resist, react) = self.read_jacobian(hi, lo);
+            writeln!(f, "jacobian ({hi}, {lo}): {resist:e} {react:e}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Evaluates (a DC analysis of) the compiled model `desc` at a single operating point.
+/// All terminals are connected, `params` sets real parameters by name and `voltages`
+/// sets node voltages (all other nodes are at 0V). The returned simulation contains the
+/// residuals and Jacobian entries, which allows checking the numeric results of code
+/// generation and automatic differentiation end-to-end.
+pub fn evaluate_point(
+    desc: &'static OsdiDescriptor,
+    params: &[(&str, f64)],
+    voltages: &[(&str, f64)],
+) -> Result<MockSimulation> {
+    let model = desc.new_model();
+    for &(name, val) in params {
+        let param = desc.param_index(name).with_context(|| format!("unknown parameter {name}"))?;
+        model.set_real_param(param, val);
+    }
+    model.process_params()?;
+    let (_, sim) = model.dc_point(voltages)?;
+    Ok(sim)
+}
+
+/// Residual and Jacobian of a single evaluation, see [`OsdiModel::evaluate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evaluation {
+    residual: Vec<f64>,
+    jacobian: Vec<f64>,
+}
+
+impl Evaluation {
+    /// The (resistive) residual of every node in the order of [`OsdiDescriptor::nodes`].
+    /// Collapsed nodes report the residual of the node they were collapsed into
+    /// (zero for nodes collapsed into ground).
+    pub fn residual(&self) -> &[f64] {
+        &self.residual
+    }
+
+    /// The (resistive) Jacobian entries in the order of [`OsdiDescriptor::matrix_entries`].
+    pub fn jacobian(&self) -> &[f64] {
+        &self.jacobian
+    }
+}
+
+impl OsdiModel {
+    /// Evaluates (a DC analysis of) a new instance of this model at a single operating point
+    /// without requiring any raw OSDI calls. All terminals are connected and `voltages` sets
+    /// node voltages by name (all other nodes are at 0V). [`OsdiModel::process_params`] must
+    /// have been called after setting the model parameters.
+    pub fn evaluate(&self, voltages: &[(&str, f64)]) -> Result<Evaluation> {
+        let (instance, sim) = self.dc_point(voltages)?;
+        let node_mapping = instance.node_mapping();
+        let residual = node_mapping
+            .iter()
+            .map(|node| match node.get() {
+                0 => 0.0,
+                node => sim.residual_resist[node as usize],
+            })
+            .collect();
+        let jacobian = self
+            .descriptor
+            .matrix_entries()
+            .iter()
+            .map(|entry| {
+                let column = node_mapping[entry.nodes.node_1 as usize].get();
+                let row = node_mapping[entry.nodes.node_2 as usize].get();
+                match sim.get_jacobian_entry(row, column) {
+                    0 => 0.0,
+                    i => unsafe { sim.jacobian_resist[i].get().read() },
+                }
+            })
+            .collect();
+        Ok(Evaluation { residual, jacobian })
+    }
+
+    fn dc_point(&self, voltages: &[(&str, f64)]) -> Result<(OsdiInstance, MockSimulation)> {
+        let mut instance = self.new_instance();
+        let mut sim = instance.mock_simulation(self, self.descriptor.num_terminals, 300.0)?;
+        sim.next_iter();
+        for &(node, voltage) in voltages {
+            if !sim.nodes.contains(node) {
+                bail!("unknown node {node}");
+            }
+            sim.set_voltage(node, voltage);
+        }
+        let ret_flags = instance.eval(self, &mut sim, EvalFlags::ANALYSIS_DC);
+        if ret_flags.contains(EvalRetFlags::EVAL_RET_FLAG_FATAL) {
+            bail!("evaluation of the model failed ($fatal)");
+        }
+        instance.load_dae(self, &mut sim);
+        Ok((instance, sim))
+    }
+}
+
 impl OsdiInstance {
     pub(super) fn mock_simulation(
         &mut self,
@@ -177,6 +288,14 @@ impl OsdiInstance {
         }
         sim.state_1.resize(self.descriptor.num_states as usize, 0.0);
         sim.state_2.resize(self.descriptor.num_states as usize, 0.0);
+        // every state gets a dedicated slot in the state vectors
+        unsafe {
+            let data = self.data as *mut u8;
+            let state_idx: *mut u32 = data.add(self.descriptor.state_idx_off as usize).cast();
+            for i in 0..self.descriptor.num_states {
+                *state_idx.add(i as usize) = i;
+            }
+        }
         sim.noise_dense.resize(self.descriptor.num_noise_src as usize, 0.0);
         Ok(sim)
     }
@@ -240,7 +359,7 @@ impl OsdiInstance {
         };
         let mut sim_info = OsdiSimInfo {
             paras: sim_params,
-            abstime: 0.0,
+            abstime: sim.abstime,
             prev_solve: sim.solve.as_ptr() as *mut f64,
             prev_state: sim.state_1.as_mut_ptr(),
             next_state: sim.state_2.as_mut_ptr(),
@@ -8,7 +8,7 @@
 
 
 #define OSDI_VERSION_MAJOR_CURR 0
-#define OSDI_VERSION_MINOR_CURR 4
+#define OSDI_VERSION_MINOR_CURR 5
 
 #define PARA_TY_MASK 3
 #define PARA_TY_REAL 0
@@ -138,6 +138,13 @@ typedef struct OsdiNoiseSource {
   OsdiNodePair nodes;
 }OsdiNoiseSource;
 
+typedef struct OsdiNoiseCorrelation {
+  uint32_t noise_1;
+  uint32_t noise_2;
+  double real;
+  double imag;
+}OsdiNoiseCorrelation;
+
 typedef struct OsdiDescriptor {
   char *name;
 
@@ -168,6 +175,8 @@ typedef struct OsdiDescriptor {
 
   uint32_t bound_step_offset;
 
+  /* The instance data must be zero initialized before setup_instance is called for the
+     first time and free_instance must be called before it is deallocated (OSDI 0.5). */
   uint32_t instance_size;
   uint32_t model_size;
 
@@ -202,6 +211,12 @@ typedef struct OsdiDescriptor {
   OsdiNodePair* inputs;
   void (*load_jacobian_with_offset_resist)(void *inst, void* model, size_t offset);
   void (*load_jacobian_with_offset_react)(void *inst, void* model, size_t offset);
+  uint32_t num_gmin_branches;
+  OsdiNodePair* gmin_branches;
+  uint32_t num_noise_correlations;
+  OsdiNoiseCorrelation* noise_correlations;
+  /* Releases the memory owned by an instance (the strings produced by $sformat). */
+  void (*free_instance)(void *inst, void* model);
 }OsdiDescriptor;
 
 
@@ -6,7 +6,7 @@ use llvm::{
 };
 
 use crate::compilation_unit::OsdiCompilationUnit;
-use crate::metadata::osdi_0_4::{ACCESS_FLAG_INSTANCE, ACCESS_FLAG_SET};
+use crate::metadata::osdi_0_5::{ACCESS_FLAG_INSTANCE, ACCESS_FLAG_SET};
 
 impl<'ll> OsdiCompilationUnit<'_, '_, 'll> {
     pub fn access_function_prototype(&self) -> &'ll llvm::Value {
@@ -1,17 +1,17 @@
-use hir::CompilationDB;
+use hir::{CompilationDB, HostFunction};
 use hir_lower::fmt::{DisplayKind, FmtArg, FmtArgKind};
-use hir_lower::{CallBackKind, RetFlag, HirInterner};
+use hir_lower::{CallBackKind, RetFlag, HirInterner, RandomDist};
 use lasso::Rodeo;
 use llvm::Linkage;
 use llvm::{
     IntPredicate, LLVMAddIncoming, LLVMAppendBasicBlockInContext, LLVMBuildAdd,
     LLVMBuildArrayMalloc, LLVMBuildBr, LLVMBuildCall2, LLVMBuildCondBr, LLVMBuildFMul,
     LLVMBuildFree, LLVMBuildICmp, LLVMBuildInBoundsGEP2, LLVMBuildLoad2, LLVMBuildPhi,
-    LLVMGetParam, LLVMIsDeclaration, LLVMPositionBuilderAtEnd, LLVMSetLinkage,
+    LLVMBuildRet, LLVMGetParam, LLVMIsDeclaration, LLVMPositionBuilderAtEnd, LLVMSetLinkage,
     LLVMSetUnnamedAddress, UnnamedAddr, UNNAMED,
 };
 use mir::{FuncRef, Function};
-use mir_llvm::{CallbackFun, CodegenCx, LLVMBackend, ModuleLlvm, BuiltCallbackFun};
+use mir_llvm::{symbol_name, BuiltCallbackFun, CallbackFun, CodegenCx, LLVMBackend, ModuleLlvm};
 use sim_back::dae::DaeSystem;
 use sim_back::init::Initialization;
 use sim_back::node_collapse::NodeCollapse;
@@ -20,7 +20,7 @@ use typed_index_collections::TiVec;
 use typed_indexmap::TiSet;
 
 use crate::inst_data::OsdiInstanceData;
-use crate::metadata::osdi_0_4::{
+use crate::metadata::osdi_0_5::{
     stdlib_bitcode, OsdiTys, LOG_FMT_ERR, LOG_LVL_DEBUG, LOG_LVL_DISPLAY, LOG_LVL_ERR,
     LOG_LVL_FATAL, LOG_LVL_INFO, LOG_LVL_WARN,
 };
@@ -86,11 +86,7 @@ impl<'a, 'b, 'll> OsdiCompilationUnit<'a, 'b, 'll> {
                 let ptr = cx
                     .define_global("OSDI_LIM_TABLE", ty)
                     .unwrap_or_else(|| unreachable!("symbol OSDI_LIM_TABLE already defined"));
-                unsafe {
-                    llvm::LLVMSetLinkage(ptr, llvm::Linkage::ExternalLinkage);
-                    llvm::LLVMSetUnnamedAddress(ptr, llvm::UnnamedAddr::No);
-                    llvm::LLVMSetDLLStorageClass(ptr, llvm::DLLStorageClass::Export);
-                }
+                cx.export_global(ptr);
                 Some(ptr)
             } else {
                 None
@@ -122,7 +118,7 @@ impl<'a> OsdiModule<'a> {
         module: &'a CompiledModule,
         lim_table: &'a TiSet<OsdiLimId, OsdiLimFunction>,
     ) -> Self {
-        let sym = base_n::encode(module.info.module.uuid(db) as u128, base_n::CASE_INSENSITIVE);
+        let sym = symbol_name(&module.info.module.name(db));
         let CompiledModule {
             info,
             dae_system,
@@ -221,15 +217,92 @@ pub fn general_callbacks<'ll>(
                 | CallBackKind::StoreLimit(_)
                 | CallBackKind::LimDiscontinuity
                 | CallBackKind::Analysis
+                | CallBackKind::LastCrossing(_)
+                | CallBackKind::CrossEvent(_)
+                | CallBackKind::Past(_)
                 | CallBackKind::NoiseTable(_)
                 | CallBackKind::WhiteNoise { .. }
                 | CallBackKind::FlickerNoise { .. }
+                | CallBackKind::Format { .. }
                 | CallBackKind::TimeDerivative => return None,
 
+                CallBackKind::Random { dist, next_seed } => {
+                    let cx = &builder.cx;
+                    let mut args = vec![cx.ty_int()];
+                    args.resize(dist.num_args() as usize + 1, cx.ty_double());
+                    let (name, ret_ty) = if *next_seed {
+                        (dist.stdlib_seed_fun(), cx.ty_int())
+                    } else {
+                        let ret_ty = match dist {
+                            RandomDist::Uniform => cx.ty_int(),
+                            RandomDist::Normal
+                            | RandomDist::UniformReal
+                            | RandomDist::Exponential
+                            | RandomDist::Poisson => cx.ty_double(),
+                        };
+                        (dist.stdlib_fun(), ret_ty)
+                    };
+                    let fun_ty = cx.ty_func(&args, ret_ty);
+                    let fun = cx
+                        .get_func_by_name(name)
+                        .unwrap_or_else(|| panic!("stdlib function {name} is missing"));
+                    CallbackFun::Prebuilt(BuiltCallbackFun {
+                        fun_ty,
+                        fun,
+                        state: Box::new([]),
+                        num_state: 0,
+                    })
+                }
+
                 CallBackKind::Print { kind, arg_tys } => {
-                    let (fun, fun_ty) = print_callback(builder.cx, *kind, arg_tys);
+                    let (fun, fun_ty) =
+                        print_callback(builder.cx, *kind, arg_tys, PrintTarget::Log);
                     CallbackFun::Prebuilt(BuiltCallbackFun { fun_ty, fun, state: Box::new([handle]), num_state: 0 })
                 }, 
+                CallBackKind::FilePrint { arg_tys } => {
+                    let (fun, fun_ty) = print_callback(
+                        builder.cx,
+                        DisplayKind::Display,
+                        arg_tys,
+                        PrintTarget::File,
+                    );
+                    CallbackFun::Prebuilt(BuiltCallbackFun {
+                        fun_ty,
+                        fun,
+                        state: Box::new([handle]),
+                        num_state: 0,
+                    })
+                }
+                CallBackKind::HostFunction(fun) => {
+                    CallbackFun::Prebuilt(host_function_callback(builder.cx, fun))
+                }
+                CallBackKind::FileOpen => {
+                    let fun = builder
+                        .cx
+                        .get_func_by_name("file_open")
+                        .expect("stdlib function file_open is missing");
+                    let fun_ty = builder.cx.ty_func(&[ptr_ty, ptr_ty, ptr_ty], builder.cx.ty_int());
+                    CallbackFun::Prebuilt(BuiltCallbackFun {
+                        fun_ty,
+                        fun,
+                        state: Box::new([handle]),
+                        num_state: 0,
+                    })
+                }
+                CallBackKind::FileClose => {
+                    let fun = builder
+                        .cx
+                        .get_func_by_name("file_close")
+                        .expect("stdlib function file_close is missing");
+                    let fun_ty =
+                        builder.cx.ty_func(&[ptr_ty, builder.cx.ty_int()], builder.cx.ty_void());
+                    CallbackFun::Prebuilt(BuiltCallbackFun {
+                        fun_ty,
+                        fun,
+                        state: Box::new([handle]),
+                        num_state: 0,
+                    })
+                }
                 CallBackKind::SetRetFlag( flag ) => {
                     let fun = if *flag==RetFlag::Abort {
                         // Fatal
@@ -264,14 +337,94 @@ pub fn general_callbacks<'ll>(
         .collect()
 }
 
+/// The symbol of the function pointer that the simulator sets to implement `fun`.
+pub fn host_function_symbol(fun: &HostFunction) -> String {
+    format!("osdi_host_{}", fun.name)
+}
+
+/// Builds a function that calls the host function `fun` through the function pointer
+/// exported by the library (see [`host_function_symbol`]).
+fn host_function_callback<'ll>(
+    cx: &CodegenCx<'_, 'll>,
+    fun: &HostFunction,
+) -> BuiltCallbackFun<'ll> {
+    let args: Vec<_> = fun.args.iter().map(|ty| lltype(ty, cx)).collect();
+    let fun_ty = cx.ty_func(&args, lltype(&fun.return_ty, cx));
+    let sym = host_function_symbol(fun);
+    let fun_ptr =
+        cx.get_declared_value(&sym).unwrap_or_else(|| cx.define_global(&sym, cx.ty_ptr()).unwrap());
+    let name = cx.local_callback_name();
+    let cb = cx.declare_int_fn(&name, fun_ty);
+    unsafe {
+        let bb = LLVMAppendBasicBlockInContext(cx.llcx, cb, UNNAMED);
+        let llbuilder = llvm::LLVMCreateBuilderInContext(cx.llcx);
+        LLVMPositionBuilderAtEnd(llbuilder, bb);
+        let host_fun = LLVMBuildLoad2(llbuilder, cx.ty_ptr(), fun_ptr, UNNAMED);
+        let params: Vec<_> = (0..args.len()).map(|i| LLVMGetParam(cb, i as u32)).collect();
+        let res = LLVMBuildCall2(
+            llbuilder,
+            fun_ty,
+            host_fun,
+            params.as_ptr(),
+            params.len() as u32,
+            UNNAMED,
+        );
+        LLVMBuildRet(llbuilder, res);
+        llvm::LLVMDisposeBuilder(llbuilder);
+    }
+    BuiltCallbackFun { fun_ty, fun: cb, state: Box::new([]), num_state: 0 }
+}
+
+/// Builds the callback for a `$sformat` call. `str_ptr` points to the instance data
+/// field that owns the string formatted last by this call (see [`FormatStr`]).
+///
+/// [`FormatStr`]: crate::inst_data::FormatStr
+pub fn format_callback<'ll>(
+    cx: &CodegenCx<'_, 'll>,
+    arg_tys: &[FmtArg],
+    handle: &'ll llvm::Value,
+    str_ptr: &'ll llvm::Value,
+) -> CallbackFun<'ll> {
+    let (fun, fun_ty) = print_callback(cx, DisplayKind::Display, arg_tys, PrintTarget::Str);
+    CallbackFun::Prebuilt(BuiltCallbackFun {
+        fun_ty,
+        fun,
+        state: Box::new([handle, str_ptr]),
+        num_state: 0,
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PrintTarget {
+    /// pass the message to `osdi_log`
+    Log,
+    /// write the message to the file descriptor passed (before the format literal)
+    /// as the second argument
+    File,
+    /// store the message in the string field passed as the second argument
+    /// and return it (`$sformat`)
+    Str,
+}
+
+/// Builds a function that formats its arguments and passes the message to `target`.
 fn print_callback<'ll>(
     cx: &CodegenCx<'_, 'll>,
     kind: hir_lower::fmt::DisplayKind,
     arg_tys: &[FmtArg],
+    target: PrintTarget,
 ) -> (&'ll llvm::Value, &'ll llvm::Type) {
-    let mut args = vec![cx.ty_ptr(), cx.ty_ptr()];
+    let mut args = vec![cx.ty_ptr()];
+    match target {
+        PrintTarget::Log => (),
+        PrintTarget::File => args.push(cx.ty_int()),
+        PrintTarget::Str => args.push(cx.ty_ptr()),
+    }
+    args.push(cx.ty_ptr());
     args.extend(arg_tys.iter().map(|arg| lltype(&arg.ty, cx)));
-    let fun_ty = cx.ty_func(&args, cx.ty_void());
+    // index of the format literal parameter
+    let fmt_param = args.len() as u32 - arg_tys.len() as u32 - 1;
+    let ret_ty = if target == PrintTarget::Str { cx.ty_ptr() } else { cx.ty_void() };
+    let fun_ty = cx.ty_func(&args, ret_ty);
     let name = cx.local_callback_name();
     let fun = cx.declare_int_fn(&name, fun_ty);
     unsafe {
@@ -284,8 +437,8 @@ fn print_callback<'ll>(
 
         LLVMPositionBuilderAtEnd(llbuilder, entry_bb);
         let handle = LLVMGetParam(fun, 0);
-        let fmt_lit = LLVMGetParam(fun, 1);
-        let mut args = vec![cx.const_null_ptr(), cx.const_usize(0), LLVMGetParam(fun, 1)];
+        let fmt_lit = LLVMGetParam(fun, fmt_param);
+        let mut args = vec![cx.const_null_ptr(), cx.const_usize(0), fmt_lit];
 
         let exp_table = cx.get_declared_value("EXP").expect("constant EXP missing from stdlib");
         let exp_table_ty = cx.ty_array(cx.ty_double(), 11);
@@ -300,7 +453,7 @@ fn print_callback<'ll>(
         let mut free = Vec::new();
 
         for (i, arg) in arg_tys.iter().enumerate() {
-            let val = LLVMGetParam(fun, i as u32 + 2);
+            let val = LLVMGetParam(fun, i as u32 + fmt_param + 1);
             match arg.kind {
                 FmtArgKind::Binary => {
                     let formatted_str = LLVMBuildCall2(
@@ -346,7 +499,9 @@ fn print_callback<'ll>(
                 FmtArgKind::Other => args.push(val),
             }
         }
-        args.extend((1..(2 + arg_tys.len())).map(|arg| LLVMGetParam(fun, arg as u32)));
+        args.extend(
+            (fmt_param..(fmt_param + 1 + arg_tys.len() as u32)).map(|arg| LLVMGetParam(fun, arg)),
+        );
         let (fun_ty, fun) = cx.intrinsic("snprintf").unwrap();
         let len = LLVMBuildCall2(llbuilder, fun_ty, fun, args.as_ptr(), args.len() as u32, UNNAMED);
         let is_err = LLVMBuildICmp(llbuilder, IntPredicate::IntSLT, len, cx.const_int(0), UNNAMED);
@@ -389,11 +544,42 @@ fn print_callback<'ll>(
         LLVMAddIncoming(flags, [lvl, lvl_and_err].as_ptr(), [write_bb, err_bb].as_ptr(), 2);
         let msg = LLVMBuildPhi(llbuilder, cx.ty_ptr(), UNNAMED);
         LLVMAddIncoming(msg, [ptr, fmt_lit].as_ptr(), [write_bb, err_bb].as_ptr(), 2);
-        let fun_ptr = cx.get_declared_value("osdi_log").expect("symbol osdi_log is missing");
-        let fun_ty = cx.ty_func(&[cx.ty_ptr(), cx.ty_ptr(), cx.ty_int()], cx.ty_void());
-        let fun = LLVMBuildLoad2(llbuilder, cx.ty_ptr(), fun_ptr, UNNAMED);
-        LLVMBuildCall2(llbuilder, fun_ty, fun, [handle, msg, flags].as_ptr(), 3, UNNAMED);
-        llvm::LLVMBuildRetVoid(llbuilder);
+        match target {
+            PrintTarget::Log => {
+                let fun_ptr =
+                    cx.get_declared_value("osdi_log").expect("symbol osdi_log is missing");
+                let fun_ty = cx.ty_func(&[cx.ty_ptr(), cx.ty_ptr(), cx.ty_int()], cx.ty_void());
+                let fun = LLVMBuildLoad2(llbuilder, cx.ty_ptr(), fun_ptr, UNNAMED);
+                LLVMBuildCall2(llbuilder, fun_ty, fun, [handle, msg, flags].as_ptr(), 3, UNNAMED);
+                llvm::LLVMBuildRetVoid(llbuilder);
+            }
+            PrintTarget::File => {
+                let fd = LLVMGetParam(fun, 1);
+                let fun_ty =
+                    cx.ty_func(&[cx.ty_ptr(), cx.ty_int(), cx.ty_ptr(), cx.ty_int()], cx.ty_void());
+                let fun =
+                    cx.get_func_by_name("file_write").expect("file_write missing from stdlib");
+                LLVMBuildCall2(
+                    llbuilder,
+                    fun_ty,
+                    fun,
+                    [handle, fd, msg, flags].as_ptr(),
+                    4,
+                    UNNAMED,
+                );
+                llvm::LLVMBuildRetVoid(llbuilder);
+            }
+            PrintTarget::Str => {
+                let slot = LLVMGetParam(fun, 1);
+                let fun_ty = cx.ty_func(&[cx.ty_ptr(), cx.ty_ptr(), cx.ty_int()], cx.ty_ptr());
+                let fun = cx
+                    .get_func_by_name("sformat_store")
+                    .expect("sformat_store missing from stdlib");
+                let res =
+                    LLVMBuildCall2(llbuilder, fun_ty, fun, [slot, msg, flags].as_ptr(), 3, UNNAMED);
+                llvm::LLVMBuildRet(llbuilder, res);
+            }
+        }
         llvm::LLVMDisposeBuilder(llbuilder);
     }
 
@@ -1,4 +1,4 @@
-use hir_lower::{CallBackKind, CurrentKind, LimitState, ParamKind};
+use hir_lower::{CallBackKind, LimitState, ParamKind};
 use llvm::IntPredicate::{IntNE, IntULT};
 use llvm::{
     LLVMAppendBasicBlockInContext, LLVMBuildAlloca, LLVMBuildAnd, LLVMBuildBr, LLVMBuildCall2,
@@ -12,9 +12,9 @@ use sim_back::SimUnknownKind;
 use typed_index_collections::TiVec;
 
 use crate::bitfield::{is_flag_set, is_flag_set_mem, is_flag_unset};
-use crate::compilation_unit::{general_callbacks, OsdiCompilationUnit};
-use crate::inst_data::OsdiInstanceParam;
-use crate::metadata::osdi_0_4::{
+use crate::compilation_unit::{format_callback, general_callbacks, OsdiCompilationUnit};
+use crate::inst_data::{FormatStr, OsdiInstanceParam};
+use crate::metadata::osdi_0_5::{
     ANALYSIS_IC, CALC_NOISE, CALC_OP, CALC_REACT_JACOBIAN, CALC_REACT_LIM_RHS, CALC_REACT_RESIDUAL,
     CALC_RESIST_JACOBIAN, CALC_RESIST_LIM_RHS, CALC_RESIST_RESIDUAL, ENABLE_LIM, EVAL_RET_FLAG_LIM,
     INIT_LIM,
@@ -126,7 +126,7 @@ impl<'ll> OsdiCompilationUnit<'_, '_, 'll> {
             }
         };
 
-        let state_idx: TiVec<LimitState, _> = (0..intern.lim_state.len())
+        let state_idx: TiVec<LimitState, _> = (0..intern.num_states())
             .map(|i| unsafe { inst_data.read_state_idx(cx, i.into(), instance, builder.llbuilder) })
             .collect();
 
@@ -157,7 +157,6 @@ impl<'ll> OsdiCompilationUnit<'_, '_, 'll> {
                                 hi
                             }
                         }
-                        ParamKind::Current(CurrentKind::Port(_)) => cx.const_real(0.0),
                         ParamKind::Abstime => {
                             let loc = MemLoc::struct_gep(
                                 sim_info,
@@ -297,6 +296,107 @@ impl<'ll> OsdiCompilationUnit<'_, '_, 'll> {
                     let fun_ty = cx.ty_func(&[cx.ty_ptr()], cx.ty_void());
                     CallbackFun::Prebuilt(BuiltCallbackFun { fun_ty, fun, state: Box::new([ret_flags]), num_state: 0 })
                 }
+                CallBackKind::LastCrossing(state) => {
+                    let fun = builder
+                        .cx
+                        .get_func_by_name("last_crossing")
+                        .expect("stdlib function last_crossing is missing");
+                    let fun_ty = cx.ty_func(
+                        &[
+                            cx.ty_ptr(),
+                            cx.ty_int(),
+                            cx.ty_int(),
+                            cx.ty_int(),
+                            cx.ty_int(),
+                            cx.ty_int(),
+                            cx.ty_double(),
+                            cx.ty_int(),
+                            cx.ty_double(),
+                        ],
+                        cx.ty_double(),
+                    );
+                    let [val, time, crossing, prev_val, prev_time] =
+                        intern.crossing_state_slots(state);
+                    CallbackFun::Prebuilt(BuiltCallbackFun {
+                        fun_ty,
+                        fun,
+                        state: Box::new([
+                            sim_info,
+                            state_idx[val],
+                            state_idx[time],
+                            state_idx[crossing],
+                            state_idx[prev_val],
+                            state_idx[prev_time],
+                        ]),
+                        num_state: 0,
+                    })
+                }
+                CallBackKind::CrossEvent(state) => {
+                    let fun = builder
+                        .cx
+                        .get_func_by_name("cross_event")
+                        .expect("stdlib function cross_event is missing");
+                    let fun_ty = cx.ty_func(
+                        &[
+                            cx.ty_ptr(),
+                            cx.ty_int(),
+                            cx.ty_int(),
+                            cx.ty_int(),
+                            cx.ty_int(),
+                            cx.ty_int(),
+                            cx.ty_double(),
+                            cx.ty_int(),
+                            cx.ty_double(),
+                            cx.ty_double(),
+                            cx.ty_double(),
+                        ],
+                        cx.ty_int(),
+                    );
+                    let [val, time, crossing, prev_val, prev_time] =
+                        intern.crossing_state_slots(state);
+                    CallbackFun::Prebuilt(BuiltCallbackFun {
+                        fun_ty,
+                        fun,
+                        state: Box::new([
+                            sim_info,
+                            state_idx[val],
+                            state_idx[time],
+                            state_idx[crossing],
+                            state_idx[prev_val],
+                            state_idx[prev_time],
+                        ]),
+                        num_state: 0,
+                    })
+                }
+                CallBackKind::Past(state) => {
+                    let fun = builder
+                        .cx
+                        .get_func_by_name("past")
+                        .expect("stdlib function past is missing");
+                    let fun_ty = cx.ty_func(
+                        &[
+                            cx.ty_ptr(),
+                            cx.ty_int(),
+                            cx.ty_int(),
+                            cx.ty_int(),
+                            cx.ty_double(),
+                            cx.ty_double(),
+                        ],
+                        cx.ty_double(),
+                    );
+                    let [past, val, time] = intern.past_state_slots(state);
+                    CallbackFun::Prebuilt(BuiltCallbackFun {
+                        fun_ty,
+                        fun,
+                        state: Box::new([
+                            sim_info,
+                            state_idx[past],
+                            state_idx[val],
+                            state_idx[time],
+                        ]),
+                        num_state: 0,
+                    })
+                }
                 CallBackKind::Analysis => {
                     let fun = builder
                         .cx
@@ -305,6 +405,12 @@ impl<'ll> OsdiCompilationUnit<'_, '_, 'll> {
                     let fun_ty = cx.ty_func(&[cx.ty_ptr(), cx.ty_ptr()], cx.ty_int());
                     CallbackFun::Prebuilt(BuiltCallbackFun { fun_ty, fun, state: Box::new([sim_info]), num_state: 0 })
                 }
+                CallBackKind::Format { ref arg_tys } => {
+                    let str_ptr = unsafe {
+                        inst_data.format_str_ptr(FormatStr::Eval(func), instance, builder.llbuilder)
+                    };
+                    format_callback(cx, arg_tys, handle, str_ptr)
+                }
                 _ => continue,
             };
             builder.callbacks[func] = Some(cb);
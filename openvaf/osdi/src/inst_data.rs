@@ -1,13 +1,13 @@
 use ahash::RandomState;
 use hir::{CompilationDB, ParamSysFun, Parameter, Variable};
-use hir_lower::{HirInterner, LimitState, ParamKind, PlaceKind};
-use indexmap::IndexMap;
+use hir_lower::{CallBackKind, HirInterner, LimitState, ParamKind, PlaceKind};
+use indexmap::{IndexMap, IndexSet};
 use llvm::{
     IntPredicate, LLVMBuildFAdd, LLVMBuildFSub, LLVMBuildGEP2, LLVMBuildICmp, LLVMBuildIntCast2,
     LLVMBuildLoad2, LLVMBuildStore, LLVMBuildStructGEP2, LLVMConstInt, LLVMOffsetOfElement,
     LLVMSetFastMath, TargetData, UNNAMED,
 };
-use mir::{strip_optbarrier, Const, Function, Param, ValueDef, F_ZERO};
+use mir::{strip_optbarrier, Const, FuncRef, Function, Param, ValueDef, F_ZERO};
 use mir_llvm::{CodegenCx, MemLoc};
 use sim_back::dae::{self, MatrixEntryId, SimUnknown};
 use sim_back::init::CacheSlot;
@@ -25,6 +25,14 @@ pub enum OsdiInstanceParam {
     User(Parameter),
 }
 
+/// A `$sformat` call in either `eval` or `setup_instance`. The string it
+/// formatted last is owned by the instance and freed by `free_instance`.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum FormatStr {
+    Eval(FuncRef),
+    Init(FuncRef),
+}
+
 pub const NUM_CONST_FIELDS: u32 = 8;
 pub const PARAM_GIVEN: u32 = 0;
 pub const JACOBIAN_PTR_RESIST: u32 = 1;
@@ -203,6 +211,7 @@ pub struct OsdiInstanceData<'ll> {
     pub params: IndexMap<OsdiInstanceParam, &'ll llvm::Type, RandomState>,
     pub eval_outputs: TiMap<EvalOutputSlot, mir::Value, &'ll llvm::Type>,
     pub cache_slots: TiVec<CacheSlot, &'ll llvm::Type>,
+    pub format_strs: IndexSet<FormatStr, RandomState>,
 
     pub residual: TiVec<SimUnknown, Residual>,
     pub noise: Vec<NoiseSource>,
@@ -229,9 +238,10 @@ impl<'ll> OsdiInstanceData<'ll> {
             .sys_fun_alias
             .keys()
             .map(|param| (OsdiInstanceParam::Builtin(*param), ty_f64));
-        let user_inst_params = module.info.params.iter().filter_map(|(param, info)| {
-            info.is_instance.then(|| (OsdiInstanceParam::User(*param), lltype(&param.ty(db), cx)))
-        });
+        let user_inst_params = module
+            .info
+            .instance_params()
+            .map(|(param, _)| (OsdiInstanceParam::User(param), lltype(&param.ty(db), cx)));
         let params: IndexMap<_, _, _> =
             builtin_inst_params.chain(alias_inst_params).chain(user_inst_params).collect();
 
@@ -284,7 +294,21 @@ impl<'ll> OsdiInstanceData<'ll> {
         let cache_slots: TiVec<_, _> =
             module.init.cache_slots.raw.values().map(|ty| lltype(ty, cx)).collect();
 
-        let state_idx = cx.ty_array(cx.ty_int(), module.intern.lim_state.len() as u32);
+        let format_calls = |intern: &HirInterner| {
+            intern
+                .callbacks
+                .iter_enumerated()
+                .filter(|(_, kind)| matches!(kind, CallBackKind::Format { .. }))
+                .map(|(func, _)| func)
+                .collect::<Vec<_>>()
+        };
+        let format_strs: IndexSet<_, _> = format_calls(module.intern)
+            .into_iter()
+            .map(FormatStr::Eval)
+            .chain(format_calls(&module.init.intern).into_iter().map(FormatStr::Init))
+            .collect();
+
+        let state_idx = cx.ty_array(cx.ty_int(), module.intern.num_states() as u32);
         let static_fields: [_; NUM_CONST_FIELDS as usize] = [
             param_given,
             jacobian_ptr,
@@ -301,6 +325,7 @@ impl<'ll> OsdiInstanceData<'ll> {
             .chain(params.values().copied())
             .chain(cache_slots.iter().copied())
             .chain(eval_outputs.raw.values().copied())
+            .chain(format_strs.iter().map(|_| cx.ty_ptr()))
             .collect();
 
         let name = &module.sym;
@@ -318,6 +343,7 @@ impl<'ll> OsdiInstanceData<'ll> {
             params,
             eval_outputs,
             cache_slots,
+            format_strs,
             residual,
             noise,
             opvars,
@@ -481,6 +507,22 @@ impl<'ll> OsdiInstanceData<'ll> {
             + u32::from(slot)
     }
 
+    /// Pointer to the field that holds the string formatted last by a `$sformat` call.
+    pub unsafe fn format_str_ptr(
+        &self,
+        format: FormatStr,
+        ptr: &'ll llvm::Value,
+        llbuilder: &llvm::Builder<'ll>,
+    ) -> &'ll llvm::Value {
+        let pos = self.format_strs.get_index_of(&format).unwrap();
+        let elem = NUM_CONST_FIELDS
+            + self.params.len() as u32
+            + self.cache_slots.len() as u32
+            + self.eval_outputs.len() as u32
+            + pos as u32;
+        LLVMBuildStructGEP2(llbuilder, self.ty, ptr, elem, UNNAMED)
+    }
+
     unsafe fn load_eval_output_slot(
         &self,
         llbuilder: &llvm::Builder<'ll>,
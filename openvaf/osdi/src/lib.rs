@@ -4,20 +4,22 @@ use hir::{CompilationDB, ParamSysFun, Type};
 use hir_lower::{CallBackKind, HirInterner, ParamKind};
 use lasso::Rodeo;
 use llvm::{LLVMABISizeOfType, LLVMDisposeTargetData, LLVMPrintModuleToString, OptLevel};
-use mir_llvm::{CodegenCx, LLVMBackend};
+use mir_llvm::{CodegenCx, LLVMBackend, ModuleLlvm};
 use salsa::ParallelDatabase;
 use sim_back::{CompiledModule, ModuleInfo};
+use stdx::timing::PhaseTimer;
 use stdx::{impl_debug_display, impl_idx_from};
 use target::spec::Target;
 use typed_index_collections::TiVec;
 use typed_indexmap::TiSet;
 
 use std::ffi::{CStr, CString};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
 use std::sync::{Arc, Mutex};
 
-use crate::compilation_unit::{new_codegen, OsdiCompilationUnit, OsdiModule};
-use crate::metadata::osdi_0_4::OsdiTys;
+use crate::compilation_unit::{host_function_symbol, new_codegen, OsdiCompilationUnit, OsdiModule};
+use crate::metadata::osdi_0_5::{OsdiTys, OSDI_VERSION_MAJOR_CURR, OSDI_VERSION_MINOR_CURR};
 use crate::metadata::OsdiLimFunction;
 
 mod access;
@@ -32,7 +34,15 @@ mod load;
 mod noise;
 mod setup;
 
-const OSDI_VERSION: (u32, u32) = (0, 4);
+/// The OSDI ABI version implemented by the generated libraries. It is exported as
+/// `OSDI_VERSION_MAJOR`/`OSDI_VERSION_MINOR` so that simulators can reject libraries
+/// they can not load.
+///
+/// The ABI is defined by the C header in `header/` and this version is taken directly from
+/// that header. Any change to the layout of the OSDI structs or the meaning of the
+/// flags/return codes requires a new header (and therefore a new version):
+/// additions bump the minor version, incompatible changes bump the major version.
+const OSDI_VERSION: (u32, u32) = (OSDI_VERSION_MAJOR_CURR, OSDI_VERSION_MINOR_CURR);
 
 pub fn compile<'a>(
     db: &'a CompilationDB,
@@ -45,18 +55,26 @@ pub fn compile<'a>(
     dump_mir: bool, 
     dump_unopt_mir: bool, 
     dump_ir: bool, 
+    emit_llvm_ir: bool,
+    print_stats: bool,
+    print_timing: bool,
+    gmin: bool,
 ) -> (Vec<Utf8PathBuf>, Vec<CompiledModule<'a>>, Rodeo) {
     let mut literals = Rodeo::new();
     let mut lim_table = TiSet::default();
+    let mut host_functions = BTreeSet::new();
     let mnames: Vec<_> = modules.iter().map(|m| {m.module.name(db)}).collect();
     let modules: Vec<_> = modules
         .iter()
         .map(|module| {
-            let mir = CompiledModule::new(db, module, &mut literals, dump_unopt_mir, dump_mir);
+            let mir = CompiledModule::new(db, module, &mut literals, dump_unopt_mir, dump_mir, print_stats, print_timing, gmin);
             for cb in mir.intern.callbacks.iter() {
                 if let CallBackKind::BuiltinLimit { name, num_args } = *cb {
                     lim_table.ensure(OsdiLimFunction { name, num_args: num_args - 2 });
                 }
+                if let CallBackKind::HostFunction(fun) = cb {
+                    host_functions.insert(host_function_symbol(fun));
+                }
             }
             mir
         })
@@ -93,6 +111,7 @@ pub fn compile<'a>(
     let main_file = dst.with_extension("o");
     
     let irs = Arc::new(Mutex::new(HashMap::new()));
+    let mut timer = PhaseTimer::new(print_timing);
     
     rayon_core::scope(|scope| {
         let db = db;
@@ -115,11 +134,18 @@ pub fn compile<'a>(
                     let mut irs = irs_clone.lock().unwrap();
                     irs.insert((i, access), cx.to_str().to_string());
                 }
-                debug_assert!(llmod.verify_and_print());
+                debug_verify(&llmod);
+
+                if emit_llvm_ir {
+                    write_llvm_ir(&llmod, dst, Some(&access), false);
+                }
 
                 if emit {
                     let path = &paths[i * 4];
                     llmod.optimize();
+                    if emit_llvm_ir {
+                        write_llvm_ir(&llmod, dst, Some(&access), true);
+                    }
                     assert_eq!(llmod.emit_object(path.as_ref()), Ok(()))
                 }
             });
@@ -138,7 +164,11 @@ pub fn compile<'a>(
                     let mut irs = irs_clone.lock().unwrap();
                     irs.insert((i, "setup_model".to_string()), cx.to_str().to_string());
                 }
-                debug_assert!(llmod.verify_and_print());
+                debug_verify(&llmod);
+
+                if emit_llvm_ir {
+                    write_llvm_ir(&llmod, dst, Some(&name), false);
+                }
 
                 if emit {
                     let path = &paths[i * 4 + 1];
@@ -161,11 +191,18 @@ pub fn compile<'a>(
                     let mut irs = irs_clone.lock().unwrap();
                     irs.insert((i, "setup_instance".to_string()), cx.to_str().to_string());
                 }
-                debug_assert!(llmod.verify_and_print());
+                debug_verify(&llmod);
+
+                if emit_llvm_ir {
+                    write_llvm_ir(&llmod, dst, Some(&name), false);
+                }
 
                 if emit {
                     let path = &paths[i * 4 + 2];
                     llmod.optimize();
+                    if emit_llvm_ir {
+                        write_llvm_ir(&llmod, dst, Some(&name), true);
+                    }
                     assert_eq!(llmod.emit_object(path.as_ref()), Ok(()))
                 }
             });
@@ -184,11 +221,18 @@ pub fn compile<'a>(
                     let mut irs = irs_clone.lock().unwrap();
                     irs.insert((i, "eval".to_string()), cx.to_str().to_string());
                 }
-                debug_assert!(llmod.verify_and_print());
+                debug_verify(&llmod);
+
+                if emit_llvm_ir {
+                    write_llvm_ir(&llmod, dst, Some(&access), false);
+                }
 
                 if emit {
                     let path = &paths[i * 4 + 3];
                     llmod.optimize();
+                    if emit_llvm_ir {
+                        write_llvm_ir(&llmod, dst, Some(&access), true);
+                    }
                     assert_eq!(llmod.emit_object(path.as_ref()), Ok(()))
                 }
             });
@@ -250,25 +294,34 @@ pub fn compile<'a>(
             );
         }
 
-        let osdi_log =
-            cx.get_declared_value("osdi_log").expect("symbol osdi_log missing from std lib");
-        let val = cx.const_null_ptr();
-        unsafe {
-            llvm::LLVMSetInitializer(osdi_log, val);
-            llvm::LLVMSetLinkage(osdi_log, llvm::Linkage::ExternalLinkage);
-            llvm::LLVMSetUnnamedAddress(osdi_log, llvm::UnnamedAddr::No);
-            llvm::LLVMSetDLLStorageClass(osdi_log, llvm::DLLStorageClass::Export);
+        // function pointers that are set by the simulator after loading the library
+        for name in ["osdi_log", "osdi_fopen", "osdi_fwrite", "osdi_fclose"] {
+            let sym = cx
+                .get_declared_value(name)
+                .unwrap_or_else(|| panic!("symbol {name} missing from std lib"));
+            let val = cx.const_null_ptr();
+            unsafe { llvm::LLVMSetInitializer(sym, val) };
+            cx.export_global(sym);
+        }
+        for sym in &host_functions {
+            cx.export_val(sym, cx.ty_ptr(), cx.const_null_ptr(), false);
         }
 
-        debug_assert!(llmod.verify_and_print());
+        debug_verify(&llmod);
+
+        if emit_llvm_ir {
+            write_llvm_ir(&llmod, dst, None, false);
+        }
 
         if emit {
-            // println!("{}", llmod.to_str());
             llmod.optimize();
-            // println!("{}", llmod.to_str());
+            if emit_llvm_ir {
+                write_llvm_ir(&llmod, dst, None, true);
+            }
             assert_eq!(llmod.emit_object(main_file.as_ref()), Ok(()))
         }
     });
+    timer.finish("LLVM code generation and optimization");
 
     if dump_ir {
         let irs_clone = Arc::clone(&irs);
@@ -286,6 +339,35 @@ pub fn compile<'a>(
     (paths, compiled_modules, literals)
 }
 
+/// Writes the LLVM IR of `llmod` next to `dst`. The IR of the codegen unit `unit` is written
+/// to `<dst>.<unit>.ll` before and to `<dst>.<unit>.opt.ll` after the optimization pipeline ran.
+fn write_llvm_ir(llmod: &ModuleLlvm, dst: &Utf8Path, unit: Option<&str>, optimized: bool) {
+    let mut extension = unit.map_or_else(String::new, |unit| format!("{unit}."));
+    if optimized {
+        extension.push_str("opt.");
+    }
+    extension.push_str("ll");
+    let path = dst.with_extension(extension);
+    if let Err(err) = fs::write(&path, llmod.print_ir().to_bytes()) {
+        panic!("failed to write LLVM IR to {path}: {err}")
+    }
+}
+
+/// Invalid LLVM IR is a compiler bug. In debug builds it is reported with the verifier errors
+/// of each invalid function.
+fn debug_verify(llmod: &ModuleLlvm) {
+    if cfg!(debug_assertions) {
+        let errors = llmod.verify_and_collect();
+        if !errors.is_empty() {
+            let mut msg = String::new();
+            for (fun, err) in errors {
+                msg.push_str(&format!("\n{fun}: {err}"));
+            }
+            panic!("generated invalid LLVM IR:{msg}")
+        }
+    }
+}
+
 impl OsdiModule<'_> {
     fn intern_names(&self, literals: &mut Rodeo, db: &CompilationDB) {
         literals.get_or_intern(&*self.info.module.name(db));
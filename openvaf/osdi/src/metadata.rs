@@ -15,16 +15,16 @@ use crate::inst_data::{
     OsdiInstanceParam, COLLAPSED, JACOBIAN_PTR_REACT, JACOBIAN_PTR_RESIST, NODE_MAPPING, STATE_IDX
 };
 use crate::load::JacobianLoadType;
-use crate::metadata::osdi_0_4::{
-    OsdiDescriptor, OsdiJacobianEntry, OsdiNode, OsdiNodePair, OsdiNoiseSource, OsdiParamOpvar,
-    OsdiTys, JACOBIAN_ENTRY_REACT, JACOBIAN_ENTRY_REACT_CONST, JACOBIAN_ENTRY_RESIST,
-    JACOBIAN_ENTRY_RESIST_CONST, PARA_KIND_INST, PARA_KIND_MODEL, PARA_KIND_OPVAR, PARA_TY_INT,
-    PARA_TY_REAL, PARA_TY_STR,
+use crate::metadata::osdi_0_5::{
+    OsdiDescriptor, OsdiJacobianEntry, OsdiNode, OsdiNodePair, OsdiNoiseCorrelation,
+    OsdiNoiseSource, OsdiParamOpvar, OsdiTys, JACOBIAN_ENTRY_REACT, JACOBIAN_ENTRY_REACT_CONST,
+    JACOBIAN_ENTRY_RESIST, JACOBIAN_ENTRY_RESIST_CONST, PARA_KIND_INST, PARA_KIND_MODEL,
+    PARA_KIND_OPVAR, PARA_TY_INT, PARA_TY_REAL, PARA_TY_STR,
 };
 use crate::ty_len;
 
 #[allow(unused_parens, dead_code)]
-pub mod osdi_0_4;
+pub mod osdi_0_5;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct OsdiLimFunction {
@@ -34,7 +34,7 @@ pub struct OsdiLimFunction {
 
 impl OsdiLimFunction {
     pub fn to_ll_val<'ll>(self, ctx: &CodegenCx<'_, 'll>, tys: &'ll OsdiTys) -> &'ll llvm::Value {
-        osdi_0_4::OsdiLimFunction {
+        osdi_0_5::OsdiLimFunction {
             name: ctx.literals.resolve(&self.name).to_owned(),
             num_args: self.num_args,
             func_ptr: ctx.const_null_ptr(),
@@ -185,6 +185,7 @@ impl<'ll> OsdiCompilationUnit<'_, '_, 'll> {
         let OsdiCompilationUnit { inst_data, module, .. } = self;
         let mut jacobian_ptr_react_offset =
             unsafe { LLVMOffsetOfElement(target_data, inst_data.ty, JACOBIAN_PTR_REACT) } as u32;
+        let linear_nodes = module.info.linear_nodes(self.db);
 
         module
             .dae_system
@@ -193,12 +194,17 @@ impl<'ll> OsdiCompilationUnit<'_, '_, 'll> {
             .map(|entry| {
                 let mut flags = 0;
                 let mut react_ptr_off = u32::MAX;
+                // rows that only contain (* linear *) contributions are constant
+                let linear = matches!(
+                    module.dae_system.unknowns[entry.row],
+                    SimUnknownKind::KirchoffLaw(node) if linear_nodes.contains(&node)
+                );
 
-                if self.is_const(entry, false) {
+                if linear || self.is_const(entry, false) {
                     flags |= JACOBIAN_ENTRY_RESIST_CONST
                 }
 
-                if self.is_const(entry, true) {
+                if linear || self.is_const(entry, true) {
                     flags |= JACOBIAN_ENTRY_REACT_CONST
                 }
 
@@ -241,6 +247,48 @@ impl<'ll> OsdiCompilationUnit<'_, '_, 'll> {
         .collect()
     }
 
+    pub fn gmin_branches(&self) -> Vec<OsdiNodePair> {
+        self.module
+            .dae_system
+            .gmin_branches
+            .iter()
+            .map(|&(node1, node2)| OsdiNodePair {
+                node_1: node1.into(),
+                node_2: node2.map_or(u32::MAX, u32::from),
+            })
+            .collect()
+    }
+
+    /// A `$noise_correlation` applies to all (named) noise sources with a matching name, sources
+    /// that were removed because their power is zero are skipped.
+    pub fn noise_correlations(&self) -> Vec<OsdiNoiseCorrelation> {
+        let OsdiCompilationUnit { module, cx, .. } = *self;
+        let names: Vec<_> = module
+            .dae_system
+            .noise_sources
+            .iter()
+            .map(|src| cx.literals.resolve(&src.name))
+            .collect();
+        let indices = |name: &str| -> Vec<u32> {
+            (0..names.len() as u32).filter(|&i| names[i as usize] == name).collect()
+        };
+
+        let mut res = Vec::new();
+        for corr in &module.info.noise_correlations {
+            for noise_1 in indices(&corr.noise_1) {
+                for noise_2 in indices(&corr.noise_2) {
+                    res.push(OsdiNoiseCorrelation {
+                        noise_1,
+                        noise_2,
+                        real: corr.real,
+                        imag: corr.imag,
+                    })
+                }
+            }
+        }
+        res
+    }
+
     pub fn descriptor(
         &self,
         target_data: &llvm::TargetData,
@@ -248,6 +296,8 @@ impl<'ll> OsdiCompilationUnit<'_, '_, 'll> {
     ) -> OsdiDescriptor<'ll> {
         let collapsible = self.collapsible();
         let inputs = self.inputs();
+        let gmin_branches = self.gmin_branches();
+        let noise_correlations = self.noise_correlations();
         let OsdiCompilationUnit { ref inst_data, ref model_data, module, cx, .. } = *self;
         
         unsafe {
@@ -316,7 +366,7 @@ impl<'ll> OsdiCompilationUnit<'_, '_, 'll> {
                 load_jacobian_resist: self.load_jacobian(JacobianLoadType::Resist, false),
                 load_jacobian_react: self.load_jacobian(JacobianLoadType::React, false),
                 load_jacobian_tran: self.load_jacobian(JacobianLoadType::Tran, false),
-                num_states: self.module.intern.lim_state.len() as u32,
+                num_states: self.module.intern.num_states() as u32,
                 load_limit_rhs_resist: self.load_lim_rhs(false),
                 load_limit_rhs_react: self.load_lim_rhs(true),
                 given_flag_model: self.given_flag_model(), 
@@ -329,6 +379,11 @@ impl<'ll> OsdiCompilationUnit<'_, '_, 'll> {
                 inputs: inputs, 
                 load_jacobian_with_offset_resist: self.load_jacobian(JacobianLoadType::Resist, true),
                 load_jacobian_with_offset_react: self.load_jacobian(JacobianLoadType::React, true),
+                num_gmin_branches: gmin_branches.len() as u32,
+                gmin_branches,
+                num_noise_correlations: noise_correlations.len() as u32,
+                noise_correlations,
+                free_instance: self.free_instance(),
             }
         }
     }
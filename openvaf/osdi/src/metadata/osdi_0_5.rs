@@ -3,17 +3,17 @@
 use mir_llvm::CodegenCx;
 
 const STDLIB_BITCODE_X86_64_UNKNOWN_LINUX_GNU: &[u8] =
-    include_bytes!(concat!(env!("OUT_DIR"), "/stdlib_0_4_x86_64-unknown-linux-gnu.bc"));
+    include_bytes!(concat!(env!("OUT_DIR"), "/stdlib_0_5_x86_64-unknown-linux-gnu.bc"));
 const STDLIB_BITCODE_X86_64_PC_WINDOWS_MSVC: &[u8] =
-    include_bytes!(concat!(env!("OUT_DIR"), "/stdlib_0_4_x86_64-pc-windows-msvc.bc"));
+    include_bytes!(concat!(env!("OUT_DIR"), "/stdlib_0_5_x86_64-pc-windows-msvc.bc"));
 const STDLIB_BITCODE_X86_64_APPLE_MACOSX10_15_0: &[u8] =
-    include_bytes!(concat!(env!("OUT_DIR"), "/stdlib_0_4_x86_64-apple-macosx10.15.0.bc"));
+    include_bytes!(concat!(env!("OUT_DIR"), "/stdlib_0_5_x86_64-apple-macosx10.15.0.bc"));
 const STDLIB_BITCODE_AARCH64_UNKNOWN_LINUX_GNU: &[u8] =
-    include_bytes!(concat!(env!("OUT_DIR"), "/stdlib_0_4_aarch64-unknown-linux-gnu.bc"));
+    include_bytes!(concat!(env!("OUT_DIR"), "/stdlib_0_5_aarch64-unknown-linux-gnu.bc"));
 const STDLIB_BITCODE_AARCH64_PC_WINDOWS_MSVC: &[u8] =
-    include_bytes!(concat!(env!("OUT_DIR"), "/stdlib_0_4_aarch64-pc-windows-msvc.bc"));
+    include_bytes!(concat!(env!("OUT_DIR"), "/stdlib_0_5_aarch64-pc-windows-msvc.bc"));
 const STDLIB_BITCODE_ARM64_APPLE_MACOSX11_0_0: &[u8] =
-    include_bytes!(concat!(env!("OUT_DIR"), "/stdlib_0_4_arm64-apple-macosx11.0.0.bc"));
+    include_bytes!(concat!(env!("OUT_DIR"), "/stdlib_0_5_arm64-apple-macosx11.0.0.bc"));
 pub fn stdlib_bitcode(target: &target::spec::Target) -> &'static [u8] {
     match &*target.llvm_target {
         "x86_64-unknown-linux-gnu" => STDLIB_BITCODE_X86_64_UNKNOWN_LINUX_GNU,
@@ -26,7 +26,7 @@ pub fn stdlib_bitcode(target: &target::spec::Target) -> &'static [u8] {
     }
 }
 pub const OSDI_VERSION_MAJOR_CURR: u32 = 0;
-pub const OSDI_VERSION_MINOR_CURR: u32 = 4;
+pub const OSDI_VERSION_MINOR_CURR: u32 = 5;
 pub const PARA_TY_MASK: u32 = 3;
 pub const PARA_TY_REAL: u32 = 0;
 pub const PARA_TY_INT: u32 = 1;
@@ -292,6 +292,32 @@ impl OsdiTyBuilder<'_, '_, '_> {
         self.osdi_noise_source = Some(ty);
     }
 }
+pub struct OsdiNoiseCorrelation {
+    pub noise_1: u32,
+    pub noise_2: u32,
+    pub real: f64,
+    pub imag: f64,
+}
+impl OsdiNoiseCorrelation {
+    pub fn to_ll_val<'ll>(&self, ctx: &CodegenCx<'_, 'll>, tys: &'ll OsdiTys) -> &'ll llvm::Value {
+        let fields = [
+            ctx.const_unsigned_int(self.noise_1),
+            ctx.const_unsigned_int(self.noise_2),
+            ctx.const_real(self.real),
+            ctx.const_real(self.imag),
+        ];
+        let ty = tys.osdi_noise_correlation;
+        ctx.const_struct(ty, &fields)
+    }
+}
+impl OsdiTyBuilder<'_, '_, '_> {
+    fn osdi_noise_correlation(&mut self) {
+        let ctx = self.ctx;
+        let fields = [ctx.ty_int(), ctx.ty_int(), ctx.ty_double(), ctx.ty_double()];
+        let ty = ctx.ty_struct("OsdiNoiseCorrelation", &fields);
+        self.osdi_noise_correlation = Some(ty);
+    }
+}
 pub struct OsdiDescriptor<'ll> {
     pub name: String,
     pub num_nodes: u32,
@@ -339,6 +365,11 @@ pub struct OsdiDescriptor<'ll> {
     pub inputs: Vec<OsdiNodePair>,
     pub load_jacobian_with_offset_resist: &'ll llvm::Value,
     pub load_jacobian_with_offset_react: &'ll llvm::Value,
+    pub num_gmin_branches: u32,
+    pub gmin_branches: Vec<OsdiNodePair>,
+    pub num_noise_correlations: u32,
+    pub noise_correlations: Vec<OsdiNoiseCorrelation>,
+    pub free_instance: &'ll llvm::Value,
 }
 impl<'ll> OsdiDescriptor<'ll> {
     pub fn to_ll_val(&self, ctx: &CodegenCx<'_, 'll>, tys: &'ll OsdiTys) -> &'ll llvm::Value {
@@ -348,6 +379,9 @@ impl<'ll> OsdiDescriptor<'ll> {
         let arr_9: Vec<_> = self.noise_sources.iter().map(|it| it.to_ll_val(ctx, tys)).collect();
         let arr_14: Vec<_> = self.param_opvar.iter().map(|it| it.to_ll_val(ctx, tys)).collect();
         let arr_43: Vec<_> = self.inputs.iter().map(|it| it.to_ll_val(ctx, tys)).collect();
+        let arr_47: Vec<_> = self.gmin_branches.iter().map(|it| it.to_ll_val(ctx, tys)).collect();
+        let arr_49: Vec<_> =
+            self.noise_correlations.iter().map(|it| it.to_ll_val(ctx, tys)).collect();
         let fields = [
             ctx.const_str_uninterned(&self.name),
             ctx.const_unsigned_int(self.num_nodes),
@@ -395,6 +429,11 @@ impl<'ll> OsdiDescriptor<'ll> {
             ctx.const_arr_ptr(tys.osdi_node_pair, &arr_43),
             self.load_jacobian_with_offset_resist,
             self.load_jacobian_with_offset_react,
+            ctx.const_unsigned_int(self.num_gmin_branches),
+            ctx.const_arr_ptr(tys.osdi_node_pair, &arr_47),
+            ctx.const_unsigned_int(self.num_noise_correlations),
+            ctx.const_arr_ptr(tys.osdi_noise_correlation, &arr_49),
+            self.free_instance,
         ];
         let ty = tys.osdi_descriptor;
         ctx.const_struct(ty, &fields)
@@ -450,6 +489,11 @@ impl OsdiTyBuilder<'_, '_, '_> {
             ctx.ty_ptr(),
             ctx.ty_ptr(),
             ctx.ty_ptr(),
+            ctx.ty_int(),
+            ctx.ty_ptr(),
+            ctx.ty_int(),
+            ctx.ty_ptr(),
+            ctx.ty_ptr(),
         ];
         let ty = ctx.ty_struct("OsdiDescriptor", &fields);
         self.osdi_descriptor = Some(ty);
@@ -468,6 +512,7 @@ pub struct OsdiTys<'ll> {
     pub osdi_node: &'ll llvm::Type,
     pub osdi_param_opvar: &'ll llvm::Type,
     pub osdi_noise_source: &'ll llvm::Type,
+    pub osdi_noise_correlation: &'ll llvm::Type,
     pub osdi_descriptor: &'ll llvm::Type,
 }
 impl<'ll> OsdiTys<'ll> {
@@ -486,6 +531,7 @@ impl<'ll> OsdiTys<'ll> {
             osdi_node: None,
             osdi_param_opvar: None,
             osdi_noise_source: None,
+            osdi_noise_correlation: None,
             osdi_descriptor: None,
         };
         builder.osdi_lim_function();
@@ -499,6 +545,7 @@ impl<'ll> OsdiTys<'ll> {
         builder.osdi_node();
         builder.osdi_param_opvar();
         builder.osdi_noise_source();
+        builder.osdi_noise_correlation();
         builder.osdi_descriptor();
         builder.finish()
     }
@@ -517,6 +564,7 @@ struct OsdiTyBuilder<'a, 'b, 'll> {
     osdi_node: Option<&'ll llvm::Type>,
     osdi_param_opvar: Option<&'ll llvm::Type>,
     osdi_noise_source: Option<&'ll llvm::Type>,
+    osdi_noise_correlation: Option<&'ll llvm::Type>,
     osdi_descriptor: Option<&'ll llvm::Type>,
 }
 impl<'ll> OsdiTyBuilder<'_, '_, 'll> {
@@ -533,6 +581,7 @@ impl<'ll> OsdiTyBuilder<'_, '_, 'll> {
             osdi_node: self.osdi_node.unwrap(),
             osdi_param_opvar: self.osdi_param_opvar.unwrap(),
             osdi_noise_source: self.osdi_noise_source.unwrap(),
+            osdi_noise_correlation: self.osdi_noise_correlation.unwrap(),
             osdi_descriptor: self.osdi_descriptor.unwrap(),
         }
     }
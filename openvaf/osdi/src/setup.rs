@@ -2,16 +2,16 @@ use hir_lower::{CallBackKind, ParamInfoKind, ParamKind, PlaceKind};
 
 use llvm::IntPredicate::IntSLT;
 use llvm::{
-    LLVMAppendBasicBlockInContext, LLVMBuildBr, LLVMBuildCondBr, LLVMBuildRetVoid,
-    LLVMCreateBuilderInContext, LLVMDisposeBuilder, LLVMGetParam, LLVMPositionBuilderAtEnd,
-    UNNAMED,
+    LLVMAppendBasicBlockInContext, LLVMBuildBr, LLVMBuildCondBr, LLVMBuildFree, LLVMBuildLoad2,
+    LLVMBuildRetVoid, LLVMBuildStore, LLVMCreateBuilderInContext, LLVMDisposeBuilder, LLVMGetParam,
+    LLVMPositionBuilderAtEnd, UNNAMED,
 };
 use mir::ControlFlowGraph;
 use mir_llvm::{Builder, BuilderVal, BuiltCallbackFun, CallbackFun, CodegenCx, InlineCallbackBuilder};
 use sim_back::SimUnknownKind;
 
-use crate::compilation_unit::{general_callbacks, OsdiCompilationUnit};
-use crate::inst_data::OsdiInstanceParam;
+use crate::compilation_unit::{format_callback, general_callbacks, OsdiCompilationUnit};
+use crate::inst_data::{FormatStr, OsdiInstanceParam};
 
 impl<'ll> OsdiCompilationUnit<'_, '_, 'll> {
     fn mark_collapsed(&self) -> (&'ll llvm::Value, &'ll llvm::Type) {
@@ -375,6 +375,16 @@ impl<'ll> OsdiCompilationUnit<'_, '_, 'll> {
                         num_state: 2,
                     })
                 }
+                CallBackKind::Format { arg_tys } => {
+                    let str_ptr = unsafe {
+                        inst_data.format_str_ptr(
+                            FormatStr::Init(call_id),
+                            instance,
+                            builder.llbuilder,
+                        )
+                    };
+                    format_callback(cx, arg_tys, handle, str_ptr)
+                }
                 _ => continue,
             };
 
@@ -459,4 +469,32 @@ impl<'ll> OsdiCompilationUnit<'_, '_, 'll> {
 
         llfunc
     }
+
+    /// Frees the strings owned by the instance (see [`FormatStr`]).
+    /// Simulators must call this before the instance data is released.
+    pub fn free_instance(&self) -> &'ll llvm::Value {
+        let OsdiCompilationUnit { inst_data, cx, .. } = self;
+        let fn_type = cx.ty_func(&[cx.ty_ptr(), cx.ty_ptr()], cx.ty_void());
+        let name = &format!("free_instance_{}", &self.module.sym);
+        let llfunc = cx.declare_int_c_fn(name, fn_type);
+
+        unsafe {
+            let entry = LLVMAppendBasicBlockInContext(cx.llcx, llfunc, UNNAMED);
+            let llbuilder = LLVMCreateBuilderInContext(cx.llcx);
+            LLVMPositionBuilderAtEnd(llbuilder, entry);
+
+            let inst = LLVMGetParam(llfunc, 0);
+            for &format in inst_data.format_strs.iter() {
+                let ptr = inst_data.format_str_ptr(format, inst, llbuilder);
+                let str = LLVMBuildLoad2(llbuilder, cx.ty_ptr(), ptr, UNNAMED);
+                LLVMBuildFree(llbuilder, str);
+                LLVMBuildStore(llbuilder, cx.const_null_ptr(), ptr);
+            }
+
+            LLVMBuildRetVoid(llbuilder);
+            LLVMDisposeBuilder(llbuilder);
+        }
+
+        llfunc
+    }
 }
@@ -7,7 +7,10 @@ extern void *memcpy (void *__restrict __dest, const void *__restrict __src,
 		     size_t __n);
 extern void *malloc (size_t __size);
 extern void *realloc (void *__ptr, size_t __size);
+extern void free (void *__ptr);
 extern double log(double);
+extern double exp(double);
+extern double sqrt(double);
 extern int strcmp(const char*, const char*);
 #define NULL ((void*)0)
 #else
@@ -17,13 +20,13 @@ extern int strcmp(const char*, const char*);
 #include "string.h"
 #endif
 
-#ifndef OSDI_0_4
-#include "header/osdi_0_4.h"
+#ifndef OSDI_0_5
+#include "header/osdi_0_5.h"
 #endif
 
 // no header was included explicitly so just use the newest version
 #ifndef OSDI_VERSION_MAJOR_CURR
-#include "header/osdi_0_4.h"
+#include "header/osdi_0_5.h"
 #endif
 
 
@@ -42,6 +45,50 @@ char *concat(const char *s1, const char *s2) {
 typedef void (*osdi_log_ptr)(void *handle, char *msg, uint32_t lvl);
 extern osdi_log_ptr osdi_log;
 
+// File I/O ($fopen/$fwrite/$fclose) is forwarded to the simulator which owns the
+// file descriptor table. Just like osdi_log these pointers are set by the simulator
+// after loading the library. As the model may be evaluated many times, osdi_fopen
+// should return the already assigned descriptor if a file is opened again.
+// osdi_fwrite receives the same flags as osdi_log (LOG_FMT_ERR if formatting failed)
+// and takes ownership of msg.
+typedef uint32_t (*osdi_fopen_ptr)(void *handle, char *name, char *mode);
+typedef void (*osdi_fwrite_ptr)(void *handle, uint32_t fd, char *msg, uint32_t flags);
+typedef void (*osdi_fclose_ptr)(void *handle, uint32_t fd);
+extern osdi_fopen_ptr osdi_fopen;
+extern osdi_fwrite_ptr osdi_fwrite;
+extern osdi_fclose_ptr osdi_fclose;
+
+uint32_t file_open(void *handle, char *name, char *mode) {
+  if (osdi_fopen == NULL) {
+    return 0;
+  }
+  return osdi_fopen(handle, name, mode);
+}
+
+// simulators without file I/O support still receive the output as a log message
+void file_write(void *handle, uint32_t fd, char *msg, uint32_t flags) {
+  if (osdi_fwrite == NULL) {
+    osdi_log(handle, msg, LOG_LVL_DISPLAY | (flags & LOG_FMT_ERR));
+  } else {
+    osdi_fwrite(handle, fd, msg, flags);
+  }
+}
+
+void file_close(void *handle, uint32_t fd) {
+  if (osdi_fclose != NULL) {
+    osdi_fclose(handle, fd);
+  }
+}
+
+// $sformat: slot is a field of the instance data that owns the string the call site
+// formatted last. It is freed once a new string is formatted or by free_instance.
+// If formatting failed msg is the (static) format literal which must not be freed.
+char *sformat_store(char **slot, char *msg, uint32_t flags) {
+  free(*slot);
+  *slot = (flags & LOG_FMT_ERR) ? NULL : msg;
+  return msg;
+}
+
 #define SCMP(p1, p2, s1, s2, eq) for(p1=s1, p2=s2;*p1 && *p2 && *p1==*p2;p1++, p2++); eq = (*p1==*p2);
 
 double simparam(void *params_, void *handle, uint32_t *flags, char *name) {
@@ -185,6 +232,119 @@ double store_lim(void *sim_info_, int idx, double val) {
   return val;
 }
 
+// last_crossing/cross use five states: the value of the argument, the time and the time of
+// the last crossing (negative if none occurred yet) at the last accepted timepoint as well as
+// the value of the argument and the time of the previous evaluation.
+// prev_state contains the state of the previous Newton iteration (which may be at the same
+// timepoint or belong to a rejected timestep). The previous evaluation is therefore only
+// committed as the last accepted timepoint once abstime advances beyond its time.
+
+// Returns 1 if val crossed zero (in direction dir) between prev_time and time and stores the
+// (linearly interpolated) time of the crossing in crossing. Values within tol of zero already
+// count as a crossing.
+static int find_crossing(double prev_val, double prev_time, double val, double time, int dir,
+                         double tol, double *crossing) {
+  int rising = prev_val < 0.0 && val >= -tol;
+  int falling = prev_val > 0.0 && val <= tol;
+  if (time > prev_time && ((dir >= 0 && rising) || (dir <= 0 && falling))) {
+    // val may still be on the other side of zero (within tol)
+    double frac = prev_val != val ? prev_val / (prev_val - val) : 1.0;
+    *crossing = prev_time + (time - prev_time) * (frac < 1.0 ? frac : 1.0);
+    return 1;
+  }
+  return 0;
+}
+
+// Returns 1 if val crossed zero since the last accepted timepoint and stores the time of the
+// last crossing (including a crossing since the last accepted timepoint) in crossing.
+static int update_crossing_state(OsdiSimInfo *sim_info, const int idx[5], double val,
+                                 int dir, double abstime, double tol, double *crossing) {
+  double accepted_val = val;
+  double accepted_time = abstime;
+  double accepted_crossing = -1.0;
+  int crossed = 0;
+  if (tol < 0.0) {
+    tol = -tol;
+  }
+  // values within the tolerance are stored as zero so the event is not triggered twice
+  double stored_val = val >= -tol && val <= tol ? 0.0 : val;
+  *crossing = -1.0;
+  if ((sim_info->flags & ANALYSIS_TRAN) && !(sim_info->flags & ANALYSIS_IC)) {
+    accepted_val = sim_info->prev_state[idx[0]];
+    accepted_time = sim_info->prev_state[idx[1]];
+    accepted_crossing = sim_info->prev_state[idx[2]];
+    double prev_time = sim_info->prev_state[idx[4]];
+    if (abstime > prev_time) {
+      // the timepoint of the previous evaluation was accepted
+      double prev_val = sim_info->prev_state[idx[3]];
+      find_crossing(accepted_val, accepted_time, prev_val, prev_time, dir, tol,
+                    &accepted_crossing);
+      accepted_val = prev_val;
+      accepted_time = prev_time;
+    }
+    *crossing = accepted_crossing;
+    crossed = find_crossing(accepted_val, accepted_time, val, abstime, dir, tol, crossing);
+  } else {
+    accepted_val = stored_val;
+  }
+  sim_info->next_state[idx[0]] = accepted_val;
+  sim_info->next_state[idx[1]] = accepted_time;
+  sim_info->next_state[idx[2]] = accepted_crossing;
+  sim_info->next_state[idx[3]] = stored_val;
+  sim_info->next_state[idx[4]] = abstime;
+  return crossed;
+}
+
+// last_crossing(val, dir): returns the time of the last crossing (negative if none occurred
+// yet)
+double last_crossing(void *sim_info_, int idx_val, int idx_time, int idx_crossing,
+                     int idx_prev_val, int idx_prev_time, double val, int dir,
+                     double abstime) {
+  int idx[5] = {idx_val, idx_time, idx_crossing, idx_prev_val, idx_prev_time};
+  double crossing;
+  update_crossing_state((OsdiSimInfo *)sim_info_, idx, val, dir, abstime, 0.0, &crossing);
+  return crossing;
+}
+
+// @(cross(val, dir, time_tol, expr_tol)): uses the same states as last_crossing and
+// returns 1 if val crossed zero (in direction dir) between the last accepted
+// timepoint and abstime. Values within expr_tol of zero already count as a crossing.
+// OSDI offers no way to reject a timestep so time_tol can not be enforced here,
+// the event is triggered at the first timepoint after the crossing instead.
+// A tolerance of zero means that no tolerance was specified.
+int cross_event(void *sim_info_, int idx_val, int idx_time, int idx_crossing,
+                int idx_prev_val, int idx_prev_time, double val, int dir, double abstime,
+                double time_tol, double expr_tol) {
+  int idx[5] = {idx_val, idx_time, idx_crossing, idx_prev_val, idx_prev_time};
+  double crossing;
+  (void)time_tol;
+  return update_crossing_state((OsdiSimInfo *)sim_info_, idx, val, dir, abstime, expr_tol,
+                               &crossing);
+}
+
+// $past(val): returns the value of val at the last accepted timepoint. Outside of a
+// transient analysis (and while computing its initial conditions) there is no previous
+// timepoint so val itself is returned.
+// prev_state contains the state of the previous Newton iteration (which may be at the
+// same timepoint or belong to a rejected timestep). The value of the previous evaluation
+// is therefore only committed as the past value once abstime advances beyond its time.
+double past(void *sim_info_, int idx_past, int idx_val, int idx_time, double val,
+            double abstime) {
+  OsdiSimInfo *sim_info = (OsdiSimInfo *)sim_info_;
+  double past = val;
+  if ((sim_info->flags & ANALYSIS_TRAN) && !(sim_info->flags & ANALYSIS_IC)) {
+    if (abstime > sim_info->prev_state[idx_time]) {
+      past = sim_info->prev_state[idx_val];
+    } else {
+      past = sim_info->prev_state[idx_past];
+    }
+  }
+  sim_info->next_state[idx_past] = past;
+  sim_info->next_state[idx_val] = val;
+  sim_info->next_state[idx_time] = abstime;
+  return past;
+}
+
 int analysis(void *sim_info_, char *name) {
   OsdiSimInfo *sim_info = (OsdiSimInfo *)sim_info_;
   uint32_t flags = sim_info->flags;
@@ -207,3 +367,120 @@ double store_delay(void *sim_info_, double *dst, double val) {
 
   return *dst;
 }
+
+// Random number generators used by $random and $rdist_*. These follow the
+// reference implementation in the Verilog standard (IEEE 1364, 17.9.3) and are
+// pure functions of the seed, so that runs with the same seed are reproducible.
+// Each distribution has a companion *_seed function that returns the updated
+// seed (Verilog-A seeds are inout arguments). It receives the same arguments as
+// the sample function because the number of uniform draws may depend on them.
+
+static double rand_uniform(int *seed, int start, int end) {
+  union {
+    float s;
+    unsigned int stemp;
+  } u;
+  double d = 0.00000011920928955078125;
+  double a, b, c;
+
+  if (*seed == 0) {
+    *seed = 259341593;
+  }
+  if (start >= end) {
+    a = 0.0;
+    b = 2147483647.0;
+  } else {
+    a = (double)start;
+    b = (double)end;
+  }
+  *seed = (int)(69069u * (unsigned int)*seed + 1u);
+  u.stemp = (unsigned int)*seed;
+  u.stemp = (u.stemp >> 9) | 0x3f800000;
+  c = (double)u.s;
+  c = c + (c * d);
+  c = ((b - a) * (c - 1.0)) + a;
+  return c;
+}
+
+static double rand_normal(int *seed, double mean, double deviation) {
+  double v1 = 0.0, v2, s = 1.0;
+  while ((s >= 1.0) || (s == 0.0)) {
+    v1 = rand_uniform(seed, -1, 1);
+    v2 = rand_uniform(seed, -1, 1);
+    s = v1 * v1 + v2 * v2;
+  }
+  s = v1 * sqrt(-2.0 * log(s) / s);
+  return s * deviation + mean;
+}
+
+static int rand_int(int *seed) {
+  double r = rand_uniform(seed, -2147483647 - 1, 2147483647);
+  r = (r + 2147483648.0) / 4294967295.0;
+  r = r * 4294967296.0 - 2147483648.0;
+  if (r >= 0) {
+    return (int)r;
+  }
+  return (int)(r - 1);
+}
+
+int va_random(int seed) { return rand_int(&seed); }
+
+int va_random_seed(int seed) {
+  rand_int(&seed);
+  return seed;
+}
+
+double va_rdist_normal(int seed, double mean, double deviation) {
+  return rand_normal(&seed, mean, deviation);
+}
+
+int va_rdist_normal_seed(int seed, double mean, double deviation) {
+  rand_normal(&seed, mean, deviation);
+  return seed;
+}
+
+double va_rdist_uniform(int seed, double start, double end) {
+  return start + (end - start) * rand_uniform(&seed, 0, 1);
+}
+
+int va_rdist_uniform_seed(int seed, double start, double end) {
+  rand_uniform(&seed, 0, 1);
+  return seed;
+}
+
+static double rand_exponential(int *seed, double mean) {
+  double n = rand_uniform(seed, 0, 1);
+  if (n != 0.0) {
+    n = -log(n) * mean;
+  }
+  return n;
+}
+
+double va_rdist_exponential(int seed, double mean) {
+  return rand_exponential(&seed, mean);
+}
+
+int va_rdist_exponential_seed(int seed, double mean) {
+  rand_exponential(&seed, mean);
+  return seed;
+}
+
+static double rand_poisson(int *seed, double mean) {
+  int n = 0;
+  double p = exp(-mean);
+  double q = rand_uniform(seed, 0, 1);
+  while (p < q) {
+    n++;
+    q = rand_uniform(seed, 0, 1) * q;
+  }
+  return (double)n;
+}
+
+double va_rdist_poisson(int seed, double mean) {
+  return rand_poisson(&seed, mean);
+}
+
+int va_rdist_poisson_seed(int seed, double mean) {
+  rand_poisson(&seed, mean);
+  return seed;
+}
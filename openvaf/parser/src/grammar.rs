@@ -34,6 +34,14 @@ const TYPE_TS: TokenSet = TokenSet::new(&[REAL_KW, INTEGER_KW, STRING_KW]);
 
 pub(crate) fn source_file(p: &mut Parser) {
     let m = p.start();
+    items(p, |_| ());
+    m.complete(p, SOURCE_FILE);
+}
+
+/// Parses all top level items (modules, natures and disciplines) of a file.
+/// `item_done` is called every time the parser is at a point where all events
+/// that have been produced so far belong to completed items.
+pub(crate) fn items(p: &mut Parser, mut item_done: impl FnMut(&mut Parser)) {
     let mut error_range: Option<CompletedMarker> = None;
     while !p.at(EOF) {
         let m = p.start();
@@ -70,8 +78,13 @@ pub(crate) fn source_file(p: &mut Parser) {
                 }
             }
         }
+
+        // consecutive garbage is merged into a single error node so
+        // we can only report once a proper item follows
+        if error_range.is_none() {
+            item_done(p)
+        }
     }
-    m.complete(p, SOURCE_FILE);
 }
 
 fn ty(p: &mut Parser) {
@@ -78,7 +78,9 @@ pub(super) fn decl_name(p: &mut Parser) -> bool {
 }
 
 pub(super) fn var_decl(p: &mut Parser, m: Marker) {
-    ty(p);
+    if !p.eat(T![genvar]) {
+        ty(p);
+    }
     decl_list(p, T![;], var, MODULE_ITEM_OR_ATTR_RECOVERY);
     p.eat(T![;]);
     m.complete(p, VAR_DECL);
@@ -86,7 +88,11 @@ pub(super) fn var_decl(p: &mut Parser, m: Marker) {
 
 fn var(p: &mut Parser) -> bool {
     let m = p.start();
-    name_r(p, TokenSet::new(&[T![,], T![=], T![;]]));
+    name_r(p, TokenSet::new(&[T![,], T![=], T![;], T!['[']]));
+    if p.at(T!['[']) {
+        // array variable (real x[0:2])
+        range_or_expr(p);
+    }
     if p.eat(T![=]) {
         expr(p);
     }
@@ -105,7 +111,11 @@ pub(super) fn parameter_decl(p: &mut Parser, m: Marker) {
 const PARAM_RECOVER: TokenSet = MODULE_ITEM_OR_ATTR_RECOVERY.union(TokenSet::new(&[T![,], T![;]]));
 fn parameter(p: &mut Parser) -> bool {
     let m = p.start();
-    name_r(p, TokenSet::new(&[T![,], T![;]]));
+    name_r(p, TokenSet::new(&[T![,], T![;], T!['[']]));
+    if p.at(T!['[']) {
+        // array parameter (localparam real x[0:1] = '{1.0, 2.0})
+        range_or_expr(p);
+    }
     p.expect(T![=]);
     expr(p);
     while !p.at_ts(PARAM_RECOVER) {
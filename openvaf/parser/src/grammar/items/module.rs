@@ -9,6 +9,7 @@ const MODULE_ITEM_RECOVERY: TokenSet = DIRECTION_TS.union(TokenSet::new(&[
     STRING_KW,
     REAL_KW,
     INTEGER_KW,
+    GENVAR_KW,
     PARAMETER_KW,
     LOCALPARAM_KW,
     ENDMODULE_KW,
@@ -131,7 +132,7 @@ fn module_items(p: &mut Parser) {
             BRANCH_KW => {
                 branch_decl(p, m);
             }
-            INTEGER_KW | REAL_KW | STRING_KW => var_decl(p, m),
+            INTEGER_KW | REAL_KW | STRING_KW | GENVAR_KW => var_decl(p, m),
             INPUT_KW | OUTPUT_KW | INOUT_KW => port_decl::<false>(p, m),
             _ => {
                 error_range = if let Some(error_range) = error_range {
@@ -2,8 +2,9 @@ use super::*;
 
 pub(crate) const PATH_SEGMENT_TS: TokenSet = TokenSet::new(&[IDENT, ROOT_KW]);
 
+/// Parses a (possibly qualified) path. If the first segment is missing an error
+/// is reported without consuming any tokens so callers can recover.
 pub(crate) fn path(p: &mut Parser) -> CompletedMarker {
-    assert!(p.at_ts(PATH_SEGMENT_TS));
     let path = p.start();
     p.expect_ts(PATH_SEGMENT_TS);
     let mut qual = path.complete(p, PATH);
@@ -60,27 +60,37 @@ fn assign_or_expr(p: &mut Parser) -> bool {
 fn event_stmt(p: &mut Parser, m: Marker) {
     p.bump(T![@]);
     p.expect(T!['(']);
-    p.expect_ts_r(
-        TokenSet::new(&[INITIAL_STEP_KW, FINAL_STEP_KW]),
-        TokenSet::new(&[T![')'], T!['(']]),
-    );
-    if p.eat(T!['(']) {
-        while !p.at_ts(TokenSet::new(&[T![')'], T![begin], ENDMODULE_KW])) {
-            let mut succ = p.expect(STR_LIT);
-            if !p.at(T![')']) {
-                succ |= p.expect_with(T![,], &[T![')'], T![,]]);
-                if !succ {
-                    p.bump_any()
-                }
-            }
-        }
-        p.eat(T![')']);
+    event(p);
+    while p.eat(T![or]) {
+        event(p);
     }
     p.expect(T![')']);
     stmt_with_attrs(p);
     m.complete(p, EVENT_STMT);
 }
 
+fn event(p: &mut Parser) {
+    let m = p.start();
+    if p.eat_ts(TokenSet::new(&[INITIAL_STEP_KW, FINAL_STEP_KW])) {
+        if p.eat(T!['(']) {
+            while !p.at_ts(TokenSet::new(&[T![')'], T![begin], ENDMODULE_KW])) {
+                let mut succ = p.expect(STR_LIT);
+                if !p.at(T![')']) {
+                    succ |= p.expect_with(T![,], &[T![')'], T![,]]);
+                    if !succ {
+                        p.bump_any()
+                    }
+                }
+            }
+            p.eat(T![')']);
+        }
+    } else {
+        // analog event functions like cross(..)
+        expr(p);
+    }
+    m.complete(p, EVENT);
+}
+
 fn if_stmt(p: &mut Parser, m: Marker) {
     p.bump(IF_KW);
     p.expect(T!['(']);
@@ -17,6 +17,8 @@ mod event;
 mod grammar;
 mod output;
 mod parser;
+#[cfg(test)]
+mod tests;
 
 pub use error::SyntaxError;
 use stdx::pretty;
@@ -48,6 +50,24 @@ pub fn parse(tokens: &[SyntaxKind]) -> Output {
     event::process(p.finish())
 }
 
+/// Parse given tokens like [`parse`] but hand the output to `on_item` as soon
+/// as a top level item (module, nature or discipline) has been parsed.
+/// Every top level item is part of exactly one output and the outputs are
+/// passed to `on_item` in order. Tokens that do not form an item are merged
+/// into a single `ERROR` node which is passed to `on_item` together with the
+/// item that follows it (or on its own at the end of the file).
+///
+/// Unlike [`parse`] the outputs do not contain the enclosing `SOURCE_FILE` node.
+/// Otherwise concatenating all outputs yields the same events as [`parse`].
+pub fn parse_items(tokens: &[SyntaxKind], mut on_item: impl FnMut(Output)) {
+    let mut p = parser::Parser::new(tokens);
+    grammar::items(&mut p, |p| on_item(event::process(p.take_events())));
+    let rem = p.finish();
+    if !rem.is_empty() {
+        on_item(event::process(rem))
+    }
+}
+
 pub struct Error {
     pub expected: pretty::List<Vec<Token>>,
     pub found: Token,
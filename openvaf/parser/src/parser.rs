@@ -1,6 +1,7 @@
 //! See [`Parser`].
 
 use std::cell::Cell;
+use std::mem;
 
 use drop_bomb::DropBomb;
 use stdx::pretty::List;
@@ -35,6 +36,13 @@ impl<'t> Parser<'t> {
         self.events
     }
 
+    /// Removes all events produced so far. This must only be called
+    /// while no `Marker` or `CompletedMarker` is alive as these refer
+    /// to events by position.
+    pub(crate) fn take_events(&mut self) -> Vec<Event> {
+        mem::take(&mut self.events)
+    }
+
     /// Returns the kind of the current token.
     /// If parser has already reached the end of input,
     /// the special `EOF` kind is returned.
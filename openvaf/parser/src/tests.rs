@@ -0,0 +1,56 @@
+use tokens::parser::SyntaxKind::*;
+
+use crate::{parse, parse_items, Output, Step, SyntaxKind};
+
+fn top_level_nodes(output: &Output) -> Vec<SyntaxKind> {
+    let mut depth = 0;
+    let mut nodes = Vec::new();
+    for step in output.iter() {
+        match step {
+            Step::Enter { kind } => {
+                if depth == 0 {
+                    nodes.push(kind)
+                }
+                depth += 1;
+            }
+            Step::Exit => depth -= 1,
+            _ => (),
+        }
+    }
+    nodes
+}
+
+fn steps(output: &Output) -> Vec<String> {
+    output.iter().map(|step| format!("{step:?}")).collect()
+}
+
+#[test]
+fn parse_items_partitions_items() {
+    let tokens = [
+        MODULE_KW,
+        IDENT,
+        SEMICOLON,
+        ENDMODULE_KW,
+        INT_NUMBER,
+        INT_NUMBER,
+        SEMICOLON,
+        INT_NUMBER,
+        MODULE_KW,
+        IDENT,
+        SEMICOLON,
+        ENDMODULE_KW,
+        INT_NUMBER,
+    ];
+
+    let mut outputs = Vec::new();
+    parse_items(&tokens, |output| outputs.push(output));
+
+    let nodes: Vec<_> = outputs.iter().map(top_level_nodes).collect();
+    assert_eq!(nodes, vec![vec![MODULE_DECL], vec![ERROR, MODULE_DECL], vec![ERROR]]);
+
+    let items: Vec<_> = outputs.iter().flat_map(steps).collect();
+    let file = steps(&parse(&tokens));
+    assert_eq!(file.first().map(String::as_str), Some("Enter { kind: SOURCE_FILE }"));
+    assert_eq!(file.last().map(String::as_str), Some("Exit"));
+    assert_eq!(items, file[1..file.len() - 1]);
+}
@@ -67,6 +67,12 @@ pub fn preprocess(sources: &dyn SourceProvider, file: FileId) -> Preprocess {
 pub trait SourceProvider {
     fn include_dirs(&self, root_file: FileId) -> Arc<[VfsPath]>;
     fn macro_flags(&self, file_root: FileId) -> Arc<[Arc<str>]>;
+    /// Maximum nesting depth of macro expansions.
+    fn macro_depth_limit(&self, root_file: FileId) -> u32;
+    /// Include the bundled standard headers without an explicit `` `include ``:
+    /// `disciplines.vams` if no disciplines or natures are declared and `constants.vams`
+    /// once one of its macros is used without being defined.
+    fn implicit_std_includes(&self, root_file: FileId) -> bool;
 
     fn file_text(&self, file: FileId) -> Result<Arc<str>, FileReadError>;
     fn file_path(&self, file: FileId) -> VfsPath;
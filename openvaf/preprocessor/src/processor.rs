@@ -6,13 +6,15 @@ use ahash::AHashMap;
 use stdx::{impl_debug_display, impl_idx_from};
 use text_size::{TextRange, TextSize};
 use tokens::parser::SyntaxKind;
-use tokens::SyntaxKind::{L_PAREN, R_PAREN};
+use tokens::SyntaxKind::{DISCIPLINE_KW, L_PAREN, NATURE_KW, R_PAREN};
 // use tracing::{debug, debug_span, trace};
 use typed_index_collections::{TiSlice, TiVec};
+use vfs::va_std::{self, CONSTANTS_PATHS, DISCIPLINES_PATHS};
 use vfs::{FileId, VfsPath};
 
 use crate::diagnostics::PreprocessorDiagnostic::{
-    self, MacroArgumentCountMismatch, MacroNotFound, UnexpectedToken,
+    self, MacroArgumentCountMismatch, MacroDepthLimit, MacroNotFound, MacroRecursion,
+    UnexpectedToken,
 };
 use crate::grammar::{parse_condition, parse_define, parse_include, parse_macro_call};
 use crate::parser::{CompilerDirective, Parser, PreprocessorToken};
@@ -25,6 +27,10 @@ pub(crate) struct Processor<'a> {
     arena: &'a ScopedTextArea,
     macros: AHashMap<&'a str, Macro<'a>>,
     include_dirs: Arc<[VfsPath]>,
+    /// The macros that are currently being expanded (innermost last).
+    expansion_stack: Vec<&'a str>,
+    macro_depth_limit: u32,
+    implicit_std_includes: bool,
 }
 
 impl<'a> Processor<'a> {
@@ -51,6 +57,9 @@ impl<'a> Processor<'a> {
             arena: storage,
             sources,
             include_dirs: sources.include_dirs(root_file),
+            expansion_stack: Vec::new(),
+            macro_depth_limit: sources.macro_depth_limit(root_file),
+            implicit_std_includes: sources.implicit_std_includes(root_file),
         };
         Ok(res)
     }
@@ -64,9 +73,33 @@ impl<'a> Processor<'a> {
             Parser::new(self.arena.get(0), SourceContext::ROOT, working_dir, &mut dst, &mut err);
         self.process_file(parser, &mut err);
 
+        // files that declare their own disciplines would conflict with the standard ones
+        if self.implicit_std_includes
+            && !dst.iter().any(|token| matches!(token.kind, DISCIPLINE_KW | NATURE_KW))
+        {
+            let mut std_tokens = Vec::new();
+            let span = CtxSpan::dummy();
+            self.include_std_header(DISCIPLINES_PATHS[0], span, &mut std_tokens, &mut err);
+            std_tokens.append(&mut dst);
+            dst = std_tokens;
+        }
+
         (dst, err)
     }
 
+    /// Includes one of the standard headers bundled with OpenVAF (`/std/<header>`).
+    fn include_std_header(
+        &mut self,
+        header: &str,
+        span: CtxSpan,
+        dst: &mut Vec<Token>,
+        errors: &mut Diagnostics,
+    ) {
+        let std_dir = VfsPath::new_virtual_path("/std".to_owned());
+        // without the bundled headers the names are simply reported as not found
+        let _ = self.include_file(header, span, dst, errors, &std_dir);
+    }
+
     pub(crate) fn is_macro_defined(&mut self, name: &'a str) -> bool {
         self.macros.contains_key(name)
     }
@@ -149,8 +182,6 @@ impl<'a> Processor<'a> {
         dst: &mut Vec<Token>,
         errors: &mut Diagnostics,
     ) {
-        // TODO track recursion
-        //
         let parent_ctx_span = self.source_map.ctx_data(span.ctx).decl.range.start();
         if let Some(def) = self.macros.get(&call.name).cloned() {
             let new_args: TiVec<_, _> = call
@@ -168,11 +199,27 @@ impl<'a> Processor<'a> {
                 .collect();
 
             if new_args.len() == def.arg_cnt || def.arg_cnt == 0 {
+                // a recursive macro would be expanded forever
+                if self.expansion_stack.contains(&call.name) {
+                    errors.push(MacroRecursion { name: call.name.to_owned(), span });
+                    return;
+                }
+                if self.expansion_stack.len() >= self.macro_depth_limit as usize {
+                    errors.push(MacroDepthLimit {
+                        name: call.name.to_owned(),
+                        limit: self.macro_depth_limit,
+                        span,
+                    });
+                    return;
+                }
+
                 let ctx = self.source_map.add_ctx(def.span.to_file_span(&self.source_map), span);
+                self.expansion_stack.push(call.name);
                 for ParsedToken { kind, range } in &def.body {
                     let span = CtxSpan { range: range - def.span.range.start(), ctx };
                     self.process_macro_token(kind, span, &new_args, dst, errors)
                 }
+                self.expansion_stack.pop();
                 if new_args.len() > def.arg_cnt {
                     // macro definition has no arguments, but some were parsed as part of the call
                     // so put the arguments back
@@ -191,6 +238,18 @@ impl<'a> Processor<'a> {
                     span,
                 })
             }
+        } else if self.implicit_std_includes
+            && !self.is_macro_defined("CONSTANTS_VAMS")
+            && va_std::std_header_declaring(call.name) == Some(CONSTANTS_PATHS[0])
+        {
+            // constants.vams only contains macro definitions so it does not produce tokens
+            let mut std_tokens = Vec::new();
+            self.include_std_header(CONSTANTS_PATHS[0], span, &mut std_tokens, errors);
+            if self.is_macro_defined(call.name) {
+                self.call_macro(call, span, args, dst, errors)
+            } else {
+                errors.push(MacroNotFound { name: call.name.to_owned(), span })
+            }
         } else {
             errors.push(MacroNotFound { name: call.name.to_owned(), span })
         }
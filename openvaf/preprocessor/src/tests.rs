@@ -9,6 +9,7 @@ use crate::{preprocess, Preprocess, SourceProvider};
 struct TestSourceProvider {
     vfs: RefCell<Vfs>,
     include_dirs: Arc<[VfsPath]>,
+    implicit_std_includes: bool,
 }
 
 impl TestSourceProvider {
@@ -16,7 +17,7 @@ impl TestSourceProvider {
         let mut vfs = Vfs::default();
         vfs.insert_std_lib();
         include_dirs.push(VfsPath::new_virtual_path("/std".to_owned()));
-        Self { vfs: RefCell::new(vfs), include_dirs: Arc::from(include_dirs) }
+        Self { vfs: RefCell::new(vfs), include_dirs: Arc::from(include_dirs), implicit_std_includes: false }
     }
 }
 
@@ -29,6 +30,14 @@ impl SourceProvider for TestSourceProvider {
         Arc::new([])
     }
 
+    fn macro_depth_limit(&self, _root_file: FileId) -> u32 {
+        16
+    }
+
+    fn implicit_std_includes(&self, _root_file: FileId) -> bool {
+        self.implicit_std_includes
+    }
+
     fn file_text(&self, file: FileId) -> Result<Arc<str>, crate::FileReadError> {
         let vfs = self.vfs.borrow();
         vfs.file_contents(file).map(Arc::from)
@@ -181,3 +190,121 @@ fn source_map_triple_replacement() {
         "source_map_triple_replacement",
     )
 }
+
+#[test]
+fn nested_conditions() {
+    check_prepocessor_single_file(
+        r#"
+`define OUTER
+`define SQUARE(x) ((x)*(x))
+`define NORM(x,y) `SQUARE(x)+`SQUARE(y)
+`ifdef OUTER
+    `ifdef INNER
+        ERROR1
+    `elsif OUTER
+        `ifndef INNER
+            `NORM(a,`SQUARE(b))
+        `else
+            ERROR2
+        `endif
+    `else
+        ERROR3
+    `endif
+`else
+    ERROR4
+`endif
+"#,
+        "nested_conditions",
+    )
+}
+
+#[test]
+fn include_search_path() {
+    let sources = TestSourceProvider::new(vec![VfsPath::new_virtual_path("/inc".to_owned())]);
+    let root_file = {
+        let mut vfs = sources.vfs.borrow_mut();
+        vfs.add_virt_file(
+            "/inc/helpers.vams",
+            "`define TWICE(x) (2*(x))\n`define SCALE 1.5\n`define HELPERS_LOADED\n"
+                .to_owned()
+                .into(),
+        );
+        vfs.add_virt_file(
+            "/models/root.va",
+            r#"
+`include "helpers.vams"
+`ifdef HELPERS_LOADED
+`TWICE(`SCALE)
+`endif
+"#
+            .to_owned()
+            .into(),
+        )
+    };
+    check_prepocessor(sources, root_file, "include_search_path")
+}
+
+fn preprocessor_errors(src: &str) -> Vec<String> {
+    let sources = TestSourceProvider::new(vec![]);
+    let file =
+        sources.vfs.borrow_mut().add_virt_file("/macro_limit_test.va", src.to_owned().into());
+    let Preprocess { diagnostics, .. } = preprocess(&sources, file);
+    diagnostics.iter().map(|diag| diag.to_string()).collect()
+}
+
+#[test]
+fn macro_recursion() {
+    let errors = preprocessor_errors(
+        r#"
+`define FOO(x) `BAR(x)+1
+`define BAR(x) `FOO(x)
+`FOO(a)
+"#,
+    );
+    assert_eq!(errors, ["macro '`FOO' was called recursively"]);
+}
+
+#[test]
+fn macro_depth_limit() {
+    let mut src: String = (1..=20).map(|i| format!("`define M{i} `M{} x\n", i - 1)).collect();
+    src.push_str("`define M0 x\n`M20\n");
+    let errors = preprocessor_errors(&src);
+    assert_eq!(errors, ["expansion of macro '`M4' exceeds the maximum nesting depth of 16"]);
+}
+
+/// Returns the text of all tokens (separated by spaces) and the diagnostics
+fn preprocess_implicit_std_includes(src: &str) -> (String, Vec<String>) {
+    let mut sources = TestSourceProvider::new(vec![]);
+    sources.implicit_std_includes = true;
+    let file = sources.vfs.borrow_mut().add_virt_file("/std_test.va", src.to_owned().into());
+    let Preprocess { ts, diagnostics, sm } = preprocess(&sources, file);
+    let vfs = sources.vfs.borrow();
+    let text: Vec<_> = ts
+        .iter()
+        .filter(|token| !token.kind.is_trivia())
+        .map(|token| {
+            let filespan = token.span.to_file_span(&sm);
+            vfs.file_contents(filespan.file).unwrap()[filespan.range].to_owned()
+        })
+        .collect();
+    (text.join(" "), diagnostics.iter().map(|diag| diag.to_string()).collect())
+}
+
+#[test]
+fn implicit_std_includes() {
+    let (text, errors) = preprocess_implicit_std_includes("x = `P_Q * `M_PI;");
+    assert_eq!(errors, Vec::<String>::new());
+    assert!(text.starts_with("discipline \\logic ;"), "{text}");
+    assert!(text.contains("discipline electrical ;"), "{text}");
+    assert!(text.ends_with("x = 1.602176462e-19 * 3.14159265358979323846 ;"), "{text}");
+
+    // declaring disciplines disables the implicit disciplines.vams
+    let (text, errors) = preprocess_implicit_std_includes(
+        "discipline custom enddiscipline\nx = `P_Q;\n`include \"constants.vams\"",
+    );
+    assert_eq!(errors, Vec::<String>::new());
+    assert_eq!(text, "discipline custom enddiscipline x = 1.602176462e-19 ;");
+
+    let (_, errors) = preprocess_implicit_std_includes("x = `P_UNKNOWN;");
+    assert_eq!(errors.len(), 1, "{errors:?}");
+}
@@ -1,7 +1,9 @@
 use bitset::{BitSet, SparseBitMatrix};
 use hir::CompilationDB;
-use hir_lower::{HirInterner, MirBuilder, PlaceKind};
+use hir_lower::{CallBackKind, HirInterner, MirBuilder, PlaceKind};
 use lasso::Rodeo;
+use mir::builder::InstBuilder;
+use mir::cursor::{Cursor, FuncCursor};
 use mir::{Block, ControlFlowGraph, DominatorTree, Function, Inst, Value};
 use mir_opt::{
     aggressive_dead_code_elimination, dead_code_elimination, inst_combine, propagate_direct_taint,
@@ -66,7 +68,11 @@ impl<'a> Context<'a> {
     }
 
     pub fn optimize(&mut self, stage: OptimiziationStage) -> GVN {
-        if stage == OptimiziationStage::Initial {
+        // auto_diff generates the derivatives of every variable that depends on an
+        // unknown, most of which never end up in the jacobian. Remove them
+        // before the (expensive) post derivative passes see them.
+        if stage != OptimiziationStage::Final {
+            self.output_values.ensure(self.func.dfg.num_values());
             dead_code_elimination(&mut self.func, &self.output_values);
         }
         sparse_conditional_constant_propagation(&mut self.func, &self.cfg);
@@ -82,6 +88,7 @@ impl<'a> Context<'a> {
         gvn.init(&self.func, &self.dom_tree, self.intern.params.len() as u32);
         gvn.solve(&mut self.func);
         gvn.remove_unnecessary_insts(&mut self.func, &self.dom_tree);
+        gvn.hoist_common_insts(&mut self.func, &self.cfg, &self.dom_tree);
 
         if stage == OptimiziationStage::Final {
             let mut control_dep = SparseBitMatrix::new_square(0);
@@ -123,6 +130,19 @@ impl<'a> Context<'a> {
         }
     }
 
+    /// Reads the simulator parameter `name` (`$simparam(name, default)`) at the end of the
+    /// function. The call is tracked like any other callback so that it is evaluated
+    /// during each iteration instead of being cached during instance setup.
+    pub fn simparam_opt(&mut self, literals: &mut Rodeo, name: &str, default: f64) -> Value {
+        let name = self.func.dfg.sconst(literals.get_or_intern(name));
+        let default = self.func.dfg.fconst(default.into());
+        let func_ref = self.intern.ensure_callback(&mut self.func, CallBackKind::SimParamOpt);
+        let mut cursor = FuncCursor::new(&mut self.func).at_exit();
+        let inst = cursor.ins().call(func_ref, &[name, default]);
+        self.intern.callback_uses[func_ref].push(inst);
+        self.func.dfg.first_result(inst)
+    }
+
     pub fn init_op_dependent_insts(&mut self, dom_frontiers: &mut SparseBitMatrix<Block, Block>) {
         self.dom_tree.compute_dom_frontiers(&self.cfg, dom_frontiers);
         let dfg = &mut self.func.dfg;
@@ -48,12 +48,22 @@ pub struct DaeSystem {
     /// Jacobian entry counts
     pub num_resistive : u32, 
     pub num_reactive : u32, 
+    /// The (nonlinear) branches that a simulator controlled `gmin` conductance was
+    /// added to. Only populated if gmin was enabled during compilation.
+    pub gmin_branches: Vec<(SimUnknown, Option<SimUnknown>)>,
 }
 
 impl DaeSystem {
-    pub(crate) fn new(ctx: &mut Context, contributions: topology::Topology) -> DaeSystem {
-        let mut builder =
-            Builder::new(ctx).with_small_signal_network(contributions.small_signal_vals);
+    /// Builds the DAE system from the branch `contributions`. If `gmin` is provided
+    /// a conductance with that value is added in parallel to every nonlinear branch.
+    pub(crate) fn new(
+        ctx: &mut Context,
+        contributions: topology::Topology,
+        gmin: Option<Value>,
+    ) -> DaeSystem {
+        let mut builder = Builder::new(ctx)
+            .with_small_signal_network(contributions.small_signal_vals)
+            .with_gmin(gmin);
 
         for (branch, contributions) in contributions.branches.raw {
             builder.build_branch(branch, &contributions)
@@ -61,6 +71,7 @@ impl DaeSystem {
         for (eq, contributions) in contributions.implicit_equations.iter_enumerated() {
             builder.build_implicit_equation(eq, contributions)
         }
+        builder.build_port_flows();
         builder.finish()
     }
 
@@ -9,8 +9,8 @@ use indexmap::IndexSet;
 use mir::builder::InstBuilder;
 use mir::cursor::{Cursor, FuncCursor};
 use mir::{
-    strip_optbarrier, Block, ControlFlowGraph, DominatorTree, Inst, KnownDerivatives, Unknown,
-    Value, FALSE, F_ZERO, TRUE, F_ONE
+    strip_optbarrier, Block, ControlFlowGraph, DominatorTree, Inst, InstructionData,
+    KnownDerivatives, Opcode, Unknown, Value, ValueDef, FALSE, F_ZERO, TRUE, F_ONE
 };
 use mir_autodiff::auto_diff;
 use typed_index_collections::TiVec;
@@ -62,6 +62,7 @@ pub(super) struct Builder<'a> {
     pub(super) dom_tree: &'a mut DominatorTree,
     pub(super) op_dependent_insts: &'a BitSet<Inst>,
     pub(super) output_values: &'a mut BitSet<Value>,
+    pub(super) gmin: Option<Value>,
 }
 
 impl<'a> Builder<'a> {
@@ -76,6 +77,7 @@ impl<'a> Builder<'a> {
             dom_tree: &mut ctx.dom_tree,
             op_dependent_insts: &ctx.op_dependent_insts,
             output_values: &mut ctx.output_values,
+            gmin: None,
         };
 
         // ensure ports are the first unknowns and always have an unknown
@@ -83,8 +85,11 @@ impl<'a> Builder<'a> {
             builder.build_node(port)
         }
 
+        // nets declared as ground are the reference node and have no equation
         for node in ctx.module.module.internal_nodes(builder.db) {
-            builder.build_node(node)
+            if !node.is_gnd(builder.db) {
+                builder.build_node(node)
+            }
         }
 
         builder
@@ -128,6 +133,11 @@ impl<'a> Builder<'a> {
         self
     }
 
+    pub(super) fn with_gmin(mut self, gmin: Option<Value>) -> Self {
+        self.gmin = gmin;
+        self
+    }
+
     /// Return a list of all parameters that read from one of the simulation
     /// unknowns and therefore need to be considered during matrix construction.
     /// These need to be constructed from the list of parameters instead of the list
@@ -178,16 +188,9 @@ impl<'a> Builder<'a> {
                     }
                 }, 
                 ParamKind::Current ( cur_kind ) => {
-                    match cur_kind {
-                        CurrentKind::Port (_) => {
-                            // TODO?
-                        },
-                        _ => {
-                            let u = SimUnknownKind::Current(cur_kind);
-                            if let Some(u) = self.system.unknowns.index(&u) {
-                                self.system.model_inputs.push((u32::from(u), std::u32::MAX));
-                            }
-                        }
+                    let u = SimUnknownKind::Current(cur_kind);
+                    if let Some(u) = self.system.unknowns.index(&u) {
+                        self.system.model_inputs.push((u32::from(u), std::u32::MAX));
                     }
                 }, 
                 ParamKind::ImplicitUnknown(ieq_kind) => {
@@ -511,6 +514,29 @@ impl<'a> Builder<'a> {
         );
     }
 
+    /// The flow through a port (`I(<port>)`) is an extra unknown whose equation equates it
+    /// to the flow into the port, which is the residual of the kirchoff law of the port.
+    /// Must be called after all branches were built.
+    pub(super) fn build_port_flows(&mut self) {
+        let port_flows: Vec<_> = self
+            .intern
+            .live_params(&self.cursor.func.dfg)
+            .filter_map(|(_, kind, val)| match *kind {
+                ParamKind::Current(CurrentKind::Port(port)) => Some((port, val)),
+                _ => None,
+            })
+            .collect();
+
+        for (port, flow) in port_flows {
+            let kirchoff_law = self.ensure_unknown(SimUnknownKind::KirchoffLaw(port));
+            let Residual { resist, react, .. } = self.system.residual[kirchoff_law];
+            let residual = get_residual!(self, SimUnknownKind::Current(CurrentKind::Port(port)));
+            residual.add(&mut self.cursor, false, resist);
+            add(&mut self.cursor, &mut residual.react, react, false);
+            residual.add(&mut self.cursor, true, flow);
+        }
+    }
+
     fn mfactor_multiply(&mut self, mfactor: Value, srcfactor : Value) -> Value {
         match (mfactor, srcfactor) {
             // Leave srcfactor unchanged if mfactor is 1
@@ -713,6 +739,75 @@ impl<'a> Builder<'a> {
         }
         // self.add_noise(contrib, hi, lo, true);
         self.add_noise(contrib, hi, lo);
+
+        if let Some(gmin) = self.gmin {
+            if self.is_nonlinear(contrib.resist, &mut AHashMap::new()) {
+                self.add_gmin(gmin, dst)
+            }
+        }
+    }
+
+    /// Adds a conductance `gmin` in parallel to the branch `dst`. The simulator can
+    /// increase gmin to help the newton iteration converge (gmin stepping).
+    fn add_gmin(&mut self, gmin: Value, dst: BranchWrite) {
+        let (hi, lo) = dst.nodes(self.db);
+        let voltage = self.intern.ensure_param(&mut self.cursor, ParamKind::Voltage { hi, lo });
+        let current = self.cursor.ins().fmul(gmin, voltage);
+        let hi = self.ensure_unknown(SimUnknownKind::KirchoffLaw(hi));
+        let lo = lo.map(|lo| self.ensure_unknown(SimUnknownKind::KirchoffLaw(lo)));
+        self.system.residual[hi].add(&mut self.cursor, false, current);
+        if let Some(lo) = lo {
+            self.system.residual[lo].add(&mut self.cursor, true, current);
+        }
+        self.system.gmin_branches.push((hi, lo));
+    }
+
+    /// Returns whether `val` depends nonlinearly on the operating point. Values that are
+    /// not build from sums of operating point dependent values and products with operating
+    /// point independent factors are conservatively assumed to be nonlinear.
+    fn is_nonlinear(&self, val: Value, visited: &mut AHashMap<Value, bool>) -> bool {
+        let func = &*self.cursor.func;
+        let inst = match func.dfg.value_def(val) {
+            ValueDef::Result(inst, _) => inst,
+            ValueDef::Param(_) | ValueDef::Const(_) | ValueDef::Invalid => return false,
+        };
+        // instructions created by the topology analysis are not part of op_dependent_insts
+        let is_new = usize::from(inst) >= self.op_dependent_insts.domain_size();
+        if !is_new && !self.op_dependent_insts.contains(inst) {
+            return false;
+        }
+        if let Some(&res) = visited.get(&val) {
+            return res;
+        }
+        let op_dependent = |val: Value| match func.dfg.value_def(val) {
+            ValueDef::Result(inst, _)
+                if usize::from(inst) >= self.op_dependent_insts.domain_size() =>
+            {
+                true
+            }
+            _ => is_op_dependent(func, val, self.op_dependent_insts, self.intern),
+        };
+        let res = match func.dfg.insts[inst] {
+            InstructionData::Binary { opcode: Opcode::Fadd | Opcode::Fsub, args } => {
+                self.is_nonlinear(args[0], visited) || self.is_nonlinear(args[1], visited)
+            }
+            InstructionData::Binary { opcode: Opcode::Fmul, args } => {
+                match (op_dependent(args[0]), op_dependent(args[1])) {
+                    (true, true) => true,
+                    (true, false) => self.is_nonlinear(args[0], visited),
+                    (false, _) => self.is_nonlinear(args[1], visited),
+                }
+            }
+            InstructionData::Binary { opcode: Opcode::Fdiv, args } => {
+                op_dependent(args[1]) || self.is_nonlinear(args[0], visited)
+            }
+            InstructionData::Unary { opcode: Opcode::Fneg | Opcode::OptBarrier, arg } => {
+                self.is_nonlinear(arg, visited)
+            }
+            _ => true,
+        };
+        visited.insert(val, res);
+        res
     }
 
     fn add_source_equation(&mut self, contrib: &Contribution, eq_val: Value, dst: BranchWrite) {
@@ -3,13 +3,15 @@ use std::fs;
 use expect_test::expect_file;
 use hir::diagnostics::ConsoleSink;
 use hir::CompilationDB;
+use hir_lower::CallBackKind;
 use indoc::indoc;
 use lasso::Rodeo;
+use mir::InstructionData;
 use stdx::{integration_test_dir, openvaf_test_data};
 
 use crate::context::{Context, OptimiziationStage};
 use crate::dae::DaeSystem;
-use crate::topology;
+use crate::{topology, SimUnknownKind};
 
 fn run_test(src: &str) {
     let db = CompilationDB::new_virtual(src).unwrap();
@@ -20,7 +22,7 @@ fn run_test(src: &str) {
     context.compute_cfg();
     context.optimize(OptimiziationStage::Initial);
     let topology = topology::Topology::new(&mut context);
-    let mut dae_system = DaeSystem::new(&mut context, topology);
+    let mut dae_system = DaeSystem::new(&mut context, topology, None);
     context.compute_cfg();
     context.optimize(OptimiziationStage::Final);
     dae_system.sparsify(&mut context);
@@ -33,6 +35,26 @@ fn run_test(src: &str) {
     expect_file![test_dir.join(format!("{name}_mir.snap"))].assert_eq(&func)
 }
 
+/// Returns the (named) nodes of all branches that a gmin conductance was added to
+fn gmin_branches(src: &str) -> Vec<(String, Option<String>)> {
+    let db = CompilationDB::new_virtual(src).unwrap();
+    let module = crate::collect_modules(&db, false, &mut ConsoleSink::new(&db)).unwrap().remove(0);
+    let mut literals = Rodeo::new();
+    let mut context = Context::new(&db, &mut literals, &module);
+    context.compute_outputs(true);
+    context.compute_cfg();
+    context.optimize(OptimiziationStage::Initial);
+    let topology = topology::Topology::new(&mut context);
+    let gmin = context.simparam_opt(&mut literals, "gmin", 0.0);
+    let dae_system = DaeSystem::new(&mut context, topology, Some(gmin));
+    assert!(context.func.validate());
+    let name = |unknown| match dae_system.unknowns[unknown] {
+        SimUnknownKind::KirchoffLaw(node) => node.name(&db).to_string(),
+        kind => unreachable!("gmin added to {kind:?}"),
+    };
+    dae_system.gmin_branches.iter().map(|&(hi, lo)| (name(hi), lo.map(name))).collect()
+}
+
 #[test]
 fn diode() {
     let src = fs::read_to_string(integration_test_dir("DIODE").join("diode.va")).unwrap();
@@ -146,3 +168,73 @@ fn dyn_switch_branch() {
     "#};
     run_test(src);
 }
+
+#[test]
+fn diode_gmin() {
+    let src = fs::read_to_string(integration_test_dir("DIODE").join("diode.va")).unwrap();
+    let branches = gmin_branches(&src);
+    assert!(branches.contains(&("A".to_owned(), Some("CI".to_owned()))), "{branches:?}");
+}
+
+#[test]
+fn linear_gmin() {
+    let src = indoc! {r#"
+        `include "disciplines.vams"
+        module linear_gmin(inout a, inout c);
+            electrical a, c;
+            parameter real r=1.0;
+            analog begin
+                I(a, c) <+ 2 * V(a, c) / r - V(c) + 1.0;
+            end
+        endmodule
+    "#};
+    assert_eq!(gmin_branches(src), Vec::new());
+}
+
+#[test]
+fn post_derivative_dce() {
+    let src = indoc! {r#"
+        `include "disciplines.vams"
+        module post_derivative_dce(inout a, inout c);
+            electrical a, c;
+            parameter real is=1e-14;
+            real id;
+            analog begin
+                id = is * (exp(V(a, c) / $vt) - 1);
+                I(a, c) <+ id + white_noise(2 * 1.6e-19 * id, "shot");
+                $strobe("id=%g", id);
+            end
+        endmodule
+    "#};
+    let db = CompilationDB::new_virtual(src).unwrap();
+    let module = crate::collect_modules(&db, false, &mut ConsoleSink::new(&db)).unwrap().remove(0);
+    let mut literals = Rodeo::new();
+    let mut context = Context::new(&db, &mut literals, &module);
+    context.compute_outputs(true);
+    context.compute_cfg();
+    context.optimize(OptimiziationStage::Initial);
+    let topology = topology::Topology::new(&mut context);
+    let _dae_system = DaeSystem::new(&mut context, topology, None);
+
+    // auto_diff leaves unused derivatives behind that must be removed
+    let before = context.func.stats();
+    context.compute_cfg();
+    context.optimize(OptimiziationStage::PostDerivative);
+    let after = context.func.stats();
+    assert!(context.func.validate());
+    assert!(after.insts < before.insts, "{before} -> {after}");
+
+    // calls with side effects are not dead code
+    let callbacks: Vec<_> = context
+        .func
+        .layout
+        .blocks()
+        .flat_map(|bb| context.func.layout.block_insts(bb))
+        .filter_map(|inst| match context.func.dfg.insts[inst] {
+            InstructionData::Call { func_ref, .. } => Some(&context.intern.callbacks[func_ref]),
+            _ => None,
+        })
+        .collect();
+    assert!(callbacks.iter().any(|cb| matches!(cb, CallBackKind::Print { .. })), "{callbacks:?}");
+    assert!(callbacks.iter().any(|cb| cb.is_noise()), "{callbacks:?}");
+}
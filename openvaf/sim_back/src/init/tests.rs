@@ -22,7 +22,7 @@ fn run_test(src: &str) {
     cx.optimize(OptimiziationStage::Initial);
 
     let topology = Topology::new(&mut cx);
-    let mut dae_system = DaeSystem::new(&mut cx, topology);
+    let mut dae_system = DaeSystem::new(&mut cx, topology, None);
 
     cx.compute_cfg();
     let gvn = cx.optimize(OptimiziationStage::PostDerivative);
@@ -4,6 +4,7 @@ use lasso::Rodeo;
 use mir::Function;
 use mir_opt::{simplify_cfg, sparse_conditional_constant_propagation};
 use stdx::impl_debug_display;
+use stdx::timing::PhaseTimer;
 
 pub use module_info::{collect_modules, ModuleInfo};
 
@@ -156,9 +157,19 @@ impl<'a> CompiledModule<'a> {
         literals: &mut Rodeo,
         dump_unopt_mir: bool, 
         dump_mir: bool, 
+        print_stats: bool,
+        print_timing: bool,
+        gmin: bool,
     ) -> CompiledModule<'a> {
+        let mut timer = PhaseTimer::new(print_timing);
         // Build MIR for the module
         let mut cx = Context::new(db, literals, module);
+        let name = module.module.name(db);
+        timer.finish(format_args!("lowering {name} to MIR"));
+
+        if print_stats {
+            println!("MIR of {name} after lowering: {}", cx.func.stats());
+        }
 
         if dump_unopt_mir {
             println!("Unoptimized MIR (no DAE) of {}", module.module.name(db));
@@ -174,8 +185,14 @@ impl<'a> CompiledModule<'a> {
         // Add extra stuff needed for evaluating the DAE system
         let topology = Topology::new(&mut cx);
         debug_assert!(cx.func.validate());
-        let mut dae_system = DaeSystem::new(&mut cx, topology);
+        let gmin = gmin.then(|| cx.simparam_opt(literals, "gmin", 0.0));
+        let mut dae_system = DaeSystem::new(&mut cx, topology, gmin);
         debug_assert!(cx.func.validate());
+        timer.finish(format_args!("derivatives of {name}"));
+
+        if print_stats {
+            println!("MIR of {name} after derivatives: {}", cx.func.stats());
+        }
 
         if dump_unopt_mir {
             println!("Partially optimized MIR (with DAE) of {}", module.module.name(db));
@@ -201,11 +218,7 @@ impl<'a> CompiledModule<'a> {
         
         // TODO: refactor param intilization to use tables
         // Make a list of instance parameters
-        let inst_params: Vec<_> = module
-            .params
-            .iter()
-            .filter_map(|(param, info)| info.is_instance.then_some(*param))
-            .collect();
+        let inst_params: Vec<_> = module.instance_params().map(|(param, _)| param).collect();
         // Add initialization of instance parameters
         init.intern.insert_param_init(db, &mut init.func, literals, false, true, &inst_params);
         
@@ -225,7 +238,14 @@ impl<'a> CompiledModule<'a> {
         simplify_cfg(&mut model_param_setup, &mut cx.cfg);
         sparse_conditional_constant_propagation(&mut model_param_setup, &cx.cfg);
         simplify_cfg(&mut model_param_setup, &mut cx.cfg);
+        timer.finish(format_args!("optimizing the MIR of {name}"));
         
+        if print_stats {
+            println!("Optimized model setup MIR of {name}: {}", model_param_setup.stats());
+            println!("Optimized instance setup MIR of {name}: {}", init.func.stats());
+            println!("Optimized evaluation MIR of {name}: {}", cx.func.stats());
+        }
+
         if dump_mir {
             println!("Optimized model setup MIR of {}", module.module.name(db));
             print_mir(literals, &model_param_setup);
@@ -1,8 +1,9 @@
 use ahash::AHashSet;
 use hir::diagnostics::{BaseDB, ConsoleSink, Diagnostic, FileId, Label, LabelStyle, Report};
 use hir::{
-    CompilationDB, CompilationUnit, DiagnosticSink, Module, ParamSysFun, Parameter,
-    ResolvedAliasParameter, ScopeDef, Variable,
+    AstCache, BranchWrite, BuiltIn, CompilationDB, CompilationUnit, DiagnosticSink, Module, Node,
+    ParamSysFun, Parameter, ResolvedAliasParameter, ResolvedFun, ScopeDef, Stmt, StmtId, Type,
+    Variable,
 };
 use indexmap::IndexMap;
 use smol_str::SmolStr;
@@ -42,9 +43,15 @@ pub fn collect_modules(
 
 pub struct ModuleInfo {
     pub module: Module,
+    /// Parameters in declaration order. Simulators usually index parameters by position,
+    /// so this order (which determines the parameter order in the generated model
+    /// descriptors) must never depend on hashing.
     pub params: IndexMap<Parameter, ParamInfo, ahash::RandomState>,
     pub sys_fun_alias: IndexMap<ParamSysFun, Vec<SmolStr>, ahash::RandomState>,
     pub op_vars: IndexMap<Variable, OpVar, ahash::RandomState>,
+    pub contributions: IndexMap<StmtId, ContributeInfo, ahash::RandomState>,
+    /// Correlations between noise sources declared with `$noise_correlation`.
+    pub noise_correlations: Vec<NoiseCorrelation>,
 }
 
 impl ModuleInfo {
@@ -85,6 +92,11 @@ impl ModuleInfo {
                         continue;
                     }
 
+                    // array elements are only resolved during lowering
+                    if matches!(var.ty(db), Type::Array { .. }) {
+                        continue;
+                    }
+
                     // check that we are not in a block
                     let name_len = name.len();
                     let path = declarations.to_path(name);
@@ -112,6 +124,8 @@ impl ModuleInfo {
                     op_vars.insert(var, OpVar { unit: units, description: desc });
                 }
 
+                // localparams are constants that can not be set by the simulator
+                ScopeDef::Parameter(param) if param.is_local(db) => (),
                 ScopeDef::Parameter(param) => {
                     let units = param
                         .get_attr(db, &ast, "units")
@@ -181,6 +195,7 @@ impl ModuleInfo {
                 }
 
                 ScopeDef::AliasParameter(alias) => match alias.resolve(db).unwrap() {
+                    ResolvedAliasParameter::Parameter(param) if param.is_local(db) => (),
                     ResolvedAliasParameter::Parameter(param) => {
                         params.entry(param).or_default().alias.push(declarations.to_path(name))
                     }
@@ -193,7 +208,69 @@ impl ModuleInfo {
             }
         }
 
-        ModuleInfo { module, params, op_vars, sys_fun_alias }
+        let analog_block = module.analog_block(db);
+        let body = analog_block.borrow();
+        let contributions = body
+            .stmts()
+            .filter_map(|(stmt, data)| match data {
+                Stmt::Contribute { branch, .. } => {
+                    Some((stmt, ContributeInfo::new(db, &ast, module, stmt, branch)))
+                }
+                _ => None,
+            })
+            .collect();
+
+        // the arguments were already validated to be literals and `$noise_correlation` is only
+        // allowed unconditionally in the analog block, so only the statements at the top level
+        // (and within blocks) are searched
+        let noise_correlation = |expr| match body.get_expr(expr) {
+            hir::Expr::Call { fun: ResolvedFun::BuiltIn(BuiltIn::noise_correlation), args } => {
+                Some(NoiseCorrelation {
+                    noise_1: body.as_literal(args[0])?.unwrap_str().into(),
+                    noise_2: body.as_literal(args[1])?.unwrap_str().into(),
+                    real: body.as_real_literal(args[2])?,
+                    imag: body.as_real_literal(args[3])?,
+                })
+            }
+            _ => None,
+        };
+        let mut noise_correlations = Vec::new();
+        let mut stmts: Vec<_> = body.entry().iter().rev().copied().collect();
+        while let Some(stmt) = stmts.pop() {
+            match body.get_stmt(stmt) {
+                Some(Stmt::Block { body: block }) => stmts.extend(block.iter().rev()),
+                Some(Stmt::Expr(expr)) => noise_correlations.extend(noise_correlation(expr)),
+                _ => (),
+            }
+        }
+
+        ModuleInfo { module, params, op_vars, sys_fun_alias, contributions, noise_correlations }
+    }
+
+    /// Parameters annotated with `(* type="instance" *)` that can be set per instance.
+    pub fn instance_params(&self) -> impl Iterator<Item = (Parameter, &ParamInfo)> + '_ {
+        self.params.iter().filter(|(_, info)| info.is_instance).map(|(param, info)| (*param, info))
+    }
+
+    /// Parameters that can only be set on the model card.
+    /// Parameters without a `type` attribute are model parameters.
+    pub fn model_params(&self) -> impl Iterator<Item = (Parameter, &ParamInfo)> + '_ {
+        self.params.iter().filter(|(_, info)| !info.is_instance).map(|(param, info)| (*param, info))
+    }
+
+    /// Nodes that only receive contributions marked with `(* linear *)`.
+    /// The jacobian entries in the rows of these nodes do not change between iterations.
+    pub fn linear_nodes(&self, db: &CompilationDB) -> AHashSet<Node> {
+        let mut linear = AHashSet::new();
+        let mut nonlinear = AHashSet::new();
+        for info in self.contributions.values() {
+            let (hi, lo) = info.branch.nodes(db);
+            let dst = if info.linear { &mut linear } else { &mut nonlinear };
+            dst.insert(hi);
+            dst.extend(lo);
+        }
+        linear.retain(|node| !nonlinear.contains(node));
+        linear
     }
 }
 
@@ -255,6 +332,50 @@ pub struct ParamInfo {
     pub is_instance: bool,
 }
 
+/// Simulator hints attached to a contribution statement with attributes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContributeInfo {
+    pub branch: BranchWrite,
+    /// `(* linear *)`: the contribution depends linearly on the unknowns
+    /// so the simulator can cache its jacobian entries
+    pub linear: bool,
+    /// attributes that are not known to OpenVAF, these are preserved but otherwise ignored
+    pub attrs: Vec<SmolStr>,
+}
+
+impl ContributeInfo {
+    fn new(
+        db: &CompilationDB,
+        ast: &AstCache,
+        module: Module,
+        stmt: StmtId,
+        branch: BranchWrite,
+    ) -> ContributeInfo {
+        let mut res = ContributeInfo { branch, linear: false, attrs: Vec::new() };
+        for attr in module.stmt_attrs(db, ast, stmt) {
+            let name = match attr.name() {
+                Some(name) => name,
+                None => continue,
+            };
+            match &*name.text() {
+                "linear" => res.linear = true,
+                name => res.attrs.push(name.into()),
+            }
+        }
+        res
+    }
+}
+
+/// `$noise_correlation(noise_1, noise_2, real, imag)`: the cross spectral density of the
+/// noise sources named `noise_1` and `noise_2` is `(real + j*imag) * sqrt(S_1 * S_2)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoiseCorrelation {
+    pub noise_1: SmolStr,
+    pub noise_2: SmolStr,
+    pub real: f64,
+    pub imag: f64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OpVar {
     pub unit: String,
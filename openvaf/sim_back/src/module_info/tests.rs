@@ -124,6 +124,50 @@ fn parameters() {
     .assert_debug_eq(&params);
 }
 
+#[test]
+fn instance_and_model_params() {
+    let src = indoc! {r#"
+        module test;
+            (* desc="device width", type="instance" *) parameter real w=1e-6;
+            (* desc="oxide thickness" *) parameter real tox=2e-9;
+            (* type="model" *) parameter real vth0=0.4;
+        endmodule
+    "#};
+    let db = CompilationDB::new_virtual(src).unwrap();
+    let modules = super::collect_modules(&db, false, &mut ConsoleSink::new(&db)).unwrap();
+    let instance: Vec<_> = modules[0].instance_params().map(|(_, info)| &info.name).collect();
+    let model: Vec<_> = modules[0].model_params().map(|(_, info)| &info.name).collect();
+    assert_eq!(instance, ["w"]);
+    assert_eq!(model, ["tox", "vth0"]);
+}
+
+#[test]
+fn contribution_hints() {
+    let src = indoc! {r#"
+        module test(a, b, c);
+            inout a, b, c;
+            electrical a, b, c;
+            parameter real r=1k;
+            analog begin
+                (* linear *) I(a, b) <+ V(a, b) / r;
+                (* linear, foo="bar" *) I(a, b) <+ ddt(1e-12 * V(a, b));
+                (* foo *) I(b, c) <+ 1e-12 * exp(V(b, c));
+            end
+        endmodule
+    "#};
+    let db = CompilationDB::new_virtual(src).unwrap();
+    let modules = super::collect_modules(&db, false, &mut ConsoleSink::new(&db)).unwrap();
+    let hints: Vec<_> = modules[0]
+        .contributions
+        .values()
+        .map(|info| (info.linear, info.attrs.iter().map(|attr| attr.as_str()).collect::<Vec<_>>()))
+        .collect();
+    assert_eq!(hints, [(true, vec![]), (true, vec!["foo"]), (false, vec!["foo"])]);
+    let linear: Vec<_> =
+        modules[0].linear_nodes(&db).into_iter().map(|node| node.name(&db)).collect();
+    assert_eq!(linear, ["a"]);
+}
+
 #[test]
 fn opvars() {
     let src = indoc! {r#"
@@ -171,3 +215,42 @@ fn opvars() {
     "#]]
     .assert_debug_eq(&params);
 }
+
+#[test]
+fn param_order() {
+    let src = indoc! {r#"
+        module test;
+            parameter real zeta = 1.0;
+            (* type="instance" *) parameter real alpha = 1.0;
+            parameter integer mid = 1, beta = 2;
+            localparam real hidden = 2.0;
+            (* type="instance" *) parameter real yank = 1.0;
+            parameter real aardvark = 1.0;
+            aliasparam zeta_alias = zeta;
+        endmodule
+    "#};
+
+    // simulators index parameters by position so the order must be
+    // stable across compilations and follow the declaration order
+    let param_names = || {
+        let db = CompilationDB::new_virtual(src).unwrap();
+        let modules = super::collect_modules(&db, false, &mut ConsoleSink::new(&db)).unwrap();
+        let module = &modules[0];
+        let names = |params: Vec<&super::ParamInfo>| -> Vec<String> {
+            params.into_iter().map(|info| info.name.to_string()).collect()
+        };
+        (
+            names(module.params.values().collect()),
+            names(module.instance_params().map(|(_, info)| info).collect()),
+            names(module.model_params().map(|(_, info)| info).collect()),
+        )
+    };
+
+    let (all, instance, model) = param_names();
+    assert_eq!(all, ["zeta", "alpha", "mid", "beta", "yank", "aardvark"]);
+    assert_eq!(instance, ["alpha", "yank"]);
+    assert_eq!(model, ["zeta", "mid", "beta", "aardvark"]);
+    for _ in 0..4 {
+        assert_eq!(param_names(), (all.clone(), instance.clone(), model.clone()));
+    }
+}
@@ -1,6 +1,8 @@
 mod expr_ext;
 mod generated;
 mod node_ext;
+#[cfg(test)]
+mod tests;
 mod traits;
 
 use std::marker::PhantomData;
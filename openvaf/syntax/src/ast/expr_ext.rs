@@ -247,36 +247,48 @@ impl ast::Literal {
 }
 
 impl ast::StdRealNumber {
-    pub fn value(&self) -> f64 {
-        let src = self.syntax.text();
-        src.parse().unwrap()
+    /// The value of this literal or `None` if it is malformed (like `1e` or `1.5e+`).
+    pub fn value(&self) -> Option<f64> {
+        std_real_value(self.syntax.text())
     }
 }
 
 impl ast::SiRealNumber {
     pub fn value(&self) -> f64 {
-        let src = self.syntax.text();
-        let (src, scale_char) = src.split_at(src.len() - 1);
-        let exp = match scale_char {
-            "T" => 12,
-            "G" => 9,
-            "M" => 6,
-            "K" | "k" => 3,
-            "m" => -3,
-            "u" => -6,
-            "n" => -9,
-            "p" => -12,
-            "f" => -15,
-            "a" => -18,
-            _ => unreachable!(),
-        };
-        src.parse::<f64>().unwrap() * (10_f64).powi(exp)
+        si_real_value(self.syntax.text())
     }
 }
 
+pub(crate) fn std_real_value(src: &str) -> Option<f64> {
+    src.replace('_', "").parse().ok()
+}
+
+/// Parses a real number with a scale factor (like `1.5p`). The scale factor is turned
+/// into an exponent before parsing so that the result is correctly rounded
+/// (`1.5p` is exactly the same value as `1.5e-12`).
+pub(crate) fn si_real_value(src: &str) -> f64 {
+    let (src, scale_char) = src.split_at(src.len() - 1);
+    let exp = match scale_char {
+        "T" => 12,
+        "G" => 9,
+        "M" => 6,
+        "K" | "k" => 3,
+        "m" => -3,
+        "u" => -6,
+        "n" => -9,
+        "p" => -12,
+        "f" => -15,
+        "a" => -18,
+        _ => unreachable!(),
+    };
+    let src = src.replace('_', "");
+    format!("{src}e{exp}").parse().unwrap()
+}
+
 impl ast::IntNumber {
-    pub fn value(&self) -> i32 {
-        self.syntax.text().parse().unwrap()
+    /// The value of this literal or `None` if it does not fit into an (32 bit) integer.
+    pub fn value(&self) -> Option<i32> {
+        self.syntax.text().replace('_', "").parse().ok()
     }
 }
 
@@ -305,6 +317,22 @@ impl ast::SelectExpr {
     }
 }
 
+impl ast::IndexExpr {
+    pub fn index(&self) -> Option<ast::Expr> {
+        support::children(self.syntax()).nth(1)
+    }
+}
+
+impl ast::MinTypMaxExpr {
+    pub fn typ(&self) -> Option<ast::Expr> {
+        support::children(self.syntax()).nth(1)
+    }
+
+    pub fn max(&self) -> Option<ast::Expr> {
+        support::children(self.syntax()).nth(2)
+    }
+}
+
 pub enum AsssigmentOp {
     /// a variable assignment stmt
     /// lhs must be an identifier (example `I = V(a,c)/R;`)
@@ -6,8 +6,8 @@ use std::iter::successors;
 use stdx::impl_debug;
 
 use super::{
-    AnalogBehaviour, ArgListOwner, Assign, AstChildTokens, AstChildren, Constraint, EventStmt,
-    Expr, ForStmt, Function, ModulePortKind, Path, PortFlow, Range, Stmt, StrLit,
+    AnalogBehaviour, ArgListOwner, Assign, AstChildTokens, AstChildren, Constraint, Event, Expr,
+    ForStmt, Function, ModulePortKind, Path, PortFlow, Range, Stmt, StrLit,
 };
 use crate::ast::{self, support, AstNode};
 use crate::SyntaxKind::{IDENT, ROOT_KW};
@@ -137,6 +137,42 @@ impl ast::Range {
     }
 }
 
+impl ast::Var {
+    /// Returns the bounds of an array variable (`real x[0:2]`) or `None` if this variable
+    /// is not an array or the bounds are not integer literals (reported during validation).
+    pub fn array_bounds(&self) -> Option<(i32, i32)> {
+        array_bounds(self.range()?)
+    }
+}
+
+impl ast::Param {
+    /// Returns the bounds of an array parameter (`localparam real x[0:1] = '{1.0, 2.0}`)
+    /// or `None` if this parameter is not an array or the bounds are not integer literals.
+    pub fn array_bounds(&self) -> Option<(i32, i32)> {
+        array_bounds(self.range()?)
+    }
+}
+
+fn array_bounds(range: ast::Range) -> Option<(i32, i32)> {
+    if !range.start_inclusive() || !range.end_inclusive() {
+        return None;
+    }
+    Some((array_bound(range.start()?)?, array_bound(range.end()?)?))
+}
+
+fn array_bound(expr: ast::Expr) -> Option<i32> {
+    match expr {
+        ast::Expr::Literal(lit) => match lit.kind() {
+            ast::LiteralKind::IntNumber(val) => val.value(),
+            _ => None,
+        },
+        ast::Expr::PrefixExpr(expr) if expr.op_kind() == Some(ast::UnaryOp::Neg) => {
+            array_bound(expr.expr()?)?.checked_neg()
+        }
+        _ => None,
+    }
+}
+
 impl ast::IfStmt {
     pub fn then_branch(&self) -> Option<ast::Stmt> {
         support::children(self.syntax()).next()
@@ -228,7 +264,7 @@ impl ForStmt {
     }
 }
 
-impl EventStmt {
+impl Event {
     pub fn sim_phases(&self) -> AstChildTokens<StrLit> {
         support::child_token(self.syntax())
     }
@@ -0,0 +1,30 @@
+use crate::ast::expr_ext::{si_real_value, std_real_value};
+
+#[test]
+fn si_real_numbers() {
+    assert_eq!(si_real_value("1T"), 1e12);
+    assert_eq!(si_real_value("1G"), 1e9);
+    assert_eq!(si_real_value("1M"), 1e6);
+    assert_eq!(si_real_value("1K"), 1e3);
+    assert_eq!(si_real_value("1k"), 1e3);
+    assert_eq!(si_real_value("1m"), 1e-3);
+    assert_eq!(si_real_value("1u"), 1e-6);
+    assert_eq!(si_real_value("1n"), 1e-9);
+    assert_eq!(si_real_value("1p"), 1e-12);
+    assert_eq!(si_real_value("1f"), 1e-15);
+    assert_eq!(si_real_value("1a"), 1e-18);
+    assert_eq!(si_real_value("1.5p"), 1.5e-12);
+    assert_eq!(si_real_value("0.1n"), 0.1e-9);
+    assert_eq!(si_real_value("4.7u"), 4.7e-6);
+    assert_eq!(si_real_value("1_000.5k"), 1000.5e3);
+}
+
+#[test]
+fn std_real_numbers() {
+    assert_eq!(std_real_value("1.5"), Some(1.5));
+    assert_eq!(std_real_value("1."), Some(1.0));
+    assert_eq!(std_real_value("2e-3"), Some(2e-3));
+    assert_eq!(std_real_value("1_000.5E+2"), Some(1000.5e2));
+    assert_eq!(std_real_value("1e"), None);
+    assert_eq!(std_real_value("1.5e+"), None);
+}
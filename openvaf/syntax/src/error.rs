@@ -108,6 +108,26 @@ pub enum SyntaxError {
         range: TextRange,
         ty: TextRange,
     },
+
+    IntLiteralTooLarge {
+        range: TextRange,
+    },
+
+    MalformedRealLiteral {
+        range: TextRange,
+    },
+
+    IllegalArrayBounds {
+        range: TextRange,
+    },
+
+    ArrayVarDefault {
+        default: TextRange,
+    },
+
+    IllegalArrayParam {
+        param: TextRange,
+    },
 }
 
 use SyntaxError::*;
@@ -137,5 +157,10 @@ impl_display! {
         IllegalNetType{found,..} => "{} nets are currently not supported!",found;
         RangeConstraintForNonNumericParameter{param,..} => "non-numeric parameter '{}' has range bounds", param;
         PortNotDeclaredInModule{name,..} => "port '{name}' was not declared in the module head";
+        IntLiteralTooLarge{..} => "integer literal too large";
+        MalformedRealLiteral{..} => "malformed real literal";
+        IllegalArrayBounds{..} => "array bounds must be integer literals";
+        ArrayVarDefault{..} => "array variables can not be initialized";
+        IllegalArrayParam{..} => "array parameters must be localparams with a type and an array literal as default";
     }
 }
@@ -199,6 +199,41 @@ impl SourceFile {
 
         Parse::new(green, errors, ctx_map)
     }
+
+    /// Same as [`SourceFile::parse`] but calls `on_item` every time a top level
+    /// item (module, nature or discipline) has been parsed. This allows reporting
+    /// the syntax errors in the first items of a file before the whole file is parsed.
+    /// Errors found during validation are only available in the final [`Parse`].
+    pub fn parse_with_progress(
+        db: &dyn SourceProvider,
+        root_file: FileId,
+        preprocess: &Preprocess,
+        on_item: &mut dyn FnMut(ParseProgress<'_>),
+    ) -> Parse<SourceFile> {
+        let (green, mut errors, ctx_map) =
+            parsing::parse_text_with_progress(db, root_file, preprocess, on_item);
+        let root = SyntaxNode::new_root(green.clone());
+
+        validation::validate(&root, &mut errors);
+
+        assert_eq!(root.kind(), SyntaxKind::SOURCE_FILE);
+
+        Parse::new(green, errors, ctx_map)
+    }
+}
+
+/// Progress report passed to the callback of [`SourceFile::parse_with_progress`].
+#[derive(Debug)]
+pub struct ParseProgress<'a> {
+    /// The kind of the first top level node that was just parsed.
+    /// Tokens that could not be parsed as an item are reported as an `ERROR`
+    /// node together with the item that follows them (if any).
+    pub item: SyntaxKind,
+    /// The syntax errors found while parsing this item.
+    /// Just like [`Parse::errors`] their spans refer to the preprocessed source.
+    pub errors: &'a [SyntaxError],
+    /// The length of the preprocessed source that has been parsed so far.
+    pub parsed: TextSize,
 }
 
 /// Matches a `SyntaxNode` against an `ast` type.
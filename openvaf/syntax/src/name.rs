@@ -408,6 +408,13 @@ pub mod kw_comp {
     }
 }
 
+/// Names of non-standard builtin functions. Unlike [`kw`] these names are not reserved.
+pub mod ext {
+    keywords! {
+        sign,
+    }
+}
+
 pub mod sysfun {
     macro_rules! system_functions {
         ($($ident:ident),* $(,)?) => {
@@ -480,6 +487,7 @@ pub mod sysfun {
         error,
         info,
         abstime,
+        realtime,
 
         bitstoreal,
         realtobits,
@@ -510,6 +518,12 @@ pub mod sysfun {
         pow,
         floor,
         ceil,
+        rint,
+        round,
+        trunc,
+        rtoi,
+        itor,
+        signum,
         sin,
         cos,
         tan,
@@ -536,7 +550,9 @@ pub mod sysfun {
 
         discontinuity,
         limit,
+        past,
         bound_step,
+        noise_correlation,
 
         mfactor,
         xposition,
@@ -7,25 +7,67 @@ use vfs::FileId;
 
 use crate::parsing::tree_builder::SyntaxTreeBuilder;
 use crate::syntax_node::GreenNode;
-use crate::SyntaxError;
+use crate::{ParseProgress, SyntaxError, SyntaxKind};
 
 pub(crate) fn parse_text(
     sources: &dyn SourceProvider,
     root_file: FileId,
     Preprocess { ts, sm, .. }: &Preprocess,
 ) -> (GreenNode, Vec<SyntaxError>, Vec<(TextRange, SourceContext, TextSize)>) {
-    // tokens without whitespaces/comments
-    let parser_tokens: Vec<_> = ts
-        .iter()
+    let parser_tokens = parser_tokens(ts);
+    let mut builder = SyntaxTreeBuilder::new(sources, root_file, ts, sm);
+    build_tree(&mut builder, &parser::parse(&parser_tokens));
+    let (tree, parser_errors, ctx_map) = builder.finish();
+
+    (tree, parser_errors, ctx_map)
+}
+
+pub(crate) fn parse_text_with_progress(
+    sources: &dyn SourceProvider,
+    root_file: FileId,
+    Preprocess { ts, sm, .. }: &Preprocess,
+    on_item: &mut dyn FnMut(ParseProgress<'_>),
+) -> (GreenNode, Vec<SyntaxError>, Vec<(TextRange, SourceContext, TextSize)>) {
+    let parser_tokens = parser_tokens(ts);
+    let mut builder = SyntaxTreeBuilder::new(sources, root_file, ts, sm);
+    builder.start_node(SyntaxKind::SOURCE_FILE);
+    let mut reported_errors = 0;
+    parser::parse_items(&parser_tokens, |output| {
+        let item = output
+            .iter()
+            .find_map(|step| match step {
+                parser::Step::Enter { kind } => Some(kind),
+                _ => None,
+            })
+            .unwrap_or(SyntaxKind::ERROR);
+        build_tree(&mut builder, &output);
+        let errors = builder.errors();
+        on_item(ParseProgress {
+            item,
+            errors: &errors[reported_errors..],
+            parsed: builder.text_pos(),
+        });
+        reported_errors = errors.len();
+    });
+    builder.finish_node();
+
+    builder.finish()
+}
+
+/// tokens without whitespaces/comments
+fn parser_tokens(ts: &[::preprocessor::Token]) -> Vec<SyntaxKind> {
+    ts.iter()
         .filter_map(|token| {
             if token.kind.is_trivia() {
                 return None;
             }
             Some(token.kind)
         })
-        .collect();
-    let mut builder = SyntaxTreeBuilder::new(sources, root_file, ts, sm);
-    for step in parser::parse(&parser_tokens).iter() {
+        .collect()
+}
+
+fn build_tree(builder: &mut SyntaxTreeBuilder, output: &parser::Output) {
+    for step in output.iter() {
         match step {
             parser::Step::Token { kind } => builder.token(kind),
             parser::Step::Enter { kind } => builder.start_node(kind),
@@ -33,8 +75,4 @@ pub(crate) fn parse_text(
             parser::Step::Error { err } => builder.error(err.clone()),
         }
     }
-
-    let (tree, parser_errors, ctx_map) = builder.finish();
-
-    (tree, parser_errors, ctx_map)
 }
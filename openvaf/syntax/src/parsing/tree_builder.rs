@@ -192,6 +192,16 @@ impl<'a> SyntaxTreeBuilder<'a> {
         (self.inner.finish(), self.errors, self.ranges)
     }
 
+    /// The errors that have been emitted so far.
+    pub(super) fn errors(&self) -> &[SyntaxError] {
+        &self.errors
+    }
+
+    /// The position in the (preprocessed) source text up to which tokens have been consumed.
+    pub(super) fn text_pos(&self) -> TextSize {
+        self.text_pos
+    }
+
     fn eat_trivia(&mut self) {
         while let Some(&token) = self.tokens.get(self.token_pos) {
             if !token.kind.is_trivia() {
@@ -24,6 +24,7 @@ pub(crate) fn validate(root: &SyntaxNode, errors: &mut Vec<SyntaxError>) {
                 ast::Name(name) => validate_name(name,errors),
                 ast::ModuleDecl(module) => validate_module(module,errors),
                 ast::ParamDecl(param) => validate_param(param, errors),
+                ast::Var(var) => validate_var(var, errors),
                 _ => validate_net_type_token(node,errors)
             }
         }
@@ -31,6 +32,10 @@ pub(crate) fn validate(root: &SyntaxNode, errors: &mut Vec<SyntaxError>) {
 }
 
 fn validate_param(param_decl: ast::ParamDecl, errors: &mut Vec<SyntaxError>) {
+    for param in param_decl.paras() {
+        validate_array_param(&param_decl, param, errors);
+    }
+
     let range_allowed =
         param_decl.ty().map_or(true, |ty| ty.integer_token().is_some() | ty.real_token().is_some());
     if range_allowed {
@@ -52,6 +57,33 @@ fn validate_param(param_decl: ast::ParamDecl, errors: &mut Vec<SyntaxError>) {
     }
 }
 
+/// Array parameters can not be set by the simulator (only scalar parameters are exported)
+/// so they must be constants with one default value for every element.
+fn validate_array_param(
+    param_decl: &ast::ParamDecl,
+    param: ast::Param,
+    errors: &mut Vec<SyntaxError>,
+) {
+    let range = if let Some(range) = param.range() { range } else { return };
+    if param.array_bounds().is_none() {
+        errors.push(SyntaxError::IllegalArrayBounds { range: range.syntax().text_range() });
+    }
+    let is_const = param_decl.localparam_token().is_some() && param_decl.ty().is_some();
+    if !is_const || !matches!(param.default(), Some(Expr::ArrayExpr(_))) {
+        errors.push(SyntaxError::IllegalArrayParam { param: param.syntax().text_range() });
+    }
+}
+
+fn validate_var(var: ast::Var, errors: &mut Vec<SyntaxError>) {
+    let range = if let Some(range) = var.range() { range } else { return };
+    if var.array_bounds().is_none() {
+        errors.push(SyntaxError::IllegalArrayBounds { range: range.syntax().text_range() });
+    }
+    if let Some(default) = var.default() {
+        errors.push(SyntaxError::ArrayVarDefault { default: default.syntax().text_range() });
+    }
+}
+
 fn validate_net_type_token(node: SyntaxNode, errors: &mut Vec<SyntaxError>) {
     if matches!(node.kind(), SyntaxKind::NET_DECL | SyntaxKind::PORT_DECL) {
         if let Some(token) = support::token(&node, NET_TYPE) {
@@ -167,6 +199,16 @@ fn validate_literal(literal: ast::Literal, errors: &mut Vec<SyntaxError>) {
     {
         errors.push(SyntaxError::IllegalInfToken { range: literal.syntax().text_range() });
     }
+
+    match literal.kind() {
+        LiteralKind::IntNumber(lit) if lit.value().is_none() => {
+            errors.push(SyntaxError::IntLiteralTooLarge { range: literal.syntax().text_range() });
+        }
+        LiteralKind::StdRealNumber(lit) if lit.value().is_none() => {
+            errors.push(SyntaxError::MalformedRealLiteral { range: literal.syntax().text_range() });
+        }
+        _ => (),
+    }
 }
 
 fn validate_path(path: ast::Path, errors: &mut Vec<SyntaxError>) {
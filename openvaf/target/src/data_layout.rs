@@ -0,0 +1,93 @@
+//! Parsing of [LLVM data layout strings](https://llvm.org/docs/LangRef.html#data-layout).
+//!
+//! Simulators that load OSDI libraries must interpret the structs defined by the OSDI ABI.
+//! The information required for that (pointer size, endianness and the alignment of the
+//! primitive types) is encoded in the data layout of the target.
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
+/// The (in memory) layout of the primitive types used by the OSDI ABI.
+/// All sizes and alignments are in bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DataLayout {
+    pub endian: Endian,
+    pub pointer_size: u32,
+    pub pointer_align: u32,
+    pub i32_align: u32,
+    pub i64_align: u32,
+    pub f64_align: u32,
+    /// The natural alignment of the stack, `None` if not specified.
+    pub stack_align: Option<u32>,
+}
+
+impl Default for DataLayout {
+    /// The defaults that LLVM uses for all specifications missing from a data layout string.
+    fn default() -> DataLayout {
+        DataLayout {
+            endian: Endian::Big,
+            pointer_size: 64,
+            pointer_align: 64,
+            i32_align: 32,
+            i64_align: 32,
+            f64_align: 64,
+            stack_align: None,
+        }
+    }
+}
+
+impl DataLayout {
+    pub fn parse(data_layout: &str) -> Result<DataLayout, String> {
+        let parse_bits = |spec: &str, bits: &str| {
+            bits.parse::<u32>()
+                .map_err(|_| format!("invalid size '{bits}' in data layout specification '{spec}'"))
+        };
+        // returns (size, abi alignment) of a specification like `i64:64`
+        let size_align = |spec: &str, rest: &str| -> Result<(u32, Option<u32>), String> {
+            let mut parts = rest.split(':');
+            let size = parse_bits(spec, parts.next().unwrap_or_default())?;
+            let align = parts.next().map(|align| parse_bits(spec, align)).transpose()?;
+            Ok((size, align))
+        };
+
+        let mut res = DataLayout::default();
+        for spec in data_layout.split('-').filter(|spec| !spec.is_empty()) {
+            // the specification kind is a single character (which may not be ASCII)
+            let kind_len = spec.char_indices().nth(1).map_or(spec.len(), |(i, _)| i);
+            match spec.split_at(kind_len) {
+                ("e", "") => res.endian = Endian::Little,
+                ("E", "") => res.endian = Endian::Big,
+                ("S", bits) => res.stack_align = Some(parse_bits(spec, bits)?),
+                ("p", rest) => {
+                    let (addr_space, rest) = rest.split_once(':').unwrap_or((rest, ""));
+                    // only the default address space is used for the OSDI ABI
+                    if !matches!(addr_space, "" | "0") {
+                        continue;
+                    }
+                    let (size, align) = size_align(spec, rest)?;
+                    res.pointer_size = size;
+                    res.pointer_align = align.unwrap_or(size);
+                }
+                ("i", rest) => match size_align(spec, rest)? {
+                    (32, Some(align)) => res.i32_align = align,
+                    (64, Some(align)) => res.i64_align = align,
+                    _ => (),
+                },
+                ("f", rest) => {
+                    if let (64, Some(align)) = size_align(spec, rest)? {
+                        res.f64_align = align
+                    }
+                }
+                // mangling, native integer widths, vectors, aggregates, ...
+                _ => (),
+            }
+        }
+        Ok(res)
+    }
+}
@@ -0,0 +1,67 @@
+use crate::data_layout::{DataLayout, Endian};
+use crate::spec::{get_targets, Target};
+
+#[test]
+fn x86_64() {
+    let target = Target::search("x86_64-unknown-linux").unwrap();
+    let layout = target.parse_data_layout().unwrap();
+    assert_eq!(
+        layout,
+        DataLayout {
+            endian: Endian::Little,
+            pointer_size: 64,
+            pointer_align: 64,
+            i32_align: 32,
+            i64_align: 64,
+            f64_align: 64,
+            stack_align: Some(128),
+        }
+    );
+}
+
+#[test]
+fn aarch64() {
+    let target = Target::search("aarch64-unknown-linux").unwrap();
+    let layout = target.parse_data_layout().unwrap();
+    assert_eq!(
+        layout,
+        DataLayout {
+            endian: Endian::Little,
+            pointer_size: 64,
+            pointer_align: 64,
+            i32_align: 32,
+            i64_align: 64,
+            f64_align: 64,
+            stack_align: Some(128),
+        }
+    );
+}
+
+#[test]
+fn explicit_pointers() {
+    let layout = DataLayout::parse("E-p:32:32-i64:32:64-f64:32-S64").unwrap();
+    assert_eq!(layout.endian, Endian::Big);
+    assert_eq!((layout.pointer_size, layout.pointer_align), (32, 32));
+    assert_eq!(layout.i64_align, 32);
+    assert_eq!(layout.f64_align, 32);
+    assert_eq!(layout.stack_align, Some(64));
+
+    let err = DataLayout::parse("e-p:abc:64").unwrap_err();
+    assert_eq!(err, "invalid size 'abc' in data layout specification 'p:abc:64'");
+}
+
+#[test]
+fn non_ascii_specification() {
+    // unknown specifications are ignored, even if they start with a multibyte character
+    let layout = DataLayout::parse("e-é:64-é-p:32:32").unwrap();
+    assert_eq!(layout.endian, Endian::Little);
+    assert_eq!(layout.pointer_size, 32);
+}
+
+#[test]
+fn pointer_width() {
+    for target in get_targets() {
+        let layout = target.parse_data_layout().unwrap();
+        assert_eq!(layout.pointer_size, target.pointer_width, "{}", target.llvm_target);
+    }
+}
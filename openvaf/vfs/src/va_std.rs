@@ -391,6 +391,28 @@ pub const CONSTANTS_PATHS: [&str; 3] = ["constants.vams", "constants.va", "const
 pub const DISCIPLINES_PATHS: [&str; 4] =
     ["disciplines.vams", "disciplines.va", "disciplines.h", "discipline.h"];
 
+/// Returns the standard header that declares `name`: a macro from `constants.vams`
+/// or a discipline, nature or access function from `disciplines.vams`.
+pub fn std_header_declaring(name: &str) -> Option<&'static str> {
+    let declares = |src: &str, keywords: &[&str]| {
+        src.lines().any(|line| {
+            let mut tokens = line
+                .split(|c: char| c.is_whitespace() || c == ';' || c == '=')
+                .filter(|token| !token.is_empty());
+            matches!(tokens.next(), Some(token) if keywords.contains(&token))
+                && tokens.next() == Some(name)
+        })
+    };
+
+    if declares(CONSTANTS_SRC, &["`define"]) {
+        Some(CONSTANTS_PATHS[0])
+    } else if declares(DISCIPLINCES_SRC, &["discipline", "nature", "access"]) {
+        Some(DISCIPLINES_PATHS[0])
+    } else {
+        None
+    }
+}
+
 impl Vfs {
     pub fn insert_std_lib(&mut self) {
         for name in CONSTANTS_PATHS {
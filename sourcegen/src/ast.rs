@@ -645,6 +645,9 @@ fn lower_rule(acc: &mut Vec<Field>, grammar: &Grammar, label: Option<&String>, r
                     | "else_branch"
                     | "then_val"
                     | "else_val"
+                    | "typ"
+                    | "max"
+                    | "index"
                     | "start"
                     | "end"
                     | "ident"
@@ -95,6 +95,8 @@ pub(crate) const KINDS_SRC: KindsSrc = KindsSrc {
         "initial",
         "final_step",
         "aliasparam",
+        "genvar",
+        "or",
     ],
     literals: &["INT_NUMBER", "STD_REAL_NUMBER", "SI_REAL_NUMBER", "STR_LIT"],
     tokens: &["ERROR", "IDENT", "SYSFUN", "NET_TYPE", "WHITESPACE", "COMMENT"],
@@ -119,6 +121,7 @@ pub(crate) const KINDS_SRC: KindsSrc = KindsSrc {
         "DIRECTION",
         "DISCIPLINE_DECL",
         "DISCIPLINE_ATTR",
+        "EVENT",
         "EVENT_STMT",
         "FOR_STMT",
         "FUNCTION",
@@ -147,6 +150,8 @@ pub(crate) const KINDS_SRC: KindsSrc = KindsSrc {
         "PREFIX_EXPR",
         "RANGE",
         "SELECT_EXPR",
+        "MIN_TYP_MAX_EXPR",
+        "INDEX_EXPR",
         "TYPE",
         "VAR",
         "VAR_DECL",
@@ -4,7 +4,7 @@ use quote::{format_ident, quote};
 use stdx::iter::multiunzip;
 use stdx::SKIP_HOST_TESTS;
 
-const ANALOG_OPERATORS: [&str; 17] = [
+const ANALOG_OPERATORS: [&str; 18] = [
     "absdelay",
     "ddt",
     "idt",
@@ -20,11 +20,12 @@ const ANALOG_OPERATORS: [&str; 17] = [
     "laplace_zp",
     "limexp",
     "last_crossing",
+    "cross",
     "slew",
     "transition",
 ];
 
-const UNSUPPORTED: [&str; 50] = [
+const UNSUPPORTED: [&str; 37] = [
     "simprobe",
     "analog_node_alias",
     "analog_port_alias",
@@ -38,19 +39,11 @@ const UNSUPPORTED: [&str; 50] = [
     "laplace_np",
     "laplace_zd",
     "laplace_zp",
-    "last_crossing",
     "slew",
     "transition",
-    "fclose",
-    "fopen",
-    "fdisplay",
-    "fwrite",
-    "fstrobe",
-    "fmonitor",
     "fgets",
     "fscanf",
     "swrite",
-    "sformat",
     "sscanf",
     "rewind",
     "fseek",
@@ -66,18 +59,13 @@ const UNSUPPORTED: [&str; 50] = [
     "dist_erlang",
     "dist_normal",
     "dist_t",
-    "random",
     "arandom",
     "rdist_chi_square",
-    "rdist_exponential",
-    "rdist_poisson",
-    "rdist_uniform",
     "rdist_erlang",
-    "rdist_normal",
     "rdist_t",
 ];
 
-const ANALOG_OPERATORS_SYSFUN: [&str; 1] = ["$limit"];
+const ANALOG_OPERATORS_SYSFUN: [&str; 2] = ["$limit", "$past"];
 
 const ANALYSIS_FUNS: [&str; 6] =
     ["analysis", "ac_stim", "noise_table", "noise_table_log", "white_noise", "flicker_noise"];
@@ -111,9 +99,13 @@ const BUILTINS: [&str; 26] = [
     "tanh",
 ];
 
+/// Non-standard builtins that are supported for compatibility with other compilers.
+/// Their names are not reserved so they can be shadowed by user definitions.
+const EXT_BUILTINS: [&str; 1] = ["sign"];
+
 const PARAM_SYSFUNS: [&str; 6] = ["mfactor", "xposition", "yposition", "angle", "hflip", "vflip"];
 
-const SYSFUNS: [&str; 81] = [
+const SYSFUNS: [&str; 90] = [
     "$display",
     "$strobe",
     "$write",
@@ -144,6 +136,7 @@ const SYSFUNS: [&str; 81] = [
     "$error",
     "$info",
     "$abstime",
+    "$realtime",
     "$dist_chi_square",
     "$dist_exponential",
     "$dist_poisson",
@@ -168,6 +161,12 @@ const SYSFUNS: [&str; 81] = [
     "$pow",
     "$floor",
     "$ceil",
+    "$rint",
+    "$round",
+    "$trunc",
+    "$rtoi",
+    "$itor",
+    "$signum",
     "$sin",
     "$cos",
     "$tan",
@@ -192,10 +191,11 @@ const SYSFUNS: [&str; 81] = [
     "$port_connected",
     "$analog_node_alias",
     "$analog_port_alias",
-    // "$table_model",
+    "$table_model",
     "$test$plusargs",
     "$value$plusargs",
     "$bound_step",
+    "$noise_correlation",
 ];
 
 #[test]
@@ -205,6 +205,7 @@ fn generate_builtins() {
     }
     let iter = BUILTINS
         .into_iter()
+        .chain(EXT_BUILTINS)
         .chain(SYSFUNS)
         .chain(ANALYSIS_FUNS)
         .chain(ANALOG_OPERATORS_SYSFUN)
@@ -215,7 +216,13 @@ fn generate_builtins() {
             let variant =
                 if is_sysfun { builtin[1..].replace('$', "_") } else { builtin.to_owned() };
             let ident = format_ident!("{}", variant);
-            let prefix = if is_sysfun { format_ident!("sysfun") } else { format_ident!("kw") };
+            let prefix = if is_sysfun {
+                format_ident!("sysfun")
+            } else if EXT_BUILTINS.contains(&builtin) {
+                format_ident!("ext")
+            } else {
+                format_ident!("kw")
+            };
             (prefix, ident, variant)
         });
 
@@ -303,7 +310,7 @@ fn generate_builtins() {
 
     let header = "use ahash::RandomState;
         use indexmap::IndexMap;
-        use syntax::name::{kw, sysfun, Name};
+        use syntax::name::{ext, kw, sysfun, Name};
 
         use crate::nameres::ScopeDefItem;
     ";
@@ -82,6 +82,9 @@ opcodes! {
         Clog2
         Floor
         Ceil
+        Rint
+        Round
+        Trunc
         Sin
         Cos
         Tan
@@ -698,8 +698,61 @@ impl ToTokens for RustFunc<'_> {
 }
 
 fn gen_bindings<'a>(tys: &IndexMap<&'a str, OsdiStruct<'a>, RandomState>) -> String {
-    let tys = tys.iter().map(|(_, ty)| RustStruct(ty));
-    quote!(#(#tys)*).to_string()
+    let structs = tys.iter().map(|(_, ty)| RustStruct(ty));
+    let layout_test = gen_layout_test(tys);
+    quote!(#(#structs)* #layout_test).to_string()
+}
+
+/// Generates a test that ensures that the generated `#[repr(C)]` bindings have
+/// exactly the layout the C compiler produces for the header on 64-bit targets.
+fn gen_layout_test<'a>(tys: &IndexMap<&'a str, OsdiStruct<'a>, RandomState>) -> TokenStream {
+    let idents = tys.keys().map(|ident| format_ident!("{ident}"));
+    let idents2 = idents.clone();
+    let (sizes, aligns): (Vec<_>, Vec<_>) =
+        tys.values().map(|ty| struct_layout(ty, tys)).unzip();
+    quote! {
+        #[cfg(all(test, target_pointer_width = "64"))]
+        #[test]
+        fn layout() {
+            #(assert_eq!(std::mem::size_of::<#idents>(), #sizes);)*
+            #(assert_eq!(std::mem::align_of::<#idents2>(), #aligns);)*
+        }
+    }
+}
+
+/// size and alignment of a struct/union (following the C layout rules of 64-bit targets)
+fn struct_layout<'a>(
+    ty: &OsdiStruct<'a>,
+    lut: &IndexMap<&'a str, OsdiStruct<'a>, RandomState>,
+) -> (usize, usize) {
+    let mut size = 0;
+    let mut align = 1;
+    for (_, field) in &ty.fields {
+        let (field_size, field_align) = field_layout(field, lut);
+        align = align.max(field_align);
+        if ty.is_union {
+            size = size.max(field_size);
+        } else {
+            size = (size + field_align - 1) / field_align * field_align + field_size;
+        }
+    }
+    ((size + align - 1) / align * align, align)
+}
+
+fn field_layout<'a>(
+    ty: &Ty<'a>,
+    lut: &IndexMap<&'a str, OsdiStruct<'a>, RandomState>,
+) -> (usize, usize) {
+    if ty.indirection != 0 || ty.func_args.is_some() {
+        return (8, 8);
+    }
+    match ty.base {
+        BaseTy::F64 | BaseTy::Usize => (8, 8),
+        BaseTy::I32 | BaseTy::U32 => (4, 4),
+        BaseTy::Char | BaseTy::Bool => (1, 1),
+        BaseTy::Struct(name) => struct_layout(&lut[name], lut),
+        BaseTy::Void => unreachable!("void can only be used behind a pointer"),
+    }
 }
 
 fn gen_llvm_tys<'a>(tys: &IndexMap<&'a str, OsdiStruct<'a>, RandomState>) -> String {
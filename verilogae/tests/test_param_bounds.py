@@ -0,0 +1,18 @@
+import verilogae
+
+model = verilogae.load_info("param_bounds_vae.va")
+mc = model.modelcard
+
+# (min, min_inclusive, max, max_inclusive)
+expected = {
+    "up": (0.0, False, 3.0, False),
+    "down": (0.0, False, 3.0, False),
+    "ints": (0, True, 10, True),
+    "incl_first": (1.0, True, 2.0, True),
+    "excl_first": (1.0, True, 2.0, True),
+}
+
+for name, bounds in expected.items():
+    param = mc[name]
+    res = (param.min, param.min_inclusive, param.max, param.max_inclusive)
+    assert res == bounds, f"bounds of {name}: {res} != {bounds}"
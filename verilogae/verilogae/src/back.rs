@@ -2,7 +2,9 @@ use std::borrow::Borrow;
 
 use camino::Utf8Path;
 use hir::Type;
-use hir_lower::{CallBackKind, CurrentKind, HirInterner, ParamInfoKind, ParamKind, PlaceKind};
+use hir_lower::{
+    CallBackKind, CurrentKind, HirInterner, ParamInfoKind, ParamKind, PlaceKind, RandomDist,
+};
 use lasso::Rodeo;
 use llvm::{OptLevel, UNNAMED};
 use mir::{ControlFlowGraph, FuncRef, Function};
@@ -64,13 +66,43 @@ pub fn stub_callbacks<'ll>(
                     CallbackFun::Prebuilt(cx.const_callback(&[cx.ty_double()], cx.const_real(0.0)))
                 }
                 CallBackKind::Print { .. }
+                | CallBackKind::FilePrint { .. }
+                | CallBackKind::FileClose
                 | CallBackKind::ParamInfo(_, _)
                 | CallBackKind::BuiltinLimit { .. }
                 | CallBackKind::StoreLimit(_)
                 | CallBackKind::LimDiscontinuity
+                | CallBackKind::LastCrossing(_)
+                | CallBackKind::CrossEvent(_)
+                | CallBackKind::Past(_)
                 | CallBackKind::CollapseHint(_, _) 
                 | CallBackKind::SetRetFlag { .. } => return None,
                 CallBackKind::Analysis => CallbackFun::Prebuilt(cx.const_callback(&[cx.ty_ptr()], cx.const_int(1))),
+                // verilogae does not write any files
+                CallBackKind::FileOpen => CallbackFun::Prebuilt(
+                    cx.const_callback(&[cx.ty_ptr(), cx.ty_ptr()], cx.const_int(0)),
+                ),
+                // the format literal is returned unformatted
+                CallBackKind::Format { arg_tys } => {
+                    let mut args = vec![cx.ty_ptr()];
+                    args.extend(arg_tys.iter().map(|arg| lltype(&arg.ty, cx)));
+                    CallbackFun::Prebuilt(cx.const_return(&args, 0))
+                }
+                CallBackKind::HostFunction(fun) => {
+                    unreachable!("verilogae does not register host functions (${})", fun.name)
+                }
+                // there is no monte carlo analysis in verilogae so always use the nominal value
+                CallBackKind::Random { dist, next_seed } => {
+                    let mut args = vec![cx.ty_int()];
+                    args.resize(dist.num_args() as usize + 1, cx.ty_double());
+                    let res = match dist {
+                        _ if *next_seed => cx.const_return(&args, 0),
+                        RandomDist::Uniform => cx.const_callback(&args, cx.const_int(0)),
+                        // the nominal value of $rdist_uniform is its lower bound
+                        _ => cx.const_return(&args, 1),
+                    };
+                    CallbackFun::Prebuilt(res)
+                }
             };
 
             Some(res)
@@ -303,6 +303,8 @@ impl ModelInfo {
                     });
                 }
 
+                // localparams are constants that can not be set by the user
+                ScopeDef::Parameter(param) if param.is_local(db) => (),
                 ScopeDef::Parameter(param) => {
                     let units = param
                         .get_attr(db, &ast, "units")
@@ -353,9 +355,9 @@ impl ModelInfo {
                     let mut default_val = |attr: Attr| {
                         let val = match attr.val() {
                             Some(ref expr @ Expr::Literal(ref lit)) => match lit.kind() {
-                                LiteralKind::IntNumber(val) => val.value() as f64,
+                                LiteralKind::IntNumber(val) => val.value()? as f64,
                                 LiteralKind::SiRealNumber(val) => val.value(),
-                                LiteralKind::StdRealNumber(val) => val.value(),
+                                LiteralKind::StdRealNumber(val) => val.value()?,
                                 _ => {
                                     let diag = IllegalExpr {
                                         expr: expr.clone(),
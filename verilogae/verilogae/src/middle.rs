@@ -115,6 +115,9 @@ pub fn build_module_mir(
     dom_tree.compute(&func, &cfg, true, false, true);
     let unknowns = intern.unknowns(&mut func, false);
     auto_diff(&mut func, &dom_tree, &unknowns, &[]);
+    // remove derivatives that are never used by any output
+    output_values.ensure(func.dfg.num_values());
+    dead_code_elimination(&mut func, &output_values);
     cfg.clear();
     cfg.compute(&func);
     sparse_conditional_constant_propagation(&mut func, &cfg);